@@ -8,7 +8,7 @@ fn main() {
     // Build the clap::Command using the builder API (avoids needing to import
     // the derive struct from the main crate, which is not accessible to build scripts).
     let version: &'static str = env::var("CARGO_PKG_VERSION").unwrap().leak();
-    let cmd = clap::Command::new("nv-swaptop")
+    let mut cmd = clap::Command::new("nv-swaptop")
         .version(version)
         .about("Real-time TUI monitor for swap, NUMA topology, and GPU memory (Linux)")
         .long_about(
@@ -16,7 +16,7 @@ fn main() {
              usage, NUMA memory topology, and GPU memory in real time on Linux systems.\n\n\
              It is designed for systems where GPU HBM is exposed as a NUMA node \
              (e.g. NVIDIA Grace Blackwell GB200), but works on any Linux system with swap \
-             configured. GPU features require nvidia-smi in PATH."
+             configured. GPU features require nvidia-smi (or, for AMD GPUs, rocm-smi) in PATH."
         )
         .after_long_help(
 r#"KEYBOARD CONTROLS
@@ -24,24 +24,111 @@ r#"KEYBOARD CONTROLS
     1/2/3/4      Jump to Swap / NUMA / GPU / Unified view directly
     Esc, q       Quit
     Ctrl-C       Quit
-    d, Down      Scroll down
-    u, Up        Scroll up
+    Ctrl-Z       Suspend to the shell (resume with fg)
+    d, Down      Scroll down (Down selects in the Swap view)
+    u, Up        Scroll up (Up selects in the Swap view)
     Home         Scroll to top
     End          Scroll to bottom
     PageDown     Page down
     PageUp       Page up
-    k/m/g        Switch units: KB / MB / GB
-    a            Toggle process aggregation by name
-    t            Cycle colour theme (Default, Solarized, Monokai, Dracula, Nord)
-    s            Cycle sort column (swap, gpu_mem, numa, name)
+                 (the NUMA, GPU and Unified tables scroll too, each keeping
+                 its own position across view switches)
+    k/m/g/T      Switch units: KB / MB / GB / TB
+    B            Toggle binary (KiB, 1024) and decimal (kB, 1000) units
+    . / F5       Refresh now, bypassing the active view's NUMA/GPU caches
+    Space        Pause / resume refreshing (keys still work; the title shows PAUSED)
+    a            Toggle process aggregation by name, in the Swap and Unified
+                 views (GPU view: group processes by GPU with a VRAM subtotal
+                 each)
+    n            Cycle mount namespace: off, MNT NS column, group by namespace
+    z            Mark a baseline / clear it (swap and GPU columns show change since baseline)
+    J            Jump to the process using the most swap
+    K            Swap view: send SIGTERM to the selected process after a y/n prompt;
+                 K again in the prompt switches to SIGKILL
+    t            Cycle colour theme (Default, Solarized, Monokai, Dracula, Nord, Colorblind)
+    s            Cycle sort column (swap, swap+rss, gpu_mem, numa, name)
+    R            Unified view: show/hide the SWAP+RSS (total footprint) column
+    G            Unified view: show/hide GPU% (share of the process's GPU memory)
+    F            Unified view: cycle location filter: all, CPU-only, GPU-only, CPU+GPU
+    c            Toggle "used" swap between including and excluding SwapCached
+    r            Reverse sort direction (shown as ▲/▼ after sort: in the title and on
+                 the active column header)
+    S            Re-sort the process lists now (see --resort-interval)
+    H            Dismiss the swappiness hint (shown again when the condition recurs)
+    C            Swap view: show/hide pages swapped in/out since boot (from /proc/vmstat)
+    Mouse        Click a Unified view column header to sort by it; again to reverse
     h            Toggle swap device panel
+    p            Toggle swap devices between table and percentage bars
+    l            Cycle swap devices table layout: auto / wide / narrow
+    D            Swap devices sizes in a readable unit plus exact KB
+    o            Show swap devices' I/O scheduler and read-ahead columns
+    f            Maximize the swap process list / restore the chart
+    [ / ]        Move the swap chart cursor back / forward in time; the title shows
+                 the selected sample and its change to now
+    b            Overlay the swap chart's mean and a ±1 standard deviation band
+    y            Chart series menu: 1-3 toggle used, mean and ±1 sigma lines, 4 a swap
+                 in/out pages-per-second panel (/proc/vmstat); y/Esc closes
+    v            Hide / show the swap chart (the device panel still follows h)
+    L            Swap chart: logarithmic y-axis, ln(1 + KB)
+    N            NUMA view: order topology table by node id / memory used
+    P            NUMA view: hide / show per-process section (skips numa_maps)
+    A            GPU view: collapse all GPUs into one summed row / per-GPU rows
+    i            GPU view: show MIG instances / flatten into physical GPUs
+    V            GPU view: chart the next GPU's used VRAM history
     Left/Right   Decrease/increase refresh interval (100ms steps, 1ms–10s)
+    x            Cycle swap chart x-axis labels: ticks / seconds / minutes
+                 (wall-clock labels assume the current refresh interval)
+    e            Type an exact refresh interval in ms (Enter to apply, Esc to cancel)
+    /            Filter process lists by name (case-insensitive substring, every
+                 view); Backspace edits, Enter keeps the filter, Esc clears it
+    w            Capture the effective settings; printed as TOML on stderr on
+                 quit, for reference (nv-swaptop does not read it back)
 
 VIEWS
-    Swap       Animated swap usage chart, per-process swap list, optional device panel
-    NUMA       NUMA node topology and per-process memory distribution across nodes
-    GPU        GPU device summary (memory, temperature) and per-GPU process list
-    Unified    Combined CPU+GPU+NUMA process table with per-node memory columns
+    Swap       Animated swap usage chart, per-process swap list, optional device panel;
+               RSS is each process's resident set size (VmRSS); SWP I/O is its VmSwap
+               change per second (KB/s, + = out, - = in); TREND is the change
+               since the previous refresh (↑/↓ amount, → unchanged, new)
+    NUMA       NUMA node topology and per-process memory distribution across nodes;
+               N sorts the topology by memory used, node columns stay in id order;
+               USAGE bars turn amber at 70% and red at 90%, HBM nodes fill with ▓;
+               a Distances panel shows the node distance matrix (10 = local)
+    GPU        Used VRAM history chart for one GPU (V cycles), GPU device summary
+               (memory, temperature, SM%/MEM% utilisation) and per-GPU process list;
+               GPUs holding processes at or below --idle-gpu-util utilisation are
+               highlighted as idle-but-held
+    Unified    Combined CPU+GPU+NUMA process table with per-node memory columns;
+               its bottom border shows the share of total swap held by CPU-only
+               and by CPU+GPU processes
+
+    The bottom border of every view shows its totals as used / free: swap
+    (Swap), node memory (NUMA), VRAM summed over all GPUs (GPU), or swap
+    and VRAM together (Unified).
+
+ALERTS
+    --swap-alert and --gpu-alert take a percentage. An event is logged when
+    used swap (or memory on the fullest GPU) rises to the threshold and again
+    when it falls back below it, naming the top process. --thrash-alert
+    takes a swap-in+out rate in pages/sec and logs the start and end of each
+    thrash episode the same way, naming the top swapping process. Events are
+    shown on the status line. --alert-log FILE also appends them to FILE,
+    prefixed with a UTC timestamp; without it, builds with the syslog
+    feature send them to syslog/journald, and others write them to stderr
+    only when stderr is not a terminal.
+
+USED SWAP
+    By default "used" swap is SwapTotal - SwapFree from /proc/meminfo, as
+    reported by free(1). With --exclude-swap-cache (toggle: c), SwapCached
+    is also subtracted: those pages have a copy in RAM and can be freed from
+    swap without I/O. The choice applies to the chart, titles, percentages
+    and alerts alike.
+
+THRASH LEVEL
+    The bottom border shows a low/medium/high swap-thrash gauge combining
+    PSI memory "full" pressure with the swap-in+out page rate. Both must be
+    elevated for HIGH: PSI full avg10 1%/10% and 100/1000 pages/sec are the
+    medium/high thresholds. Without PSI the rate alone is used, capped at
+    MEDIUM.
 
 COLOUR CODING (Unified and NUMA views)
     Memory cells are colour-coded by locality:
@@ -51,40 +138,258 @@ COLOUR CODING (Unified and NUMA views)
 
 DATA SOURCES
     Swap totals        /proc/meminfo
+    Swappiness hint    MemTotal, MemAvailable from /proc/meminfo; /proc/sys/vm/swappiness
     Per-process swap   /proc/[pid]/status (VmSwap field)
+    Mount namespace    /proc/[pid]/ns/mnt inode; "host" when it matches PID 1's
     Swap devices       /proc/swaps
-    NUMA topology      /sys/devices/system/node/nodeN/meminfo, cpulist
-    NUMA per-process   /proc/[pid]/numa_maps
+    Swap device media  /sys/dev/block/MAJ:MIN/queue/rotational (HDD vs SSD/NVMe)
+    Swap device queue  /sys/dev/block/MAJ:MIN/queue/scheduler, read_ahead_kb
+    Swap I/O counters  /proc/vmstat (pswpin, pswpout)
+    Memory pressure    /proc/pressure/memory (PSI "full" avg10)
+    NUMA topology      /sys/devices/system/node/nodeN/meminfo, cpulist, distance
+    NUMA per-process   /proc/[pid]/numa_maps; pages are weighted by the line's
+                       kernelpagesize_kB, or for "huge" lines without it by
+                       Hugepagesize from /proc/meminfo
     CPU→NUMA mapping   /proc/[pid]/stat field 39 mapped via topology
-    GPU devices        nvidia-smi --query-gpu (CSV output)
+    GPU devices        nvidia-smi --query-gpu (CSV output), one run that also
+                       reads the optional fields below; drivers that reject
+                       one of them get a run per field instead
+    GPU reserved mem   nvidia-smi --query-gpu=memory.reserved (optional, newer drivers)
+    GPU utilisation    nvidia-smi --query-gpu=utilization.gpu,utilization.memory
+                       (optional): compute (SM%) and memory bandwidth (MEM%)
     GPU processes      nvidia-smi --query-compute-apps (CSV output)
-    GPU→NUMA mapping   /sys/bus/pci/devices/<pci_bus_id>/numa_node
+    GPU process cmd    /proc/[pid]/cmdline, when the PID is local and matches
+                       the nvidia-smi name; otherwise the nvidia-smi name
+    MIG mode           nvidia-smi --query-gpu=mig.mode.current (optional)
+    MIG instances      nvidia-smi -q -x (XML), when MIG mode is enabled
+    NVML               builds with the nvml feature read GPU devices, processes
+                       and per-process utilisation from libnvidia-ml instead of
+                       nvidia-smi, falling back to nvidia-smi when NVML cannot
+                       be initialised
+    AMD GPUs           rocm-smi --showmeminfo vram --json and --showpids --json,
+                       used only when nvidia-smi is not installed; name,
+                       temperature, bus id and utilisation from
+                       --showproductname --showtemp --showbus --showuse
+                       --showmemuse --json (optional). rocm-smi reports one
+                       VRAM figure per process, shown on its first GPU
+                       (--showpidgpus)
+    GPU→NUMA mapping   /sys/bus/pci/devices/<pci_bus_id>/numa_node; GPUs
+                       reporting -1 are paired in index order with the
+                       remaining CPU-less nodes whose memory is within 10%
+                       of their VRAM
 
 CACHING
     nv-swaptop uses TTL-based caching to minimise system overhead:
       NUMA topology    30 seconds
-      NUMA maps        5 seconds (only when NUMA or Unified view active)
+      NUMA maps        5 seconds (only when Unified, or NUMA with its per-process
+                       section shown)
       GPU devices      10 seconds
       GPU processes    1 second
 
 THEMES
-    Five built-in colour themes: Default, Solarized, Monokai, Dracula, Nord.
-    Cycle with the 't' key.
+    Six built-in colour themes: Default, Solarized, Monokai, Dracula, Nord,
+    Colorblind. Cycle with the 't' key, or pick one at startup with --theme.
 
 SUPPORTED ARCHITECTURES
     x86_64, aarch64 (Grace Blackwell, Grace Hopper), ppc64le, riscv64,
     s390x, loongarch64.
 
 ENVIRONMENT
-    nvidia-smi must be in PATH for GPU features. Falls back gracefully
-    when absent. NUMA features require /sys/devices/system/node/."#
+    nvidia-smi must be in PATH for GPU features, unless NVIDIA_SMI_PATH (or
+    --nvidia-smi, which takes precedence) names the binary. Falls back gracefully
+    when absent; if it is present but fails, the GPU view shows its error.
+    Builds with the nvml feature need no nvidia-smi when libnvidia-ml loads.
+    Without nvidia-smi, AMD GPUs are read with rocm-smi from PATH. NUMA
+    features require /sys/devices/system/node/."#
         )
         .arg(
             clap::Arg::new("demo")
                 .long("demo")
                 .help("Run with synthetic demo data instead of real system data")
                 .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("theme")
+                .long("theme")
+                .value_name("THEME")
+                .value_parser(["default", "solarized", "monokai", "dracula", "nord", "colorblind"])
+                .help("Colour theme to start with (`t` cycles at runtime); colorblind avoids red/green status colours"),
+        )
+        .arg(
+            clap::Arg::new("dump-numa-csv")
+                .long("dump-numa-csv")
+                .help("Print per-process NUMA memory (KB per node, CPU node, misaligned flag) for the top swap users as CSV and exit")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("once")
+                .long("once")
+                .help("Print one snapshot (swap totals, swap and GPU processes, GPU devices, merged list) and exit, without the TUI")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(["json", "tsv"])
+                .default_value("json")
+                .help("Output format for --once"),
+        )
+        .arg(
+            clap::Arg::new("print-config")
+                .long("print-config")
+                .help("Print the effective settings as TOML on stderr and exit, for reference only; it is not read back (`w` does the same at runtime, on quit)")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("exclude-swap-cache")
+                .long("exclude-swap-cache")
+                .help("Count only swap not also cached in RAM as \"used\" (used - SwapCached)")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("pid")
+                .long("pid")
+                .value_name("PID[,PID...]")
+                .value_delimiter(',')
+                .help("Only show these processes (comma-separated PIDs) in every view"),
+        )
+        .arg(
+            clap::Arg::new("max-processes")
+                .long("max-processes")
+                .value_name("N")
+                .default_value("5000")
+                .help("Render at most N swap processes (the largest first); the rest are summarised"),
+        )
+        .arg(
+            clap::Arg::new("top-n")
+                .long("top-n")
+                .value_name("N")
+                .default_value("20")
+                .help("Read and show NUMA distribution for the N largest swap users (NUMA view, --dump-numa-csv)"),
+        )
+        .arg(
+            clap::Arg::new("history")
+                .long("history")
+                .value_name("N")
+                .default_value("60")
+                .help("Keep N samples per chart line (the swap chart spans N refreshes)"),
+        )
+        .arg(
+            clap::Arg::new("resort-interval")
+                .long("resort-interval")
+                .value_name("N")
+                .default_value("1")
+                .help("Re-sort process lists only every N refresh ticks (S re-sorts now); values still update every tick"),
+        )
+        .arg(
+            clap::Arg::new("swap-alert")
+                .long("swap-alert")
+                .value_name("PERCENT")
+                .help("Log an alert when used swap crosses PERCENT of total swap"),
+        )
+        .arg(
+            clap::Arg::new("idle-gpu-util")
+                .long("idle-gpu-util")
+                .value_name("PERCENT")
+                .help("GPU view: highlight GPUs with processes but utilisation at or below PERCENT (default 0)"),
+        )
+        .arg(
+            clap::Arg::new("idle-gpu-color")
+                .long("idle-gpu-color")
+                .value_name("COLOR")
+                .help("Colour for idle-but-held GPUs: a name (e.g. magenta) or #RRGGBB; defaults to the theme's warning colour"),
+        )
+        .arg(
+            clap::Arg::new("swappiness-hint-available")
+                .long("swappiness-hint-available")
+                .value_name("PERCENT")
+                .default_value("50")
+                .help("Hint at vm.swappiness tuning when swap is in use while MemAvailable is at least PERCENT of RAM"),
+        )
+        .arg(
+            clap::Arg::new("swappiness-hint-min-mb")
+                .long("swappiness-hint-min-mb")
+                .value_name("MB")
+                .default_value("1")
+                .help("Ignore less than MB of used swap for the swappiness hint"),
+        )
+        .arg(
+            clap::Arg::new("swap-highlight-mb")
+                .long("swap-highlight-mb")
+                .value_name("SIZE")
+                .help("Highlight processes holding more than SIZE of swap (Swap and Unified views); SIZE is MB, or takes a unit such as 512M, 1G or 2GB"),
+        )
+        .arg(
+            clap::Arg::new("nvidia-smi")
+                .long("nvidia-smi")
+                .value_name("PATH")
+                .help("Run PATH as nvidia-smi instead of looking it up on PATH (overrides $NVIDIA_SMI_PATH)"),
+        )
+        .arg(
+            clap::Arg::new("gpu-pmon")
+                .long("gpu-pmon")
+                .help("GPU view: sample per-process SM/memory utilisation with `nvidia-smi pmon` (takes about a second per GPU refresh)")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("verbose")
+                .long("verbose")
+                .help("Report skipped nvidia-smi/sysfs lines on stderr, with the reason (redirect with 2>FILE)")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("gpu-alert")
+                .long("gpu-alert")
+                .value_name("PERCENT")
+                .help("Log an alert when memory use on any GPU crosses PERCENT of its total"),
+        )
+        .arg(
+            clap::Arg::new("thrash-alert")
+                .long("thrash-alert")
+                .value_name("PAGES")
+                .help("Log an alert when swap-in plus swap-out crosses PAGES pages per second"),
+        )
+        .arg(
+            clap::Arg::new("alert-log")
+                .long("alert-log")
+                .value_name("FILE")
+                .help("Append alerts to FILE (instead of syslog, or stderr when it is not a terminal); alerts are always shown on the status line"),
+        )
+        .arg(
+            clap::Arg::new("record")
+                .long("record")
+                .value_name("PATH")
+                .help("Append a CSV row per refresh (swap totals, top process, GPU totals) to PATH"),
+        )
+        .arg(
+            clap::Arg::new("record-max-mb")
+                .long("record-max-mb")
+                .value_name("MB")
+                .default_value("100")
+                .help("Rotate the --record file once it would exceed MB (PATH becomes PATH.1, and so on)"),
+        )
+        .arg(
+            clap::Arg::new("record-keep")
+                .long("record-keep")
+                .value_name("N")
+                .default_value("5")
+                .help("Rotated --record files to keep (PATH.1 is the newest); 0 keeps none"),
+        );
+    if env::var_os("CARGO_FEATURE_SERVE").is_some() {
+        cmd = cmd.arg(
+            clap::Arg::new("serve")
+                .long("serve")
+                .value_name("ADDR")
+                .help("Serve /metrics (Prometheus) and /snapshot.json over HTTP on ADDR (e.g. 127.0.0.1:9184)"),
+        )
+        .arg(
+            clap::Arg::new("headless")
+                .long("headless")
+                .action(clap::ArgAction::SetTrue)
+                .help("With --serve, only serve: no TUI; runs until interrupted"),
         );
+    }
 
     // Generate manpage
     let man = clap_mangen::Man::new(cmd);
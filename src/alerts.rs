@@ -0,0 +1,399 @@
+//! Threshold alerts for swap and GPU memory usage (`--swap-alert`,
+//! `--gpu-alert`) and for the swap in+out rate (`--thrash-alert`). Only edge
+//! transitions are reported: an alert is logged once when usage crosses its
+//! threshold and once when it drops back below.
+//!
+//! Every event is shown on the status line. It is also appended to the
+//! `--alert-log` file when one is given; otherwise, with the `syslog` feature,
+//! sent to the local syslog socket (which journald also listens on). Without
+//! either, events are written to stderr with a UTC timestamp, but only when
+//! stderr is not the terminal the TUI draws on.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::data::{GpuDevice, GpuProcessInfo, ProcessSwapInfo};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AlertThresholds {
+    /// Used swap, as a percentage of total swap
+    pub swap_percent: Option<f64>,
+    /// Used memory on the fullest GPU, as a percentage of its total
+    pub gpu_percent: Option<f64>,
+    /// Swap-in plus swap-out pages per second, from the vmstat counters
+    pub thrash_pages_per_sec: Option<f64>,
+}
+
+impl AlertThresholds {
+    pub fn is_empty(&self) -> bool {
+        self.swap_percent.is_none() && self.gpu_percent.is_none() && self.thrash_pages_per_sec.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    Swap,
+    Gpu,
+    Thrash,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertEvent {
+    pub kind: AlertKind,
+    /// true when the threshold was crossed upwards, false when it cleared
+    pub raised: bool,
+    /// Percent used, or pages per second for `Thrash`
+    pub value: f64,
+    pub threshold: f64,
+    /// (pid, name) of the largest consumer at the time of the transition
+    pub top_process: Option<(u32, String)>,
+}
+
+impl fmt::Display for AlertEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = if self.raised { "above" } else { "back below" };
+        match self.kind {
+            AlertKind::Swap | AlertKind::Gpu => {
+                let what = if self.kind == AlertKind::Swap { "swap usage" } else { "GPU memory usage" };
+                write!(f, "{} {:.1}% {} threshold {:.1}%", what, self.value, state, self.threshold)?;
+            }
+            AlertKind::Thrash => write!(
+                f,
+                "swap in+out {:.0} pages/s {} threshold {:.0} pages/s",
+                self.value, state, self.threshold
+            )?,
+        }
+        if let Some((pid, name)) = &self.top_process {
+            write!(f, "; top process {} (pid {})", name, pid)?;
+        }
+        Ok(())
+    }
+}
+
+/// Tracks which alerts are currently active so that only transitions are emitted.
+#[derive(Debug, Default)]
+pub struct AlertTracker {
+    thresholds: AlertThresholds,
+    swap_active: bool,
+    gpu_active: bool,
+    thrash_active: bool,
+}
+
+impl AlertTracker {
+    pub fn new(thresholds: AlertThresholds) -> Self {
+        Self { thresholds, ..Default::default() }
+    }
+
+    pub fn thresholds(&self) -> AlertThresholds {
+        self.thresholds
+    }
+
+    /// Compare current usage against the thresholds and return any alerts
+    /// that were raised or cleared since the previous call. `swap_pages_per_sec`
+    /// is None until two vmstat samples have been taken.
+    pub fn update(
+        &mut self,
+        used_swap_kb: u64,
+        total_swap_kb: u64,
+        swap_pages_per_sec: Option<f64>,
+        swap_procs: &[ProcessSwapInfo],
+        gpu_devices: &[GpuDevice],
+        gpu_procs: &[GpuProcessInfo],
+    ) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        let top_swap = || {
+            swap_procs
+                .iter()
+                .max_by(|a, b| a.swap_size.total_cmp(&b.swap_size))
+                .map(|p| (p.pid, p.name.clone()))
+        };
+
+        if let Some(threshold) = self.thresholds.swap_percent
+            && total_swap_kb > 0
+        {
+            let percent = used_swap_kb as f64 / total_swap_kb as f64 * 100.0;
+            if let Some(event) = transition(&mut self.swap_active, AlertKind::Swap, percent, threshold, top_swap()) {
+                events.push(event);
+            }
+        }
+
+        if let Some(threshold) = self.thresholds.thrash_pages_per_sec
+            && let Some(rate) = swap_pages_per_sec
+            && let Some(event) = transition(&mut self.thrash_active, AlertKind::Thrash, rate, threshold, top_swap())
+        {
+            events.push(event);
+        }
+
+        if let Some(threshold) = self.thresholds.gpu_percent
+            && let Some((index, percent)) = fullest_gpu(gpu_devices)
+        {
+            let top = gpu_procs
+                .iter()
+                .filter(|p| p.gpu_index == index)
+                .max_by_key(|p| p.gpu_memory_used_kb)
+                .map(|p| (p.pid, p.name.clone()));
+            if let Some(event) = transition(&mut self.gpu_active, AlertKind::Gpu, percent, threshold, top) {
+                events.push(event);
+            }
+        }
+
+        events
+    }
+}
+
+fn transition(
+    active: &mut bool,
+    kind: AlertKind,
+    value: f64,
+    threshold: f64,
+    top_process: Option<(u32, String)>,
+) -> Option<AlertEvent> {
+    let above = value >= threshold;
+    if above == *active {
+        return None;
+    }
+    *active = above;
+    Some(AlertEvent { kind, raised: above, value, threshold, top_process })
+}
+
+/// (index, used percent) of the GPU with the highest memory utilisation.
+fn fullest_gpu(devices: &[GpuDevice]) -> Option<(u32, f64)> {
+    devices
+        .iter()
+        .filter(|d| d.memory_total_kb > 0)
+        .map(|d| (d.index, d.memory_used_kb as f64 / d.memory_total_kb as f64 * 100.0))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Destination for alert events, besides the status line.
+pub enum AlertLogger {
+    /// `--alert-log` file, opened (appending) on the first event
+    File { path: PathBuf, file: Option<File> },
+    #[cfg(feature = "syslog")]
+    Syslog(syslog::Logger<syslog::LoggerBackend, syslog::Formatter3164>),
+    /// stderr, when redirected away from the terminal
+    Stderr,
+    /// Nothing beyond the status line: stderr is the TUI's terminal
+    StatusLine,
+}
+
+impl AlertLogger {
+    /// Use `log_file` when given, else connect to syslog/journald when built
+    /// with the `syslog` feature, falling back to stderr if it is not a tty.
+    pub fn connect(log_file: Option<PathBuf>) -> Self {
+        if let Some(path) = log_file {
+            return AlertLogger::File { path, file: None };
+        }
+        #[cfg(feature = "syslog")]
+        {
+            let formatter = syslog::Formatter3164 {
+                facility: syslog::Facility::LOG_DAEMON,
+                hostname: None,
+                process: env!("CARGO_PKG_NAME").into(),
+                pid: std::process::id(),
+            };
+            if let Ok(logger) = syslog::unix(formatter) {
+                return AlertLogger::Syslog(logger);
+            }
+        }
+        if io::stderr().is_terminal() {
+            AlertLogger::StatusLine
+        } else {
+            AlertLogger::Stderr
+        }
+    }
+
+    /// The `--alert-log` path, if events go to a file.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            AlertLogger::File { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Raised alerts are logged at warning severity, cleared ones at notice.
+    pub fn log(&mut self, event: &AlertEvent) -> io::Result<()> {
+        match self {
+            AlertLogger::File { path, file } => {
+                let file = match file {
+                    Some(file) => file,
+                    None => file.insert(OpenOptions::new().create(true).append(true).open(&*path)?),
+                };
+                writeln!(file, "{}", log_line(event))
+            }
+            #[cfg(feature = "syslog")]
+            AlertLogger::Syslog(logger) => {
+                let message = event.to_string();
+                let result = if event.raised {
+                    logger.warning(&message)
+                } else {
+                    logger.notice(&message)
+                };
+                result.map_err(|e| io::Error::other(e.to_string()))
+            }
+            AlertLogger::Stderr => {
+                eprintln!("{}", log_line(event));
+                Ok(())
+            }
+            AlertLogger::StatusLine => Ok(()),
+        }
+    }
+}
+
+/// "2026-10-17T09:30:00Z warning: swap usage ..."
+fn log_line(event: &AlertEvent) -> String {
+    format!("{} {}: {}", utc_now(), severity_label(event), event)
+}
+
+fn utc_now() -> String {
+    utc_timestamp(SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0))
+}
+
+/// "2026-10-17T09:30:00Z" for seconds since the Unix epoch.
+fn utc_timestamp(secs: u64) -> String {
+    // Civil-from-days: days since 1970-01-01 to a proleptic Gregorian date
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    let rem = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, rem / 3600, rem / 60 % 60, rem % 60
+    )
+}
+
+fn severity_label(event: &AlertEvent) -> &'static str {
+    if event.raised { "warning" } else { "notice" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swap_proc(pid: u32, name: &str, swap_size: f64) -> ProcessSwapInfo {
+        ProcessSwapInfo { pid, name: name.into(), swap_size, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 }
+    }
+
+    fn gpu_device(index: u32, used_kb: u64, total_kb: u64) -> GpuDevice {
+        GpuDevice {
+            index, name: "GPU".into(), memory_total_kb: total_kb, memory_used_kb: used_kb,
+            memory_free_kb: total_kb - used_kb, numa_node_id: None, temperature: None,
+            pci_bus_id: String::new(),
+            memory_reserved_kb: None,
+            mig_instances: Vec::new(),
+            utilization_percent: None,
+            memory_utilization_percent: None,
+        }
+    }
+
+    #[test]
+    fn test_swap_alert_logs_only_edges() {
+        let mut tracker = AlertTracker::new(AlertThresholds { swap_percent: Some(50.0), ..Default::default() });
+        let procs = vec![swap_proc(1, "small", 10.0), swap_proc(2, "hog", 900.0)];
+
+        assert!(tracker.update(400, 1000, None, &procs, &[], &[]).is_empty());
+
+        let events = tracker.update(600, 1000, None, &procs, &[], &[]);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].raised);
+        assert_eq!(events[0].kind, AlertKind::Swap);
+        assert_eq!(events[0].top_process, Some((2, "hog".to_string())));
+
+        // Still above: no repeat
+        assert!(tracker.update(700, 1000, None, &procs, &[], &[]).is_empty());
+
+        let events = tracker.update(100, 1000, None, &procs, &[], &[]);
+        assert_eq!(events.len(), 1);
+        assert!(!events[0].raised);
+    }
+
+    #[test]
+    fn test_swap_alert_ignored_without_swap() {
+        let mut tracker = AlertTracker::new(AlertThresholds { swap_percent: Some(0.0), ..Default::default() });
+        assert!(tracker.update(0, 0, None, &[], &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_gpu_alert_uses_fullest_device_and_its_top_process() {
+        let mut tracker = AlertTracker::new(AlertThresholds { gpu_percent: Some(90.0), ..Default::default() });
+        let devices = vec![gpu_device(0, 10, 100), gpu_device(1, 95, 100)];
+        let procs = vec![
+            GpuProcessInfo { pid: 10, name: "big_on_0".into(), gpu_index: 0, gpu_memory_used_kb: 10, sm_util: None, mem_util: None },
+            GpuProcessInfo { pid: 11, name: "trainer".into(), gpu_index: 1, gpu_memory_used_kb: 90, sm_util: None, mem_util: None },
+        ];
+        let events = tracker.update(0, 0, None, &[], &devices, &procs);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, AlertKind::Gpu);
+        assert_eq!(events[0].top_process, Some((11, "trainer".to_string())));
+    }
+
+    #[test]
+    fn test_alert_event_display() {
+        let event = AlertEvent {
+            kind: AlertKind::Swap, raised: true, value: 82.3, threshold: 80.0,
+            top_process: Some((42, "postgres".into())),
+        };
+        assert_eq!(
+            event.to_string(),
+            "swap usage 82.3% above threshold 80.0%; top process postgres (pid 42)"
+        );
+    }
+
+    #[test]
+    fn test_thrash_alert_logs_crossings_with_top_swapper() {
+        let mut tracker = AlertTracker::new(AlertThresholds { thrash_pages_per_sec: Some(1000.0), ..Default::default() });
+        let procs = vec![swap_proc(1, "small", 10.0), swap_proc(2, "hog", 900.0)];
+
+        // No rate until two vmstat samples exist
+        assert!(tracker.update(0, 0, None, &procs, &[], &[]).is_empty());
+        assert!(tracker.update(0, 0, Some(400.0), &procs, &[], &[]).is_empty());
+
+        let events = tracker.update(0, 0, Some(1520.0), &procs, &[], &[]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, AlertKind::Thrash);
+        assert!(events[0].raised);
+        assert_eq!(
+            events[0].to_string(),
+            "swap in+out 1520 pages/s above threshold 1000 pages/s; top process hog (pid 2)"
+        );
+
+        // Still thrashing: no repeat
+        assert!(tracker.update(0, 0, Some(3000.0), &procs, &[], &[]).is_empty());
+
+        let events = tracker.update(0, 0, Some(10.0), &procs, &[], &[]);
+        assert_eq!(events.len(), 1);
+        assert!(!events[0].raised);
+    }
+
+    #[test]
+    fn test_file_logger_appends_lines() {
+        let path = std::env::temp_dir().join(format!("nv-swaptop-alerts-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let event = AlertEvent { kind: AlertKind::Gpu, raised: false, value: 40.0, threshold: 90.0, top_process: None };
+        let mut logger = AlertLogger::connect(Some(path.clone()));
+        assert_eq!(logger.path(), Some(path.as_path()));
+        logger.log(&event).unwrap();
+        AlertLogger::connect(Some(path.clone())).log(&event).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("Z notice: GPU memory usage 40.0% back below threshold 90.0%"));
+    }
+
+    #[test]
+    fn test_utc_timestamp() {
+        assert_eq!(utc_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(utc_timestamp(1_791_624_659), "2026-10-10T09:30:59Z");
+    }
+}
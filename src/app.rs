@@ -1,26 +1,54 @@
-use crate::data::{ActiveView, DataProvider, GpuDevice, GpuProcessInfo, NumaNode, ProcessNumaInfo, SizeUnits, SwapUpdate, UnifiedProcessInfo};
+use crate::data::{ActiveView, Baseline, DataProvider, GpuDevice, GpuProcessInfo, GpuStatus, NumaNode, PidWatch, ProcessNames, ProcessNumaInfo, ProcessSwapInfo, SizeUnits, SwapDeltas, SwapUpdate, SwapUsedMode, SwappinessHintThresholds, ThrashLevel, UnifiedProcessInfo, UnitBase, counter_rate, swapping_with_free_ram, thrash_level};
+use crate::alerts::{AlertLogger, AlertThresholds, AlertTracker};
+use crate::config::EffectiveConfig;
+use crate::record::{RecordRow, Recorder};
 use crate::theme::{Theme, ThemeType};
 use crate::ui;
+use crate::ui::debug_overlay::FrameTimings;
+use crate::ui::gpu_view::IdleGpuHighlight;
+use crate::ui::process_list::{ListScroll, NamespaceMode};
+use crate::ui::unified_view::LocationFilter;
 use color_eyre::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::{
     DefaultTerminal, Frame,
     layout::{Constraint, Direction, Layout},
     style::{Style, Stylize},
     text::Line,
-    widgets::{Block, BorderType, ScrollbarState},
+    widgets::{Block, BorderType, Paragraph, ScrollbarState},
 };
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+// Refresh interval bounds (ms), shared by Left/Right stepping and the `e` prompt
+const MIN_TIMEOUT_MS: u64 = 1;
+const MAX_TIMEOUT_MS: u64 = 10000;
+
 // Cache TTLs
 const NUMA_TOPOLOGY_TTL: Duration = Duration::from_secs(30);
 const NUMA_MAPS_TTL: Duration = Duration::from_secs(5);
 const GPU_DEVICES_TTL: Duration = Duration::from_secs(10);
 const GPU_PROCESSES_TTL: Duration = Duration::from_secs(1);
+const DEVICE_SOURCES_TTL: Duration = Duration::from_secs(30);
+
+/// How long a status message (e.g. the outcome of `K`) stays in the footer
+const STATUS_MESSAGE_TTL: Duration = Duration::from_secs(4);
+
+/// Default samples kept per chart line, one per refresh (`--history`)
+pub const DEFAULT_HISTORY_LEN: usize = 60;
+
+/// Default cap on swap process rows built per refresh (`--max-processes`)
+pub const DEFAULT_MAX_PROCESSES: usize = 5000;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SortColumn {
     Swap,
+    /// Swap + RSS
+    Committed,
     GpuMem,
     Name,
     NumaNode,
@@ -29,16 +57,28 @@ pub enum SortColumn {
 impl SortColumn {
     pub(crate) fn next(self) -> Self {
         match self {
-            SortColumn::Swap => SortColumn::GpuMem,
+            SortColumn::Swap => SortColumn::Committed,
+            SortColumn::Committed => SortColumn::GpuMem,
             SortColumn::GpuMem => SortColumn::NumaNode,
             SortColumn::NumaNode => SortColumn::Name,
             SortColumn::Name => SortColumn::Swap,
         }
     }
 
+    /// Natural direction: largest first for sizes, A→Z / lowest first otherwise.
+    pub(crate) fn descending_by_default(self) -> bool {
+        matches!(self, SortColumn::Swap | SortColumn::Committed | SortColumn::GpuMem)
+    }
+
+    /// ▼ when sorted largest / last first, ▲ otherwise.
+    pub(crate) fn arrow(self, reversed: bool) -> char {
+        if self.descending_by_default() != reversed { '▼' } else { '▲' }
+    }
+
     fn label(self) -> &'static str {
         match self {
             SortColumn::Swap => "swap",
+            SortColumn::Committed => "swap+rss",
             SortColumn::GpuMem => "gpu_mem",
             SortColumn::Name => "name",
             SortColumn::NumaNode => "numa",
@@ -50,25 +90,146 @@ pub struct App {
     provider: Box<dyn DataProvider>,
     running: bool,
     display_devices: bool,
+    device_bars: bool,
+    /// Swap devices table columns: auto by width, or forced (`l`)
+    device_layout: ui::swap_devices::DeviceLayout,
+    numa_node_sort: ui::numa_view::NumaNodeSort,
+    /// Swap devices table: scheduler and read-ahead columns (`o`)
+    show_device_queue: bool,
+    /// Swap devices table: sizes in a readable unit and exact KB (`D`)
+    dual_units: bool,
+    maximize_processes: bool,
+    /// Swap view without the usage chart (`v`); devices still follow `h`
+    chart_hidden: bool,
+    /// Swap chart y-axis is logarithmic, ln(1 + KB) (`L`)
+    log_y: bool,
+    show_mig_instances: bool,
+    /// GPU view: one aggregate row in place of the per-GPU rows (`A`)
+    collapse_gpus: bool,
+    /// GPU view: processes grouped under a VRAM subtotal per GPU (`a`)
+    group_gpu_processes: bool,
+    /// NUMA view: topology only, without the per-process section (`P`);
+    /// its numa_maps reads are skipped too
+    numa_processes_hidden: bool,
+    idle_gpu_highlight: IdleGpuHighlight,
+    swappiness_hint: SwappinessHintThresholds,
+    /// Swap size (KB) above which process rows are highlighted
+    swap_highlight_kb: Option<u64>,
+    /// `H` hid the swappiness hint; re-armed once the condition clears
+    swappiness_hint_dismissed: bool,
+    /// Swap view: show pages swapped in/out since boot (`C`)
+    show_since_boot: bool,
+    /// Unified view: show the SWAP+RSS column (`R`)
+    show_committed: bool,
+    /// Unified view: show each process's share of its GPU's memory (`G`)
+    show_gpu_percent: bool,
+    /// Unified view: list only processes in one location (`F`)
+    location_filter: LocationFilter,
     pub vertical_scroll_state: ScrollbarState,
     pub vertical_scroll: usize,
     pub swap_size_unit: SizeUnits,
+    /// Powers of 1024 (KiB, MiB) or 1000 (kB, MB) for displayed sizes (`B`)
+    unit_base: UnitBase,
     pub swap_processes_lines: Vec<Line<'static>>,
+    swap_process_rows: Vec<ProcessSwapInfo>,
+    max_processes: usize,
+    /// Largest swap users whose numa_maps are read and shown (`--top-n`)
+    top_n: usize,
+    /// Selected swap row as (PID, name): matched by PID, or by name when
+    /// rows are aggregated, so it follows the process as rows move
+    selected: Option<(u32, String)>,
     pub last_update: Option<Instant>,
+    /// Refreshes are skipped while set (Space); keys and redraws continue
+    paused: bool,
     pub chart_info: SwapUpdate,
+    used_mode: SwapUsedMode,
     pub aggregated: bool,
     current_theme: ThemeType,
     time_window: [f64; 2],
+    /// Samples kept per chart line, and the chart's width in ticks (`--history`)
+    history_len: usize,
+    time_axis_unit: ui::chart::TimeAxisUnit,
     chart_data: Vec<(f64, f64)>,
+    /// Swap-in / swap-out pages per second, sampled with `chart_data` once
+    /// two vmstat readings give a rate
+    swap_rate_data: [Vec<(f64, f64)>; 2],
+    /// Chart cursor, in samples back from the newest (`[` / `]`)
+    chart_cursor: Option<usize>,
+    /// Swap chart series drawn: used, mean, ±1σ band (`b`, or the `y` menu)
+    chart_series: ui::chart::VisibleSeries,
+    /// Series menu open (`y`): number keys toggle chart series
+    series_menu: bool,
     timeout: u64,
+    /// Digits typed so far while the refresh-interval prompt (`e`) is open
+    interval_input: Option<String>,
+    /// Process name filter (`/`): lists show only names containing it,
+    /// ignoring case; empty shows everything
+    name_filter: String,
+    /// Filter prompt open: typed characters edit `name_filter`
+    filter_input: bool,
+    /// Pending `K` confirmation: the process and the signal to send on `y`
+    kill_prompt: Option<KillPrompt>,
+    /// Transient footer message and when it was set
+    status_message: Option<(String, Instant)>,
     visible_height: usize,
+    /// Scroll positions of the NUMA, GPU and unified tables, one per view
+    numa_scroll: ListScroll,
+    gpu_scroll: ListScroll,
+    unified_scroll: ListScroll,
     pub(crate) active_view: ActiveView,
     numa_nodes: Vec<NumaNode>,
     process_numa_infos: Vec<ProcessNumaInfo>,
+    gpu_status: GpuStatus,
     gpu_devices: Vec<GpuDevice>,
     gpu_processes: Vec<GpuProcessInfo>,
+    /// Full command lines of local GPU processes, by PID
+    gpu_cmdlines: HashMap<u32, String>,
+    /// The process query listed nothing (or failed) while the GPUs are
+    /// clearly in use: nvidia-smi is likely withholding processes
+    gpu_processes_hidden: bool,
+    /// Used VRAM (KB) per GPU index over time, one sample per device
+    /// refresh; x is seconds since `gpu_history_epoch`
+    gpu_memory_history: HashMap<u32, Vec<(f64, f64)>>,
+    gpu_history_epoch: Instant,
+    /// GPU index whose history the GPU view charts (`V` cycles)
+    charted_gpu: u32,
     unified_procs: Vec<UnifiedProcessInfo>,
+    /// `unified_procs` were grouped by name; `aggregated` may have been
+    /// toggled since, until the next refresh
+    unified_aggregated: bool,
     sort_column: SortColumn,
+    /// Invert the column's natural sort direction
+    sort_reversed: bool,
+    /// Where the unified table was last drawn, for mapping header clicks
+    unified_area: ratatui::layout::Rect,
+    // Previous (pswpin, pswpout, sampled at) for swap I/O rates
+    prev_swap_counters: Option<(u64, u64, Instant)>,
+    swap_in_rate: f64,
+    swap_out_rate: f64,
+    thrash: Option<ThrashLevel>,
+    baseline: Option<Baseline>,
+    process_names: ProcessNames,
+    /// Mount namespace column / grouping in the swap list (`n`)
+    namespace_mode: NamespaceMode,
+    swap_deltas: SwapDeltas,
+    watch: Option<PidWatch>,
+    /// Refresh ticks since startup
+    tick: u64,
+    /// Process lists are re-sorted every this many ticks (`--resort-interval`);
+    /// in between, rows keep their order and only their values update
+    resort_interval: u64,
+    ticks_since_sort: u64,
+    alerts: Option<(AlertTracker, AlertLogger)>,
+    /// `--record`: one CSV row per refresh tick
+    recorder: Option<Recorder>,
+    /// Ctrl-Z pressed; the run loop suspends before the next draw
+    suspend_requested: bool,
+    /// `.`/F5 pressed; the run loop refreshes without waiting for the tick
+    refresh_requested: bool,
+    /// Self-timing overlay (`--debug`)
+    debug: Option<FrameTimings>,
+    /// Settings captured with `w`, printed once the terminal is restored
+    config_snapshot: Option<String>,
     demo: bool,
     demo_start: Option<Instant>,
     // Cache timestamps
@@ -76,6 +237,9 @@ pub struct App {
     numa_maps_last: Option<Instant>,
     gpu_devices_last: Option<Instant>,
     gpu_processes_last: Option<Instant>,
+    /// Mount source per swap device name, for the devices panel
+    device_sources: HashMap<String, String>,
+    device_sources_last: Option<Instant>,
 }
 
 impl App {
@@ -84,45 +248,276 @@ impl App {
             provider,
             running: false,
             display_devices: false,
+            device_bars: false,
+            device_layout: ui::swap_devices::DeviceLayout::default(),
+            numa_node_sort: ui::numa_view::NumaNodeSort::default(),
+            show_device_queue: false,
+            dual_units: false,
+            maximize_processes: false,
+            chart_hidden: false,
+            log_y: false,
+            show_mig_instances: false,
+            collapse_gpus: false,
+            group_gpu_processes: false,
+            numa_processes_hidden: false,
+            idle_gpu_highlight: IdleGpuHighlight::default(),
+            swappiness_hint: SwappinessHintThresholds::default(),
+            swap_highlight_kb: None,
+            swappiness_hint_dismissed: false,
+            show_since_boot: false,
+            show_committed: false,
+            show_gpu_percent: false,
+            location_filter: LocationFilter::default(),
             vertical_scroll_state: ScrollbarState::default(),
             vertical_scroll: 0,
             swap_size_unit: SizeUnits::KB,
+            unit_base: UnitBase::default(),
             swap_processes_lines: Vec::new(),
+            swap_process_rows: Vec::new(),
+            max_processes: DEFAULT_MAX_PROCESSES,
+            top_n: crate::data::NUMA_MAPS_TOP_N,
+            selected: None,
             last_update: None,
+            paused: false,
             chart_info: SwapUpdate::default(),
+            used_mode: SwapUsedMode::default(),
             aggregated: false,
             current_theme: ThemeType::Dracula,
-            time_window: [0.0, 60.0],
+            time_window: [0.0, DEFAULT_HISTORY_LEN as f64],
+            history_len: DEFAULT_HISTORY_LEN,
+            time_axis_unit: ui::chart::TimeAxisUnit::default(),
             chart_data: Vec::new(),
+            swap_rate_data: Default::default(),
+            chart_cursor: None,
+            chart_series: ui::chart::VisibleSeries::default(),
+            series_menu: false,
             timeout: 1000,
+            interval_input: None,
+            name_filter: String::new(),
+            filter_input: false,
+            kill_prompt: None,
+            status_message: None,
             visible_height: 0,
+            numa_scroll: ListScroll::default(),
+            gpu_scroll: ListScroll::default(),
+            unified_scroll: ListScroll::default(),
             active_view: ActiveView::default(),
             numa_nodes: Vec::new(),
             process_numa_infos: Vec::new(),
+            gpu_status: GpuStatus::default(),
             gpu_devices: Vec::new(),
             gpu_processes: Vec::new(),
+            gpu_cmdlines: HashMap::new(),
+            gpu_processes_hidden: false,
+            gpu_memory_history: HashMap::new(),
+            gpu_history_epoch: Instant::now(),
+            charted_gpu: 0,
             unified_procs: Vec::new(),
+            unified_aggregated: false,
             sort_column: SortColumn::Swap,
+            sort_reversed: false,
+            unified_area: ratatui::layout::Rect::default(),
+            prev_swap_counters: None,
+            swap_in_rate: 0.0,
+            swap_out_rate: 0.0,
+            thrash: None,
+            baseline: None,
+            process_names: ProcessNames::default(),
+            namespace_mode: NamespaceMode::default(),
+            swap_deltas: SwapDeltas::default(),
+            watch: None,
+            tick: 0,
+            resort_interval: 1,
+            ticks_since_sort: 0,
+            alerts: None,
+            recorder: None,
+            suspend_requested: false,
+            refresh_requested: false,
+            debug: None,
+            config_snapshot: None,
             demo,
             demo_start: None,
             numa_topology_last: None,
             numa_maps_last: None,
             gpu_devices_last: None,
             gpu_processes_last: None,
+            device_sources: HashMap::new(),
+            device_sources_last: None,
+        }
+    }
+
+    /// Cap the number of swap process rows built per refresh.
+    pub fn with_max_processes(mut self, max_processes: usize) -> Self {
+        self.max_processes = max_processes;
+        self
+    }
+
+    /// Read and show NUMA distribution for the `top_n` largest swap users.
+    pub fn with_top_n(mut self, top_n: usize) -> Self {
+        self.top_n = top_n;
+        self
+    }
+
+    /// Keep `samples` points per chart line; the swap chart spans that many
+    /// refresh ticks.
+    pub fn with_history_len(mut self, samples: usize) -> Self {
+        self.history_len = samples;
+        self.time_window = [0.0, samples as f64];
+        self
+    }
+
+    pub fn with_resort_interval(mut self, ticks: u64) -> Self {
+        self.resort_interval = ticks.max(1);
+        self
+    }
+
+    /// Restrict all views to `pids` (`--pid`). A no-op when empty.
+    pub fn with_watched_pids(mut self, pids: Vec<u32>) -> Self {
+        if !pids.is_empty() {
+            self.watch = Some(PidWatch::new(pids));
+        }
+        self
+    }
+
+    /// Start with `theme` (`--theme`). A no-op when `None`.
+    pub fn with_theme(mut self, theme: Option<ThemeType>) -> Self {
+        if let Some(theme) = theme {
+            self.current_theme = theme;
+        }
+        self
+    }
+
+    pub fn with_used_mode(mut self, used_mode: SwapUsedMode) -> Self {
+        self.used_mode = used_mode;
+        self
+    }
+
+    /// Enable threshold alerts, also appended to `log_file` when given.
+    /// A no-op when no threshold is set.
+    pub fn with_alerts(mut self, thresholds: AlertThresholds, log_file: Option<PathBuf>) -> Self {
+        if !thresholds.is_empty() {
+            self.alerts = Some((AlertTracker::new(thresholds), AlertLogger::connect(log_file)));
+        }
+        self
+    }
+
+    /// Configure the GPU view's idle-but-held highlight.
+    pub fn with_idle_gpu_highlight(mut self, highlight: IdleGpuHighlight) -> Self {
+        self.idle_gpu_highlight = highlight;
+        self
+    }
+
+    pub fn with_swappiness_hint(mut self, thresholds: SwappinessHintThresholds) -> Self {
+        self.swappiness_hint = thresholds;
+        self
+    }
+
+    /// Highlight process rows holding more than `kb` of swap.
+    pub fn with_swap_highlight(mut self, kb: Option<u64>) -> Self {
+        self.swap_highlight_kb = kb;
+        self
+    }
+
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Show render/refresh/nvidia-smi timings in a corner overlay.
+    pub fn with_debug(mut self, debug: bool) -> Self {
+        self.debug = debug.then(FrameTimings::default);
+        self
+    }
+
+    /// The effective settings: startup flags plus the current toggles.
+    pub fn effective_config(&self) -> EffectiveConfig {
+        let thresholds = self
+            .alerts
+            .as_ref()
+            .map(|(tracker, _)| tracker.thresholds())
+            .unwrap_or_default();
+        EffectiveConfig {
+            interval_ms: self.timeout,
+            unit: match self.swap_size_unit {
+                SizeUnits::KB => "kb",
+                SizeUnits::MB => "mb",
+                SizeUnits::GB => "gb",
+                SizeUnits::TB => "tb",
+            },
+            unit_base: self.unit_base.label(),
+            theme: self.current_theme,
+            view: match self.active_view {
+                ActiveView::Swap => "swap",
+                ActiveView::Numa => "numa",
+                ActiveView::Gpu => "gpu",
+                ActiveView::Unified => "unified",
+            },
+            sort: self.sort_column.label(),
+            sort_reversed: self.sort_reversed,
+            resort_interval: self.resort_interval,
+            aggregated: self.aggregated,
+            namespace: match self.namespace_mode {
+                NamespaceMode::Off => "off",
+                NamespaceMode::Annotate => "annotate",
+                NamespaceMode::Group => "group",
+            },
+            exclude_swap_cache: self.used_mode == SwapUsedMode::ExcludeCached,
+            max_processes: self.max_processes,
+            top_n: self.top_n,
+            history_len: self.history_len,
+            pid: self.watch.as_ref().map(|w| w.pids().to_vec()).unwrap_or_default(),
+            swap_alert: thresholds.swap_percent,
+            gpu_alert: thresholds.gpu_percent,
+            thrash_alert: thresholds.thrash_pages_per_sec,
+            alert_log: self.alerts.as_ref().and_then(|(_, logger)| logger.path().map(PathBuf::from)),
+            idle_gpu_util: self.idle_gpu_highlight.max_utilization_percent,
+            idle_gpu_color: self.idle_gpu_highlight.color,
+            swappiness_hint_available: self.swappiness_hint.available_percent,
+            swappiness_hint_min_mb: self.swappiness_hint.min_used_kb / 1024,
+            swap_highlight_mb: self.swap_highlight_kb.map(|kb| kb / 1024),
+            record: self.recorder.as_ref().map(|r| (r.path().to_path_buf(), r.max_bytes() / (1024 * 1024), r.keep())),
+            show_devices: self.display_devices,
+            device_bars: self.device_bars,
+            device_layout: self.device_layout.label(),
+            numa_node_sort: self.numa_node_sort.label(),
+            numa_processes_hidden: self.numa_processes_hidden,
+            device_queue: self.show_device_queue,
+            dual_units: self.dual_units,
+            maximize_processes: self.maximize_processes,
+            chart_hidden: self.chart_hidden,
+            log_y: self.log_y,
+            chart_series: self.chart_series.labels(),
+            show_mig_instances: self.show_mig_instances,
+            collapse_gpus: self.collapse_gpus,
+            group_gpu_processes: self.group_gpu_processes,
+            show_committed: self.show_committed,
+            show_gpu_percent: self.show_gpu_percent,
+            location_filter: match self.location_filter {
+                LocationFilter::All => "all",
+                LocationFilter::CpuOnly => "cpu_only",
+                LocationFilter::GpuOnly => "gpu_only",
+                LocationFilter::CpuAndGpu => "cpu_and_gpu",
+            },
+            since_boot: self.show_since_boot,
+            time_axis: match self.time_axis_unit {
+                ui::chart::TimeAxisUnit::Ticks => "ticks",
+                ui::chart::TimeAxisUnit::Seconds => "seconds",
+                ui::chart::TimeAxisUnit::Minutes => "minutes",
+            },
         }
     }
 
-    pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
+    /// Runs until quit. Returns the TOML captured with `w`, if any, for the
+    /// caller to print after restoring the terminal.
+    pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<Option<String>> {
         self.running = true;
         if self.demo {
             self.demo_start = Some(Instant::now());
         }
-        self.swap_processes_lines = ui::process_list::create_process_lines(
-            self.provider.as_ref(),
-            &self.swap_size_unit,
-            self.aggregated,
-        );
+        self.refresh_process_lines(0.0, true);
         self.chart_info = self.provider.get_swap_info(&self.swap_size_unit)?;
+        self.refresh_device_sources();
+        self.update_swap_rates();
         self.refresh_numa_data();
         self.refresh_gpu_data();
         self.last_update = Some(Instant::now());
@@ -131,35 +526,401 @@ impl App {
             if event::poll(Duration::from_millis(100))? {
                 self.handle_crossterm_events()?;
             }
+            if self.suspend_requested {
+                self.suspend_requested = false;
+                suspend(&mut terminal)?;
+            }
 
-            if let Some(last_update) = self.last_update
-                && last_update.elapsed() >= Duration::from_millis(self.timeout)
-            {
-                self.chart_info = self.provider.get_swap_info(&self.swap_size_unit)?;
-                self.update_chart_data();
-                self.last_update = Some(Instant::now());
-                self.swap_processes_lines = ui::process_list::create_process_lines(
-                    self.provider.as_ref(),
-                    &self.swap_size_unit,
-                    self.aggregated,
-                );
-                if self.active_view == ActiveView::Numa || self.active_view == ActiveView::Unified {
-                    self.refresh_numa_data();
-                }
-                if self.active_view == ActiveView::Gpu || self.active_view == ActiveView::Unified {
-                    self.refresh_gpu_data();
-                }
-                if self.active_view == ActiveView::Unified {
-                    self.refresh_unified_data();
-                }
+            if self.refresh_due() {
+                self.refresh()?;
             }
 
+            let draw_start = Instant::now();
             terminal.draw(|frame| self.render(frame))?;
+            if let Some(debug) = self.debug.as_mut() {
+                debug.render = draw_start.elapsed();
+            }
             self.demo_auto_cycle();
         }
+        Ok(self.config_snapshot)
+    }
+
+    /// A refresh is due once the interval has elapsed, or on request (`.`),
+    /// unless paused with Space.
+    fn refresh_due(&self) -> bool {
+        !self.paused
+            && self.last_update.is_some_and(|last_update| {
+                self.refresh_requested || last_update.elapsed() >= Duration::from_millis(self.timeout)
+            })
+    }
+
+    /// One refresh tick: sample swap, processes and the active view's data.
+    fn refresh(&mut self) -> Result<()> {
+        self.refresh_requested = false;
+        let refresh_start = Instant::now();
+        let since_last = self.last_update.map_or(0.0, |last_update| last_update.elapsed().as_secs_f64());
+        self.tick += 1;
+        let resort = self.take_resort_due();
+        self.update_watch();
+        self.chart_info = self.provider.get_swap_info(&self.swap_size_unit)?.or_previous(&self.chart_info);
+        self.refresh_device_sources();
+        self.update_swap_rates();
+        self.rearm_swappiness_hint();
+        self.update_chart_data();
+        self.last_update = Some(Instant::now());
+        self.refresh_process_lines(since_last, resort);
+        if self.active_view == ActiveView::Numa || self.active_view == ActiveView::Unified {
+            self.refresh_numa_data();
+        }
+        if self.active_view == ActiveView::Gpu || self.active_view == ActiveView::Unified {
+            self.refresh_gpu_data();
+        }
+        if self.active_view == ActiveView::Unified {
+            self.refresh_unified_data(resort);
+        }
+        self.check_alerts();
+        self.record_tick()?;
+        if let Some(debug) = self.debug.as_mut() {
+            debug.refresh = refresh_start.elapsed();
+        }
+        Ok(())
+    }
+
+    fn rebuild_process_lines(&mut self) {
+        self.collect_process_rows();
+        self.build_process_lines();
+    }
+
+    /// Refresh-tick variant of `rebuild_process_lines` that also samples the
+    /// per-process swap rates, `elapsed_secs` after the previous sample.
+    /// Other rebuilds (unit, aggregation, baseline changes) leave them alone.
+    /// Without `resort`, rows keep their previous order.
+    fn refresh_process_lines(&mut self, elapsed_secs: f64, resort: bool) {
+        let previous = std::mem::take(&mut self.swap_process_rows);
+        self.collect_process_rows();
+        if !resort {
+            ui::process_list::keep_previous_order(&mut self.swap_process_rows, &previous, ui::process_list::swap_row_key);
+        }
+        self.swap_deltas.update(&self.swap_process_rows, &self.swap_size_unit, self.rows_aggregated(), elapsed_secs);
+        self.build_process_lines();
+    }
+
+    fn collect_process_rows(&mut self) {
+        self.swap_process_rows = ui::process_list::collect_process_rows(
+            self.provider.as_ref(),
+            &self.swap_size_unit,
+            self.aggregated,
+            self.namespace_mode == NamespaceMode::Group,
+            self.watch.as_mut(),
+        );
+        // Aggregated rows have no PID of their own
+        if !self.rows_aggregated() {
+            self.process_names.observe(&self.swap_process_rows);
+        }
+    }
+
+    fn build_process_lines(&mut self) {
+        // Drop the selection once its process is no longer listed
+        if self.selected_row().is_none() {
+            self.selected = None;
+        }
+        self.swap_processes_lines = ui::process_list::create_process_lines(
+            &self.swap_process_rows,
+            &self.swap_size_unit,
+            self.unit_base,
+            self.rows_aggregated(),
+            self.baseline.as_ref(),
+            &self.process_names,
+            &self.swap_deltas,
+            self.namespace_mode,
+            self.provider.host_mnt_ns(),
+            self.max_processes,
+            self.swap_highlight_kb.map(|kb| (kb, Theme::from(self.current_theme).critical)),
+            &self.name_filter,
+        );
+    }
+
+    /// Swap rows are groups (by name with `a`, or by mount namespace) rather
+    /// than single processes.
+    fn rows_aggregated(&self) -> bool {
+        self.aggregated || self.namespace_mode == NamespaceMode::Group
+    }
+
+    /// Note watched PIDs that have exited; called once per refresh tick.
+    fn update_watch(&mut self) {
+        if let Some(watch) = self.watch.as_mut() {
+            let provider = self.provider.as_ref();
+            watch.update(self.tick, |pid| provider.is_process_alive(pid));
+        }
+    }
+
+    /// Select the process with the most swap and scroll it into view.
+    fn jump_to_heaviest(&mut self) {
+        // Index among the rows the filter leaves, as listed
+        let shown: Vec<ProcessSwapInfo> =
+            ui::process_list::shown_rows(&self.swap_process_rows, &self.name_filter, self.max_processes)
+                .cloned()
+                .collect();
+        let Some(row) = ui::process_list::heaviest_row(&shown) else {
+            return;
+        };
+        self.select_row(row);
+    }
+
+    /// Select the process listed at swap row `row` and scroll it into view.
+    fn select_row(&mut self, row: usize) {
+        self.selected = ui::process_list::shown_rows(&self.swap_process_rows, &self.name_filter, self.max_processes)
+            .nth(row)
+            .map(|p| (p.pid, p.name.clone()));
+        // +1 for the header line; the list block has a 2-line border
+        self.vertical_scroll = ui::process_list::scroll_to_reveal(
+            row + 1,
+            self.vertical_scroll,
+            self.visible_height.saturating_sub(2),
+        );
+        self.vertical_scroll_state = self.vertical_scroll_state.position(self.vertical_scroll);
+    }
+
+    /// ▲/▼ in the Swap view: move the selection one row, starting from the
+    /// top row when nothing is selected yet.
+    fn move_selection(&mut self, down: bool) {
+        let shown = ui::process_list::shown_rows(&self.swap_process_rows, &self.name_filter, self.max_processes).count();
+        if shown == 0 {
+            return;
+        }
+        let row = match (self.selected_row(), down) {
+            (None, _) => 0,
+            (Some(row), true) => (row + 1).min(shown - 1),
+            (Some(row), false) => row.saturating_sub(1),
+        };
+        self.select_row(row);
+    }
+
+    /// Index of the selected process among the listed rows, if it is
+    /// still listed.
+    fn selected_row(&self) -> Option<usize> {
+        let (pid, name) = self.selected.as_ref()?;
+        let aggregated = self.rows_aggregated();
+        ui::process_list::shown_rows(&self.swap_process_rows, &self.name_filter, self.max_processes)
+            .position(|p| if aggregated { p.name == *name } else { p.pid == *pid })
+    }
+
+    /// `K`: ask to confirm sending SIGTERM to the selected process.
+    fn open_kill_prompt(&mut self) {
+        if self.active_view != ActiveView::Swap || self.selected_row().is_none() {
+            self.set_status("K: select a process with ▲/▼ first");
+            return;
+        }
+        if self.rows_aggregated() {
+            self.set_status("K: grouped rows have no single PID");
+            return;
+        }
+        if let Some((pid, name)) = self.selected.clone() {
+            self.kill_prompt = Some(KillPrompt { pid, name, signal: KillSignal::Term });
+        }
+    }
+
+    /// Keys while the kill confirmation is open: `y` sends, `K` switches to
+    /// SIGKILL, anything else cancels.
+    fn on_kill_prompt_key(&mut self, code: KeyCode) {
+        let Some(prompt) = self.kill_prompt.as_mut() else { return };
+        match code {
+            KeyCode::Char('K') => prompt.signal = KillSignal::Kill,
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                let prompt = self.kill_prompt.take().expect("checked above");
+                // Rows may have refreshed while the prompt was open
+                let listed = !self.rows_aggregated() && self.swap_process_rows.iter().any(|p| p.pid == prompt.pid);
+                let message = if !listed {
+                    format!("kill {}: no longer listed, nothing sent", prompt.pid)
+                } else {
+                    match send_signal(prompt.pid, prompt.signal) {
+                        Ok(()) => format!("sent {} to {} ({})", prompt.signal.name(), prompt.pid, prompt.name),
+                        Err(e) => kill_error_message(prompt.pid, &e),
+                    }
+                };
+                self.set_status(&message);
+                self.request_refresh();
+            }
+            _ => self.kill_prompt = None,
+        }
+    }
+
+    fn set_status(&mut self, message: &str) {
+        self.status_message = Some((message.to_string(), Instant::now()));
+    }
+
+    /// The status message, until it expires.
+    fn status_line(&self, theme: &Theme) -> Line<'static> {
+        match &self.status_message {
+            Some((message, since)) if since.elapsed() < STATUS_MESSAGE_TTL => {
+                Line::from(format!(" {} ", message)).bold().fg(theme.secondary)
+            }
+            _ => Line::default(),
+        }
+    }
+
+    /// Zero the display against the current per-process swap/GPU usage,
+    /// or clear the baseline if one is already set.
+    fn toggle_baseline(&mut self) {
+        if self.baseline.take().is_none() {
+            let swap_procs = self.provider.get_processes_swap(&SizeUnits::KB).unwrap_or_default();
+            let gpu_procs = if self.provider.is_gpu_available() {
+                self.provider.get_gpu_processes().unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            self.baseline = Some(Baseline::capture(&swap_procs, &gpu_procs));
+        }
+        self.rebuild_process_lines();
+    }
+
+    /// Resolve the mount source of newly listed swap devices. Known devices
+    /// keep their cached source until the TTL expires.
+    fn refresh_device_sources(&mut self) {
+        let expired = self
+            .device_sources_last
+            .map(|t| t.elapsed() >= DEVICE_SOURCES_TTL)
+            .unwrap_or(true);
+        if expired {
+            self.device_sources.clear();
+            self.device_sources_last = Some(Instant::now());
+        }
+        crate::data::swap::resolve_device_sources(
+            &mut self.device_sources,
+            &self.chart_info.swap_devices,
+            crate::data::swap::find_mount_device,
+        );
+    }
+
+    /// Derive swap-in/out pages per second from the cumulative vmstat counters
+    /// and re-score the thrash level. Needs two samples before a level is shown.
+    fn update_swap_rates(&mut self) {
+        let (Some(swap_in), Some(swap_out)) =
+            (self.chart_info.swap_in_pages, self.chart_info.swap_out_pages)
+        else {
+            self.thrash = None;
+            return;
+        };
+        let now = Instant::now();
+        if let Some((prev_in, prev_out, prev_at)) = self.prev_swap_counters {
+            let elapsed = now.duration_since(prev_at).as_secs_f64();
+            self.swap_in_rate = counter_rate(prev_in, swap_in, elapsed);
+            self.swap_out_rate = counter_rate(prev_out, swap_out, elapsed);
+            self.thrash = Some(thrash_level(
+                self.chart_info.memory_full_avg10,
+                self.swap_in_rate + self.swap_out_rate,
+            ));
+        }
+        self.prev_swap_counters = Some((swap_in, swap_out, now));
+    }
+
+    /// Show any alert transitions on the status line and log them. Alerts are
+    /// evaluated in KB regardless of the display unit, against the swap rows
+    /// as listed (so `--pid` applies). GPU data is refreshed (TTL-cached) even
+    /// when the GPU view is not active, but only for a GPU threshold.
+    fn check_alerts(&mut self) {
+        let Some(thresholds) = self.alerts.as_ref().map(|(tracker, _)| tracker.thresholds()) else {
+            return;
+        };
+        if thresholds.gpu_percent.is_some() {
+            self.refresh_gpu_data();
+        }
+        // Swap totals are KB in every unit; reuse this tick's sample, which
+        // has already had a torn meminfo read replaced
+        let (used_kb, total_kb) = (self.chart_info.used_kb(self.used_mode), self.chart_info.total_swap);
+        let swap_procs: Vec<ProcessSwapInfo> = self
+            .swap_process_rows
+            .iter()
+            .map(|row| ProcessSwapInfo {
+                swap_size: crate::data::unit_to_kb(row.swap_size, &self.swap_size_unit) as f64,
+                ..row.clone()
+            })
+            .collect();
+        // A thrash level is only scored once two vmstat samples give a rate
+        let swap_rate = self.thrash.map(|_| self.swap_in_rate + self.swap_out_rate);
+        let Some((tracker, logger)) = self.alerts.as_mut() else {
+            return;
+        };
+        let events = tracker.update(
+            used_kb,
+            total_kb,
+            swap_rate,
+            &swap_procs,
+            &self.gpu_devices,
+            &self.gpu_processes,
+        );
+        let mut messages = Vec::new();
+        for event in &events {
+            let mut message = format!("alert: {}", event);
+            if let Err(e) = logger.log(event) {
+                message.push_str(&format!(" (not logged: {})", e));
+            }
+            messages.push(message);
+        }
+        if !messages.is_empty() {
+            self.set_status(&messages.join("; "));
+        }
+    }
+
+    /// Append this tick's row to the `--record` file. Like alerts, GPU data is
+    /// refreshed (TTL-cached) whatever the active view. The top process is
+    /// the largest swap row as shown, so it follows `--pid` and aggregation.
+    fn record_tick(&mut self) -> Result<()> {
+        if self.recorder.is_none() {
+            return Ok(());
+        }
+        self.refresh_gpu_data();
+        let top_process = ui::process_list::heaviest_row(&self.swap_process_rows).map(|i| {
+            let row = &self.swap_process_rows[i];
+            let pid = (!self.rows_aggregated()).then_some(row.pid);
+            (pid, row.name.clone(), crate::data::unit_to_kb(row.swap_size, &self.swap_size_unit))
+        });
+        let gpu = (!self.gpu_devices.is_empty()).then(|| {
+            (
+                self.gpu_devices.iter().map(|d| d.memory_used_kb).sum(),
+                self.gpu_devices.iter().map(|d| d.memory_total_kb).sum(),
+            )
+        });
+        let row = RecordRow {
+            timestamp_ms: crate::record::now_ms(),
+            total_swap_kb: self.chart_info.total_swap,
+            used_swap_kb: self.chart_info.used_kb(self.used_mode),
+            top_process,
+            gpu,
+        };
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder
+                .record(&row)
+                .map_err(|e| color_eyre::eyre::eyre!("cannot write --record file {}: {}", recorder.path().display(), e))?;
+        }
         Ok(())
     }
 
+    /// Refresh on the next loop iteration, bypassing the NUMA and GPU caches
+    /// that feed the active view (and the GPU cache when alerts or `--record`
+    /// need it) and the swap device sources.
+    fn request_refresh(&mut self) {
+        self.refresh_requested = true;
+        self.device_sources_last = None;
+        if matches!(self.active_view, ActiveView::Numa | ActiveView::Unified) {
+            self.numa_topology_last = None;
+            self.numa_maps_last = None;
+        }
+        if matches!(self.active_view, ActiveView::Gpu | ActiveView::Unified) || self.alerts.is_some() || self.recorder.is_some() {
+            self.gpu_devices_last = None;
+            self.gpu_processes_last = None;
+        }
+    }
+
+    /// Show or hide the NUMA per-process section. Showing it again drops the
+    /// numa_maps cache, which went stale while hidden.
+    fn toggle_numa_processes(&mut self) {
+        self.numa_processes_hidden = !self.numa_processes_hidden;
+        if !self.numa_processes_hidden {
+            self.numa_maps_last = None;
+        }
+    }
+
     fn refresh_numa_data(&mut self) {
         if !self.provider.is_numa_available() {
             return;
@@ -178,8 +939,14 @@ impl App {
             self.numa_topology_last = Some(Instant::now());
         }
 
-        // NUMA maps: 5s TTL, only when NUMA or Unified view active
-        if self.active_view != ActiveView::Numa && self.active_view != ActiveView::Unified {
+        // NUMA maps: 5s TTL, only when Unified or the NUMA per-process
+        // section is shown
+        let maps_shown = match self.active_view {
+            ActiveView::Numa => !self.numa_processes_hidden,
+            ActiveView::Unified => true,
+            _ => false,
+        };
+        if !maps_shown {
             return;
         }
 
@@ -189,20 +956,20 @@ impl App {
             .unwrap_or(true);
 
         if should_refresh_maps {
-            let mut infos = Vec::new();
-            if let Ok(mut procs) = self.provider.get_processes_swap(&self.swap_size_unit) {
-                procs.sort_by(|a, b| {
-                    b.swap_size
-                        .partial_cmp(&a.swap_size)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                });
-                for proc in procs.iter().take(20) {
-                    if let Ok(mut info) = self.provider.get_process_numa_maps(proc.pid, &proc.name) {
-                        info.cpu_node = proc.last_cpu
-                            .and_then(|cpu| crate::data::numa::cpu_to_numa_node(cpu, &self.numa_nodes));
-                        infos.push(info);
-                    }
-                }
+            let mut procs = self.provider.get_processes_swap(&self.swap_size_unit).unwrap_or_default();
+            if let Some(watch) = self.watch.as_mut() {
+                watch.filter_swap(&mut procs);
+            }
+            let infos = crate::data::collect_process_numa_infos(
+                self.provider.as_ref(),
+                &procs,
+                &self.numa_nodes,
+                self.top_n,
+            );
+            // Memory on a node the topology doesn't know about (hotplug): re-read
+            // the topology next tick instead of waiting out its TTL
+            if infos.iter().any(|info| info.kb_outside(&self.numa_nodes) > 0) {
+                self.numa_topology_last = None;
             }
             self.process_numa_infos = infos;
             self.numa_maps_last = Some(Instant::now());
@@ -210,22 +977,62 @@ impl App {
     }
 
     fn refresh_gpu_data(&mut self) {
-        if !self.provider.is_gpu_available() {
-            return;
+        let start = Instant::now();
+        let called = self.fetch_gpu_data();
+        if called && let Some(debug) = self.debug.as_mut() {
+            debug.nvidia_smi = Some(start.elapsed());
+        }
+    }
+
+    /// Append each GPU's used VRAM to its history.
+    fn record_gpu_memory(&mut self) {
+        let x = self.gpu_history_epoch.elapsed().as_secs_f64();
+        for device in &self.gpu_devices {
+            let history = self.gpu_memory_history.entry(device.index).or_default();
+            push_chart_sample(history, (x, device.memory_used_kb as f64), self.history_len);
         }
+    }
+
+    /// `V`: chart the next GPU, by index, wrapping to the first.
+    fn cycle_charted_gpu(&mut self) {
+        let mut indices: Vec<u32> = self.gpu_devices.iter().map(|d| d.index).collect();
+        indices.sort_unstable();
+        self.charted_gpu = indices
+            .iter()
+            .copied()
+            .find(|&index| index > self.charted_gpu)
+            .or(indices.first().copied())
+            .unwrap_or(0);
+    }
+
+    /// The charted GPU's index and VRAM history, if it has any samples.
+    fn charted_gpu_history(&self) -> Option<(u32, &[(f64, f64)])> {
+        let history = self.gpu_memory_history.get(&self.charted_gpu)?;
+        (!history.is_empty()).then_some((self.charted_gpu, history.as_slice()))
+    }
 
-        // Devices: 10s TTL
+    /// Returns whether nvidia-smi was invoked (i.e. any cache had expired).
+    fn fetch_gpu_data(&mut self) -> bool {
+        // Devices and nvidia-smi status: 10s TTL
         let should_refresh_devices = self
             .gpu_devices_last
             .map(|t| t.elapsed() >= GPU_DEVICES_TTL)
             .unwrap_or(true);
 
         if should_refresh_devices {
-            if let Ok(devices) = self.provider.get_gpu_devices() {
-                self.gpu_devices = devices;
-            }
+            self.gpu_status = self.provider.gpu_status();
             self.gpu_devices_last = Some(Instant::now());
         }
+        if self.gpu_status != GpuStatus::Available {
+            return should_refresh_devices;
+        }
+
+        if should_refresh_devices
+            && let Ok(devices) = self.provider.get_gpu_devices()
+        {
+            self.gpu_devices = devices;
+            self.record_gpu_memory();
+        }
 
         // Processes: 1s TTL
         let should_refresh_procs = self
@@ -234,32 +1041,75 @@ impl App {
             .unwrap_or(true);
 
         if should_refresh_procs {
-            if let Ok(procs) = self.provider.get_gpu_processes() {
+            let result = self.provider.get_gpu_processes();
+            let listed = result.as_ref().is_ok_and(|procs| !procs.is_empty());
+            if let Ok(mut procs) = result {
+                if let Some(watch) = self.watch.as_mut() {
+                    watch.filter_gpu(&mut procs);
+                }
+                self.gpu_cmdlines = self.provider.gpu_process_cmdlines(&procs);
                 self.gpu_processes = procs;
             }
+            self.gpu_processes_hidden = !listed && crate::data::gpu::processes_hidden(&self.gpu_devices);
             self.gpu_processes_last = Some(Instant::now());
         }
+        should_refresh_devices || should_refresh_procs
+    }
+
+    /// Whether this refresh tick may reorder the process lists: one tick in
+    /// every `resort_interval`.
+    fn take_resort_due(&mut self) -> bool {
+        self.ticks_since_sort += 1;
+        if self.ticks_since_sort >= self.resort_interval {
+            self.ticks_since_sort = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Re-sort both process lists now (`S`) and restart the resort interval.
+    fn resort_now(&mut self) {
+        self.ticks_since_sort = 0;
+        self.rebuild_process_lines();
+        self.sort_unified_procs();
     }
 
-    fn refresh_unified_data(&mut self) {
-        let swap_procs = self
+    /// Without `resort`, rows keep their previous order.
+    fn refresh_unified_data(&mut self, resort: bool) {
+        // Unified rows hold KB and are converted at render time
+        let mut swap_procs = self
             .provider
-            .get_processes_swap(&self.swap_size_unit)
+            .get_processes_swap(&SizeUnits::KB)
             .unwrap_or_default();
-        self.unified_procs = crate::data::merge_process_data(
+        if let Some(watch) = self.watch.as_mut() {
+            watch.filter_swap(&mut swap_procs);
+            swap_procs.extend(watch.exited_rows());
+        }
+        let merged = crate::data::merge_process_data(
             &swap_procs,
             &self.gpu_processes,
             &self.process_numa_infos,
             &self.numa_nodes,
             &self.gpu_devices,
         );
+        let previous = std::mem::take(&mut self.unified_procs);
+        self.unified_procs = if self.aggregated { crate::data::aggregate_unified(merged) } else { merged };
+        self.unified_aggregated = self.aggregated;
         self.sort_unified_procs();
+        if !resort {
+            // Aggregated rows all have PID 0
+            ui::process_list::keep_previous_order(&mut self.unified_procs, &previous, |p| (p.pid, p.name.clone()));
+        }
     }
 
     fn sort_unified_procs(&mut self) {
         match self.sort_column {
             SortColumn::Swap => {
-                self.unified_procs.sort_by(|a, b| b.swap_kb.cmp(&a.swap_kb));
+                self.unified_procs.sort_by_key(|p| std::cmp::Reverse(p.swap_kb));
+            }
+            SortColumn::Committed => {
+                self.unified_procs.sort_by_key(|p| std::cmp::Reverse(p.committed_kb()));
             }
             SortColumn::GpuMem => {
                 self.unified_procs.sort_by(|a, b| {
@@ -273,6 +1123,45 @@ impl App {
                 self.unified_procs.sort_by(|a, b| a.cpu_nodes.first().cmp(&b.cpu_nodes.first()));
             }
         }
+        if self.sort_reversed {
+            self.unified_procs.reverse();
+        }
+    }
+
+    /// The SWAP+RSS column is shown when toggled on, or while it is the sort key.
+    fn committed_column_visible(&self) -> bool {
+        self.show_committed || self.sort_column == SortColumn::Committed
+    }
+
+    /// Sort by `column`; choosing the active column again flips the direction.
+    fn sort_by_column(&mut self, column: SortColumn) {
+        if self.sort_column == column {
+            self.sort_reversed = !self.sort_reversed;
+        } else {
+            self.sort_column = column;
+            self.sort_reversed = false;
+        }
+        self.sort_unified_procs();
+    }
+
+    /// Left click on a unified view column header sorts by that column.
+    fn on_mouse_event(&mut self, mouse: MouseEvent) {
+        if self.active_view != ActiveView::Unified
+            || mouse.kind != MouseEventKind::Down(MouseButton::Left)
+        {
+            return;
+        }
+        // The header is the first row inside the table's border; only the
+        // rows below it scroll
+        let area = self.unified_area;
+        let header_y = area.y + 1;
+        if mouse.row != header_y || mouse.column <= area.x || mouse.column >= area.right() {
+            return;
+        }
+        let x = mouse.column - area.x - 1;
+        if let Some(column) = ui::unified_view::header_column_at(x, self.numa_nodes.len(), self.committed_column_visible()) {
+            self.sort_by_column(column);
+        }
     }
 
     fn render(&mut self, frame: &mut Frame) {
@@ -284,108 +1173,192 @@ impl App {
         match self.active_view {
             ActiveView::Swap => self.render_swap_view(frame, main_area, &theme),
             ActiveView::Numa => {
+                // Taken out for the call: the filtered rows borrow self
+                let mut scroll = std::mem::take(&mut self.numa_scroll);
                 ui::numa_view::render_numa_view(
                     frame,
                     main_area,
                     &theme,
                     &self.numa_nodes,
-                    &self.process_numa_infos,
+                    &self.filtered(&self.process_numa_infos, |info| self.filter_matches(&info.name)),
                     self.provider.is_numa_available(),
                     &self.swap_size_unit,
+                    self.unit_base,
+                    self.numa_node_sort,
+                    !self.numa_processes_hidden,
+                    self.top_n,
+                    &mut scroll,
                 );
+                self.numa_scroll = scroll;
             }
             ActiveView::Gpu => {
+                let mut scroll = std::mem::take(&mut self.gpu_scroll);
                 ui::gpu_view::render_gpu_view(
                     frame,
                     main_area,
                     &theme,
                     &self.gpu_devices,
-                    &self.gpu_processes,
-                    self.provider.is_gpu_available(),
+                    // The list shows command lines where known, so match those too
+                    &self.filtered(&self.gpu_processes, |p| {
+                        self.filter_matches(&p.name) || self.gpu_cmdlines.get(&p.pid).is_some_and(|c| self.filter_matches(c))
+                    }),
+                    &self.gpu_status,
                     &self.swap_size_unit,
+                    self.unit_base,
+                    self.show_mig_instances,
+                    &self.idle_gpu_highlight,
+                    &self.gpu_cmdlines,
+                    self.collapse_gpus,
+                    self.group_gpu_processes,
+                    self.gpu_processes_hidden,
+                    self.charted_gpu_history(),
+                    &mut scroll,
                 );
+                self.gpu_scroll = scroll;
             }
             ActiveView::Unified => {
+                self.unified_area = main_area;
+                let mut scroll = std::mem::take(&mut self.unified_scroll);
                 ui::unified_view::render_unified_view(
                     frame,
                     main_area,
                     &theme,
-                    &self.unified_procs,
+                    &self.filtered(&self.unified_procs, |p| self.filter_matches(&p.name)),
                     &self.swap_size_unit,
+                    self.unit_base,
                     &self.numa_nodes,
+                    self.unified_aggregated,
+                    self.baseline.as_ref(),
+                    self.sort_column,
+                    self.sort_reversed,
+                    self.committed_column_visible(),
+                    &self.gpu_devices,
+                    self.show_gpu_percent,
+                    self.location_filter,
+                    self.chart_info.total_swap,
+                    self.swap_highlight_kb,
+                    &mut scroll,
                 );
+                self.unified_scroll = scroll;
             }
         }
 
         frame.render_widget(main_block, frame.area());
+        if let Some(timings) = &self.debug {
+            ui::debug_overlay::render_debug_overlay(frame, main_area, &theme, timings);
+        }
+    }
+
+    fn filter_matches(&self, name: &str) -> bool {
+        ui::process_list::name_matches(name, &self.name_filter)
+    }
+
+    /// The `items` the `/` filter leaves; borrowed as they are when no
+    /// filter is set.
+    fn filtered<'a, T: Clone>(&self, items: &'a [T], matches: impl Fn(&T) -> bool) -> Cow<'a, [T]> {
+        if self.name_filter.is_empty() {
+            return Cow::Borrowed(items);
+        }
+        Cow::Owned(items.iter().filter(|item| matches(item)).cloned().collect())
     }
 
     fn render_swap_view(&mut self, frame: &mut Frame, main_area: ratatui::layout::Rect, theme: &Theme) {
+        let info: Vec<Line<'static>> = [self.since_boot_line(theme), self.swappiness_hint_line(theme)]
+            .into_iter()
+            .flatten()
+            .collect();
+        let main_area = if info.is_empty() {
+            main_area
+        } else {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(info.len() as u16), Constraint::Min(0)])
+                .split(main_area);
+            frame.render_widget(Paragraph::new(info), chunks[0]);
+            chunks[1]
+        };
+        if self.maximize_processes || (self.chart_hidden && !self.display_devices) {
+            self.render_process_panel(frame, main_area, theme);
+            return;
+        }
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
             .split(main_area);
 
-        if self.display_devices {
+        if self.chart_hidden {
+            // Devices take the whole upper area
+            self.render_device_panel(frame, chunks[0], theme);
+        } else if self.display_devices {
             let upper_chunks = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
                 .split(chunks[0]);
-
-            ui::chart::render_animated_chart(
-                frame,
-                upper_chunks[1],
-                theme,
-                &self.chart_data,
-                self.time_window,
-                self.chart_info.total_swap,
-                self.chart_info.used_swap,
-                &self.swap_size_unit,
-                self.display_devices,
-            );
-            ui::process_list::render_processes_list(
-                frame,
-                chunks[1],
-                theme,
-                &self.swap_size_unit,
-                &self.swap_processes_lines,
-                &mut self.vertical_scroll,
-                &mut self.vertical_scroll_state,
-                &mut self.visible_height,
-            );
-            ui::swap_devices::render_swap_devices(
-                frame,
-                upper_chunks[0],
-                theme,
-                &self.chart_info.swap_devices,
-                &self.swap_size_unit,
-                self.chart_info.total_swap,
-                self.chart_info.used_swap,
-                self.display_devices,
-            );
+            self.render_chart_panel(frame, upper_chunks[1], theme);
+            self.render_device_panel(frame, upper_chunks[0], theme);
         } else {
-            ui::chart::render_animated_chart(
-                frame,
-                chunks[0],
-                theme,
-                &self.chart_data,
-                self.time_window,
-                self.chart_info.total_swap,
-                self.chart_info.used_swap,
-                &self.swap_size_unit,
-                self.display_devices,
-            );
-            ui::process_list::render_processes_list(
-                frame,
-                chunks[1],
-                theme,
-                &self.swap_size_unit,
-                &self.swap_processes_lines,
-                &mut self.vertical_scroll,
-                &mut self.vertical_scroll_state,
-                &mut self.visible_height,
-            );
+            self.render_chart_panel(frame, chunks[0], theme);
         }
+        self.render_process_panel(frame, chunks[1], theme);
+    }
+
+    fn render_chart_panel(&self, frame: &mut Frame, area: ratatui::layout::Rect, theme: &Theme) {
+        ui::chart::render_animated_chart(
+            frame,
+            area,
+            theme,
+            &self.chart_data,
+            self.time_window,
+            self.chart_info.total_swap,
+            self.chart_info.used_kb(self.used_mode),
+            &self.swap_size_unit,
+            self.unit_base,
+            self.display_devices,
+            self.time_axis_unit,
+            self.timeout,
+            self.chart_cursor,
+            self.chart_series,
+            self.series_menu,
+            [&self.swap_rate_data[0], &self.swap_rate_data[1]],
+            self.log_y,
+        );
+    }
+
+    fn render_device_panel(&self, frame: &mut Frame, area: ratatui::layout::Rect, theme: &Theme) {
+        ui::swap_devices::render_swap_devices(
+            frame,
+            area,
+            theme,
+            &self.chart_info.swap_devices,
+            &self.device_sources,
+            &self.swap_size_unit,
+            self.unit_base,
+            self.chart_info.total_swap,
+            self.chart_info.used_kb(self.used_mode),
+            ui::process_list::swapping_process_count(&self.swap_process_rows),
+            self.display_devices,
+            self.device_bars,
+            self.device_layout,
+            self.show_device_queue,
+            self.dual_units,
+        );
+    }
+
+    fn render_process_panel(&mut self, frame: &mut Frame, area: ratatui::layout::Rect, theme: &Theme) {
+        let selected_row = self.selected_row();
+        ui::process_list::render_processes_list(
+            frame,
+            area,
+            theme,
+            &self.swap_size_unit,
+            self.unit_base,
+            &self.swap_processes_lines,
+            &mut self.vertical_scroll,
+            &mut self.vertical_scroll_state,
+            &mut self.visible_height,
+            selected_row,
+        );
     }
 
     fn create_main_block(&self, theme: &Theme) -> Block<'static> {
@@ -400,7 +1373,18 @@ impl App {
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(theme.border))
             .title(
-                Line::from(format!(" nv-swaptop [{}] sort:{} ", view_label, self.sort_column.label()))
+                Line::from(format!(
+                    " nv-swaptop [{}]{} sort:{}{}{}{}{}{}{} ",
+                    view_label,
+                    if self.paused { " PAUSED" } else { "" },
+                    self.sort_column.label(),
+                    self.sort_column.arrow(self.sort_reversed),
+                    if self.resort_interval > 1 { format!(" every {} ticks", self.resort_interval) } else { String::new() },
+                    if self.baseline.is_some() { " Δbaseline" } else { "" },
+                    if self.watch.is_some() { " pid-watch" } else { "" },
+                    if self.used_mode == SwapUsedMode::ExcludeCached { " used-excl-cache" } else { "" },
+                    if self.name_filter.is_empty() { String::new() } else { format!(" filter:/{}", self.name_filter) },
+                ))
                     .bold()
                     .fg(theme.primary)
                     .left_aligned(),
@@ -411,34 +1395,155 @@ impl App {
                     .fg(theme.primary)
                     .right_aligned(),
             )
-            .title(
-                Line::from(format!(" < {:?}ms >  Tab/1-4:view  s:sort ", self.timeout))
-                    .bold()
-                    .fg(theme.primary)
-                    .centered(),
-            )
+            .title(self.interval_title(theme).centered())
+            .title_bottom(self.totals_line(theme).left_aligned())
+            .title_bottom(self.status_line(theme).centered())
+            .title_bottom(self.thrash_line(theme).right_aligned())
             .style(Style::default().bg(theme.background).fg(theme.text))
     }
 
-    fn update_chart_data(&mut self) {
-        let timestamp = self.time_window[1];
-        let swap_usage = self.chart_info.used_swap as f64;
-        self.chart_data.push((timestamp, swap_usage));
-        if self.chart_data.len() > 60 {
-            self.chart_data.drain(0..1);
+    /// Refresh interval, or the interval or filter prompt while one is open.
+    fn interval_title(&self, theme: &Theme) -> Line<'static> {
+        if self.filter_input {
+            return Line::from(format!(" filter: /{}_  Enter:keep Esc:clear ", self.name_filter))
+                .bold()
+                .fg(theme.secondary);
+        }
+        if let Some(prompt) = &self.kill_prompt {
+            let escalate = if prompt.signal == KillSignal::Term { " K:SIGKILL" } else { "" };
+            return Line::from(format!(
+                " send {} to {} ({})? y:send{} other:cancel ",
+                prompt.signal.name(),
+                prompt.pid,
+                prompt.name,
+                escalate
+            ))
+            .bold()
+            .fg(theme.critical);
+        }
+        match &self.interval_input {
+            Some(input) => Line::from(format!(
+                " refresh ms ({}-{}): {}_  Enter:set Esc:cancel ",
+                MIN_TIMEOUT_MS, MAX_TIMEOUT_MS, input
+            ))
+            .bold()
+            .fg(theme.secondary),
+            None => Line::from(format!(" < {:?}ms >  Tab/1-4:view  s:sort ", self.timeout))
+                .bold()
+                .fg(theme.primary),
         }
-        self.time_window[0] += 1.0;
-        self.time_window[1] += 1.0;
     }
 
-    fn handle_crossterm_events(&mut self) -> Result<()> {
-        match event::read()? {
-            Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
-            Event::Mouse(_) => {}
-            Event::Resize(_, _) => {}
-            _ => {}
-        }
-        Ok(())
+    /// Footer totals for the active view: swap, VRAM, node memory, or swap
+    /// and VRAM together in the unified view.
+    fn totals_line(&self, theme: &Theme) -> Line<'static> {
+        use ui::footer::{gpu_totals, numa_totals, swap_totals};
+        let swap = swap_totals(self.chart_info.total_swap, self.chart_info.used_kb(self.used_mode));
+        let totals: Vec<_> = match self.active_view {
+            ActiveView::Swap => vec![swap],
+            ActiveView::Numa => numa_totals(&self.numa_nodes).into_iter().collect(),
+            ActiveView::Gpu => gpu_totals(&self.gpu_devices).into_iter().collect(),
+            ActiveView::Unified => std::iter::once(swap).chain(gpu_totals(&self.gpu_devices)).collect(),
+        };
+        ui::footer::totals_line(&totals, &self.swap_size_unit, self.unit_base, theme)
+    }
+
+    fn swapping_with_free_ram(&self) -> bool {
+        swapping_with_free_ram(&self.chart_info, self.chart_info.used_kb(self.used_mode), &self.swappiness_hint)
+    }
+
+    /// A dismissed hint shows again the next time the condition occurs.
+    fn rearm_swappiness_hint(&mut self) {
+        if !self.swapping_with_free_ram() {
+            self.swappiness_hint_dismissed = false;
+        }
+    }
+
+    /// "Swapping with free RAM" hint over the Swap view, unless dismissed.
+    fn swappiness_hint_line(&self, theme: &Theme) -> Option<Line<'static>> {
+        if self.swappiness_hint_dismissed || !self.swapping_with_free_ram() {
+            return None;
+        }
+        let available = self.chart_info.mem_available_percent().unwrap_or_default();
+        let swappiness = self.chart_info.swappiness.map(|v| v.to_string()).unwrap_or_else(|| "?".into());
+        Some(
+            Line::from(format!(
+                " {} swapped with {:.0}% of RAM available: vm.swappiness = {}; lower it (e.g. sysctl vm.swappiness=10) to favour dropping page cache  (H: dismiss)",
+                ui::unified_view::format_mem(self.chart_info.used_kb(self.used_mode), &self.swap_size_unit, self.unit_base),
+                available,
+                swappiness,
+            ))
+            .fg(theme.warn),
+        )
+    }
+
+    /// Cumulative swap traffic since boot (`C`), for telling a box that
+    /// swaps habitually from a one-off spike. None when hidden or when
+    /// /proc/vmstat has no swap counters.
+    fn since_boot_line(&self, theme: &Theme) -> Option<Line<'static>> {
+        if !self.show_since_boot {
+            return None;
+        }
+        let (in_pages, out_pages) = (self.chart_info.swap_in_pages?, self.chart_info.swap_out_pages?);
+        let amount = |pages: u64, kb: Option<u64>| match kb {
+            Some(kb) => format!(
+                "{} ({} pages)",
+                ui::unified_view::format_mem(kb, &self.swap_size_unit, self.unit_base),
+                pages
+            ),
+            None => format!("{} pages", pages),
+        };
+        let kb = self.chart_info.swapped_since_boot_kb();
+        Some(
+            Line::from(format!(
+                " since boot: swapped in {}, out {}  (C: hide)",
+                amount(in_pages, kb.map(|(kb_in, _)| kb_in)),
+                amount(out_pages, kb.map(|(_, kb_out)| kb_out)),
+            ))
+            .fg(theme.secondary),
+        )
+    }
+
+    fn thrash_line(&self, theme: &Theme) -> Line<'static> {
+        let (label, color) = match self.thrash {
+            Some(ThrashLevel::Low) => ("LOW", theme.good),
+            Some(ThrashLevel::Medium) => ("MEDIUM", theme.warn),
+            Some(ThrashLevel::High) => ("HIGH", theme.critical),
+            None => return Line::from(""),
+        };
+        Line::from(vec![
+            " thrash: ".into(),
+            label.fg(color).bold(),
+            format!(
+                " (in {:.0}/s out {:.0}/s) ",
+                self.swap_in_rate, self.swap_out_rate
+            )
+            .into(),
+        ])
+    }
+
+    fn update_chart_data(&mut self) {
+        let timestamp = self.time_window[1];
+        let swap_usage = self.chart_info.used_kb(self.used_mode) as f64;
+        push_chart_sample(&mut self.chart_data, (timestamp, swap_usage), self.history_len);
+        // Rates exist once a thrash level could be scored
+        if self.thrash.is_some() {
+            let [swap_in, swap_out] = &mut self.swap_rate_data;
+            push_chart_sample(swap_in, (timestamp, self.swap_in_rate), self.history_len);
+            push_chart_sample(swap_out, (timestamp, self.swap_out_rate), self.history_len);
+        }
+        self.time_window[0] += 1.0;
+        self.time_window[1] += 1.0;
+    }
+
+    fn handle_crossterm_events(&mut self) -> Result<()> {
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
+            Event::Mouse(mouse) => self.on_mouse_event(mouse),
+            Event::Resize(_, _) => {}
+            _ => {}
+        }
+        Ok(())
     }
 
     pub(crate) fn cycle_view(&mut self) {
@@ -455,9 +1560,29 @@ impl App {
             return;
         }
 
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.quit();
+            return;
+        }
+        if self.interval_input.is_some() {
+            self.on_interval_input_key(key.code);
+            return;
+        }
+        if self.filter_input {
+            self.on_filter_input_key(key.code);
+            return;
+        }
+        if self.kill_prompt.is_some() {
+            self.on_kill_prompt_key(key.code);
+            return;
+        }
+        if self.series_menu {
+            self.on_series_menu_key(key.code);
+            return;
+        }
+
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') => self.quit(),
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit(),
 
             // View switching
             KeyCode::Tab => self.cycle_view(),
@@ -466,66 +1591,211 @@ impl App {
             KeyCode::Char('3') => self.active_view = ActiveView::Gpu,
             KeyCode::Char('4') => self.active_view = ActiveView::Unified,
 
-            KeyCode::Char('d') | KeyCode::Down => {
-                self.vertical_scroll = self.vertical_scroll.saturating_add(1);
-                self.vertical_scroll_state =
-                    self.vertical_scroll_state.position(self.vertical_scroll);
-            }
-            KeyCode::Char('u') | KeyCode::Up => {
-                self.vertical_scroll = self.vertical_scroll.saturating_sub(1);
-                self.vertical_scroll_state =
-                    self.vertical_scroll_state.position(self.vertical_scroll);
-            }
-            KeyCode::End => {
-                self.vertical_scroll = self.swap_processes_lines.len();
-                self.vertical_scroll_state =
-                    self.vertical_scroll_state.position(self.vertical_scroll);
-            }
-            KeyCode::Home => {
-                self.vertical_scroll = 0;
-                self.vertical_scroll_state =
-                    self.vertical_scroll_state.position(self.vertical_scroll);
-            }
+            KeyCode::Down if self.active_view == ActiveView::Swap => self.move_selection(true),
+            KeyCode::Up if self.active_view == ActiveView::Swap => self.move_selection(false),
+            KeyCode::Char('d') | KeyCode::Down => self.scroll_to(self.scroll_offset().saturating_add(1)),
+            KeyCode::Char('u') | KeyCode::Up => self.scroll_to(self.scroll_offset().saturating_sub(1)),
+            KeyCode::End => self.scroll_to(usize::MAX),
+            KeyCode::Home => self.scroll_to(0),
 
-            KeyCode::PageDown => {
-                let page_size = self.visible_height.saturating_sub(4);
-                self.vertical_scroll = self
-                    .vertical_scroll
-                    .saturating_add(page_size)
-                    .min(self.swap_processes_lines.len().saturating_sub(1));
-                self.vertical_scroll_state =
-                    self.vertical_scroll_state.position(self.vertical_scroll);
-            }
-            KeyCode::PageUp => {
-                let page_size = self.visible_height.saturating_sub(4);
-                self.vertical_scroll = self.vertical_scroll.saturating_sub(page_size);
-                self.vertical_scroll_state =
-                    self.vertical_scroll_state.position(self.vertical_scroll);
-            }
+            KeyCode::PageDown => self.scroll_to(self.scroll_offset().saturating_add(self.page_size())),
+            KeyCode::PageUp => self.scroll_to(self.scroll_offset().saturating_sub(self.page_size())),
 
             KeyCode::Char('k') => self.change_unit(SizeUnits::KB),
             KeyCode::Char('m') => self.change_unit(SizeUnits::MB),
             KeyCode::Char('g') => self.change_unit(SizeUnits::GB),
+            KeyCode::Char('T') => self.change_unit(SizeUnits::TB),
+            KeyCode::Char('B') => {
+                self.unit_base = self.unit_base.toggle();
+                self.rebuild_process_lines();
+            }
 
+            KeyCode::Char('a') if self.active_view == ActiveView::Gpu => {
+                self.group_gpu_processes = !self.group_gpu_processes;
+            }
             KeyCode::Char('a') => self.aggregated = !self.aggregated,
+            KeyCode::Char('n') => {
+                self.namespace_mode = self.namespace_mode.next();
+                self.rebuild_process_lines();
+            }
+            KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.suspend_requested = true;
+            }
+            KeyCode::Char('z') => self.toggle_baseline(),
+            KeyCode::Char('J') => self.jump_to_heaviest(),
+            KeyCode::Char('.') | KeyCode::F(5) => self.request_refresh(),
+            KeyCode::Char(' ') => self.paused = !self.paused,
             KeyCode::Char('t') => self.cycle_theme(),
-            KeyCode::Char('s') => self.sort_column = self.sort_column.next(),
+            KeyCode::Char('s') => {
+                self.sort_column = self.sort_column.next();
+                self.sort_reversed = false;
+                self.sort_unified_procs();
+            }
+            KeyCode::Char('c') => {
+                self.used_mode = match self.used_mode {
+                    SwapUsedMode::IncludeCached => SwapUsedMode::ExcludeCached,
+                    SwapUsedMode::ExcludeCached => SwapUsedMode::IncludeCached,
+                };
+            }
+            KeyCode::Char('r') => {
+                self.sort_reversed = !self.sort_reversed;
+                self.sort_unified_procs();
+            }
+            KeyCode::Char('S') => self.resort_now(),
+            KeyCode::Char('H') => self.swappiness_hint_dismissed = true,
             KeyCode::Char('h') => self.display_devices = !self.display_devices,
+            KeyCode::Char('p') => self.device_bars = !self.device_bars,
+            KeyCode::Char('l') => self.device_layout = self.device_layout.next(),
+            KeyCode::Char('N') => self.numa_node_sort = self.numa_node_sort.next(),
+            KeyCode::Char('P') => self.toggle_numa_processes(),
+            KeyCode::Char('o') => self.show_device_queue = !self.show_device_queue,
+            KeyCode::Char('D') => self.dual_units = !self.dual_units,
+            KeyCode::Char('f') => self.maximize_processes = !self.maximize_processes,
+            KeyCode::Char('v') => self.chart_hidden = !self.chart_hidden,
+            KeyCode::Char('L') => self.log_y = !self.log_y,
+            KeyCode::Char('i') => self.show_mig_instances = !self.show_mig_instances,
+            KeyCode::Char('A') => self.collapse_gpus = !self.collapse_gpus,
+            KeyCode::Char('V') => self.cycle_charted_gpu(),
+            KeyCode::Char('R') => self.show_committed = !self.show_committed,
+            KeyCode::Char('G') => self.show_gpu_percent = !self.show_gpu_percent,
+            KeyCode::Char('F') => self.location_filter = self.location_filter.next(),
+            KeyCode::Char('C') => self.show_since_boot = !self.show_since_boot,
+            KeyCode::Char('x') => self.time_axis_unit = self.time_axis_unit.next(),
+            KeyCode::Char('b') => self.chart_series.toggle_stats(),
+            KeyCode::Char('y') => self.series_menu = self.chart_shown(),
+            KeyCode::Char('[') => self.move_chart_cursor(true),
+            KeyCode::Char(']') => self.move_chart_cursor(false),
+            KeyCode::Char('w') => self.config_snapshot = Some(self.effective_config().to_toml()),
             KeyCode::Left | KeyCode::Right => self.change_timout(key.code),
+            KeyCode::Char('e') => self.interval_input = Some(String::new()),
+            KeyCode::Char('/') => self.filter_input = true,
+            // Signals are only sent on Linux
+            KeyCode::Char('K') if cfg!(target_os = "linux") => self.open_kill_prompt(),
 
             _ => {}
         }
     }
 
+    /// Keys while the interval prompt is open: digits edit, Enter applies a
+    /// valid value and closes, Esc cancels. Invalid input keeps the prompt open.
+    fn on_interval_input_key(&mut self, code: KeyCode) {
+        let Some(input) = self.interval_input.as_mut() else { return };
+        match code {
+            KeyCode::Char(c) if c.is_ascii_digit() && input.len() < 5 => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                if let Some(ms) = parse_interval_ms(input) {
+                    self.timeout = ms;
+                    self.interval_input = None;
+                }
+            }
+            KeyCode::Esc => self.interval_input = None,
+            _ => {}
+        }
+    }
+
+    /// Keys while the filter prompt is open: characters and Backspace edit
+    /// the filter, which applies as it is typed; Enter keeps it and closes
+    /// the prompt, Esc clears it.
+    fn on_filter_input_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => self.name_filter.push(c),
+            KeyCode::Backspace => {
+                self.name_filter.pop();
+            }
+            KeyCode::Enter => {
+                self.filter_input = false;
+                return;
+            }
+            KeyCode::Esc => {
+                self.name_filter.clear();
+                self.filter_input = false;
+            }
+            _ => return,
+        }
+        self.build_process_lines();
+        self.scroll_to(0);
+    }
+
+    /// Keys while the series menu is open: a number toggles that chart
+    /// series, `y` or Esc closes. Everything else is ignored so number keys
+    /// do not switch views underneath the menu.
+    fn on_series_menu_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => match ui::chart::ChartSeries::from_menu_key(c) {
+                Some(series) => self.chart_series.toggle(series),
+                None if c == 'y' => self.series_menu = false,
+                None => {}
+            },
+            KeyCode::Esc => self.series_menu = false,
+            _ => {}
+        }
+    }
+
+    /// Whether the Swap view currently draws the chart.
+    fn chart_shown(&self) -> bool {
+        self.active_view == ActiveView::Swap && !self.maximize_processes && !self.chart_hidden
+    }
+
+    /// The active view's scroll position, None in the swap view, which
+    /// keeps its own alongside the row selection.
+    fn view_scroll(&self) -> Option<&ListScroll> {
+        match self.active_view {
+            ActiveView::Swap => None,
+            ActiveView::Numa => Some(&self.numa_scroll),
+            ActiveView::Gpu => Some(&self.gpu_scroll),
+            ActiveView::Unified => Some(&self.unified_scroll),
+        }
+    }
+
+    fn view_scroll_mut(&mut self) -> Option<&mut ListScroll> {
+        match self.active_view {
+            ActiveView::Swap => None,
+            ActiveView::Numa => Some(&mut self.numa_scroll),
+            ActiveView::Gpu => Some(&mut self.gpu_scroll),
+            ActiveView::Unified => Some(&mut self.unified_scroll),
+        }
+    }
+
+    fn scroll_offset(&self) -> usize {
+        self.view_scroll().map_or(self.vertical_scroll, |scroll| scroll.offset)
+    }
+
+    fn page_size(&self) -> usize {
+        self.view_scroll().map_or(self.visible_height.saturating_sub(4), ListScroll::page_size)
+    }
+
+    /// Scroll the active view's list, clamped so the last line stays at the
+    /// bottom of the panel (an empty or short list does not scroll).
+    fn scroll_to(&mut self, offset: usize) {
+        if let Some(scroll) = self.view_scroll_mut() {
+            scroll.scroll_to(offset);
+            return;
+        }
+        let max = ui::process_list::max_scroll(self.swap_processes_lines.len(), self.visible_height);
+        self.vertical_scroll = offset.min(max);
+        self.vertical_scroll_state = self.vertical_scroll_state.position(self.vertical_scroll);
+    }
+
+    /// `[` steps the chart cursor back in time (showing it first), `]`
+    /// forward; stepping past the newest sample hides it again.
+    fn move_chart_cursor(&mut self, back: bool) {
+        let oldest = self.chart_data.len().saturating_sub(1);
+        self.chart_cursor = match (self.chart_cursor, back) {
+            (None, true) => Some(0),
+            (None, false) => None,
+            (Some(c), true) => Some((c + 1).min(oldest)),
+            (Some(c), false) => c.min(oldest).checked_sub(1),
+        };
+    }
+
     fn change_unit(&mut self, unit: SizeUnits) {
         self.swap_size_unit = unit;
         if let Ok(info) = self.provider.get_swap_info(&self.swap_size_unit) {
-            self.chart_info = info;
-            self.swap_processes_lines = ui::process_list::create_process_lines(
-                self.provider.as_ref(),
-                &self.swap_size_unit,
-                self.aggregated,
-            );
+            self.chart_info = info.or_previous(&self.chart_info);
+            self.rebuild_process_lines();
         }
     }
 
@@ -535,22 +1805,19 @@ impl App {
             ThemeType::Solarized => ThemeType::Monokai,
             ThemeType::Monokai => ThemeType::Dracula,
             ThemeType::Dracula => ThemeType::Nord,
-            ThemeType::Nord => ThemeType::Default,
+            ThemeType::Nord => ThemeType::Colorblind,
+            ThemeType::Colorblind => ThemeType::Default,
         };
-        self.swap_processes_lines = ui::process_list::create_process_lines(
-            self.provider.as_ref(),
-            &self.swap_size_unit,
-            self.aggregated,
-        );
+        self.rebuild_process_lines();
     }
 
     fn change_timout(&mut self, action: KeyCode) {
         match action {
             KeyCode::Left => {
-                self.timeout = self.timeout.saturating_sub(100).max(1);
+                self.timeout = self.timeout.saturating_sub(100).max(MIN_TIMEOUT_MS);
             }
             KeyCode::Right => {
-                self.timeout = self.timeout.saturating_add(100).min(10000);
+                self.timeout = self.timeout.saturating_add(100).min(MAX_TIMEOUT_MS);
             }
             _ => {}
         }
@@ -573,6 +1840,104 @@ impl App {
     }
 }
 
+/// Signal offered by the `K` confirmation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KillSignal {
+    Term,
+    Kill,
+}
+
+impl KillSignal {
+    fn name(self) -> &'static str {
+        match self {
+            KillSignal::Term => "SIGTERM",
+            KillSignal::Kill => "SIGKILL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct KillPrompt {
+    pid: u32,
+    name: String,
+    signal: KillSignal,
+}
+
+#[cfg(target_os = "linux")]
+fn send_signal(pid: u32, signal: KillSignal) -> std::io::Result<()> {
+    let signal = match signal {
+        KillSignal::Term => libc::SIGTERM,
+        KillSignal::Kill => libc::SIGKILL,
+    };
+    // PIDs above i32::MAX do not exist; refuse rather than wrap into a
+    // negative value, which kill() reads as a process group
+    let pid = libc::pid_t::try_from(pid).map_err(|_| std::io::Error::from_raw_os_error(libc::ESRCH))?;
+    // SAFETY: kill() has no memory-safety preconditions
+    if unsafe { libc::kill(pid, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn send_signal(_pid: u32, _signal: KillSignal) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Footer message for a failed kill(): the common EPERM and ESRCH cases
+/// spelled out, anything else as the OS describes it.
+fn kill_error_message(pid: u32, error: &std::io::Error) -> String {
+    #[cfg(target_os = "linux")]
+    match error.raw_os_error() {
+        Some(libc::EPERM) => return format!("kill {}: permission denied (not your process; run as root)", pid),
+        Some(libc::ESRCH) => return format!("kill {}: no such process (already exited)", pid),
+        _ => {}
+    }
+    format!("kill {}: {}", pid, error)
+}
+
+/// Job-control suspend (Ctrl-Z): give the shell a sane terminal, stop, and
+/// restore the TUI when continued with `fg`. Raw mode turns off ISIG, so the
+/// key arrives as an event rather than as SIGTSTP from the tty driver.
+#[cfg(target_os = "linux")]
+fn suspend(terminal: &mut DefaultTerminal) -> Result<()> {
+    use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+    use crossterm::terminal::{EnterAlternateScreen, enable_raw_mode};
+
+    crossterm::execute!(std::io::stdout(), DisableMouseCapture)?;
+    ratatui::restore();
+    // SAFETY: raise() has no preconditions; the default SIGTSTP action stops
+    // the process and execution continues here on SIGCONT.
+    unsafe { libc::raise(libc::SIGTSTP) };
+    enable_raw_mode()?;
+    crossterm::execute!(std::io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    // The shell has drawn over the screen; force a full repaint
+    terminal.clear()?;
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn suspend(_terminal: &mut DefaultTerminal) -> Result<()> {
+    Ok(())
+}
+
+/// Parse a typed refresh interval, accepting only MIN_TIMEOUT_MS..=MAX_TIMEOUT_MS.
+fn parse_interval_ms(input: &str) -> Option<u64> {
+    input
+        .parse()
+        .ok()
+        .filter(|ms| (MIN_TIMEOUT_MS..=MAX_TIMEOUT_MS).contains(ms))
+}
+
+/// Append a chart point, dropping the oldest beyond `history_len`.
+fn push_chart_sample(data: &mut Vec<(f64, f64)>, point: (f64, f64), history_len: usize) {
+    data.push(point);
+    if data.len() > history_len {
+        data.drain(..data.len() - history_len);
+    }
+}
+
 /// Returns the demo view for a given elapsed time in seconds,
 /// or None if the demo should quit.
 fn demo_view_for_elapsed(secs: u64) -> Option<ActiveView> {
@@ -594,6 +1959,23 @@ mod tests {
         App::new(Box::new(MockDataProvider::new()), demo)
     }
 
+    fn swap_proc(pid: u32, name: &str, swap_size: f64) -> ProcessSwapInfo {
+        ProcessSwapInfo { pid, name: name.into(), swap_size, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 }
+    }
+
+    fn vram_device(index: u32, memory_used_kb: u64) -> crate::data::GpuDevice {
+        crate::data::GpuDevice {
+            index, name: format!("GPU {}", index), memory_total_kb: 81_920_000,
+            memory_used_kb, memory_free_kb: 81_920_000 - memory_used_kb,
+            numa_node_id: None, temperature: None,
+            pci_bus_id: String::new(),
+            memory_reserved_kb: None,
+            mig_instances: Vec::new(),
+            utilization_percent: None,
+            memory_utilization_percent: None,
+        }
+    }
+
     // --- App construction tests ---
 
     #[test]
@@ -616,6 +1998,31 @@ mod tests {
         assert_eq!(app.active_view, ActiveView::Swap);
     }
 
+    #[test]
+    fn test_shift_t_selects_tb_lowercase_t_still_cycles_theme() {
+        let mut app = make_app(false);
+        let theme = app.current_theme;
+        app.on_key_event(KeyEvent::from(KeyCode::Char('T')));
+        assert_eq!(app.swap_size_unit, SizeUnits::TB);
+        assert_eq!(app.current_theme, theme);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('t')));
+        assert_ne!(app.current_theme, theme);
+        assert_eq!(app.effective_config().unit, "tb");
+    }
+
+    #[test]
+    fn test_shift_b_toggles_unit_base() {
+        let theme = Theme::from(ThemeType::Default);
+        let mut app = make_app(false);
+        app.chart_info = app.provider.get_swap_info(&SizeUnits::KB).unwrap();
+        app.on_key_event(KeyEvent::from(KeyCode::Char('B')));
+        assert_eq!(app.unit_base, UnitBase::Decimal);
+        assert_eq!(app.effective_config().unit_base, "decimal");
+        assert_eq!(app.totals_line(&theme).to_string(), " swap used 2048000 kB / free 6144000 kB ");
+        app.on_key_event(KeyEvent::from(KeyCode::Char('B')));
+        assert_eq!(app.unit_base, UnitBase::Binary);
+    }
+
     // --- demo_view_for_elapsed pure logic tests ---
 
     #[test]
@@ -753,6 +2160,1135 @@ mod tests {
         assert!(demo_view_for_elapsed(16).is_none());
     }
 
+    // --- Swap rate / thrash tests ---
+
+    #[test]
+    fn test_update_swap_rates_needs_two_samples() {
+        let mut mock = MockDataProvider::new();
+        mock.swap_update.swap_in_pages = Some(1000);
+        mock.swap_update.swap_out_pages = Some(1000);
+        mock.swap_update.memory_full_avg10 = Some(20.0);
+        let mut app = App::new(Box::new(mock), false);
+        app.chart_info = app.provider.get_swap_info(&SizeUnits::KB).unwrap();
+
+        app.update_swap_rates();
+        assert!(app.thrash.is_none());
+
+        // Second sample 1s later with 3000 pages of swap I/O
+        let (prev_in, prev_out, at) = app.prev_swap_counters.unwrap();
+        app.prev_swap_counters = Some((prev_in, prev_out, at - Duration::from_secs(1)));
+        app.chart_info.swap_in_pages = Some(2000);
+        app.chart_info.swap_out_pages = Some(3000);
+        app.update_swap_rates();
+        assert!(app.swap_in_rate > 500.0 && app.swap_in_rate <= 1000.0);
+        assert!(app.swap_out_rate > 1000.0 && app.swap_out_rate <= 2000.0);
+        assert_eq!(app.thrash, Some(ThrashLevel::High));
+    }
+
+    #[test]
+    fn test_swap_rates_feed_rate_chart() {
+        let mut mock = MockDataProvider::new();
+        mock.swap_update.swap_in_pages = Some(1000);
+        mock.swap_update.swap_out_pages = Some(1000);
+        let mut app = App::new(Box::new(mock), false);
+        app.chart_info = app.provider.get_swap_info(&SizeUnits::KB).unwrap();
+        app.update_swap_rates();
+        app.update_chart_data();
+        // One vmstat sample: no rate yet
+        assert_eq!(app.chart_data.len(), 1);
+        assert!(app.swap_rate_data.iter().all(Vec::is_empty));
+
+        // pswpin +400, pswpout +1500 over exactly 1s
+        let (prev_in, prev_out, _) = app.prev_swap_counters.unwrap();
+        app.prev_swap_counters = Some((prev_in, prev_out, Instant::now() - Duration::from_secs(1)));
+        app.chart_info.swap_in_pages = Some(1400);
+        app.chart_info.swap_out_pages = Some(2500);
+        app.update_swap_rates();
+        app.update_chart_data();
+        let [swap_in, swap_out] = &app.swap_rate_data;
+        assert_eq!(swap_in.len(), 1);
+        assert!((swap_in[0].1 - 400.0).abs() < 1.0, "{:?}", swap_in);
+        assert!((swap_out[0].1 - 1500.0).abs() < 2.0, "{:?}", swap_out);
+        assert_eq!(swap_in[0].0, app.chart_data[1].0);
+
+        press(&mut app, KeyCode::Char('y'));
+        press(&mut app, KeyCode::Char('4'));
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("swap I/O pages/s in "));
+    }
+
+    #[test]
+    fn test_since_boot_line_toggles_and_needs_vmstat() {
+        let theme = Theme::from(ThemeType::Default);
+        let mut mock = MockDataProvider::new();
+        mock.swap_update.swap_in_pages = Some(1000);
+        mock.swap_update.swap_out_pages = Some(2560);
+        mock.swap_update.page_size_kb = Some(4);
+        let mut app = App::new(Box::new(mock), false);
+        app.chart_info = app.provider.get_swap_info(&SizeUnits::KB).unwrap();
+        assert!(app.since_boot_line(&theme).is_none());
+
+        app.on_key_event(KeyEvent::from(KeyCode::Char('C')));
+        assert_eq!(
+            app.since_boot_line(&theme).unwrap().to_string(),
+            " since boot: swapped in 4000 KiB (1000 pages), out 10240 KiB (2560 pages)  (C: hide)"
+        );
+        assert!(app.effective_config().since_boot);
+
+        app.chart_info.page_size_kb = None;
+        assert_eq!(
+            app.since_boot_line(&theme).unwrap().to_string(),
+            " since boot: swapped in 1000 pages, out 2560 pages  (C: hide)"
+        );
+        app.chart_info.swap_in_pages = None;
+        assert!(app.since_boot_line(&theme).is_none());
+    }
+
+    #[test]
+    fn test_update_swap_rates_without_vmstat() {
+        let mut app = make_app(false);
+        app.update_swap_rates();
+        app.update_swap_rates();
+        assert!(app.thrash.is_none());
+        assert!(app.thrash_line(&Theme::from(ThemeType::Default)).spans.is_empty());
+    }
+
+    #[test]
+    fn test_totals_line_follows_view() {
+        let theme = Theme::from(ThemeType::Default);
+        let mut app = make_app(false);
+        app.chart_info = app.provider.get_swap_info(&SizeUnits::KB).unwrap();
+        assert_eq!(app.totals_line(&theme).to_string(), " swap used 2000000 KiB / free 6000000 KiB ");
+
+        // No GPUs in the mock: nothing to total
+        app.on_key_event(KeyEvent::from(KeyCode::Char('3')));
+        assert_eq!(app.totals_line(&theme).to_string(), "");
+
+        app.on_key_event(KeyEvent::from(KeyCode::Char('4')));
+        assert!(app.totals_line(&theme).to_string().starts_with(" swap used"));
+    }
+
+    // --- Baseline tests ---
+
+    #[test]
+    fn test_toggle_baseline_shows_deltas() {
+        let mut app = make_app(false);
+        app.toggle_baseline();
+        assert!(app.baseline.is_some());
+        // Mock values are unchanged since capture, so both rows show +0
+        let rendered: Vec<String> = app.swap_processes_lines.iter().map(|l| l.to_string()).collect();
+        assert!(rendered[0].contains("Δ USED"));
+        assert!(rendered[1..].iter().all(|l| l.contains("+0")));
+
+        app.toggle_baseline();
+        assert!(app.baseline.is_none());
+        let rendered: Vec<String> = app.swap_processes_lines.iter().map(|l| l.to_string()).collect();
+        assert!(!rendered[0].contains("Δ"));
+    }
+
+    #[test]
+    fn test_baseline_new_process_shows_full_value() {
+        let mut app = make_app(false);
+        app.baseline = Some(Baseline::default());
+        app.rebuild_process_lines();
+        let rendered: Vec<String> = app.swap_processes_lines.iter().map(|l| l.to_string()).collect();
+        assert!(rendered.iter().any(|l| l.contains("+1024")));
+        assert!(rendered.iter().any(|l| l.contains("+512")));
+    }
+
+    // --- Maximized process list tests ---
+
+    #[test]
+    fn test_maximize_processes_gives_list_full_height() {
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 40)).unwrap();
+        let mut app = make_app(false);
+        app.rebuild_process_lines();
+
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let normal_height = app.visible_height;
+
+        app.on_key_event(KeyEvent::from(KeyCode::Char('f')));
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        assert!(app.visible_height > normal_height);
+
+        app.on_key_event(KeyEvent::from(KeyCode::Char('f')));
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        assert_eq!(app.visible_height, normal_height);
+    }
+
+    #[test]
+    fn test_log_y_toggle_labels_axis() {
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 40)).unwrap();
+        let mut app = make_app(false);
+        app.chart_info = app.provider.get_swap_info(&SizeUnits::KB).unwrap();
+        app.chart_data = vec![(59.0, 10.0), (60.0, 2_000_000.0)];
+        let screen = |terminal: &mut ratatui::Terminal<ratatui::backend::TestBackend>, app: &mut App| {
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect::<String>()
+        };
+        // Axis runs up to the swap total; the middle label is half of it
+        let linear = screen(&mut terminal, &mut app);
+        assert!(linear.contains("8000000 KiB") && linear.contains("4000000 KiB"));
+
+        app.on_key_event(KeyEvent::from(KeyCode::Char('L')));
+        assert!(app.log_y);
+        // ...or the geometric midpoint on the log axis
+        let log = screen(&mut terminal, &mut app);
+        assert!(log.contains("8000000 KiB") && log.contains("2827 KiB") && !log.contains("4000000 KiB"));
+        assert!(app.effective_config().log_y);
+
+        // Labels follow the unit
+        app.on_key_event(KeyEvent::from(KeyCode::Char('L')));
+        app.on_key_event(KeyEvent::from(KeyCode::Char('g')));
+        assert!(screen(&mut terminal, &mut app).contains("3.81 GiB"));
+    }
+
+    #[test]
+    fn test_chart_hidden_drops_chart_only() {
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 40)).unwrap();
+        let mut app = make_app(false);
+        app.rebuild_process_lines();
+        app.display_devices = false;
+
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let normal_height = app.visible_height;
+
+        // No chart and no devices: the list gets the full height
+        app.on_key_event(KeyEvent::from(KeyCode::Char('v')));
+        assert!(app.chart_hidden);
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        assert!(app.visible_height > normal_height);
+
+        // Devices shown: they keep the upper area, the list does not grow
+        app.on_key_event(KeyEvent::from(KeyCode::Char('h')));
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        assert_eq!(app.visible_height, normal_height);
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("swap devices"));
+        assert!(!content.contains("swap usage"));
+    }
+
+    #[test]
+    fn test_chart_cursor_keys() {
+        let mut app = make_app(false);
+        app.chart_data = vec![(1.0, 10.0), (2.0, 20.0), (3.0, 30.0)];
+        app.on_key_event(KeyEvent::from(KeyCode::Char('[')));
+        assert_eq!(app.chart_cursor, Some(0));
+        for _ in 0..5 {
+            app.on_key_event(KeyEvent::from(KeyCode::Char('[')));
+        }
+        // Stops at the oldest sample
+        assert_eq!(app.chart_cursor, Some(2));
+        app.on_key_event(KeyEvent::from(KeyCode::Char(']')));
+        assert_eq!(app.chart_cursor, Some(1));
+        app.display_devices = false;
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("cursor -58t: 20 KiB (Δ now +10 KiB)"));
+        app.on_key_event(KeyEvent::from(KeyCode::Char(']')));
+        app.on_key_event(KeyEvent::from(KeyCode::Char(']')));
+        assert_eq!(app.chart_cursor, None);
+    }
+
+    #[test]
+    fn test_chart_stats_toggle_renders() {
+        let mut app = make_app(false);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('b')));
+        assert!(app.chart_series.contains(ui::chart::ChartSeries::Mean));
+        assert_eq!(app.effective_config().chart_series, vec!["used", "mean", "±1σ"]);
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 40)).unwrap();
+        // Empty, single-sample and multi-sample buffers all draw
+        for sample in [None, Some(10.0), Some(30.0)] {
+            if let Some(y) = sample {
+                app.chart_data.push((app.chart_data.len() as f64, y));
+            }
+            terminal.draw(|frame| app.render(frame)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_series_menu_toggles_series_and_swallows_view_keys() {
+        let mut app = make_app(false);
+        app.running = true;
+        app.on_key_event(KeyEvent::from(KeyCode::Char('y')));
+        assert!(app.series_menu);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('2')));
+        app.on_key_event(KeyEvent::from(KeyCode::Char('1')));
+        // Number keys toggle series instead of switching views
+        assert_eq!(app.active_view, ActiveView::Swap);
+        assert_eq!(app.chart_series.labels(), vec!["mean"]);
+
+        app.chart_data = vec![(0.0, 10.0), (1.0, 30.0)];
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("series: 1 [ ] used  2 [x] mean"));
+
+        app.on_key_event(KeyEvent::from(KeyCode::Esc));
+        assert!(!app.series_menu);
+        // Esc closed the menu, not the app
+        assert!(app.running);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('2')));
+        assert_eq!(app.active_view, ActiveView::Numa);
+        // The menu only opens where the chart is drawn
+        app.on_key_event(KeyEvent::from(KeyCode::Char('y')));
+        assert!(!app.series_menu);
+    }
+
+    // --- Header click sort tests ---
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_header_click_sorts_and_toggles_direction() {
+        let mut app = make_app(false);
+        app.active_view = ActiveView::Unified;
+        app.unified_area = ratatui::layout::Rect::new(1, 1, 100, 30);
+
+        // x = area.x + border + 10 lands in the NAME column
+        app.on_mouse_event(click(12, 2));
+        assert_eq!(app.sort_column, SortColumn::Name);
+        assert!(!app.sort_reversed);
+
+        app.on_mouse_event(click(12, 2));
+        assert_eq!(app.sort_column, SortColumn::Name);
+        assert!(app.sort_reversed);
+    }
+
+    #[test]
+    fn test_resort_interval_holds_order_between_sorts() {
+        let mut app = make_app(false).with_resort_interval(3);
+        // Mock reports pid 1 (1024) above pid 2 (512); start from the opposite order
+        app.refresh_process_lines(0.0, true);
+        app.swap_process_rows.reverse();
+
+        let pids = |app: &App| app.swap_process_rows.iter().map(|p| p.pid).collect::<Vec<_>>();
+        for _ in 0..2 {
+            let resort = app.take_resort_due();
+            assert!(!resort);
+            app.refresh_process_lines(1.0, resort);
+            assert_eq!(pids(&app), vec![2, 1]);
+        }
+        let resort = app.take_resort_due();
+        assert!(resort);
+        app.refresh_process_lines(1.0, resort);
+        assert_eq!(pids(&app), vec![1, 2]);
+
+        // `S` re-sorts immediately and restarts the interval
+        app.swap_process_rows.reverse();
+        app.on_key_event(KeyEvent::from(KeyCode::Char('S')));
+        assert_eq!(pids(&app), vec![1, 2]);
+        assert!(!app.take_resort_due());
+    }
+
+    #[test]
+    fn test_swappiness_hint_dismiss_and_rearm() {
+        let theme = Theme::from(ThemeType::Default);
+        let mut app = make_app(false);
+        app.chart_info = SwapUpdate {
+            used_swap: 4096, mem_total_kb: Some(1000), mem_available_kb: Some(800), swappiness: Some(60),
+            ..Default::default()
+        };
+        let hint = app.swappiness_hint_line(&theme).unwrap().to_string();
+        assert!(hint.contains("80% of RAM available"));
+        assert!(hint.contains("vm.swappiness = 60"));
+
+        app.on_key_event(KeyEvent::from(KeyCode::Char('H')));
+        app.rearm_swappiness_hint();
+        assert!(app.swappiness_hint_line(&theme).is_none());
+
+        // RAM got tight, then free again: the hint comes back
+        app.chart_info.mem_available_kb = Some(100);
+        app.rearm_swappiness_hint();
+        app.chart_info.mem_available_kb = Some(800);
+        assert!(app.swappiness_hint_line(&theme).is_some());
+    }
+
+    #[test]
+    fn test_click_outside_header_is_ignored() {
+        let mut app = make_app(false);
+        app.active_view = ActiveView::Unified;
+        app.unified_area = ratatui::layout::Rect::new(1, 1, 100, 30);
+        app.on_mouse_event(click(12, 5));
+        assert_eq!(app.sort_column, SortColumn::Swap);
+
+        app.active_view = ActiveView::Swap;
+        app.on_mouse_event(click(12, 2));
+        assert_eq!(app.sort_column, SortColumn::Swap);
+    }
+
+    #[test]
+    fn test_reverse_sort_key() {
+        let mut app = make_app(false);
+        // Every sort key grows with the pid
+        let unified = |pid: u32| UnifiedProcessInfo {
+            pid,
+            name: format!("p{}", pid),
+            swap_kb: pid as u64 * 10,
+            cpu_nodes: vec![pid],
+            gpu_nodes: vec![],
+            kb_per_node: Default::default(),
+            gpu_memory_kb: Some(pid as u64 * 100),
+            gpu_indices: vec![],
+            location: crate::data::ProcessLocation::CpuOnly,
+            rss_kb: Some(pid as u64),
+            count: 1,
+        };
+        app.unified_procs = vec![unified(2), unified(1), unified(3)];
+        let ends = |app: &App| (app.unified_procs[0].pid, app.unified_procs[2].pid);
+        let columns = [
+            (SortColumn::Swap, (3, 1), "sort:swap▼"),
+            (SortColumn::Committed, (3, 1), "sort:swap+rss▼"),
+            (SortColumn::GpuMem, (3, 1), "sort:gpu_mem▼"),
+            (SortColumn::Name, (1, 3), "sort:name▲"),
+            (SortColumn::NumaNode, (1, 3), "sort:numa▲"),
+        ];
+        for (column, natural, title) in columns {
+            app.sort_column = column;
+            app.sort_reversed = false;
+            app.sort_unified_procs();
+            assert_eq!(ends(&app), natural, "{:?}", column);
+            let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(160, 30)).unwrap();
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+            assert!(content.contains(title), "{}", title);
+
+            app.on_key_event(KeyEvent::from(KeyCode::Char('r')));
+            assert_eq!(ends(&app), (natural.1, natural.0), "{:?} reversed", column);
+        }
+    }
+
+    #[test]
+    fn test_committed_sort_ranks_swap_plus_rss() {
+        let mut app = make_app(false);
+        let unified = |pid, swap_kb, rss_kb| UnifiedProcessInfo {
+            pid,
+            name: format!("p{}", pid),
+            swap_kb,
+            cpu_nodes: vec![],
+            gpu_nodes: vec![],
+            kb_per_node: Default::default(),
+            gpu_memory_kb: None,
+            gpu_indices: vec![],
+            location: crate::data::ProcessLocation::CpuOnly,
+            rss_kb,
+            count: 1,
+        };
+        // p1 swaps more, p2 has the larger overall footprint
+        app.unified_procs = vec![unified(1, 100, Some(10)), unified(2, 50, Some(500)), unified(3, 80, None)];
+        app.sort_unified_procs();
+        assert_eq!(app.unified_procs[0].pid, 1);
+
+        app.on_key_event(KeyEvent::from(KeyCode::Char('s')));
+        assert_eq!(app.sort_column, SortColumn::Committed);
+        assert!(app.committed_column_visible());
+        let order: Vec<u32> = app.unified_procs.iter().map(|p| p.pid).collect();
+        assert_eq!(order, vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn test_committed_column_toggle() {
+        let mut app = make_app(false);
+        assert!(!app.committed_column_visible());
+        app.on_key_event(KeyEvent::from(KeyCode::Char('R')));
+        assert!(app.committed_column_visible());
+        app.on_key_event(KeyEvent::from(KeyCode::Char('R')));
+        assert!(!app.committed_column_visible());
+    }
+
+    #[test]
+    fn test_namespace_key_groups_rows() {
+        let mut app = make_app(false);
+        assert_eq!(app.namespace_mode, NamespaceMode::Off);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('n')));
+        assert_eq!(app.namespace_mode, NamespaceMode::Annotate);
+        assert!(!app.rows_aggregated());
+        app.on_key_event(KeyEvent::from(KeyCode::Char('n')));
+        assert_eq!(app.namespace_mode, NamespaceMode::Group);
+        assert!(app.rows_aggregated());
+        // Mock processes have no namespace: a single "-" group
+        assert_eq!(app.swap_process_rows.len(), 1);
+        assert_eq!(app.swap_process_rows[0].name, "-");
+        app.on_key_event(KeyEvent::from(KeyCode::Char('n')));
+        assert_eq!(app.namespace_mode, NamespaceMode::Off);
+    }
+
+    #[test]
+    fn test_numa_node_sort_key_toggles() {
+        use crate::ui::numa_view::NumaNodeSort;
+        let mut app = make_app(false);
+        assert_eq!(app.numa_node_sort, NumaNodeSort::Id);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('N')));
+        assert_eq!(app.numa_node_sort, NumaNodeSort::Used);
+        assert_eq!(app.effective_config().numa_node_sort, "used");
+        app.on_key_event(KeyEvent::from(KeyCode::Char('N')));
+        assert_eq!(app.numa_node_sort, NumaNodeSort::Id);
+    }
+
+    #[test]
+    fn test_device_layout_key_cycles() {
+        use ui::swap_devices::DeviceLayout;
+        let mut app = make_app(false);
+        assert_eq!(app.device_layout, DeviceLayout::Auto);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('l')));
+        assert_eq!(app.device_layout, DeviceLayout::Wide);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('l')));
+        assert_eq!(app.device_layout, DeviceLayout::Narrow);
+        assert_eq!(app.effective_config().device_layout, "narrow");
+        app.on_key_event(KeyEvent::from(KeyCode::Char('l')));
+        assert_eq!(app.device_layout, DeviceLayout::Auto);
+    }
+
+    // --- Effective config tests ---
+
+    #[test]
+    fn test_effective_config_reflects_flags_and_toggles() {
+        let mut app = make_app(false)
+            .with_theme(Some(ThemeType::Nord))
+            .with_watched_pids(vec![42])
+            .with_used_mode(SwapUsedMode::ExcludeCached);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('m')));
+        app.on_key_event(KeyEvent::from(KeyCode::Char('a')));
+        app.on_key_event(KeyEvent::from(KeyCode::Char('4')));
+        app.on_key_event(KeyEvent::from(KeyCode::Char('x')));
+
+        let config = app.effective_config();
+        assert_eq!(config.theme, ThemeType::Nord);
+        assert_eq!(config.unit, "mb");
+        assert_eq!(config.view, "unified");
+        assert_eq!(config.time_axis, "seconds");
+        assert_eq!(config.pid, vec![42]);
+        assert!(config.aggregated);
+        assert_eq!(config.namespace, "off");
+        assert!(config.exclude_swap_cache);
+        assert!(config.swap_alert.is_none());
+    }
+
+    #[test]
+    fn test_w_captures_config_snapshot() {
+        let mut app = make_app(false);
+        assert!(app.config_snapshot.is_none());
+        app.on_key_event(KeyEvent::from(KeyCode::Char('w')));
+        let snapshot = app.config_snapshot.clone().unwrap();
+        assert!(snapshot.contains("interval_ms = 1000\n"));
+        // A later toggle does not change the captured settings
+        app.on_key_event(KeyEvent::from(KeyCode::Char('h')));
+        assert_eq!(app.config_snapshot, Some(snapshot));
+    }
+
+    // --- Chart history tests ---
+
+    #[test]
+    fn test_history_len_caps_chart_data() {
+        let mut app = make_app(false).with_history_len(10);
+        app.chart_info = app.provider.get_swap_info(&SizeUnits::KB).unwrap();
+        for _ in 0..25 {
+            app.update_chart_data();
+            assert!(app.chart_data.len() <= 10);
+        }
+        assert_eq!(app.chart_data.len(), 10);
+        // The window spans exactly the retained samples
+        assert_eq!(app.time_window[1] - app.time_window[0], 10.0);
+        assert_eq!(app.chart_data.first().unwrap().0, app.time_window[0]);
+        assert_eq!(app.chart_data.last().unwrap().0, app.time_window[1] - 1.0);
+        assert_eq!(app.effective_config().history_len, 10);
+    }
+
+    // --- Used swap definition tests ---
+
+    #[test]
+    fn test_used_mode_toggle_affects_chart_data() {
+        let mut mock = MockDataProvider::new();
+        mock.swap_update.swap_cached = Some(500_000);
+        let mut app = App::new(Box::new(mock), false);
+        app.chart_info = app.provider.get_swap_info(&SizeUnits::KB).unwrap();
+
+        app.update_chart_data();
+        assert_eq!(app.chart_data.last().unwrap().1, 2_000_000.0);
+
+        app.on_key_event(KeyEvent::from(KeyCode::Char('c')));
+        assert_eq!(app.used_mode, SwapUsedMode::ExcludeCached);
+        app.update_chart_data();
+        assert_eq!(app.chart_data.last().unwrap().1, 1_500_000.0);
+    }
+
+    #[test]
+    fn test_torn_meminfo_read_keeps_previous_used_swap() {
+        let mut mock = MockDataProvider::new();
+        mock.swap_update.used_swap = 0;
+        mock.swap_update.torn_read = true;
+        let mut app = App::new(Box::new(mock), false);
+        app.chart_info = SwapUpdate { total_swap: 8_000_000, used_swap: 2_000_000, ..Default::default() };
+
+        app.on_key_event(KeyEvent::from(KeyCode::Char('m')));
+        assert_eq!(app.chart_info.used_swap, 2_000_000);
+        app.update_chart_data();
+        assert_eq!(app.chart_data.last().unwrap().1, 2_000_000.0);
+    }
+
+    // --- PID watch tests ---
+
+    #[test]
+    fn test_watched_pids_restrict_process_list() {
+        let mut app = make_app(false).with_watched_pids(vec![2]);
+        app.rebuild_process_lines();
+        assert_eq!(app.swap_process_rows.len(), 1);
+        assert_eq!(app.swap_process_rows[0].pid, 2);
+    }
+
+    #[test]
+    fn test_watched_pid_exit_is_shown_then_dropped() {
+        let mut mock = MockDataProvider::new();
+        mock.processes.retain(|p| p.pid == 1);
+        mock.exited_pids = vec![2];
+        let mut app = App::new(Box::new(mock), false).with_watched_pids(vec![1, 2]);
+
+        // PID 2 was seen swapping before it exited
+        app.watch.as_mut().unwrap().filter_swap(&mut vec![swap_proc(2, "another", 512.0)]);
+
+        app.tick = 1;
+        app.update_watch();
+        app.rebuild_process_lines();
+        let names: Vec<_> = app.swap_process_rows.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["test_proc", "another (exited)"]);
+
+        app.tick = 1 + crate::data::EXITED_TICKS;
+        app.update_watch();
+        app.rebuild_process_lines();
+        assert_eq!(app.swap_process_rows.len(), 1);
+    }
+
+    #[test]
+    fn test_with_watched_pids_empty_is_noop() {
+        let app = make_app(false).with_watched_pids(vec![]);
+        assert!(app.watch.is_none());
+    }
+
+    // --- GPU status tests ---
+
+    #[test]
+    fn test_refresh_gpu_data_records_failure() {
+        let mut mock = MockDataProvider::new();
+        mock.gpu_failure = Some("Driver/library version mismatch".into());
+        let mut app = App::new(Box::new(mock), false);
+        app.refresh_gpu_data();
+        assert_eq!(app.gpu_status, GpuStatus::Failed("Driver/library version mismatch".into()));
+        assert!(app.gpu_devices.is_empty());
+    }
+
+    // --- Key handling tests ---
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.on_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_parse_interval_ms_bounds() {
+        assert_eq!(parse_interval_ms("250"), Some(250));
+        assert_eq!(parse_interval_ms("1"), Some(1));
+        assert_eq!(parse_interval_ms("10000"), Some(10000));
+        assert_eq!(parse_interval_ms("0"), None);
+        assert_eq!(parse_interval_ms("10001"), None);
+        assert_eq!(parse_interval_ms(""), None);
+    }
+
+    #[test]
+    fn test_interval_prompt_sets_timeout() {
+        let mut app = make_app(false);
+        app.running = true;
+        press(&mut app, KeyCode::Char('e'));
+        for c in "2509".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Backspace);
+        // Keys go to the prompt, not the normal bindings
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.running);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.timeout, 250);
+        assert!(app.interval_input.is_none());
+    }
+
+    #[test]
+    fn test_interval_prompt_rejects_out_of_range_and_cancels() {
+        let mut app = make_app(false);
+        press(&mut app, KeyCode::Char('e'));
+        press(&mut app, KeyCode::Char('0'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.timeout, 1000);
+        assert_eq!(app.interval_input.as_deref(), Some("0"));
+        press(&mut app, KeyCode::Esc);
+        assert!(app.interval_input.is_none());
+        assert_eq!(app.timeout, 1000);
+    }
+
+    #[test]
+    fn test_slash_filters_process_lines() {
+        let mut app = make_app(false);
+        app.running = true;
+        app.rebuild_process_lines();
+        assert_eq!(app.swap_processes_lines.len(), 3);
+
+        press(&mut app, KeyCode::Char('/'));
+        assert!(app.filter_input);
+        for c in "ANOq".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        // 'q' is part of the query, not a command
+        assert!(app.running);
+        assert_eq!(app.swap_processes_lines.len(), 1);
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.name_filter, "ANO");
+        assert_eq!(app.swap_processes_lines.len(), 2);
+        assert!(app.swap_processes_lines[1].to_string().contains("another"));
+
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.filter_input);
+        assert_eq!(app.name_filter, "ANO");
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(160, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("filter:/ANO"));
+
+        press(&mut app, KeyCode::Char('/'));
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.filter_input);
+        assert!(app.name_filter.is_empty());
+        assert_eq!(app.swap_processes_lines.len(), 3);
+        assert!(app.running);
+    }
+
+    #[test]
+    fn test_selection_follows_pid_across_refreshes() {
+        let mut app = make_app(false);
+        app.rebuild_process_lines();
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected_row(), Some(1));
+
+        // pid 2 now swaps the most and moves to the top
+        app.swap_process_rows.reverse();
+        app.build_process_lines();
+        assert_eq!(app.selected_row(), Some(0));
+        assert_eq!(app.swap_process_rows[0].pid, 2);
+
+        // Once the process is gone the selection is dropped, not moved on
+        app.swap_process_rows.retain(|p| p.pid != 2);
+        app.build_process_lines();
+        assert!(app.selected_row().is_none());
+        assert!(app.selected.is_none());
+    }
+
+    #[test]
+    fn test_arrows_move_selection_and_k_confirms() {
+        let mut app = make_app(false);
+        app.rebuild_process_lines();
+        press(&mut app, KeyCode::Char('K'));
+        assert!(app.kill_prompt.is_none());
+        assert!(app.status_message.as_ref().unwrap().0.contains("select a process"));
+
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected_row(), Some(0));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected_row(), Some(1));
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.selected_row(), Some(0));
+
+        press(&mut app, KeyCode::Char('K'));
+        let prompt = app.kill_prompt.clone().unwrap();
+        assert_eq!((prompt.pid, prompt.name.as_str(), prompt.signal), (1, "test_proc", KillSignal::Term));
+        press(&mut app, KeyCode::Char('K'));
+        assert_eq!(app.kill_prompt.as_ref().unwrap().signal, KillSignal::Kill);
+        // Anything but y cancels, and is not treated as a command
+        press(&mut app, KeyCode::Char('4'));
+        assert!(app.kill_prompt.is_none());
+        assert_eq!(app.active_view, ActiveView::Swap);
+
+        // Grouped rows have no PID to signal
+        app.aggregated = true;
+        app.rebuild_process_lines();
+        press(&mut app, KeyCode::Char('K'));
+        assert!(app.kill_prompt.is_none());
+        assert!(app.status_message.as_ref().unwrap().0.contains("no single PID"));
+    }
+
+    #[test]
+    fn test_kill_missing_pid_reports_status() {
+        // Far above pid_max (at most 2^22), so no process can have it
+        let mut mock = MockDataProvider::new();
+        mock.processes[0].pid = 2_000_000_000;
+        let mut app = App::new(Box::new(mock), false);
+        app.rebuild_process_lines();
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char('K'));
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app.kill_prompt.is_none());
+        assert_eq!(app.status_message.as_ref().unwrap().0, "kill 2000000000: no such process (already exited)");
+
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(160, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("no such process"));
+    }
+
+    #[test]
+    fn test_kill_prompt_not_sent_once_pid_unlisted() {
+        let mut app = make_app(false);
+        app.rebuild_process_lines();
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char('K'));
+        assert_eq!(app.kill_prompt.as_ref().unwrap().pid, 1);
+
+        // A refresh while the prompt is open drops pid 1 and moves pid 2 up
+        // to the selected row index; confirming must not signal pid 2
+        app.swap_process_rows.retain(|p| p.pid != 1);
+        app.build_process_lines();
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app.kill_prompt.is_none());
+        assert_eq!(app.status_message.as_ref().unwrap().0, "kill 1: no longer listed, nothing sent");
+    }
+
+    #[test]
+    fn test_kill_error_message() {
+        let eperm = std::io::Error::from_raw_os_error(libc::EPERM);
+        assert_eq!(kill_error_message(1, &eperm), "kill 1: permission denied (not your process; run as root)");
+        let other = std::io::Error::other("boom");
+        assert_eq!(kill_error_message(7, &other), "kill 7: boom");
+    }
+
+    #[test]
+    fn test_space_pauses_refresh() {
+        let mock = MockDataProvider::new();
+        let queries = mock.process_queries.clone();
+        let mut app = App::new(Box::new(mock), false);
+        app.active_view = ActiveView::Unified;
+        app.last_update = Instant::now().checked_sub(Duration::from_secs(10));
+        let tick = |app: &mut App| {
+            if app.refresh_due() {
+                app.refresh().unwrap();
+            }
+        };
+
+        press(&mut app, KeyCode::Char(' '));
+        tick(&mut app);
+        assert_eq!(queries.get(), 0, "no refresh while paused");
+        press(&mut app, KeyCode::Char('.'));
+        tick(&mut app);
+        assert_eq!(queries.get(), 0);
+
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(160, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("[Unified] PAUSED"));
+
+        // Resuming runs the pending refresh, unified rows included
+        press(&mut app, KeyCode::Char(' '));
+        tick(&mut app);
+        assert!(queries.get() > 0);
+        assert!(!app.unified_procs.is_empty());
+    }
+
+    #[test]
+    fn test_ctrl_z_requests_suspend_without_baseline() {
+        let mut app = make_app(false);
+        app.on_key_event(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL));
+        assert!(app.suspend_requested);
+        assert!(app.baseline.is_none());
+    }
+
+    // --- Refresh tests ---
+
+    #[test]
+    fn test_hidden_numa_processes_skip_numa_maps() {
+        let mut app = make_app(false);
+        app.active_view = ActiveView::Numa;
+        press(&mut app, KeyCode::Char('P'));
+        assert!(app.numa_processes_hidden);
+        assert!(app.effective_config().numa_processes_hidden);
+        app.refresh_numa_data();
+        assert!(app.numa_maps_last.is_none());
+
+        // Unified still needs the per-process reads
+        app.active_view = ActiveView::Unified;
+        app.refresh_numa_data();
+        assert!(app.numa_maps_last.is_some());
+
+        app.active_view = ActiveView::Numa;
+        press(&mut app, KeyCode::Char('P'));
+        assert!(!app.numa_processes_hidden);
+        assert!(app.numa_maps_last.is_none());
+        app.refresh_numa_data();
+        assert!(app.numa_maps_last.is_some());
+    }
+
+    #[test]
+    fn test_top_n_limits_numa_maps_reads() {
+        let mut mock = MockDataProvider::new();
+        mock.processes = (1..=12)
+            .map(|pid| swap_proc(pid, &format!("p{}", pid), pid as f64))
+            .collect();
+        let mut app = App::new(Box::new(mock), false).with_top_n(5);
+        app.active_view = ActiveView::Numa;
+        app.refresh_numa_data();
+        let pids: Vec<u32> = app.process_numa_infos.iter().map(|info| info.pid).collect();
+        assert_eq!(pids, vec![12, 11, 10, 9, 8]);
+        assert_eq!(app.effective_config().top_n, 5);
+    }
+
+    #[test]
+    fn test_refresh_key_resets_active_view_caches() {
+        let mut app = make_app(false);
+        app.numa_maps_last = Some(Instant::now());
+        app.gpu_devices_last = Some(Instant::now());
+        app.on_key_event(KeyEvent::from(KeyCode::Char('.')));
+        assert!(app.refresh_requested);
+        // Swap view: NUMA and GPU caches are left alone
+        assert!(app.numa_maps_last.is_some());
+        assert!(app.gpu_devices_last.is_some());
+
+        app.refresh_requested = false;
+        app.active_view = ActiveView::Gpu;
+        app.on_key_event(KeyEvent::from(KeyCode::F(5)));
+        assert!(app.refresh_requested);
+        assert!(app.gpu_devices_last.is_none());
+        assert!(app.numa_maps_last.is_some());
+
+        app.active_view = ActiveView::Unified;
+        app.on_key_event(KeyEvent::from(KeyCode::Char('.')));
+        assert!(app.numa_maps_last.is_none());
+    }
+
+    // --- Debug overlay tests ---
+
+    #[test]
+    fn test_debug_overlay_records_nvidia_smi_latency() {
+        let mut app = make_app(false).with_debug(true);
+        app.refresh_gpu_data();
+        assert!(app.debug.unwrap().nvidia_smi.is_some());
+
+        // Within the cache TTLs nvidia-smi is not called, so the last value stays
+        app.debug = Some(FrameTimings::default());
+        app.refresh_gpu_data();
+        assert_eq!(app.debug.unwrap().nvidia_smi, None);
+    }
+
+    #[test]
+    fn test_debug_overlay_rendered_only_with_flag() {
+        let backend = ratatui::backend::TestBackend::new(100, 30);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        let mut app = make_app(false);
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        assert!(!format!("{:?}", terminal.backend().buffer()).contains("nvidia-smi"));
+
+        let mut app = make_app(false).with_debug(true);
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        assert!(format!("{:?}", terminal.backend().buffer()).contains("nvidia-smi"));
+    }
+
+    // --- Alert tests ---
+
+    #[test]
+    fn test_with_alerts_disabled_without_thresholds() {
+        let app = make_app(false).with_alerts(AlertThresholds::default(), None);
+        assert!(app.alerts.is_none());
+    }
+
+    #[test]
+    fn test_with_alerts_enabled_with_threshold() {
+        let app = make_app(false).with_alerts(AlertThresholds { swap_percent: Some(80.0), ..Default::default() }, None);
+        assert!(app.alerts.is_some());
+        let app = make_app(false).with_alerts(AlertThresholds { thrash_pages_per_sec: Some(500.0), ..Default::default() }, None);
+        assert!(app.alerts.is_some());
+        assert_eq!(app.effective_config().thrash_alert, Some(500.0));
+    }
+
+    #[test]
+    fn test_swap_alert_shown_on_status_line_from_listed_rows() {
+        let mut mock = MockDataProvider::new();
+        mock.gpu_available = true;
+        mock.gpu_devices = vec![vram_device(0, 1024)];
+        let queries = mock.process_queries.clone();
+        let mut app = App::new(Box::new(mock), false)
+            .with_watched_pids(vec![2])
+            .with_alerts(AlertThresholds { swap_percent: Some(20.0), ..Default::default() }, None);
+        app.swap_size_unit = SizeUnits::MB;
+
+        app.refresh().unwrap();
+
+        // One /proc scan per tick, and the top process follows --pid
+        assert_eq!(queries.get(), 1);
+        let status = &app.status_message.as_ref().unwrap().0;
+        assert_eq!(status, "alert: swap usage 25.0% above threshold 20.0%; top process another (pid 2)");
+        // No GPU threshold: nvidia-smi is not queried outside the GPU views
+        assert!(app.gpu_devices.is_empty());
+    }
+
+    #[test]
+    fn test_thrash_alert_goes_to_alert_log_and_status_line() {
+        let path = std::env::temp_dir().join(format!("nv-swaptop-thrash-alert-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut app = make_app(false)
+            .with_alerts(AlertThresholds { thrash_pages_per_sec: Some(1000.0), ..Default::default() }, Some(path.clone()));
+        app.rebuild_process_lines();
+        app.thrash = Some(ThrashLevel::High);
+        (app.swap_in_rate, app.swap_out_rate) = (1500.0, 500.0);
+
+        app.check_alerts();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let message = "swap in+out 2000 pages/s above threshold 1000 pages/s; top process test_proc (pid 1)";
+        assert!(content.trim_end().ends_with(&format!("warning: {}", message)));
+        assert_eq!(app.status_message.as_ref().unwrap().0, format!("alert: {}", message));
+        assert_eq!(app.effective_config().alert_log, Some(path));
+    }
+
+    // --- Jump to heaviest tests ---
+
+    #[test]
+    fn test_jump_to_heaviest_selects_and_scrolls() {
+        let mut mock = MockDataProvider::new();
+        mock.processes = (1..=30)
+            .map(|pid| swap_proc(pid, &format!("p{}", pid), pid as f64))
+            .collect();
+        let mut app = App::new(Box::new(mock), false);
+        app.rebuild_process_lines();
+        app.visible_height = 12;
+        app.vertical_scroll = 15;
+
+        app.jump_to_heaviest();
+        // Sorted descending: pid 30 is row 0, header is line 0
+        assert_eq!(app.selected_row(), Some(0));
+        assert_eq!(app.vertical_scroll, 1);
+    }
+
+    #[test]
+    fn test_empty_list_end_key_and_scrollbar() {
+        let mut mock = MockDataProvider::new();
+        mock.processes = vec![];
+        let mut app = App::new(Box::new(mock), false);
+        app.rebuild_process_lines();
+        app.maximize_processes = true;
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        app.on_key_event(KeyEvent::from(KeyCode::End));
+        assert_eq!(app.vertical_scroll, 0);
+        app.on_key_event(KeyEvent::from(KeyCode::PageDown));
+        app.on_key_event(KeyEvent::from(KeyCode::Down));
+        assert_eq!(app.vertical_scroll, 0);
+
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        // Nothing to scroll: no scrollbar arrows or thumb
+        assert!(!content.contains('↑'));
+        assert!(!content.contains('↓'));
+    }
+
+    #[test]
+    fn test_end_key_stops_at_last_page() {
+        let mut mock = MockDataProvider::new();
+        mock.processes = (1..=50)
+            .map(|pid| swap_proc(pid, &format!("p{}", pid), pid as f64))
+            .collect();
+        let mut app = App::new(Box::new(mock), false);
+        app.rebuild_process_lines();
+        app.maximize_processes = true;
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        app.on_key_event(KeyEvent::from(KeyCode::End));
+        // 51 lines (header + 50 rows) in a 28-row panel (30 minus the outer border)
+        assert_eq!(app.vertical_scroll, 51 + 2 - app.visible_height);
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains('↑'));
+    }
+
+    #[test]
+    fn test_unified_paging_clamps_to_row_count() {
+        let mut app = make_app(false);
+        app.active_view = ActiveView::Unified;
+        app.unified_procs = (1..=40)
+            .map(|pid| UnifiedProcessInfo {
+                pid,
+                name: format!("p{}", pid),
+                swap_kb: pid as u64,
+                cpu_nodes: vec![],
+                gpu_nodes: vec![],
+                kb_per_node: Default::default(),
+                gpu_memory_kb: None,
+                gpu_indices: vec![],
+                location: crate::data::ProcessLocation::CpuOnly,
+                rss_kb: None,
+                count: 1,
+            })
+            .collect();
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let max = ui::process_list::max_scroll(app.unified_scroll.line_count, app.unified_scroll.visible_height);
+        assert!(app.unified_scroll.line_count > 40);
+        assert!(max > 0);
+
+        for _ in 0..10 {
+            app.on_key_event(KeyEvent::from(KeyCode::PageDown));
+        }
+        assert_eq!(app.unified_scroll.offset, max);
+        app.on_key_event(KeyEvent::from(KeyCode::Char('d')));
+        assert_eq!(app.unified_scroll.offset, max);
+        // The swap list's own position is untouched
+        assert_eq!(app.vertical_scroll, 0);
+
+        // Switching away and back keeps the position
+        app.on_key_event(KeyEvent::from(KeyCode::Char('1')));
+        app.on_key_event(KeyEvent::from(KeyCode::Char('4')));
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        assert_eq!(app.unified_scroll.offset, max);
+
+        // The header stays on the first row inside the border, and can
+        // still be clicked, while the rows scroll under it
+        let row_text = |terminal: &ratatui::Terminal<ratatui::backend::TestBackend>, y: u16| -> String {
+            let buf = terminal.backend().buffer();
+            (0..buf.area.width).map(|x| buf[(x, y)].symbol().to_string()).collect()
+        };
+        let header_y = app.unified_area.y + 1;
+        assert!(row_text(&terminal, header_y).contains("NAME"));
+        assert!(!row_text(&terminal, header_y + 1).contains("p0 "));
+        app.on_mouse_event(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: app.unified_area.x + 12,
+            row: header_y,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_eq!(app.sort_column, SortColumn::Name);
+
+        app.on_key_event(KeyEvent::from(KeyCode::PageUp));
+        assert_eq!(app.unified_scroll.offset, max - app.unified_scroll.page_size());
+        app.on_key_event(KeyEvent::from(KeyCode::Home));
+        assert_eq!(app.unified_scroll.offset, 0);
+    }
+
+    #[test]
+    fn test_jump_to_heaviest_empty_list() {
+        let mut mock = MockDataProvider::new();
+        mock.processes = vec![];
+        let mut app = App::new(Box::new(mock), false);
+        app.rebuild_process_lines();
+        app.jump_to_heaviest();
+        assert!(app.selected_row().is_none());
+    }
+
     // --- Unified view data tests ---
 
     #[test]
@@ -761,29 +3297,19 @@ mod tests {
 
         let mut mock = MockDataProvider::new();
         mock.gpu_available = true;
-        mock.gpu_devices = vec![GpuDevice {
-            index: 0, name: "GPU 0".into(), memory_total_kb: 81_920_000,
-            memory_used_kb: 40_000_000, memory_free_kb: 41_920_000,
-            numa_node_id: Some(2), temperature: Some(45),
-            pci_bus_id: "00:01.0".into(),
-        }];
+        mock.gpu_devices = vec![GpuDevice { numa_node_id: Some(2), temperature: Some(45), ..vram_device(0, 40_000_000) }];
         mock.gpu_processes = vec![GpuProcessInfo {
-            pid: 1, name: "test_proc".into(), gpu_index: 0, gpu_memory_used_kb: 4096,
+            pid: 1, name: "test_proc".into(), gpu_index: 0, gpu_memory_used_kb: 4096, sm_util: None, mem_util: None,
         }];
 
         let mut app = App::new(Box::new(mock), false);
         app.active_view = ActiveView::Unified;
-        app.gpu_devices = vec![GpuDevice {
-            index: 0, name: "GPU 0".into(), memory_total_kb: 81_920_000,
-            memory_used_kb: 40_000_000, memory_free_kb: 41_920_000,
-            numa_node_id: Some(2), temperature: Some(45),
-            pci_bus_id: "00:01.0".into(),
-        }];
+        app.gpu_devices = vec![GpuDevice { numa_node_id: Some(2), temperature: Some(45), ..vram_device(0, 40_000_000) }];
         app.gpu_processes = vec![GpuProcessInfo {
-            pid: 1, name: "test_proc".into(), gpu_index: 0, gpu_memory_used_kb: 4096,
+            pid: 1, name: "test_proc".into(), gpu_index: 0, gpu_memory_used_kb: 4096, sm_util: None, mem_util: None,
         }];
 
-        app.refresh_unified_data();
+        app.refresh_unified_data(true);
 
         // test_proc (pid=1) should have gpu_indices=[0] and gpu_nodes=[2]
         let proc = app.unified_procs.iter().find(|p| p.pid == 1).unwrap();
@@ -792,4 +3318,149 @@ mod tests {
         assert_eq!(proc.gpu_memory_kb, Some(4096));
         assert_eq!(proc.location, ProcessLocation::CpuAndGpu);
     }
+
+    #[test]
+    fn test_gpu_memory_history_drops_oldest_past_cap() {
+        let mut app = make_app(false);
+        for used_gb in 0..=DEFAULT_HISTORY_LEN as u64 {
+            app.gpu_devices = vec![vram_device(0, used_gb * 1_048_576), vram_device(3, 1024)];
+            app.record_gpu_memory();
+        }
+        let history = &app.gpu_memory_history[&0];
+        assert_eq!(history.len(), DEFAULT_HISTORY_LEN);
+        // The 0 GB sample went first
+        assert_eq!(history[0].1, 1_048_576.0);
+        assert_eq!(history.last().unwrap().1, DEFAULT_HISTORY_LEN as f64 * 1_048_576.0);
+        assert!(history.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert_eq!(app.gpu_memory_history[&3].len(), DEFAULT_HISTORY_LEN);
+    }
+
+    #[test]
+    fn test_gpu_view_charts_vram_and_v_cycles_gpus() {
+        let mut mock = MockDataProvider::new();
+        mock.gpu_available = true;
+        mock.gpu_devices = vec![vram_device(0, 40_000_000), vram_device(3, 1_000_000)];
+        let mut app = App::new(Box::new(mock), false);
+        app.active_view = ActiveView::Gpu;
+        app.refresh_gpu_data();
+        assert_eq!(app.gpu_memory_history[&0], vec![(app.gpu_memory_history[&0][0].0, 40_000_000.0)]);
+
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 50)).unwrap();
+        let mut screen = |app: &mut App| {
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect::<String>()
+        };
+        let content = screen(&mut app);
+        assert!(content.contains("GPU 0 VRAM 40000000 KiB / 81920000 KiB"));
+        assert!(content.contains("(V next GPU)"));
+
+        press(&mut app, KeyCode::Char('V'));
+        assert!(screen(&mut app).contains("GPU 3 VRAM 1000000 KiB"));
+        // Wraps back to the lowest index
+        press(&mut app, KeyCode::Char('V'));
+        assert_eq!(app.charted_gpu, 0);
+    }
+
+    #[test]
+    fn test_refresh_unified_data_reads_swap_in_kb() {
+        let mock = MockDataProvider::new();
+        let requested = mock.swap_unit_requested.clone();
+        let mut app = App::new(Box::new(mock), false);
+        app.swap_size_unit = SizeUnits::MB;
+
+        app.refresh_unified_data(true);
+
+        assert_eq!(*requested.borrow(), Some(SizeUnits::KB));
+        let proc = app.unified_procs.iter().find(|p| p.pid == 1).unwrap();
+        assert_eq!(proc.swap_kb, 1024);
+    }
+
+    #[test]
+    fn test_a_groups_gpu_processes_in_gpu_view() {
+        use crate::data::GpuProcessInfo;
+
+        let mut mock = MockDataProvider::new();
+        mock.gpu_available = true;
+        mock.gpu_devices = vec![vram_device(0, 2048), vram_device(1, 1024)];
+        let proc = |pid, gpu_index, gpu_memory_used_kb| GpuProcessInfo {
+            pid, name: format!("p{}", pid), gpu_index, gpu_memory_used_kb, sm_util: None, mem_util: None,
+        };
+        mock.gpu_processes = vec![proc(7, 1, 1024), proc(8, 0, 1536), proc(9, 0, 512)];
+        let mut app = App::new(Box::new(mock), false);
+        app.active_view = ActiveView::Gpu;
+        app.refresh_gpu_data();
+
+        press(&mut app, KeyCode::Char('a'));
+        assert!(app.group_gpu_processes);
+        // The swap list's aggregation is left alone
+        assert!(!app.aggregated);
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 50)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("GPU 0 total (2 processes)"));
+        assert!(content.contains("GPU 1 total (1 process)"));
+        let gpu0 = content.find("GPU 0 total").unwrap();
+        assert!(gpu0 < content.find("GPU 1 total").unwrap());
+        assert!(content[gpu0..].contains("2048 KiB"));
+    }
+
+    #[test]
+    fn test_unified_view_aggregates_by_name() {
+        let mut mock = MockDataProvider::new();
+        mock.processes = vec![swap_proc(11, "worker", 300.0), swap_proc(12, "worker", 200.0), swap_proc(13, "main", 50.0)];
+        let mut app = App::new(Box::new(mock), false);
+        app.active_view = ActiveView::Unified;
+        app.refresh_unified_data(true);
+        assert_eq!(app.unified_procs.len(), 3);
+
+        press(&mut app, KeyCode::Char('a'));
+        app.refresh_unified_data(true);
+        assert_eq!(app.unified_procs.len(), 2);
+        assert_eq!((app.unified_procs[0].name.as_str(), app.unified_procs[0].swap_kb), ("worker", 500));
+        assert_eq!(app.unified_procs[0].count, 2);
+
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let content: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("COUNT"));
+        assert!(content.contains("       2 worker"));
+    }
+
+    #[test]
+    fn test_fetch_gpu_data_resolves_cmdlines() {
+        use crate::data::GpuProcessInfo;
+
+        let mut mock = MockDataProvider::new();
+        mock.gpu_available = true;
+        mock.gpu_processes = vec![GpuProcessInfo {
+            pid: 7, name: "python3".into(), gpu_index: 0, gpu_memory_used_kb: 4096, sm_util: None, mem_util: None,
+        }];
+        mock.gpu_cmdlines = HashMap::from([(7, "python3 train.py".to_string())]);
+
+        let mut app = App::new(Box::new(mock), false);
+        app.fetch_gpu_data();
+        assert_eq!(app.gpu_cmdlines.get(&7).map(String::as_str), Some("python3 train.py"));
+    }
+
+    #[test]
+    fn test_fetch_gpu_data_flags_hidden_processes() {
+        use crate::data::GpuProcessInfo;
+
+        let busy = |processes: Vec<GpuProcessInfo>| {
+            let mut mock = MockDataProvider::new();
+            mock.gpu_available = true;
+            mock.gpu_devices = crate::data::gpu::parse_gpu_devices_csv(
+                "0, H100, 81920 MiB, 40960 MiB, 40960 MiB, 45, 00000000:01:00.0\n",
+            );
+            mock.gpu_processes = processes;
+            let mut app = App::new(Box::new(mock), false);
+            app.fetch_gpu_data();
+            app.gpu_processes_hidden
+        };
+        // 40 GB in use but nothing listed
+        assert!(busy(Vec::new()));
+        assert!(!busy(vec![GpuProcessInfo {
+            pid: 7, name: "python3".into(), gpu_index: 0, gpu_memory_used_kb: 40960 * 1024, sm_util: None, mem_util: None,
+        }]));
+    }
 }
@@ -8,6 +8,163 @@ pub struct Cli {
     /// Run with synthetic demo data instead of real system data
     #[arg(long)]
     pub demo: bool,
+
+    /// Serve /metrics (Prometheus) and /snapshot.json over HTTP on ADDR (e.g. 127.0.0.1:9184)
+    #[cfg(feature = "serve")]
+    #[arg(long, value_name = "ADDR")]
+    pub serve: Option<String>,
+
+    /// With --serve, only serve: no TUI; runs until interrupted
+    #[cfg(feature = "serve")]
+    #[arg(long, requires = "serve")]
+    pub headless: bool,
+
+    /// Colour theme to start with (`t` cycles at runtime); colorblind avoids red/green status colours
+    #[arg(long, value_enum, value_name = "THEME")]
+    pub theme: Option<crate::theme::ThemeType>,
+
+    /// Print per-process NUMA memory (KB per node, CPU node, misaligned flag) for the top swap users as CSV and exit
+    #[arg(long)]
+    pub dump_numa_csv: bool,
+
+    /// Print one snapshot (swap totals, swap and GPU processes, GPU devices, merged list) and exit, without the TUI
+    #[arg(long)]
+    pub once: bool,
+
+    /// Output format for --once
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = crate::snapshot::SnapshotFormat::Json, requires = "once")]
+    pub format: crate::snapshot::SnapshotFormat,
+
+    /// Print the effective settings as TOML on stderr and exit, for reference only; it is not read back (`w` does the same at runtime, on quit)
+    #[arg(long)]
+    pub print_config: bool,
+
+    /// Count only swap not also cached in RAM as "used" (used - SwapCached)
+    #[arg(long)]
+    pub exclude_swap_cache: bool,
+
+    /// Only show these processes (comma-separated PIDs) in every view
+    #[arg(long, value_name = "PID[,PID...]", value_delimiter = ',')]
+    pub pid: Vec<u32>,
+
+    /// Render at most N swap processes (the largest first); the rest are summarised
+    #[arg(long, value_name = "N", default_value_t = crate::app::DEFAULT_MAX_PROCESSES, conflicts_with = "once")]
+    pub max_processes: usize,
+
+    /// Read and show NUMA distribution for the N largest swap users (NUMA view, --dump-numa-csv)
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = crate::data::NUMA_MAPS_TOP_N,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        conflicts_with = "once"
+    )]
+    pub top_n: usize,
+
+    /// Keep N samples per chart line (the swap chart spans N refreshes)
+    #[arg(
+        long = "history",
+        value_name = "N",
+        default_value_t = crate::app::DEFAULT_HISTORY_LEN,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub history_len: usize,
+
+    /// Re-sort process lists only every N refresh ticks (`S` re-sorts now); values still update every tick
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    pub resort_interval: u64,
+
+    /// Log an alert when used swap crosses PERCENT of total swap
+    #[arg(long, value_name = "PERCENT", value_parser = parse_percent)]
+    pub swap_alert: Option<f64>,
+
+    /// Log an alert when memory use on any GPU crosses PERCENT of its total
+    #[arg(long, value_name = "PERCENT", value_parser = parse_percent)]
+    pub gpu_alert: Option<f64>,
+
+    /// Log an alert when swap-in plus swap-out crosses PAGES pages per second
+    #[arg(long, value_name = "PAGES", value_parser = parse_rate)]
+    pub thrash_alert: Option<f64>,
+
+    /// Append alerts to FILE (instead of syslog, or stderr when it is not a terminal); alerts are always shown on the status line
+    #[arg(long, value_name = "FILE")]
+    pub alert_log: Option<std::path::PathBuf>,
+
+    /// GPU view: highlight GPUs with processes but utilisation at or below PERCENT
+    #[arg(long, value_name = "PERCENT", default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=100))]
+    pub idle_gpu_util: u32,
+
+    /// Colour for idle-but-held GPUs: a name (e.g. magenta) or #RRGGBB; defaults to the theme's warning colour
+    #[arg(long, value_name = "COLOR", value_parser = parse_color)]
+    pub idle_gpu_color: Option<ratatui::style::Color>,
+
+    /// Hint at vm.swappiness tuning when swap is in use while MemAvailable is at least PERCENT of RAM
+    #[arg(long, value_name = "PERCENT", default_value_t = 50.0, value_parser = parse_percent)]
+    pub swappiness_hint_available: f64,
+
+    /// Ignore less than MB of used swap for the swappiness hint
+    #[arg(long, value_name = "MB", default_value_t = 1)]
+    pub swappiness_hint_min_mb: u64,
+
+    /// Highlight processes holding more than SIZE of swap (Swap and Unified views); SIZE is MB, or takes a unit such as 512M, 1G or 2GB
+    #[arg(long = "swap-highlight-mb", value_name = "SIZE", value_parser = parse_size)]
+    pub swap_highlight_kb: Option<u64>,
+
+    /// Append a CSV row per refresh (swap totals, top process, GPU totals) to PATH
+    #[arg(long, value_name = "PATH")]
+    pub record: Option<std::path::PathBuf>,
+
+    /// Rotate the --record file once it would exceed MB
+    #[arg(long, value_name = "MB", default_value_t = crate::record::DEFAULT_MAX_MB, value_parser = clap::value_parser!(u64).range(1..))]
+    pub record_max_mb: u64,
+
+    /// Rotated --record files to keep (PATH.1 is the newest); 0 keeps none
+    #[arg(long, value_name = "N", default_value_t = crate::record::DEFAULT_KEEP)]
+    pub record_keep: usize,
+
+    /// Run PATH as nvidia-smi instead of looking it up on PATH (overrides $NVIDIA_SMI_PATH)
+    #[arg(long, value_name = "PATH")]
+    pub nvidia_smi: Option<std::path::PathBuf>,
+
+    /// GPU view: sample per-process SM/memory utilisation with `nvidia-smi pmon` (takes about a second per GPU refresh)
+    #[arg(long)]
+    pub gpu_pmon: bool,
+
+    /// Report skipped nvidia-smi/sysfs lines on stderr, with the reason (redirect with 2>FILE)
+    #[arg(long)]
+    pub verbose: bool,
+
+    /// Overlay render, refresh and nvidia-smi timings (for profiling nv-swaptop itself)
+    #[arg(long, hide = true)]
+    pub debug: bool,
+}
+
+fn parse_percent(s: &str) -> Result<f64, String> {
+    let value: f64 = s.parse().map_err(|_| format!("`{s}` is not a number"))?;
+    if (0.0..=100.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("`{s}` is not between 0 and 100"))
+    }
+}
+
+fn parse_rate(s: &str) -> Result<f64, String> {
+    let value: f64 = s.parse().map_err(|_| format!("`{s}` is not a number"))?;
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("`{s}` is not a positive number"))
+    }
+}
+
+/// A size in KB; a bare number is MB.
+fn parse_size(s: &str) -> Result<u64, String> {
+    crate::data::parse_size_kb(s, &crate::data::SizeUnits::MB)
+        .ok_or_else(|| format!("`{s}` is not a size such as 512, 512M, 1G or 2GB"))
+}
+
+fn parse_color(s: &str) -> Result<ratatui::style::Color, String> {
+    s.parse().map_err(|_| format!("`{s}` is not a colour name or #RRGGBB"))
 }
 
 #[cfg(test)]
@@ -32,6 +189,192 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[cfg(feature = "serve")]
+    #[test]
+    fn test_cli_parse_serve_addr() {
+        let cli = Cli::try_parse_from(["nv-swaptop", "--serve", "127.0.0.1:9184"]).unwrap();
+        assert_eq!(cli.serve.as_deref(), Some("127.0.0.1:9184"));
+        assert!(!cli.headless);
+        let cli = Cli::try_parse_from(["nv-swaptop", "--serve", "127.0.0.1:9184", "--headless"]).unwrap();
+        assert!(cli.headless);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--headless"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_alert_thresholds() {
+        let cli = Cli::try_parse_from(["nv-swaptop", "--swap-alert", "80", "--gpu-alert", "95.5"]).unwrap();
+        assert_eq!(cli.swap_alert, Some(80.0));
+        assert_eq!(cli.gpu_alert, Some(95.5));
+        let cli = Cli::try_parse_from(["nv-swaptop", "--thrash-alert", "2000"]).unwrap();
+        assert_eq!(cli.thrash_alert, Some(2000.0));
+        assert_eq!(cli.alert_log, None);
+        let cli = Cli::try_parse_from(["nv-swaptop", "--swap-alert", "80", "--alert-log", "alerts.log"]).unwrap();
+        assert_eq!(cli.alert_log.as_deref(), Some(std::path::Path::new("alerts.log")));
+    }
+
+    #[test]
+    fn test_cli_rejects_out_of_range_alert() {
+        assert!(Cli::try_parse_from(["nv-swaptop", "--swap-alert", "120"]).is_err());
+        assert!(Cli::try_parse_from(["nv-swaptop", "--gpu-alert", "lots"]).is_err());
+        assert!(Cli::try_parse_from(["nv-swaptop", "--thrash-alert", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_max_processes_default_and_override() {
+        let cli = Cli::try_parse_from(["nv-swaptop"]).unwrap();
+        assert_eq!(cli.max_processes, crate::app::DEFAULT_MAX_PROCESSES);
+        let cli = Cli::try_parse_from(["nv-swaptop", "--max-processes", "200"]).unwrap();
+        assert_eq!(cli.max_processes, 200);
+    }
+
+    #[test]
+    fn test_cli_history() {
+        assert_eq!(Cli::try_parse_from(["nv-swaptop"]).unwrap().history_len, 60);
+        assert_eq!(Cli::try_parse_from(["nv-swaptop", "--history", "720"]).unwrap().history_len, 720);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--history", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_top_n() {
+        assert_eq!(Cli::try_parse_from(["nv-swaptop"]).unwrap().top_n, 20);
+        assert_eq!(Cli::try_parse_from(["nv-swaptop", "--top-n", "64"]).unwrap().top_n, 64);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--top-n", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_resort_interval() {
+        let cli = Cli::try_parse_from(["nv-swaptop"]).unwrap();
+        assert_eq!(cli.resort_interval, 1);
+        let cli = Cli::try_parse_from(["nv-swaptop", "--resort-interval", "5"]).unwrap();
+        assert_eq!(cli.resort_interval, 5);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--resort-interval", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_swappiness_hint_thresholds() {
+        let cli = Cli::try_parse_from(["nv-swaptop"]).unwrap();
+        assert_eq!((cli.swappiness_hint_available, cli.swappiness_hint_min_mb), (50.0, 1));
+        let cli = Cli::try_parse_from(["nv-swaptop", "--swappiness-hint-available", "70", "--swappiness-hint-min-mb", "256"]).unwrap();
+        assert_eq!((cli.swappiness_hint_available, cli.swappiness_hint_min_mb), (70.0, 256));
+        assert!(Cli::try_parse_from(["nv-swaptop", "--swappiness-hint-available", "150"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_swap_highlight() {
+        assert_eq!(Cli::try_parse_from(["nv-swaptop"]).unwrap().swap_highlight_kb, None);
+        let cli = Cli::try_parse_from(["nv-swaptop", "--swap-highlight-mb", "1024"]).unwrap();
+        assert_eq!(cli.swap_highlight_kb, Some(1024 * 1024));
+        let cli = Cli::try_parse_from(["nv-swaptop", "--swap-highlight-mb", "1G"]).unwrap();
+        assert_eq!(cli.swap_highlight_kb, Some(1024 * 1024));
+        let cli = Cli::try_parse_from(["nv-swaptop", "--swap-highlight-mb", "512M"]).unwrap();
+        assert_eq!(cli.swap_highlight_kb, Some(512 * 1024));
+        assert!(Cli::try_parse_from(["nv-swaptop", "--swap-highlight-mb", "1 lots"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_record() {
+        let cli = Cli::try_parse_from(["nv-swaptop"]).unwrap();
+        assert_eq!(cli.record, None);
+        assert_eq!((cli.record_max_mb, cli.record_keep), (crate::record::DEFAULT_MAX_MB, crate::record::DEFAULT_KEEP));
+        let cli = Cli::try_parse_from(["nv-swaptop", "--record", "swap.csv", "--record-max-mb", "10", "--record-keep", "0"]).unwrap();
+        assert_eq!(cli.record.as_deref(), Some(std::path::Path::new("swap.csv")));
+        assert_eq!((cli.record_max_mb, cli.record_keep), (10, 0));
+        assert!(Cli::try_parse_from(["nv-swaptop", "--record-max-mb", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_pid_list() {
+        let cli = Cli::try_parse_from(["nv-swaptop", "--pid", "1234,5678"]).unwrap();
+        assert_eq!(cli.pid, vec![1234, 5678]);
+        let cli = Cli::try_parse_from(["nv-swaptop"]).unwrap();
+        assert!(cli.pid.is_empty());
+        assert!(Cli::try_parse_from(["nv-swaptop", "--pid", "abc"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_exclude_swap_cache() {
+        assert!(!Cli::try_parse_from(["nv-swaptop"]).unwrap().exclude_swap_cache);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--exclude-swap-cache"]).unwrap().exclude_swap_cache);
+    }
+
+    #[test]
+    fn test_cli_parse_idle_gpu_highlight() {
+        use ratatui::style::Color;
+        let cli = Cli::try_parse_from(["nv-swaptop"]).unwrap();
+        assert_eq!(cli.idle_gpu_util, 0);
+        assert_eq!(cli.idle_gpu_color, None);
+        let cli = Cli::try_parse_from(["nv-swaptop", "--idle-gpu-util", "5", "--idle-gpu-color", "#ff00ff"]).unwrap();
+        assert_eq!(cli.idle_gpu_util, 5);
+        assert_eq!(cli.idle_gpu_color, Some(Color::Rgb(255, 0, 255)));
+        let cli = Cli::try_parse_from(["nv-swaptop", "--idle-gpu-color", "magenta"]).unwrap();
+        assert_eq!(cli.idle_gpu_color, Some(Color::Magenta));
+        assert!(Cli::try_parse_from(["nv-swaptop", "--idle-gpu-util", "101"]).is_err());
+        assert!(Cli::try_parse_from(["nv-swaptop", "--idle-gpu-color", "sparkly"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_print_config() {
+        assert!(!Cli::try_parse_from(["nv-swaptop"]).unwrap().print_config);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--print-config"]).unwrap().print_config);
+    }
+
+    #[test]
+    fn test_cli_parse_theme() {
+        let cli = Cli::try_parse_from(["nv-swaptop", "--theme", "colorblind"]).unwrap();
+        assert_eq!(cli.theme, Some(crate::theme::ThemeType::Colorblind));
+        assert!(Cli::try_parse_from(["nv-swaptop"]).unwrap().theme.is_none());
+        assert!(Cli::try_parse_from(["nv-swaptop", "--theme", "neon"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_nvidia_smi_path() {
+        let cli = Cli::try_parse_from(["nv-swaptop", "--nvidia-smi", "/opt/nvidia/bin/nvidia-smi"]).unwrap();
+        assert_eq!(cli.nvidia_smi, Some("/opt/nvidia/bin/nvidia-smi".into()));
+        assert!(Cli::try_parse_from(["nv-swaptop"]).unwrap().nvidia_smi.is_none());
+    }
+
+    #[test]
+    fn test_cli_parse_once_format() {
+        use crate::snapshot::SnapshotFormat;
+        let cli = Cli::try_parse_from(["nv-swaptop", "--once"]).unwrap();
+        assert!(cli.once);
+        assert_eq!(cli.format, SnapshotFormat::Json);
+        let cli = Cli::try_parse_from(["nv-swaptop", "--once", "--format", "tsv"]).unwrap();
+        assert_eq!(cli.format, SnapshotFormat::Tsv);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--format", "tsv"]).is_err());
+        assert!(Cli::try_parse_from(["nv-swaptop", "--once", "--format", "xml"]).is_err());
+        // --once lists every process and reads no numa_maps
+        assert!(Cli::try_parse_from(["nv-swaptop", "--once", "--max-processes", "10"]).is_err());
+        assert!(Cli::try_parse_from(["nv-swaptop", "--once", "--top-n", "5"]).is_err());
+        let cli = Cli::try_parse_from(["nv-swaptop", "--once", "--pid", "1,2", "--exclude-swap-cache"]).unwrap();
+        assert_eq!(cli.pid, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_cli_parse_dump_numa_csv() {
+        assert!(!Cli::try_parse_from(["nv-swaptop"]).unwrap().dump_numa_csv);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--dump-numa-csv"]).unwrap().dump_numa_csv);
+    }
+
+    #[test]
+    fn test_cli_parse_gpu_pmon() {
+        assert!(!Cli::try_parse_from(["nv-swaptop"]).unwrap().gpu_pmon);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--gpu-pmon"]).unwrap().gpu_pmon);
+    }
+
+    #[test]
+    fn test_cli_parse_verbose() {
+        assert!(!Cli::try_parse_from(["nv-swaptop"]).unwrap().verbose);
+        assert!(Cli::try_parse_from(["nv-swaptop", "--verbose"]).unwrap().verbose);
+    }
+
+    #[test]
+    fn test_cli_debug_flag_is_hidden() {
+        assert!(Cli::try_parse_from(["nv-swaptop", "--debug"]).unwrap().debug);
+        let help = <Cli as clap::CommandFactory>::command().render_long_help().to_string();
+        assert!(!help.contains("--debug"));
+    }
+
     #[test]
     fn test_cli_version_matches_cargo_toml() {
         let result = Cli::try_parse_from(["nv-swaptop", "-V"]);
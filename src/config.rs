@@ -0,0 +1,254 @@
+//! Effective settings (`--print-config`, `w`): startup flags combined with
+//! the runtime toggles, written as TOML. Keys are named after the matching
+//! command-line flags (snake_case) or, for settings that only have a key,
+//! after the toggle. The output is informational: nothing reads it back.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use ratatui::style::Color;
+
+use crate::theme::ThemeType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveConfig {
+    pub interval_ms: u64,
+    pub unit: &'static str,
+    pub unit_base: &'static str,
+    pub theme: ThemeType,
+    pub view: &'static str,
+    pub sort: &'static str,
+    pub sort_reversed: bool,
+    pub resort_interval: u64,
+    pub aggregated: bool,
+    pub namespace: &'static str,
+    pub exclude_swap_cache: bool,
+    pub max_processes: usize,
+    pub top_n: usize,
+    pub history_len: usize,
+    pub pid: Vec<u32>,
+    pub swap_alert: Option<f64>,
+    pub gpu_alert: Option<f64>,
+    /// Swap in+out pages per second
+    pub thrash_alert: Option<f64>,
+    pub alert_log: Option<PathBuf>,
+    pub idle_gpu_util: u32,
+    pub idle_gpu_color: Option<Color>,
+    pub swappiness_hint_available: f64,
+    pub swappiness_hint_min_mb: u64,
+    pub swap_highlight_mb: Option<u64>,
+    /// `--record` path, with its rotation size (MB) and kept file count
+    pub record: Option<(PathBuf, u64, usize)>,
+    pub show_devices: bool,
+    pub device_bars: bool,
+    pub device_layout: &'static str,
+    pub numa_node_sort: &'static str,
+    pub numa_processes_hidden: bool,
+    pub device_queue: bool,
+    pub dual_units: bool,
+    pub maximize_processes: bool,
+    pub chart_hidden: bool,
+    pub log_y: bool,
+    /// Visible chart series, by legend label
+    pub chart_series: Vec<&'static str>,
+    pub show_mig_instances: bool,
+    pub collapse_gpus: bool,
+    pub group_gpu_processes: bool,
+    pub show_committed: bool,
+    pub show_gpu_percent: bool,
+    pub location_filter: &'static str,
+    pub since_boot: bool,
+    pub time_axis: &'static str,
+}
+
+impl EffectiveConfig {
+    /// Unset optional values (no alert threshold, theme colour) are left
+    /// out rather than written as empty strings.
+    pub fn to_toml(&self) -> String {
+        let theme = self
+            .theme
+            .to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default();
+        let pids: Vec<String> = self.pid.iter().map(u32::to_string).collect();
+
+        let mut out = String::from("# nv-swaptop effective configuration\n");
+        let _ = writeln!(out, "interval_ms = {}", self.interval_ms);
+        let _ = writeln!(out, "unit = {}", toml_string(self.unit));
+        let _ = writeln!(out, "unit_base = {}", toml_string(self.unit_base));
+        let _ = writeln!(out, "theme = {}", toml_string(&theme));
+        let _ = writeln!(out, "view = {}", toml_string(self.view));
+        let _ = writeln!(out, "sort = {}", toml_string(self.sort));
+        let _ = writeln!(out, "sort_reversed = {}", self.sort_reversed);
+        let _ = writeln!(out, "resort_interval = {}", self.resort_interval);
+        let _ = writeln!(out, "aggregated = {}", self.aggregated);
+        let _ = writeln!(out, "namespace = {}", toml_string(self.namespace));
+        let _ = writeln!(out, "exclude_swap_cache = {}", self.exclude_swap_cache);
+        let _ = writeln!(out, "max_processes = {}", self.max_processes);
+        let _ = writeln!(out, "top_n = {}", self.top_n);
+        let _ = writeln!(out, "history_len = {}", self.history_len);
+        let _ = writeln!(out, "pid = [{}]", pids.join(", "));
+        if let Some(percent) = self.swap_alert {
+            let _ = writeln!(out, "swap_alert = {:?}", percent);
+        }
+        if let Some(percent) = self.gpu_alert {
+            let _ = writeln!(out, "gpu_alert = {:?}", percent);
+        }
+        if let Some(rate) = self.thrash_alert {
+            let _ = writeln!(out, "thrash_alert = {:?}", rate);
+        }
+        if let Some(path) = &self.alert_log {
+            let _ = writeln!(out, "alert_log = {}", toml_string(&path.to_string_lossy()));
+        }
+        let _ = writeln!(out, "idle_gpu_util = {}", self.idle_gpu_util);
+        if let Some(color) = self.idle_gpu_color {
+            let _ = writeln!(out, "idle_gpu_color = {}", toml_string(&color.to_string()));
+        }
+        let _ = writeln!(out, "swappiness_hint_available = {:?}", self.swappiness_hint_available);
+        let _ = writeln!(out, "swappiness_hint_min_mb = {}", self.swappiness_hint_min_mb);
+        if let Some(mb) = self.swap_highlight_mb {
+            let _ = writeln!(out, "swap_highlight_mb = {}", mb);
+        }
+        if let Some((path, max_mb, keep)) = &self.record {
+            let _ = writeln!(out, "record = {}", toml_string(&path.to_string_lossy()));
+            let _ = writeln!(out, "record_max_mb = {}", max_mb);
+            let _ = writeln!(out, "record_keep = {}", keep);
+        }
+        let _ = writeln!(out, "show_devices = {}", self.show_devices);
+        let _ = writeln!(out, "device_bars = {}", self.device_bars);
+        let _ = writeln!(out, "device_layout = {}", toml_string(self.device_layout));
+        let _ = writeln!(out, "numa_node_sort = {}", toml_string(self.numa_node_sort));
+        let _ = writeln!(out, "numa_processes_hidden = {}", self.numa_processes_hidden);
+        let _ = writeln!(out, "device_queue = {}", self.device_queue);
+        let _ = writeln!(out, "dual_units = {}", self.dual_units);
+        let _ = writeln!(out, "maximize_processes = {}", self.maximize_processes);
+        let _ = writeln!(out, "chart_hidden = {}", self.chart_hidden);
+        let _ = writeln!(out, "log_y = {}", self.log_y);
+        let series: Vec<String> = self.chart_series.iter().map(|s| toml_string(s)).collect();
+        let _ = writeln!(out, "chart_series = [{}]", series.join(", "));
+        let _ = writeln!(out, "show_mig_instances = {}", self.show_mig_instances);
+        let _ = writeln!(out, "collapse_gpus = {}", self.collapse_gpus);
+        let _ = writeln!(out, "group_gpu_processes = {}", self.group_gpu_processes);
+        let _ = writeln!(out, "show_committed = {}", self.show_committed);
+        let _ = writeln!(out, "show_gpu_percent = {}", self.show_gpu_percent);
+        let _ = writeln!(out, "location_filter = {}", toml_string(self.location_filter));
+        let _ = writeln!(out, "since_boot = {}", self.since_boot);
+        let _ = writeln!(out, "time_axis = {}", toml_string(self.time_axis));
+        out
+    }
+}
+
+/// TOML basic string: quoted, with `"`, `\` and control characters escaped.
+fn toml_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04X}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> EffectiveConfig {
+        EffectiveConfig {
+            interval_ms: 1000, unit: "kb", unit_base: "binary", theme: ThemeType::Default, view: "swap", sort: "swap",
+            sort_reversed: false, resort_interval: 1, aggregated: false, namespace: "off", exclude_swap_cache: false, max_processes: 5000,
+            top_n: 20, history_len: 60, pid: Vec::new(), swap_alert: None, gpu_alert: None, thrash_alert: None, alert_log: None, idle_gpu_util: 0, idle_gpu_color: None,
+            swappiness_hint_available: 50.0, swappiness_hint_min_mb: 1, swap_highlight_mb: None, record: None,
+            show_devices: false, device_bars: false, device_layout: "auto", numa_node_sort: "id", numa_processes_hidden: false, device_queue: false, dual_units: false,
+            maximize_processes: false, chart_hidden: false, log_y: false, chart_series: vec!["used"],
+            show_mig_instances: false, collapse_gpus: false, group_gpu_processes: false, show_committed: false, show_gpu_percent: false, location_filter: "all", since_boot: false, time_axis: "ticks",
+        }
+    }
+
+    #[test]
+    fn test_to_toml_defaults() {
+        assert_eq!(
+            config().to_toml(),
+            "# nv-swaptop effective configuration\n\
+             interval_ms = 1000\n\
+             unit = \"kb\"\n\
+             unit_base = \"binary\"\n\
+             theme = \"default\"\n\
+             view = \"swap\"\n\
+             sort = \"swap\"\n\
+             sort_reversed = false\n\
+             resort_interval = 1\n\
+             aggregated = false\n\
+             namespace = \"off\"\n\
+             exclude_swap_cache = false\n\
+             max_processes = 5000\n\
+             top_n = 20\n\
+             history_len = 60\n\
+             pid = []\n\
+             idle_gpu_util = 0\n\
+             swappiness_hint_available = 50.0\n\
+             swappiness_hint_min_mb = 1\n\
+             show_devices = false\n\
+             device_bars = false\n\
+             device_layout = \"auto\"\n\
+             numa_node_sort = \"id\"\n\
+             numa_processes_hidden = false\n\
+             device_queue = false\n\
+             dual_units = false\n\
+             maximize_processes = false\n\
+             chart_hidden = false\n\
+             log_y = false\n\
+             chart_series = [\"used\"]\n\
+             show_mig_instances = false\n\
+             collapse_gpus = false\n\
+             group_gpu_processes = false\n\
+             show_committed = false\n\
+             show_gpu_percent = false\n\
+             location_filter = \"all\"\n\
+             since_boot = false\n\
+             time_axis = \"ticks\"\n"
+        );
+    }
+
+    #[test]
+    fn test_to_toml_optional_values() {
+        let config = EffectiveConfig {
+            theme: ThemeType::Colorblind,
+            pid: vec![12, 34],
+            swap_alert: Some(80.0),
+            gpu_alert: Some(95.5),
+            thrash_alert: Some(2000.0),
+            alert_log: Some(PathBuf::from("/var/log/swap-alerts.log")),
+            idle_gpu_color: Some(Color::Rgb(255, 0, 255)),
+            swap_highlight_mb: Some(1024),
+            record: Some((PathBuf::from("/var/log/swap.csv"), 100, 5)),
+            ..config()
+        };
+        let toml = config.to_toml();
+        assert!(toml.contains("theme = \"colorblind\"\n"));
+        assert!(toml.contains("pid = [12, 34]\n"));
+        assert!(toml.contains("swap_alert = 80.0\n"));
+        assert!(toml.contains("gpu_alert = 95.5\n"));
+        assert!(toml.contains("thrash_alert = 2000.0\n"));
+        assert!(toml.contains("alert_log = \"/var/log/swap-alerts.log\"\n"));
+        assert!(toml.contains("idle_gpu_color = \"#FF00FF\"\n"));
+        assert!(toml.contains("swap_highlight_mb = 1024\n"));
+        assert!(toml.contains("record = \"/var/log/swap.csv\"\nrecord_max_mb = 100\nrecord_keep = 5\n"));
+    }
+
+    #[test]
+    fn test_toml_string_escapes() {
+        assert_eq!(toml_string("plain"), "\"plain\"");
+        assert_eq!(toml_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+    }
+}
@@ -0,0 +1,214 @@
+//! Synthetic data for `--demo`: a fixed two-socket host with two GPUs, so
+//! every view can be shown (and screenshotted) without NVIDIA hardware.
+//! Values never change between refreshes.
+
+use std::collections::HashMap;
+
+use super::types::*;
+use super::DataProvider;
+
+pub struct DemoDataProvider;
+
+/// (NUMA node, KB) pairs as numa_maps would report them
+type NodeUsage = &'static [(u32, u64)];
+
+/// (pid, name, swap KB, last CPU, memory per NUMA node)
+const DEMO_PROCESSES: &[(u32, &str, u64, i32, NodeUsage)] = &[
+    (4101, "python3", 6_291_456, 3, &[(0, 9_437_184), (2, 3_145_728)]),
+    (4102, "pt_data_worker", 2_097_152, 5, &[(0, 1_572_864), (1, 524_288)]),
+    (2310, "postgres", 1_048_576, 20, &[(1, 4_194_304)]),
+    (3877, "java", 786_432, 18, &[(0, 1_048_576), (1, 2_097_152)]),
+    (1544, "firefox", 262_144, 9, &[(0, 786_432)]),
+];
+
+/// (pid, name, GPU index, GPU memory KB, SM %, memory %). 4101 is also
+/// swapping, 4230 is GPU-only and holds memory while nearly idle.
+const DEMO_GPU_PROCESSES: &[(u32, &str, u32, u64, u32, u32)] = &[
+    (4101, "python3", 0, 41_943_040, 87, 41),
+    (4101, "python3", 1, 20_971_520, 52, 18),
+    (4230, "tritonserver", 1, 16_777_216, 3, 1),
+];
+
+const GPU_TOTAL_KB: u64 = 83_886_080;
+
+impl DataProvider for DemoDataProvider {
+    fn get_swap_info(&self, unit: &SizeUnits) -> Result<SwapUpdate, SwapDataError> {
+        let device = |name: &str, size_kb: u64, used_kb: u64, priority: isize, media: MediaClass| InfoSwap {
+            name: name.into(),
+            path: name.into(),
+            kind: "partition".into(),
+            size_kb: convert_swap(size_kb, unit.clone(), UnitBase::Binary),
+            used_kb: convert_swap(used_kb, unit.clone(), UnitBase::Binary),
+            priority,
+            media,
+            scheduler: Some("none".into()),
+            read_ahead_kb: Some(128),
+        };
+        Ok(SwapUpdate {
+            swap_devices: vec![
+                device("/dev/nvme0n1p3", 16_777_216, 8_388_608, -2, MediaClass::Nvme),
+                device("/dev/zram0", 8_388_608, 2_621_440, 100, MediaClass::Zram),
+            ],
+            total_swap: 25_165_824,
+            used_swap: 11_010_048,
+            swap_in_pages: Some(1_204_332),
+            swap_out_pages: Some(3_871_905),
+            page_size_kb: Some(4),
+            memory_full_avg10: Some(1.25),
+            swap_cached: Some(524_288),
+            mem_total_kb: Some(262_144_000),
+            mem_available_kb: Some(61_865_984),
+            swappiness: Some(60),
+            torn_read: false,
+        })
+    }
+
+    fn get_processes_swap(&self, unit: &SizeUnits) -> Result<Vec<ProcessSwapInfo>, SwapDataError> {
+        Ok(DEMO_PROCESSES
+            .iter()
+            .map(|&(pid, name, swap_kb, cpu, _)| ProcessSwapInfo {
+                pid,
+                name: name.into(),
+                swap_size: convert_swap(swap_kb, unit.clone(), UnitBase::Binary),
+                last_cpu: Some(cpu),
+                rss_kb: None,
+                mnt_ns: None,
+                count: 1,
+            })
+            .collect())
+    }
+
+    fn get_numa_topology(&self) -> Result<Vec<NumaNode>, SwapDataError> {
+        Ok(vec![
+            NumaNode {
+                id: 0,
+                memory_total_kb: 131_072_000,
+                memory_free_kb: 30_932_992,
+                cpus: (0..16).collect(),
+                node_type: NumaNodeType::Cpu,
+                distances: vec![10, 21, 80],
+            },
+            NumaNode {
+                id: 1,
+                memory_total_kb: 131_072_000,
+                memory_free_kb: 30_932_992,
+                cpus: (16..32).collect(),
+                node_type: NumaNodeType::Cpu,
+                distances: vec![21, 10, 80],
+            },
+            NumaNode {
+                id: 2,
+                memory_total_kb: GPU_TOTAL_KB,
+                memory_free_kb: 41_943_040,
+                cpus: vec![],
+                node_type: NumaNodeType::GpuHbm { gpu_index: 0 },
+                distances: vec![80, 80, 10],
+            },
+        ])
+    }
+
+    fn get_process_numa_maps(&self, pid: u32, name: &str) -> Result<ProcessNumaInfo, SwapDataError> {
+        let (_, _, _, _, nodes) = DEMO_PROCESSES
+            .iter()
+            .find(|p| p.0 == pid)
+            .ok_or_else(|| SwapDataError::Io(std::io::ErrorKind::NotFound.into()))?;
+        let kb_per_node: HashMap<u32, u64> = nodes.iter().copied().collect();
+        Ok(ProcessNumaInfo {
+            pid,
+            name: name.to_string(),
+            total_kb: kb_per_node.values().sum(),
+            kb_per_node,
+            cpu_node: None,
+        })
+    }
+
+    fn is_numa_available(&self) -> bool {
+        true
+    }
+
+    fn get_gpu_devices(&self) -> Result<Vec<GpuDevice>, SwapDataError> {
+        let device = |index: u32, used_kb: u64, numa_node_id: u32, temperature: u32, utilization: u32| GpuDevice {
+            index,
+            name: "NVIDIA H100 80GB HBM3".into(),
+            memory_total_kb: GPU_TOTAL_KB,
+            memory_used_kb: used_kb,
+            memory_free_kb: GPU_TOTAL_KB - used_kb,
+            numa_node_id: Some(numa_node_id),
+            temperature: Some(temperature),
+            pci_bus_id: format!("00000000:{:02X}:00.0", 0x18 + index * 0x20),
+            memory_reserved_kb: Some(524_288),
+            mig_instances: vec![],
+            utilization_percent: Some(utilization),
+            memory_utilization_percent: Some(utilization / 2),
+        };
+        Ok(vec![device(0, 41_943_040, 2, 64, 87), device(1, 37_748_736, 1, 41, 3)])
+    }
+
+    fn get_gpu_processes(&self) -> Result<Vec<GpuProcessInfo>, SwapDataError> {
+        Ok(DEMO_GPU_PROCESSES
+            .iter()
+            .map(|&(pid, name, gpu_index, kb, sm, mem)| GpuProcessInfo {
+                pid,
+                name: name.into(),
+                gpu_index,
+                gpu_memory_used_kb: kb,
+                sm_util: Some(sm),
+                mem_util: Some(mem),
+            })
+            .collect())
+    }
+
+    fn gpu_status(&self) -> GpuStatus {
+        GpuStatus::Available
+    }
+
+    fn is_process_alive(&self, pid: u32) -> bool {
+        DEMO_PROCESSES.iter().any(|p| p.0 == pid) || DEMO_GPU_PROCESSES.iter().any(|p| p.0 == pid)
+    }
+
+    fn gpu_process_cmdlines(&self, processes: &[GpuProcessInfo]) -> HashMap<u32, String> {
+        let cmdline = |pid: u32| match pid {
+            4101 => Some("python3 train.py --model llama-8b --batch-size 32"),
+            4230 => Some("tritonserver --model-repository=/models"),
+            _ => None,
+        };
+        processes
+            .iter()
+            .filter_map(|p| cmdline(p.pid).map(|c| (p.pid, c.to_string())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::merge_process_data;
+
+    #[test]
+    fn test_demo_provider_has_gpus_and_cpu_gpu_process() {
+        let provider = DemoDataProvider;
+        let devices = provider.get_gpu_devices().unwrap();
+        assert!(!devices.is_empty());
+        let nodes = provider.get_numa_topology().unwrap();
+        assert_eq!(nodes.len(), 3);
+        assert!(nodes.iter().any(|n| matches!(n.node_type, NumaNodeType::GpuHbm { .. })));
+
+        let swap = provider.get_processes_swap(&SizeUnits::KB).unwrap();
+        let gpu = provider.get_gpu_processes().unwrap();
+        let merged = merge_process_data(&swap, &gpu, &[], &nodes, &devices);
+        assert!(merged.iter().any(|p| p.location == ProcessLocation::CpuAndGpu));
+    }
+
+    #[test]
+    fn test_demo_provider_is_deterministic() {
+        let provider = DemoDataProvider;
+        let first = provider.get_swap_info(&SizeUnits::MB).unwrap();
+        let second = provider.get_swap_info(&SizeUnits::MB).unwrap();
+        assert_eq!((first.total_swap, first.used_swap), (second.total_swap, second.used_swap));
+        let procs = provider.get_processes_swap(&SizeUnits::MB).unwrap();
+        assert_eq!(procs[0].swap_size, 6144.0);
+        // Swap devices add up to the reported totals
+        let used: f64 = first.swap_devices.iter().map(|d| d.used_kb).sum();
+        assert_eq!(used, convert_swap(first.used_swap, SizeUnits::MB, UnitBase::Binary));
+    }
+}
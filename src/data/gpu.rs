@@ -1,47 +1,102 @@
 use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
 
-use super::types::{GpuDevice, GpuProcessInfo};
+use super::types::{GpuDevice, GpuProcessInfo, GpuStatus, MigInstance};
+use super::verbose;
+
+const APPS_QUERY: &str = "nvidia-smi --query-compute-apps";
+const GPU_QUERY: &str = "nvidia-smi --query-gpu";
+const PMON: &str = "nvidia-smi pmon";
 
 /// Convert MiB (nvidia-smi unit) to KB (internal unit).
 fn mib_to_kb(mib: u64) -> u64 {
     mib * 1024
 }
 
+/// Data rows start with a numeric GPU index. Anything else (column header,
+/// comment, blank line) is skipped without being reported; matching header
+/// prefixes instead would also drop real rows that happen to share them.
+fn is_data_row(line: &str) -> bool {
+    line.split(',').next().is_some_and(|first| first.trim().parse::<u32>().is_ok())
+}
+
+/// Split a CSV row of `fields` columns on ", ", where the column at
+/// `name_at` (a process or GPU name) may itself contain ", ". Columns before
+/// it are taken from the front and the rest from the back, and whatever is
+/// left in between is the name. None when the row has too few columns.
+fn split_around_name(line: &str, fields: usize, name_at: usize) -> Option<Vec<&str>> {
+    let mut parts: Vec<&str> = line.splitn(name_at + 1, ", ").collect();
+    let rest = parts.pop()?;
+    if parts.len() != name_at {
+        return None;
+    }
+    let mut tail: Vec<&str> = rest.rsplitn(fields - name_at, ", ").collect();
+    if tail.len() != fields - name_at {
+        return None;
+    }
+    tail.reverse();
+    parts.extend(tail);
+    Some(parts)
+}
+
+/// Parse `nvidia-smi --query-gpu=index,gpu_uuid,pci.bus_id` output into a
+/// map from each GPU's UUID and PCI bus id to its index.
+pub fn parse_gpu_ids_csv(csv: &str) -> HashMap<String, u32> {
+    let mut ids = HashMap::new();
+    for line in csv.lines() {
+        let mut fields = line.trim().split(", ");
+        let Some(Ok(index)) = fields.next().map(|f| f.trim().parse::<u32>()) else {
+            continue;
+        };
+        for id in fields.map(str::trim).filter(|id| !id.is_empty()) {
+            ids.insert(id.to_string(), index);
+        }
+    }
+    ids
+}
+
 /// Parse nvidia-smi CSV output for GPU processes.
-/// Expected CSV format: gpu_index, pid, process_name, used_gpu_memory [MiB]
-pub fn parse_gpu_processes_csv(csv: &str) -> Vec<GpuProcessInfo> {
+/// Expected CSV format: gpu, pid, process_name, used_gpu_memory [MiB], where
+/// gpu is an index or a UUID/bus id found in `gpu_ids` (see
+/// `parse_gpu_ids_csv`).
+pub fn parse_gpu_processes_csv(csv: &str, gpu_ids: &HashMap<String, u32>) -> Vec<GpuProcessInfo> {
     let mut results = Vec::new();
+    let gpu_index = |field: &str| field.parse::<u32>().ok().or_else(|| gpu_ids.get(field).copied());
+    // lines() drops the "\r" of a CRLF ending and trim() any stray one left
+    // over, so Windows-captured output parses the same
     for line in csv.lines() {
         let line = line.trim();
-        // Skip headers and empty lines
-        if line.is_empty()
-            || line.starts_with("gpu")
-            || line.starts_with('#')
-            || line.contains("[Not Supported]")
-            || line.starts_with("index")
-        {
+        // As with is_data_row, rows not naming a known GPU (column header,
+        // comment, blank line) are skipped without being reported
+        let Some(gpu_index) = gpu_index(line.split(',').next().unwrap_or("").trim()) else {
             continue;
-        }
-
-        let parts: Vec<&str> = line.split(", ").collect();
-        if parts.len() < 4 {
+        };
+        if line.contains("[Not Supported]") {
             continue;
         }
 
-        let gpu_index = match parts[0].trim().parse::<u32>() {
-            Ok(v) => v,
-            Err(_) => continue,
+        let Some(parts) = split_around_name(line, 4, 2) else {
+            verbose::skipped(APPS_QUERY, line, &format!("expected 4 fields, got {}", line.split(", ").count()));
+            continue;
         };
         let pid = match parts[1].trim().parse::<u32>() {
             Ok(v) => v,
-            Err(_) => continue,
+            Err(_) => {
+                verbose::skipped(APPS_QUERY, line, &format!("bad pid `{}`", parts[1].trim()));
+                continue;
+            }
         };
         let name = parts[2].trim().to_string();
         let mem_str = parts[3].trim().replace(" MiB", "");
         let mem_mib = match mem_str.parse::<u64>() {
             Ok(v) => v,
-            Err(_) => continue,
+            Err(_) => {
+                verbose::skipped(APPS_QUERY, line, &format!("bad memory value `{}`", parts[3].trim()));
+                continue;
+            }
         };
 
         results.push(GpuProcessInfo {
@@ -49,35 +104,121 @@ pub fn parse_gpu_processes_csv(csv: &str) -> Vec<GpuProcessInfo> {
             name,
             gpu_index,
             gpu_memory_used_kb: mib_to_kb(mem_mib),
+            sm_util: None,
+            mem_util: None,
         });
     }
     results
 }
 
+/// (gpu index, pid) -> (SM %, memory %)
+pub type PmonUtilization = HashMap<(u32, u32), (Option<u32>, Option<u32>)>;
+
+/// Parse `nvidia-smi pmon -c 1 -s u` output into (gpu index, pid) ->
+/// (SM %, memory %). Columns are located by the "# gpu pid ..." header, as
+/// newer drivers add columns (jpg, ofa); without one the classic order
+/// "gpu pid type sm mem" is assumed. "-" (no sample, or an idle GPU's
+/// placeholder row) is None.
+pub fn parse_pmon(output: &str) -> PmonUtilization {
+    let mut columns = (0, 1, 3, 4);
+    let mut results = HashMap::new();
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('#') {
+            let names: Vec<&str> = header.split_whitespace().collect();
+            let find = |name: &str| names.iter().position(|n| *n == name);
+            if let (Some(gpu), Some(pid), Some(sm), Some(mem)) = (find("gpu"), find("pid"), find("sm"), find("mem")) {
+                columns = (gpu, pid, sm, mem);
+            }
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let field = |i: usize| fields.get(i).and_then(|f| f.parse::<u32>().ok());
+        let (gpu, pid, sm, mem) = columns;
+        match (field(gpu), field(pid)) {
+            (Some(gpu), Some(pid)) => {
+                results.insert((gpu, pid), (field(sm), field(mem)));
+            }
+            // "-" pid: a GPU with no processes
+            (Some(_), None) if fields.get(pid) == Some(&"-") => {}
+            _ => verbose::skipped(PMON, line, "expected gpu index and pid"),
+        }
+    }
+    results
+}
+
+/// Fill each process's SM/memory utilisation from `parse_pmon` output.
+/// Processes pmon did not sample keep None.
+pub fn attach_pmon_utilization(
+    processes: &mut [GpuProcessInfo],
+    utilization: &PmonUtilization,
+) {
+    for process in processes {
+        if let Some(&(sm, mem)) = utilization.get(&(process.gpu_index, process.pid)) {
+            process.sm_util = sm;
+            process.mem_util = mem;
+        }
+    }
+}
+
+/// Columns of the basic `--query-gpu` device query, which every driver knows.
+pub const GPU_FIELDS: &str = "index,name,memory.total,memory.used,memory.free,temperature.gpu,pci.bus_id";
+
+/// Newer columns appended to `GPU_FIELDS` so that one nvidia-smi run reads
+/// everything. A driver that lacks any of them rejects the whole query.
+pub const GPU_EXTRA_FIELDS: &str = "memory.reserved,utilization.gpu,utilization.memory,mig.mode.current";
+
 /// Parse nvidia-smi CSV output for GPU devices.
 /// Expected CSV: index, name, memory.total [MiB], memory.used [MiB], memory.free [MiB],
 ///               temperature.gpu, pci.bus_id
 pub fn parse_gpu_devices_csv(csv: &str) -> Vec<GpuDevice> {
-    let mut results = Vec::new();
-    for line in csv.lines() {
-        let line = line.trim();
-        if line.is_empty()
-            || line.starts_with("index")
-            || line.starts_with('#')
-            || line.starts_with("name")
-        {
-            continue;
+    parse_gpu_rows(csv, 7).map(|(device, _)| device).collect()
+}
+
+/// Parse the combined `GPU_FIELDS,GPU_EXTRA_FIELDS` query into the devices,
+/// with reserved memory and utilisation filled ("[N/A]" leaving them None),
+/// and the indices of GPUs with MIG enabled.
+pub fn parse_gpu_devices_extended_csv(csv: &str) -> (Vec<GpuDevice>, Vec<u32>) {
+    let percent = |field: &str| field.trim().trim_end_matches('%').trim().parse().ok();
+    let mut devices = Vec::new();
+    let mut mig_enabled = Vec::new();
+    for (mut device, extra) in parse_gpu_rows(csv, 11) {
+        device.memory_reserved_kb = extra[0].trim().trim_end_matches("MiB").trim().parse().ok().map(mib_to_kb);
+        device.utilization_percent = percent(extra[1]);
+        device.memory_utilization_percent = percent(extra[2]);
+        if extra[3].trim() == "Enabled" {
+            mig_enabled.push(device.index);
         }
+        devices.push(device);
+    }
+    (devices, mig_enabled)
+}
 
-        let parts: Vec<&str> = line.split(", ").collect();
-        if parts.len() < 7 {
-            continue;
+/// Devices from rows of `fields` columns, the first seven being
+/// `GPU_FIELDS`, each paired with the columns after those.
+fn parse_gpu_rows(csv: &str, fields: usize) -> impl Iterator<Item = (GpuDevice, Vec<&str>)> {
+    csv.lines().filter_map(move |line| {
+        let line = line.trim();
+        if !is_data_row(line) {
+            return None;
         }
 
+        let Some(mut parts) = split_around_name(line, fields, 1) else {
+            verbose::skipped(GPU_QUERY, line, &format!("expected {fields} fields, got {}", line.split(", ").count()));
+            return None;
+        };
+
         let index = match parts[0].trim().parse::<u32>() {
             Ok(v) => v,
-            Err(_) => continue,
+            Err(_) => {
+                verbose::skipped(GPU_QUERY, line, &format!("bad gpu index `{}`", parts[0].trim()));
+                return None;
+            }
         };
+        let extra = parts.split_off(7);
         let name = parts[1].trim().to_string();
 
         let mem_total = parse_mib_field(parts[2]);
@@ -87,7 +228,7 @@ pub fn parse_gpu_devices_csv(csv: &str) -> Vec<GpuDevice> {
         let temperature = parts[5].trim().parse::<u32>().ok();
         let pci_bus_id = parts[6].trim().to_string();
 
-        results.push(GpuDevice {
+        let device = GpuDevice {
             index,
             name,
             memory_total_kb: mib_to_kb(mem_total),
@@ -96,18 +237,193 @@ pub fn parse_gpu_devices_csv(csv: &str) -> Vec<GpuDevice> {
             numa_node_id: None, // filled later by get_gpu_numa_mapping
             temperature,
             pci_bus_id,
-        });
-    }
-    results
+            memory_reserved_kb: None,
+            mig_instances: Vec::new(),
+            utilization_percent: None,
+            memory_utilization_percent: None,
+        };
+        Some((device, extra))
+    })
+}
+
+/// Parse `nvidia-smi --query-gpu=index,memory.reserved` output into
+/// index -> reserved KB. "[N/A]" and other unparsable values are skipped.
+pub fn parse_gpu_reserved_csv(csv: &str) -> HashMap<u32, u64> {
+    csv.lines()
+        .filter_map(|line| {
+            let (index, reserved) = line.split_once(", ")?;
+            let index = index.trim().parse().ok()?;
+            let mib = reserved.trim().trim_end_matches("MiB").trim().parse().ok()?;
+            Some((index, mib_to_kb(mib)))
+        })
+        .collect()
+}
+
+/// Parse `nvidia-smi --query-gpu=index,utilization.gpu,utilization.memory`
+/// output into index -> (compute %, memory bandwidth %). "[N/A]" and other
+/// unparsable values are None; GPUs reporting neither are skipped.
+pub fn parse_gpu_utilization_csv(csv: &str) -> HashMap<u32, (Option<u32>, Option<u32>)> {
+    let percent = |field: Option<&str>| field?.trim().trim_end_matches('%').trim().parse().ok();
+    csv.lines()
+        .filter_map(|line| {
+            let mut fields = line.split(", ");
+            let index = fields.next()?.trim().parse().ok()?;
+            let (gpu, memory) = (percent(fields.next()), percent(fields.next()));
+            (gpu.is_some() || memory.is_some()).then_some((index, (gpu, memory)))
+        })
+        .collect()
 }
 
 fn parse_mib_field(s: &str) -> u64 {
     s.trim().replace(" MiB", "").parse().unwrap_or(0)
 }
 
+/// Parse `nvidia-smi --query-gpu=index,mig.mode.current` output into the
+/// indices of GPUs with MIG enabled.
+pub fn parse_mig_enabled_csv(csv: &str) -> Vec<u32> {
+    csv.lines()
+        .filter_map(|line| {
+            let (index, mode) = line.split_once(", ")?;
+            (mode.trim() == "Enabled").then(|| index.trim().parse().ok())?
+        })
+        .collect()
+}
+
+/// Parse the MIG devices of `nvidia-smi -q -x` output into (parent GPU bus
+/// id, instance) pairs. Each looks like:
+///   <gpu id="00000000:07:00.0"> ... <mig_device> <index>0</index>
+///   <gpu_instance_id>1</gpu_instance_id> <compute_instance_id>0</compute_instance_id>
+///   ... <fb_memory_usage> <total>20096 MiB</total> <used>13 MiB</used> ...
+pub fn parse_mig_devices_xml(xml: &str) -> Vec<(String, MigInstance)> {
+    let mut results = Vec::new();
+    for (attrs, gpu) in xml_elements(xml, "gpu") {
+        let Some((bus_id, _)) = attrs.split_once("id=\"").and_then(|(_, rest)| rest.split_once('"')) else {
+            continue;
+        };
+        for (_, mig) in xml_elements(gpu, "mig_device") {
+            let id = |tag| xml_text(mig, tag)?.parse::<u32>().ok();
+            let (Some(device), Some(gpu_instance), Some(compute_instance)) =
+                (id("index"), id("gpu_instance_id"), id("compute_instance_id"))
+            else {
+                continue;
+            };
+            // bar1_memory_usage has total/used too; only the framebuffer counts
+            let memory = xml_text(mig, "fb_memory_usage").unwrap_or("");
+            let mib = |tag| xml_text(memory, tag).map_or(0, parse_mib_field);
+            results.push((
+                bus_id.to_string(),
+                MigInstance {
+                    device,
+                    gpu_instance,
+                    compute_instance,
+                    memory_total_kb: mib_to_kb(mib("total")),
+                    memory_used_kb: mib_to_kb(mib("used")),
+                },
+            ));
+        }
+    }
+    results
+}
+
+/// (attributes, body) of each `<tag ...>body</tag>` element in `xml`.
+/// nvidia-smi never nests an element inside one of the same name, so the
+/// first closing tag ends it.
+fn xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<(&'a str, &'a str)> {
+    let (open, close) = (format!("<{tag}"), format!("</{tag}>"));
+    let mut elements = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        let Some(end) = after.find('>') else {
+            break;
+        };
+        let attrs = &after[..end];
+        // `<gpu` also starts `<gpu_instance_id>`
+        if !attrs.is_empty() && !attrs.starts_with(char::is_whitespace) {
+            rest = after;
+            continue;
+        }
+        let body = &after[end + 1..];
+        let Some(body_end) = body.find(&close) else {
+            break;
+        };
+        elements.push((attrs.trim(), &body[..body_end]));
+        rest = &body[body_end + close.len()..];
+    }
+    elements
+}
+
+/// Trimmed body of the first `<tag>` element in `xml`.
+fn xml_text<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    xml_elements(xml, tag).first().map(|(_, body)| body.trim())
+}
+
+/// Attach parsed MIG instances to their parent devices, matched by bus id.
+pub fn attach_mig_instances(devices: &mut [GpuDevice], instances: Vec<(String, MigInstance)>) {
+    for (parent, instance) in instances {
+        if let Some(dev) = devices.iter_mut().find(|d| d.pci_bus_id.eq_ignore_ascii_case(&parent)) {
+            dev.mig_instances.push(instance);
+        }
+    }
+}
+
+/// GPU memory counted as used with no processes: driver and context
+/// overhead, which older drivers do not split out as reserved.
+const IDLE_GPU_USED_KB: u64 = 512 * 1024;
+
+/// Whether an empty or failed process query is hiding real work: some GPU
+/// has memory in use or compute activity. On locked-down nodes nvidia-smi
+/// lists devices to any user but processes only to privileged ones (or
+/// within the host PID namespace).
+pub fn processes_hidden(devices: &[GpuDevice]) -> bool {
+    devices
+        .iter()
+        .any(|d| d.memory_used_kb > IDLE_GPU_USED_KB || d.utilization_percent.is_some_and(|u| u > 0))
+}
+
+/// nvidia-smi binary to run, resolved once per process.
+static NVIDIA_SMI_PATH: OnceLock<OsString> = OnceLock::new();
+
+/// Use `path` as the nvidia-smi binary (`--nvidia-smi`). Must be called before
+/// the first nvidia-smi invocation; later calls are ignored.
+pub fn set_nvidia_smi_path(path: impl Into<OsString>) {
+    let _ = NVIDIA_SMI_PATH.set(path.into());
+}
+
+/// `--gpu-pmon`: sample per-process utilisation with `nvidia-smi pmon`,
+/// which takes about a second per call, so it is off by default.
+static PMON_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_pmon_enabled(enabled: bool) {
+    PMON_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn pmon_enabled() -> bool {
+    PMON_ENABLED.load(Ordering::Relaxed)
+}
+
+/// The configured binary: `--nvidia-smi`, else `$NVIDIA_SMI_PATH`, else
+/// "nvidia-smi" looked up on PATH.
+fn nvidia_smi_binary() -> &'static OsStr {
+    NVIDIA_SMI_PATH.get_or_init(|| binary_from_env(std::env::var_os("NVIDIA_SMI_PATH")))
+}
+
+fn binary_from_env(env: Option<OsString>) -> OsString {
+    env.filter(|v| !v.is_empty()).unwrap_or_else(|| "nvidia-smi".into())
+}
+
+/// Build an nvidia-smi invocation with a fixed C locale.
+/// Under a non-C locale nvidia-smi may print decimals with a comma ("45,5"),
+/// which breaks the numeric parsers and collides with the CSV separator.
+fn nvidia_smi_command(args: &[&str]) -> Command {
+    let mut cmd = Command::new(nvidia_smi_binary());
+    cmd.args(args).env("LC_ALL", "C");
+    cmd
+}
+
 /// Run nvidia-smi with given arguments and return stdout.
 pub fn run_nvidia_smi(args: &[&str]) -> Result<String, std::io::Error> {
-    let output = Command::new("nvidia-smi").args(args).output()?;
+    let output = nvidia_smi_command(args).output()?;
     if !output.status.success() {
         return Err(std::io::Error::other(format!(
             "nvidia-smi failed: {}",
@@ -119,11 +435,33 @@ pub fn run_nvidia_smi(args: &[&str]) -> Result<String, std::io::Error> {
 
 /// Check if nvidia-smi is available on the system.
 pub fn check_nvidia_smi_available() -> bool {
-    Command::new("nvidia-smi")
-        .arg("--query-gpu=index")
-        .arg("--format=csv,noheader")
-        .output()
-        .is_ok_and(|o| o.status.success())
+    nvidia_smi_status() == GpuStatus::Available
+}
+
+/// Probe nvidia-smi, distinguishing "not installed" from "installed but failing".
+pub fn nvidia_smi_status() -> GpuStatus {
+    status_from_output(nvidia_smi_command(&["--query-gpu=index", "--format=csv,noheader"]).output())
+}
+
+fn status_from_output(output: std::io::Result<std::process::Output>) -> GpuStatus {
+    match output {
+        Ok(o) if o.status.success() => GpuStatus::Available,
+        Ok(o) => {
+            // nvidia-smi prints most failures (e.g. "couldn't communicate
+            // with the NVIDIA driver") on stdout rather than stderr
+            let stderr = String::from_utf8_lossy(&o.stderr);
+            let stdout = String::from_utf8_lossy(&o.stdout);
+            let msg = if stderr.trim().is_empty() { stdout } else { stderr };
+            let msg = msg.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+            if msg.is_empty() {
+                GpuStatus::Failed(format!("exited with {}", o.status))
+            } else {
+                GpuStatus::Failed(msg.to_string())
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => GpuStatus::NotFound,
+        Err(e) => GpuStatus::Failed(e.to_string()),
+    }
 }
 
 /// Map GPU PCI bus IDs to NUMA node IDs via sysfs.
@@ -146,14 +484,91 @@ pub fn get_gpu_numa_mapping(devices: &[GpuDevice]) -> HashMap<u32, u32> {
     mapping
 }
 
+/// Command lines of GPU processes per (pid, start time), so /proc is read
+/// once per process rather than on every 1s refresh; the start time keeps a
+/// reused PID from inheriting another process's command line.
+static CMDLINE_CACHE: Mutex<Option<CmdlineCache>> = Mutex::new(None);
+
+type CmdlineCache = HashMap<(u32, u64), Option<String>>;
+
+/// Full command lines from /proc/[pid]/cmdline for the GPU processes that
+/// are local. PIDs that nvidia-smi reports from another PID namespace (MPS
+/// clients, other containers) are left out, so the caller keeps the
+/// nvidia-smi name for them.
+pub fn process_cmdlines(processes: &[GpuProcessInfo]) -> HashMap<u32, String> {
+    let mut cache = CMDLINE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(HashMap::new);
+    let mut seen = std::collections::HashSet::new();
+    let mut out = HashMap::new();
+
+    for proc in processes {
+        let Ok(process) = procfs::process::Process::new(proc.pid as i32) else {
+            continue;
+        };
+        let Ok(stat) = process.stat() else {
+            continue;
+        };
+        let key = (proc.pid, stat.starttime);
+        seen.insert(key);
+        let cmdline = cache.entry(key).or_insert_with(|| {
+            let args = process.cmdline().ok()?;
+            local_cmdline(&stat.comm, &args, &proc.name)
+        });
+        if let Some(cmdline) = cmdline {
+            out.insert(proc.pid, cmdline.clone());
+        }
+    }
+    cache.retain(|key, _| seen.contains(key));
+    out
+}
+
+/// Join `args` into a command line if they belong to the process nvidia-smi
+/// named `smi_name`: argv[0] or the comm (truncated to 15 bytes by the
+/// kernel) must match its basename. A mismatch means the PID refers to a
+/// different process in our namespace. Kernel threads and zombies have no
+/// arguments and also yield None.
+fn local_cmdline(comm: &str, args: &[String], smi_name: &str) -> Option<String> {
+    let argv0 = args.first()?;
+    let smi_base = basename(smi_name);
+    let comm_matches = comm == smi_base || (comm.len() == 15 && smi_base.starts_with(comm));
+    (basename(argv0) == smi_base || comm_matches).then(|| args.join(" "))
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_local_cmdline_matches_smi_name() {
+        let args: Vec<String> = ["/usr/bin/python3", "train.py", "--epochs", "10"].map(String::from).into();
+        assert_eq!(
+            local_cmdline("python3", &args, "python3").as_deref(),
+            Some("/usr/bin/python3 train.py --epochs 10")
+        );
+        // nvidia-smi may report the full path instead of the basename
+        assert!(local_cmdline("python3", &args, "/usr/bin/python3").is_some());
+        // PID belongs to an unrelated local process (other PID namespace)
+        assert_eq!(local_cmdline("bash", &["bash".to_string()], "python3"), None);
+        // Kernel threads and zombies have no arguments
+        assert_eq!(local_cmdline("python3", &[], "python3"), None);
+    }
+
+    #[test]
+    fn test_local_cmdline_truncated_comm() {
+        // argv[0] rewritten by the process, comm truncated to 15 bytes
+        let args = vec!["worker".to_string(), "--rank=0".to_string()];
+        assert!(local_cmdline("tritonserver_wo", &args, "/opt/tritonserver_worker").is_some());
+        assert_eq!(local_cmdline("triton", &args, "/opt/tritonserver_worker"), None);
+    }
+
     #[test]
     fn test_parse_gpu_processes() {
         let csv = "0, 1234, python3, 2048 MiB\n";
-        let result = parse_gpu_processes_csv(csv);
+        let result = parse_gpu_processes_csv(csv, &HashMap::new());
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].pid, 1234);
         assert_eq!(result[0].name, "python3");
@@ -161,17 +576,123 @@ mod tests {
         assert_eq!(result[0].gpu_memory_used_kb, 2048 * 1024);
     }
 
+    #[test]
+    fn test_parse_gpu_processes_by_uuid_and_bus_id() {
+        let ids = parse_gpu_ids_csv(
+            "0, GPU-1b2c3d4e-0000-1111-2222-333344445555, 00000000:18:00.0\n\
+             1, GPU-9f8e7d6c-aaaa-bbbb-cccc-ddddeeeeffff, 00000000:38:00.0\n",
+        );
+        let csv = "GPU-9f8e7d6c-aaaa-bbbb-cccc-ddddeeeeffff, 4230, tritonserver, 16384 MiB\n\
+                   GPU-1b2c3d4e-0000-1111-2222-333344445555, 4101, python3, 40960 MiB\n\
+                   GPU-00000000-dead-beef-0000-000000000000, 4999, gone, 1 MiB\n";
+        let result = parse_gpu_processes_csv(csv, &ids);
+        assert_eq!(result.len(), 2);
+        assert_eq!((result[0].pid, result[0].gpu_index), (4230, 1));
+        assert_eq!((result[1].pid, result[1].gpu_index), (4101, 0));
+
+        // The fallback query names GPUs by bus id
+        let result = parse_gpu_processes_csv("00000000:38:00.0, 4230, tritonserver, 16384 MiB\n", &ids);
+        assert_eq!(result[0].gpu_index, 1);
+    }
+
+    #[test]
+    fn test_parse_pmon_classic_columns() {
+        let output = "# gpu        pid  type    sm   mem   enc   dec   command\n\
+                      # Idx          #   C/G     %     %     %     %   name\n\
+                          0       4101     C    87    41     -     -   python3\n\
+                          0       4102     C     -     -     -     -   pt_data_worker\n\
+                          1          -     -     -     -     -     -   -\n";
+        let util = parse_pmon(output);
+        assert_eq!(util.len(), 2);
+        assert_eq!(util[&(0, 4101)], (Some(87), Some(41)));
+        assert_eq!(util[&(0, 4102)], (None, None));
+    }
+
+    #[test]
+    fn test_parse_pmon_newer_driver_columns() {
+        // Newer drivers add jpg/ofa and may move columns; the header is authoritative
+        let output = "# gpu         pid   type     sm    mem    enc    dec    jpg    ofa    command\n\
+                      # Idx           #    C/G      %      %      %      %      %      %    name\n\
+                          1        4230     C      3      1      -      -      -      -    tritonserver\n";
+        assert_eq!(parse_pmon(output)[&(1, 4230)], (Some(3), Some(1)));
+
+        // No header: classic order
+        assert_eq!(parse_pmon("0 17 C 50 20 - - x\n")[&(0, 17)], (Some(50), Some(20)));
+    }
+
+    #[test]
+    fn test_attach_pmon_utilization() {
+        let mut procs = parse_gpu_processes_csv("0, 4101, python3, 2048 MiB\n1, 4101, python3, 1024 MiB\n", &HashMap::new());
+        let util = parse_pmon("0 4101 C 87 41 - - python3\n");
+        attach_pmon_utilization(&mut procs, &util);
+        assert_eq!((procs[0].sm_util, procs[0].mem_util), (Some(87), Some(41)));
+        assert_eq!((procs[1].sm_util, procs[1].mem_util), (None, None));
+    }
+
+    #[test]
+    fn test_parse_gpu_csv_crlf_line_endings() {
+        let csv = "0, NVIDIA H100, 81920 MiB, 40960 MiB, 40960 MiB, 45, 00000000:01:00.0\r\n\
+                   1, NVIDIA H100, 81920 MiB, 0 MiB, 81920 MiB, 40, 00000000:02:00.0\r\r\n";
+        let devices = parse_gpu_devices_csv(csv);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].pci_bus_id, "00000000:01:00.0");
+        assert_eq!(devices[1].pci_bus_id, "00000000:02:00.0");
+
+        let csv = "0, 1234, python3, 2048 MiB\r\n1, 5678, trainer, 512 MiB\r\n";
+        let procs = parse_gpu_processes_csv(csv, &HashMap::new());
+        assert_eq!(procs.len(), 2);
+        assert_eq!(procs[0].gpu_memory_used_kb, 2048 * 1024);
+        assert_eq!(procs[1].gpu_memory_used_kb, 512 * 1024);
+
+        let utilization = parse_gpu_utilization_csv("0, 87 %, 40 %\r\n");
+        assert_eq!(utilization.get(&0), Some(&(Some(87), Some(40))));
+        assert_eq!(parse_gpu_reserved_csv("0, 512 MiB\r\n").get(&0), Some(&(512 * 1024)));
+        assert_eq!(parse_mig_enabled_csv("0, Enabled\r\n"), vec![0]);
+    }
+
+    #[test]
+    fn test_parse_gpu_processes_name_with_comma() {
+        let csv = "0, 1234, python, worker, 2048 MiB\n\
+                   1, 5678, trainer  , 512 MiB\n";
+        let result = parse_gpu_processes_csv(csv, &HashMap::new());
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].pid, 1234);
+        assert_eq!(result[0].name, "python, worker");
+        assert_eq!(result[0].gpu_memory_used_kb, 2048 * 1024);
+        assert_eq!(result[1].name, "trainer");
+        assert_eq!(result[1].gpu_memory_used_kb, 512 * 1024);
+    }
+
+    #[test]
+    fn test_parse_gpu_devices_name_with_comma() {
+        let csv = "0, Acme GPU, Rev 2, 81920 MiB, 40960 MiB, 40960 MiB, 45, 00000000:01:00.0\n";
+        let result = parse_gpu_devices_csv(csv);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "Acme GPU, Rev 2");
+        assert_eq!(result[0].memory_total_kb, 81920 * 1024);
+        assert_eq!(result[0].memory_used_kb, 40960 * 1024);
+        assert_eq!(result[0].pci_bus_id, "00000000:01:00.0");
+    }
+
+    #[test]
+    fn test_split_around_name() {
+        assert_eq!(split_around_name("0, 1, a, b, 2", 4, 2), Some(vec!["0", "1", "a, b", "2"]));
+        assert_eq!(split_around_name("0, 1, a, 2", 4, 2), Some(vec!["0", "1", "a", "2"]));
+        assert_eq!(split_around_name("0, 1, 2", 4, 2), None);
+        assert_eq!(split_around_name("0", 4, 2), None);
+    }
+
     #[test]
     fn test_parse_gpu_processes_empty() {
         let csv = "";
-        let result = parse_gpu_processes_csv(csv);
+        let result = parse_gpu_processes_csv(csv, &HashMap::new());
         assert!(result.is_empty());
     }
 
     #[test]
     fn test_parse_gpu_processes_malformed() {
         let csv = "this is not valid csv\n0, not_a_pid, proc, 100 MiB\n";
-        let result = parse_gpu_processes_csv(csv);
+        let result = parse_gpu_processes_csv(csv, &HashMap::new());
         assert!(result.is_empty());
     }
 
@@ -188,6 +709,50 @@ mod tests {
         assert_eq!(result[0].pci_bus_id, "00000000:01:00.0");
     }
 
+    #[test]
+    fn test_parse_gpu_devices_extended_csv() {
+        let csv = "0, Acme GPU, Rev 2, 81920 MiB, 40960 MiB, 40448 MiB, 45, 00000000:01:00.0, 512 MiB, 87 %, 40 %, [N/A]\n\
+                   1, NVIDIA A100, 40960 MiB, 0 MiB, 40960 MiB, 30, 00000000:02:00.0, [N/A], [N/A], [N/A], Enabled\n";
+        let (result, mig_enabled) = parse_gpu_devices_extended_csv(csv);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].name, "Acme GPU, Rev 2");
+        assert_eq!(result[0].pci_bus_id, "00000000:01:00.0");
+        assert_eq!(result[0].memory_reserved_kb, Some(512 * 1024));
+        assert_eq!(result[0].utilization_percent, Some(87));
+        assert_eq!(result[0].memory_utilization_percent, Some(40));
+        assert_eq!(result[1].memory_reserved_kb, None);
+        assert_eq!(result[1].utilization_percent, None);
+        assert_eq!(result[1].memory_utilization_percent, None);
+        assert_eq!(mig_enabled, vec![1]);
+    }
+
+    #[test]
+    fn test_parse_gpu_reserved_csv() {
+        let csv = "0, 512 MiB\n1, [N/A]\n2, 0 MiB\n";
+        let reserved = parse_gpu_reserved_csv(csv);
+        assert_eq!(reserved.get(&0), Some(&(512 * 1024)));
+        assert_eq!(reserved.get(&1), None);
+        assert_eq!(reserved.get(&2), Some(&0));
+    }
+
+    #[test]
+    fn test_parse_gpu_utilization_csv() {
+        let csv = "0, 0 %, 5 %\n1, [N/A], [N/A]\n2, 87 %, [N/A]\n3, [N/A], 40 %\n";
+        let utilization = parse_gpu_utilization_csv(csv);
+        assert_eq!(utilization.get(&0), Some(&(Some(0), Some(5))));
+        assert_eq!(utilization.get(&1), None);
+        assert_eq!(utilization.get(&2), Some(&(Some(87), None)));
+        assert_eq!(utilization.get(&3), Some(&(None, Some(40))));
+    }
+
+    #[test]
+    fn test_parse_gpu_utilization_csv_not_supported() {
+        let csv = "0, 75 %, [Not Supported]\n1, [Not Supported], [Not Supported]\n";
+        let utilization = parse_gpu_utilization_csv(csv);
+        assert_eq!(utilization.get(&0), Some(&(Some(75), None)));
+        assert_eq!(utilization.get(&1), None);
+    }
+
     #[test]
     fn test_gpu_mib_to_kb() {
         assert_eq!(mib_to_kb(1), 1024);
@@ -217,6 +782,191 @@ index, name, memory.total [MiB], memory.used [MiB], memory.free [MiB], temperatu
         assert_eq!(result[0].name, "NVIDIA H100");
     }
 
+    #[test]
+    fn test_process_header_skipped_by_index_not_prefix() {
+        let csv = "\
+gpu_index, pid, process_name, used_gpu_memory [MiB]
+# comment
+0, 4242, gpu_worker, 1024 MiB
+1, 4343, index_builder, 512 MiB";
+        let result = parse_gpu_processes_csv(csv, &HashMap::new());
+        let names: Vec<&str> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["gpu_worker", "index_builder"]);
+    }
+
+    #[test]
+    fn test_device_rows_with_header_like_fields() {
+        let csv = "\
+name, index, memory.total [MiB], memory.used [MiB], memory.free [MiB], temperature.gpu, pci.bus_id
+0, name-brand GPU, 1024 MiB, 0 MiB, 1024 MiB, 40, 00000000:01:00.0
+1, index GPU, 1024 MiB, 0 MiB, 1024 MiB, 40, 00000000:02:00.0";
+        let result = parse_gpu_devices_csv(csv);
+        let names: Vec<&str> = result.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["name-brand GPU", "index GPU"]);
+    }
+
+    #[test]
+    fn test_is_data_row() {
+        assert!(is_data_row("0, 1234, python3, 2048 MiB"));
+        assert!(!is_data_row("gpu_index, pid, process_name, used_gpu_memory [MiB]"));
+        assert!(!is_data_row("index, name"));
+        assert!(!is_data_row("# comment"));
+        assert!(!is_data_row(""));
+    }
+
+    #[test]
+    fn test_nvidia_smi_command_forces_c_locale() {
+        let cmd = nvidia_smi_command(&["--query-gpu=index"]);
+        let lc_all = cmd
+            .get_envs()
+            .find(|(k, _)| *k == "LC_ALL")
+            .and_then(|(_, v)| v);
+        assert_eq!(lc_all, Some(std::ffi::OsStr::new("C")));
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), vec!["--query-gpu=index"]);
+    }
+
+    #[test]
+    fn test_binary_from_env() {
+        assert_eq!(binary_from_env(None), "nvidia-smi");
+        assert_eq!(binary_from_env(Some("".into())), "nvidia-smi");
+        assert_eq!(binary_from_env(Some("/usr/local/nvidia/bin/nvidia-smi".into())), "/usr/local/nvidia/bin/nvidia-smi");
+    }
+
+    const MIG_XML: &str = r#"<?xml version="1.0" ?>
+<nvidia_smi_log>
+	<attached_gpus>2</attached_gpus>
+	<gpu id="00000000:01:00.0">
+		<product_name>NVIDIA A100-SXM4-40GB</product_name>
+		<mig_mode>
+			<current_mig>Enabled</current_mig>
+		</mig_mode>
+		<mig_devices>
+			<mig_device>
+				<index>0</index>
+				<gpu_instance_id>1</gpu_instance_id>
+				<compute_instance_id>0</compute_instance_id>
+				<fb_memory_usage>
+					<total>20096 MiB</total>
+					<reserved>0 MiB</reserved>
+					<used>13 MiB</used>
+					<free>20082 MiB</free>
+				</fb_memory_usage>
+				<bar1_memory_usage>
+					<total>32767 MiB</total>
+					<used>0 MiB</used>
+				</bar1_memory_usage>
+			</mig_device>
+			<mig_device>
+				<index>1</index>
+				<gpu_instance_id>2</gpu_instance_id>
+				<compute_instance_id>0</compute_instance_id>
+				<fb_memory_usage>
+					<total>20096 MiB</total>
+					<used>4109 MiB</used>
+				</fb_memory_usage>
+			</mig_device>
+		</mig_devices>
+	</gpu>
+	<gpu id="00000000:02:00.0">
+		<mig_devices>None</mig_devices>
+	</gpu>
+</nvidia_smi_log>
+"#;
+
+    #[test]
+    fn test_parse_mig_devices_xml() {
+        let result = parse_mig_devices_xml(MIG_XML);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, "00000000:01:00.0");
+        assert_eq!(result[0].1.gpu_instance, 1);
+        assert_eq!(result[0].1.memory_used_kb, 13 * 1024);
+        assert_eq!(result[0].1.memory_total_kb, 20096 * 1024);
+        assert_eq!(result[1].1.device, 1);
+        assert_eq!(result[1].1.memory_used_kb, 4109 * 1024);
+    }
+
+    #[test]
+    fn test_parse_mig_devices_xml_without_mig() {
+        assert!(parse_mig_devices_xml("<nvidia_smi_log><gpu id=\"00000000:01:00.0\"></gpu></nvidia_smi_log>").is_empty());
+    }
+
+    #[test]
+    fn test_parse_mig_enabled_csv() {
+        let csv = "0, Enabled\n1, Disabled\n2, [N/A]\n3, Enabled\n";
+        assert_eq!(parse_mig_enabled_csv(csv), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_attach_mig_instances_to_parent() {
+        let mut devs = parse_gpu_devices_csv(
+            "0, A100, 40960 MiB, 0 MiB, 40960 MiB, 30, 00000000:01:00.0\n\
+             1, A100, 40960 MiB, 0 MiB, 40960 MiB, 30, 00000000:02:00.0\n",
+        );
+        attach_mig_instances(&mut devs, parse_mig_devices_xml(MIG_XML));
+        assert_eq!(devs[0].mig_instances.len(), 2);
+        assert!(devs[1].mig_instances.is_empty());
+    }
+
+    #[test]
+    fn test_processes_hidden() {
+        let mut devs = parse_gpu_devices_csv(
+            "0, H100, 81920 MiB, 3 MiB, 81917 MiB, 30, 00000000:01:00.0\n\
+             1, H100, 81920 MiB, 0 MiB, 81920 MiB, 30, 00000000:02:00.0\n",
+        );
+        // Idle driver overhead only
+        assert!(!processes_hidden(&devs));
+        assert!(!processes_hidden(&[]));
+
+        devs[1].memory_used_kb = 20_000 * 1024;
+        assert!(processes_hidden(&devs));
+
+        devs[1].memory_used_kb = 0;
+        devs[0].utilization_percent = Some(40);
+        assert!(processes_hidden(&devs));
+    }
+
+    fn exit_output(code: i32, stdout: &str, stderr: &str) -> std::process::Output {
+        use std::os::unix::process::ExitStatusExt;
+        std::process::Output {
+            status: std::process::ExitStatus::from_raw(code << 8),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_status_from_output_not_found() {
+        let err = std::io::Error::from(std::io::ErrorKind::NotFound);
+        assert_eq!(status_from_output(Err(err)), GpuStatus::NotFound);
+    }
+
+    #[test]
+    fn test_status_from_output_success() {
+        assert_eq!(status_from_output(Ok(exit_output(0, "0\n", ""))), GpuStatus::Available);
+    }
+
+    #[test]
+    fn test_status_from_output_failure_reports_message() {
+        let stdout = "NVIDIA-SMI has failed because it couldn't communicate with the NVIDIA driver.\n\
+                      Make sure that the latest NVIDIA driver is installed and running.\n";
+        assert_eq!(
+            status_from_output(Ok(exit_output(9, stdout, ""))),
+            GpuStatus::Failed(
+                "NVIDIA-SMI has failed because it couldn't communicate with the NVIDIA driver.".into()
+            )
+        );
+        assert_eq!(
+            status_from_output(Ok(exit_output(18, "", "Failed to initialize NVML: Driver/library version mismatch\n"))),
+            GpuStatus::Failed("Failed to initialize NVML: Driver/library version mismatch".into())
+        );
+    }
+
+    #[test]
+    fn test_status_from_output_failure_without_message() {
+        let status = status_from_output(Ok(exit_output(1, "", "")));
+        assert!(matches!(status, GpuStatus::Failed(msg) if msg.contains("exit")));
+    }
+
     #[test]
     fn test_gpu_numa_mapping() {
         // Can't test sysfs reads without real hardware, but verify the function
@@ -232,7 +982,7 @@ index, name, memory.total [MiB], memory.used [MiB], memory.free [MiB], temperatu
         let proc_csv = "0, 100, train.py, 4096 MiB\n1, 200, infer.py, 2048 MiB\n";
         let dev_csv = "0, H100, 81920 MiB, 4096 MiB, 77824 MiB, 45, 00000000:01:00.0\n\
                         1, H100, 81920 MiB, 2048 MiB, 79872 MiB, 40, 00000000:02:00.0\n";
-        let procs = parse_gpu_processes_csv(proc_csv);
+        let procs = parse_gpu_processes_csv(proc_csv, &HashMap::new());
         let devs = parse_gpu_devices_csv(dev_csv);
         assert_eq!(procs.len(), 2);
         assert_eq!(devs.len(), 2);
@@ -1,7 +1,12 @@
+pub mod demo;
 pub mod gpu;
 pub mod swap;
 pub mod types;
 pub mod numa;
+#[cfg(feature = "nvml")]
+pub mod nvml;
+pub mod rocm;
+pub mod verbose;
 
 pub use types::*;
 
@@ -15,11 +20,169 @@ pub trait DataProvider {
     fn is_numa_available(&self) -> bool;
     fn get_gpu_devices(&self) -> Result<Vec<GpuDevice>, SwapDataError>;
     fn get_gpu_processes(&self) -> Result<Vec<GpuProcessInfo>, SwapDataError>;
-    fn is_gpu_available(&self) -> bool;
+    fn gpu_status(&self) -> GpuStatus;
+    fn is_process_alive(&self, pid: u32) -> bool;
+
+    fn is_gpu_available(&self) -> bool {
+        self.gpu_status() == GpuStatus::Available
+    }
+
+    /// Mount namespace inode of the host, for telling host processes from
+    /// containerised ones. None when unknown.
+    fn host_mnt_ns(&self) -> Option<u64> {
+        None
+    }
+
+    /// Full command lines of the given GPU processes, keyed by PID. PIDs
+    /// that are not local processes are absent.
+    fn gpu_process_cmdlines(&self, _processes: &[GpuProcessInfo]) -> HashMap<u32, String> {
+        HashMap::new()
+    }
+}
+
+/// Which `DataProvider` the TUI (and `--serve`) read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    /// Real data from /proc, /sys and the GPU tools
+    Proc,
+    /// Synthetic data (`--demo`)
+    Demo,
+}
+
+impl ProviderKind {
+    pub fn build(self) -> Box<dyn DataProvider> {
+        match self {
+            ProviderKind::Proc => Box::new(ProcDataProvider),
+            ProviderKind::Demo => Box::new(demo::DemoDataProvider),
+        }
+    }
 }
 
 pub struct ProcDataProvider;
 
+/// Default huge page size (`Hugepagesize` in /proc/meminfo), read once.
+fn huge_page_size_kb() -> Option<u64> {
+    use procfs::Current;
+    static HUGE_PAGE_SIZE_KB: std::sync::OnceLock<Option<u64>> = std::sync::OnceLock::new();
+    *HUGE_PAGE_SIZE_KB.get_or_init(|| {
+        procfs::Meminfo::current()
+            .ok()
+            .and_then(|m| m.hugepagesize)
+            .map(|bytes| bytes / 1024)
+    })
+}
+
+/// Where GPU data is read from: the NVML library when built with it and
+/// loadable, else nvidia-smi when it works, otherwise rocm-smi for AMD GPUs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GpuBackend {
+    #[cfg(feature = "nvml")]
+    Nvml,
+    Nvidia,
+    Rocm,
+}
+
+fn gpu_backend() -> Option<GpuBackend> {
+    #[cfg(feature = "nvml")]
+    if nvml::backend().is_some() {
+        return Some(GpuBackend::Nvml);
+    }
+    if gpu::check_nvidia_smi_available() {
+        Some(GpuBackend::Nvidia)
+    } else if rocm::check_rocm_smi_available() {
+        Some(GpuBackend::Rocm)
+    } else {
+        None
+    }
+}
+
+/// AMD devices with their NUMA nodes. Product name, temperature, bus id and
+/// utilisation come from a second, optional query.
+fn rocm_gpu_devices() -> Vec<GpuDevice> {
+    let Ok(meminfo) = rocm::run_rocm_smi(&["--showmeminfo", "vram", "--json"]) else {
+        return Vec::new();
+    };
+    let details = rocm::run_rocm_smi(&[
+        "--showproductname",
+        "--showtemp",
+        "--showbus",
+        "--showuse",
+        "--showmemuse",
+        "--json",
+    ])
+    .ok();
+    let mut devices = rocm::parse_rocm_devices_json(&meminfo, details.as_deref());
+    let numa_map = gpu::get_gpu_numa_mapping(&devices);
+    for dev in &mut devices {
+        dev.numa_node_id = numa_map.get(&dev.index).copied();
+    }
+    devices
+}
+
+/// NVIDIA devices from NVML with their NUMA nodes and MIG instances. None
+/// when an NVML call fails, so the caller can fall back to nvidia-smi.
+#[cfg(feature = "nvml")]
+fn nvml_gpu_devices() -> Option<Vec<GpuDevice>> {
+    let backend = nvml::backend()?;
+    let mut devices = backend.devices().ok()?;
+    let numa_map = gpu::get_gpu_numa_mapping(&devices);
+    for dev in &mut devices {
+        dev.numa_node_id = numa_map.get(&dev.index).copied();
+    }
+    if !backend.mig_enabled().is_empty() {
+        attach_smi_mig_instances(&mut devices);
+    }
+    Some(devices)
+}
+
+/// The basic nvidia-smi device query, without the optional extras.
+fn smi_gpu_devices() -> Vec<GpuDevice> {
+    gpu::run_nvidia_smi(&[&format!("--query-gpu={}", gpu::GPU_FIELDS), "--format=csv,noheader"])
+        .map(|csv| gpu::parse_gpu_devices_csv(&csv))
+        .unwrap_or_default()
+}
+
+/// Read MIG instances from `nvidia-smi -q -x`. NVML has no equivalent in
+/// nvml-wrapper, and MIG is rare enough that the extra run is fine.
+fn attach_smi_mig_instances(devices: &mut [GpuDevice]) {
+    if let Ok(xml) = gpu::run_nvidia_smi(&["-q", "-x"]) {
+        gpu::attach_mig_instances(devices, gpu::parse_mig_devices_xml(&xml));
+    }
+}
+
+/// Devices from the basic query plus one nvidia-smi run per newer field, for
+/// drivers that reject the combined query, and the indices of GPUs with MIG
+/// enabled. Each extra is left None when its own query fails.
+fn smi_gpu_devices_by_field() -> Result<(Vec<GpuDevice>, Vec<u32>), SwapDataError> {
+    let csv = gpu::run_nvidia_smi(&[&format!("--query-gpu={}", gpu::GPU_FIELDS), "--format=csv,noheader"])
+        .map_err(SwapDataError::Io)?;
+    let mut devices = gpu::parse_gpu_devices_csv(&csv);
+    // memory.reserved is newer than the main query fields
+    if let Ok(csv) = gpu::run_nvidia_smi(&["--query-gpu=index,memory.reserved", "--format=csv,noheader"]) {
+        let reserved = gpu::parse_gpu_reserved_csv(&csv);
+        for dev in &mut devices {
+            dev.memory_reserved_kb = reserved.get(&dev.index).copied();
+        }
+    }
+    // Some GPUs and vGPU profiles report utilization.gpu/.memory as [N/A]
+    if let Ok(csv) = gpu::run_nvidia_smi(&[
+        "--query-gpu=index,utilization.gpu,utilization.memory",
+        "--format=csv,noheader",
+    ]) {
+        let utilization = gpu::parse_gpu_utilization_csv(&csv);
+        for dev in &mut devices {
+            let (gpu, memory) = utilization.get(&dev.index).copied().unwrap_or_default();
+            dev.utilization_percent = gpu;
+            dev.memory_utilization_percent = memory;
+        }
+    }
+    // Older drivers reject the MIG query, which just means no MIG
+    let mig_enabled = gpu::run_nvidia_smi(&["--query-gpu=index,mig.mode.current", "--format=csv,noheader"])
+        .map(|csv| gpu::parse_mig_enabled_csv(&csv))
+        .unwrap_or_default();
+    Ok((devices, mig_enabled))
+}
+
 impl DataProvider for ProcDataProvider {
     fn get_swap_info(&self, unit: &SizeUnits) -> Result<SwapUpdate, SwapDataError> {
         swap::get_chart_info(unit.clone())
@@ -31,27 +194,23 @@ impl DataProvider for ProcDataProvider {
 
     fn get_numa_topology(&self) -> Result<Vec<NumaNode>, SwapDataError> {
         // Use GPU NUMA mapping if GPUs are available
-        let gpu_map = if gpu::check_nvidia_smi_available() {
-            if gpu::run_nvidia_smi(&[
-                "--query-gpu=index",
-                "--format=csv,noheader",
-            ]).is_ok() {
-                if let Ok(csv_with_units) = gpu::run_nvidia_smi(&[
-                    "--query-gpu=index,name,memory.total,memory.used,memory.free,temperature.gpu,pci.bus_id",
-                    "--format=csv,noheader",
-                ]) {
-                    let devices = gpu::parse_gpu_devices_csv(&csv_with_units);
-                    gpu::get_gpu_numa_mapping(&devices)
-                } else {
-                    HashMap::new()
-                }
-            } else {
-                HashMap::new()
-            }
-        } else {
-            HashMap::new()
+        let devices = match gpu_backend() {
+            #[cfg(feature = "nvml")]
+            Some(GpuBackend::Nvml) => nvml::backend()
+                .and_then(|backend| backend.devices().ok())
+                .unwrap_or_else(smi_gpu_devices),
+            Some(GpuBackend::Nvidia) => smi_gpu_devices(),
+            Some(GpuBackend::Rocm) => rocm_gpu_devices(),
+            None => Vec::new(),
         };
-        numa::discover_numa_topology("/sys/devices/system/node", &gpu_map)
+        let gpu_map = gpu::get_gpu_numa_mapping(&devices);
+        // GPUs whose PCI device reports numa_node -1 are absent from the map
+        let unaffined_gpus: Vec<(u32, u64)> = devices
+            .iter()
+            .filter(|d| !gpu_map.contains_key(&d.index))
+            .map(|d| (d.index, d.memory_total_kb))
+            .collect();
+        numa::discover_numa_topology("/sys/devices/system/node", &gpu_map, &unaffined_gpus)
             .map_err(SwapDataError::Io)
     }
 
@@ -59,33 +218,64 @@ impl DataProvider for ProcDataProvider {
         let path = format!("/proc/{}/numa_maps", pid);
         let content = std::fs::read_to_string(&path).map_err(SwapDataError::Io)?;
         let page_size_kb = procfs::page_size() / 1024;
-        Ok(numa::parse_numa_maps(&content, pid, name, page_size_kb))
+        Ok(numa::parse_numa_maps(&content, pid, name, page_size_kb, huge_page_size_kb()))
     }
 
     fn is_numa_available(&self) -> bool {
         std::path::Path::new("/sys/devices/system/node/node0").exists()
     }
 
+    fn is_process_alive(&self, pid: u32) -> bool {
+        std::path::Path::new(&format!("/proc/{}", pid)).exists()
+    }
+
+    fn host_mnt_ns(&self) -> Option<u64> {
+        swap::host_mnt_ns()
+    }
+
     fn get_gpu_devices(&self) -> Result<Vec<GpuDevice>, SwapDataError> {
-        if !gpu::check_nvidia_smi_available() {
-            return Ok(vec![]);
+        match gpu_backend() {
+            #[cfg(feature = "nvml")]
+            Some(GpuBackend::Nvml) => {
+                if let Some(devices) = nvml_gpu_devices() {
+                    return Ok(devices);
+                }
+            }
+            Some(GpuBackend::Nvidia) => {}
+            Some(GpuBackend::Rocm) => return Ok(rocm_gpu_devices()),
+            None => return Ok(vec![]),
         }
-        let csv = gpu::run_nvidia_smi(&[
-            "--query-gpu=index,name,memory.total,memory.used,memory.free,temperature.gpu,pci.bus_id",
-            "--format=csv,noheader",
-        ])
-        .map_err(SwapDataError::Io)?;
-        let mut devices = gpu::parse_gpu_devices_csv(&csv);
+        let query = format!("--query-gpu={},{}", gpu::GPU_FIELDS, gpu::GPU_EXTRA_FIELDS);
+        let (mut devices, mig_enabled) = match gpu::run_nvidia_smi(&[&query, "--format=csv,noheader"]) {
+            Ok(csv) => gpu::parse_gpu_devices_extended_csv(&csv),
+            // The driver rejected one of the newer fields: query them one by one
+            Err(_) => smi_gpu_devices_by_field()?,
+        };
         let numa_map = gpu::get_gpu_numa_mapping(&devices);
         for dev in &mut devices {
             dev.numa_node_id = numa_map.get(&dev.index).copied();
         }
+        if !mig_enabled.is_empty() {
+            attach_smi_mig_instances(&mut devices);
+        }
         Ok(devices)
     }
 
     fn get_gpu_processes(&self) -> Result<Vec<GpuProcessInfo>, SwapDataError> {
-        if !gpu::check_nvidia_smi_available() {
-            return Ok(vec![]);
+        match gpu_backend() {
+            #[cfg(feature = "nvml")]
+            Some(GpuBackend::Nvml) => {
+                if let Some(processes) = nvml::backend().and_then(|b| b.processes(gpu::pmon_enabled()).ok()) {
+                    return Ok(processes);
+                }
+            }
+            Some(GpuBackend::Nvidia) => {}
+            Some(GpuBackend::Rocm) => {
+                let pids = rocm::run_rocm_smi(&["--showpids", "--json"]).map_err(SwapDataError::Io)?;
+                let pid_gpus = rocm::run_rocm_smi(&["--showpidgpus", "--json"]).ok();
+                return Ok(rocm::parse_rocm_pids_json(&pids, pid_gpus.as_deref()));
+            }
+            None => return Ok(vec![]),
         }
         let csv = gpu::run_nvidia_smi(&[
             "--query-compute-apps=gpu_uuid,pid,process_name,used_gpu_memory",
@@ -102,11 +292,34 @@ impl DataProvider for ProcDataProvider {
                 .map_err(SwapDataError::Io)?
             }
         };
-        Ok(gpu::parse_gpu_processes_csv(&csv))
+        // Both queries name the GPU by UUID or bus id rather than index
+        let gpu_ids = gpu::run_nvidia_smi(&["--query-gpu=index,gpu_uuid,pci.bus_id", "--format=csv,noheader"])
+            .map(|csv| gpu::parse_gpu_ids_csv(&csv))
+            .unwrap_or_default();
+        let mut processes = gpu::parse_gpu_processes_csv(&csv, &gpu_ids);
+        if gpu::pmon_enabled()
+            && let Ok(output) = gpu::run_nvidia_smi(&["pmon", "-c", "1", "-s", "u"])
+        {
+            gpu::attach_pmon_utilization(&mut processes, &gpu::parse_pmon(&output));
+        }
+        Ok(processes)
     }
 
-    fn is_gpu_available(&self) -> bool {
-        gpu::check_nvidia_smi_available()
+    fn gpu_process_cmdlines(&self, processes: &[GpuProcessInfo]) -> HashMap<u32, String> {
+        gpu::process_cmdlines(processes)
+    }
+
+    fn gpu_status(&self) -> GpuStatus {
+        // A failing nvidia-smi is reported even if rocm-smi works: it means
+        // NVIDIA hardware whose driver needs attention
+        #[cfg(feature = "nvml")]
+        if nvml::backend().is_some() {
+            return GpuStatus::Available;
+        }
+        match gpu::nvidia_smi_status() {
+            GpuStatus::NotFound if rocm::check_rocm_smi_available() => GpuStatus::Available,
+            status => status,
+        }
     }
 }
 
@@ -116,9 +329,21 @@ pub struct MockDataProvider {
     pub processes: Vec<ProcessSwapInfo>,
     pub numa_nodes: Vec<NumaNode>,
     pub numa_available: bool,
+    /// Unit of the last `get_processes_swap` call, shared so tests can read
+    /// it after handing the provider to an App
+    pub swap_unit_requested: std::rc::Rc<std::cell::RefCell<Option<SizeUnits>>>,
     pub gpu_devices: Vec<GpuDevice>,
     pub gpu_processes: Vec<GpuProcessInfo>,
     pub gpu_available: bool,
+    /// Reported as `GpuStatus::Failed` when set and `gpu_available` is false
+    pub gpu_failure: Option<String>,
+    /// PIDs reported as no longer running
+    pub exited_pids: Vec<u32>,
+    pub host_mnt_ns: Option<u64>,
+    pub gpu_cmdlines: HashMap<u32, String>,
+    /// Number of `get_processes_swap` calls, shared so tests can read it
+    /// after handing the provider to an App
+    pub process_queries: std::rc::Rc<std::cell::Cell<usize>>,
 }
 
 #[cfg(test)]
@@ -129,10 +354,11 @@ impl MockDataProvider {
                 swap_devices: vec![],
                 total_swap: 8_000_000,
                 used_swap: 2_000_000,
+                ..Default::default()
             },
             processes: vec![
-                ProcessSwapInfo { pid: 1, name: "test_proc".into(), swap_size: 1024.0, last_cpu: Some(0) },
-                ProcessSwapInfo { pid: 2, name: "another".into(), swap_size: 512.0, last_cpu: Some(1) },
+                ProcessSwapInfo { pid: 1, name: "test_proc".into(), swap_size: 1024.0, last_cpu: Some(0), rss_kb: None, mnt_ns: None, count: 1 },
+                ProcessSwapInfo { pid: 2, name: "another".into(), swap_size: 512.0, last_cpu: Some(1), rss_kb: None, mnt_ns: None, count: 1 },
             ],
             numa_nodes: vec![
                 NumaNode {
@@ -141,12 +367,19 @@ impl MockDataProvider {
                     memory_free_kb: 8_000_000,
                     cpus: vec![0, 1, 2, 3],
                     node_type: NumaNodeType::Cpu,
+                    distances: vec![],
                 },
             ],
             numa_available: true,
+            swap_unit_requested: Default::default(),
             gpu_devices: vec![],
             gpu_processes: vec![],
             gpu_available: false,
+            gpu_failure: None,
+            exited_pids: vec![],
+            host_mnt_ns: None,
+            gpu_cmdlines: HashMap::new(),
+            process_queries: Default::default(),
         }
     }
 }
@@ -157,7 +390,9 @@ impl DataProvider for MockDataProvider {
         Ok(self.swap_update.clone())
     }
 
-    fn get_processes_swap(&self, _unit: &SizeUnits) -> Result<Vec<ProcessSwapInfo>, SwapDataError> {
+    fn get_processes_swap(&self, unit: &SizeUnits) -> Result<Vec<ProcessSwapInfo>, SwapDataError> {
+        *self.swap_unit_requested.borrow_mut() = Some(unit.clone());
+        self.process_queries.set(self.process_queries.get() + 1);
         Ok(self.processes.clone())
     }
 
@@ -179,6 +414,14 @@ impl DataProvider for MockDataProvider {
         self.numa_available
     }
 
+    fn is_process_alive(&self, pid: u32) -> bool {
+        !self.exited_pids.contains(&pid)
+    }
+
+    fn host_mnt_ns(&self) -> Option<u64> {
+        self.host_mnt_ns
+    }
+
     fn get_gpu_devices(&self) -> Result<Vec<GpuDevice>, SwapDataError> {
         Ok(self.gpu_devices.clone())
     }
@@ -187,12 +430,47 @@ impl DataProvider for MockDataProvider {
         Ok(self.gpu_processes.clone())
     }
 
-    fn is_gpu_available(&self) -> bool {
-        self.gpu_available
+    fn gpu_process_cmdlines(&self, _processes: &[GpuProcessInfo]) -> HashMap<u32, String> {
+        self.gpu_cmdlines.clone()
+    }
+
+    fn gpu_status(&self) -> GpuStatus {
+        match (&self.gpu_failure, self.gpu_available) {
+            (_, true) => GpuStatus::Available,
+            (Some(msg), false) => GpuStatus::Failed(msg.clone()),
+            (None, false) => GpuStatus::NotFound,
+        }
     }
 }
 
-use std::collections::HashMap as StdHashMap;
+/// Processes whose numa_maps are read per refresh (and by `--dump-numa-csv`);
+/// numa_maps is expensive to parse, so only the largest swap users are scanned.
+pub const NUMA_MAPS_TOP_N: usize = 20;
+
+/// Read numa_maps for the `limit` largest swap consumers in `swap_procs`
+/// and attach the NUMA node of the CPU each last ran on.
+pub fn collect_process_numa_infos(
+    provider: &dyn DataProvider,
+    swap_procs: &[ProcessSwapInfo],
+    numa_nodes: &[NumaNode],
+    limit: usize,
+) -> Vec<ProcessNumaInfo> {
+    let mut procs: Vec<&ProcessSwapInfo> = swap_procs.iter().collect();
+    procs.sort_by(|a, b| {
+        b.swap_size
+            .partial_cmp(&a.swap_size)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    procs
+        .into_iter()
+        .take(limit)
+        .filter_map(|proc| {
+            let mut info = provider.get_process_numa_maps(proc.pid, &proc.name).ok()?;
+            info.cpu_node = proc.last_cpu.and_then(|cpu| numa::cpu_to_numa_node(cpu, numa_nodes));
+            Some(info)
+        })
+        .collect()
+}
 
 /// Merge swap, GPU, and NUMA data into unified process info.
 /// Joins by PID. Processes appearing in both swap and GPU get `CpuAndGpu`.
@@ -204,10 +482,10 @@ pub fn merge_process_data(
     numa_nodes: &[NumaNode],
     gpu_devices: &[GpuDevice],
 ) -> Vec<UnifiedProcessInfo> {
-    let mut by_pid: StdHashMap<u32, UnifiedProcessInfo> = StdHashMap::new();
+    let mut by_pid: HashMap<u32, UnifiedProcessInfo> = HashMap::new();
 
     // Build gpu_index → numa_node_id mapping from devices
-    let gpu_numa_map: StdHashMap<u32, u32> = gpu_devices
+    let gpu_numa_map: HashMap<u32, u32> = gpu_devices
         .iter()
         .filter_map(|d| d.numa_node_id.map(|n| (d.index, n)))
         .collect();
@@ -237,6 +515,8 @@ pub fn merge_process_data(
                 gpu_memory_kb: None,
                 gpu_indices: Vec::new(),
                 location: ProcessLocation::CpuOnly,
+                rss_kb: p.rss_kb,
+                count: 1,
             },
         );
     }
@@ -265,10 +545,12 @@ pub fn merge_process_data(
                     swap_kb: 0,
                     cpu_nodes: Vec::new(),
                     gpu_nodes: gpu_node.into_iter().collect(),
-                    kb_per_node: StdHashMap::new(),
+                    kb_per_node: HashMap::new(),
                     gpu_memory_kb: Some(gp.gpu_memory_used_kb),
                     gpu_indices: vec![gp.gpu_index],
                     location: ProcessLocation::GpuOnly,
+                    rss_kb: None,
+                    count: 1,
                 },
             );
         }
@@ -307,6 +589,21 @@ pub fn merge_process_data(
 mod tests {
     use super::*;
 
+    fn swap_proc(pid: u32, name: &str, swap_size: f64) -> ProcessSwapInfo {
+        ProcessSwapInfo { pid, name: name.into(), swap_size, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 }
+    }
+
+    fn gpu_device(index: u32, memory_total_kb: u64, numa_node_id: Option<u32>) -> GpuDevice {
+        GpuDevice {
+            index, name: format!("GPU {}", index), memory_total_kb, memory_used_kb: 0, memory_free_kb: 0, numa_node_id,
+            temperature: None, pci_bus_id: format!("00:{:02x}.0", index + 1),
+            memory_reserved_kb: None,
+            mig_instances: Vec::new(),
+            utilization_percent: None,
+            memory_utilization_percent: None,
+        }
+    }
+
     #[test]
     fn test_mock_provider_returns_data() {
         let provider = MockDataProvider::new();
@@ -321,8 +618,8 @@ mod tests {
 
     #[test]
     fn test_merge_same_pid() {
-        let swap = vec![ProcessSwapInfo { pid: 100, name: "train".into(), swap_size: 1024.0, last_cpu: None }];
-        let gpu = vec![GpuProcessInfo { pid: 100, name: "train".into(), gpu_index: 0, gpu_memory_used_kb: 4096 }];
+        let swap = vec![swap_proc(100, "train", 1024.0)];
+        let gpu = vec![GpuProcessInfo { pid: 100, name: "train".into(), gpu_index: 0, gpu_memory_used_kb: 4096, sm_util: None, mem_util: None }];
         let result = merge_process_data(&swap, &gpu, &[], &[], &[]);
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].location, ProcessLocation::CpuAndGpu);
@@ -332,7 +629,7 @@ mod tests {
 
     #[test]
     fn test_cpu_only_process() {
-        let swap = vec![ProcessSwapInfo { pid: 100, name: "bash".into(), swap_size: 512.0, last_cpu: None }];
+        let swap = vec![swap_proc(100, "bash", 512.0)];
         let gpu: Vec<GpuProcessInfo> = vec![];
         let result = merge_process_data(&swap, &gpu, &[], &[], &[]);
         assert_eq!(result.len(), 1);
@@ -342,7 +639,7 @@ mod tests {
     #[test]
     fn test_gpu_only_process() {
         let swap: Vec<ProcessSwapInfo> = vec![];
-        let gpu = vec![GpuProcessInfo { pid: 200, name: "cuda_app".into(), gpu_index: 0, gpu_memory_used_kb: 8192 }];
+        let gpu = vec![GpuProcessInfo { pid: 200, name: "cuda_app".into(), gpu_index: 0, gpu_memory_used_kb: 8192, sm_util: None, mem_util: None }];
         let result = merge_process_data(&swap, &gpu, &[], &[], &[]);
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].location, ProcessLocation::GpuOnly);
@@ -352,11 +649,11 @@ mod tests {
     #[test]
     fn test_unified_sorting() {
         let swap = vec![
-            ProcessSwapInfo { pid: 1, name: "small".into(), swap_size: 100.0, last_cpu: None },
-            ProcessSwapInfo { pid: 2, name: "big".into(), swap_size: 5000.0, last_cpu: None },
+            swap_proc(1, "small", 100.0),
+            swap_proc(2, "big", 5000.0),
         ];
         let gpu = vec![
-            GpuProcessInfo { pid: 3, name: "gpu_big".into(), gpu_index: 0, gpu_memory_used_kb: 10000 },
+            GpuProcessInfo { pid: 3, name: "gpu_big".into(), gpu_index: 0, gpu_memory_used_kb: 10000, sm_util: None, mem_util: None },
         ];
         let result = merge_process_data(&swap, &gpu, &[], &[], &[]);
         assert_eq!(result.len(), 3);
@@ -370,8 +667,8 @@ mod tests {
     fn test_aggregate_unified() {
         // merge_process_data handles aggregation by PID (not by name)
         let swap = vec![
-            ProcessSwapInfo { pid: 1, name: "proc".into(), swap_size: 100.0, last_cpu: None },
-            ProcessSwapInfo { pid: 2, name: "proc".into(), swap_size: 200.0, last_cpu: None },
+            swap_proc(1, "proc", 100.0),
+            swap_proc(2, "proc", 200.0),
         ];
         let gpu: Vec<GpuProcessInfo> = vec![];
         let result = merge_process_data(&swap, &gpu, &[], &[], &[]);
@@ -381,7 +678,7 @@ mod tests {
 
     #[test]
     fn test_hbm_migration_detected() {
-        let swap = vec![ProcessSwapInfo { pid: 100, name: "migrated".into(), swap_size: 1024.0, last_cpu: None }];
+        let swap = vec![swap_proc(100, "migrated", 1024.0)];
         let gpu: Vec<GpuProcessInfo> = vec![];
         let numa_infos = vec![ProcessNumaInfo {
             pid: 100,
@@ -391,8 +688,8 @@ mod tests {
             cpu_node: None,
         }];
         let numa_nodes = vec![
-            NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-            NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 } },
+            NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+            NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 }, distances: vec![] },
         ];
         let result = merge_process_data(&swap, &gpu, &numa_infos, &numa_nodes, &[]);
         assert_eq!(result.len(), 1);
@@ -402,8 +699,8 @@ mod tests {
     #[test]
     fn test_graceful_no_gpu() {
         let swap = vec![
-            ProcessSwapInfo { pid: 1, name: "proc1".into(), swap_size: 100.0, last_cpu: None },
-            ProcessSwapInfo { pid: 2, name: "proc2".into(), swap_size: 200.0, last_cpu: None },
+            swap_proc(1, "proc1", 100.0),
+            swap_proc(2, "proc2", 200.0),
         ];
         let gpu: Vec<GpuProcessInfo> = vec![];
         let result = merge_process_data(&swap, &gpu, &[], &[], &[]);
@@ -416,8 +713,8 @@ mod tests {
         // Verify that when ProcessNumaInfo has cpu_node set, it's visible
         // This tests the wiring: last_cpu -> cpu_to_numa_node -> cpu_node
         let numa_nodes = vec![
-            NumaNode { id: 0, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu },
-            NumaNode { id: 1, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![4, 5, 6, 7], node_type: NumaNodeType::Cpu },
+            NumaNode { id: 0, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
+            NumaNode { id: 1, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![4, 5, 6, 7], node_type: NumaNodeType::Cpu, distances: vec![] },
         ];
 
         // Simulate what refresh_numa_data does: map last_cpu -> cpu_node
@@ -444,8 +741,8 @@ mod tests {
 
     #[test]
     fn test_graceful_no_numa() {
-        let swap = vec![ProcessSwapInfo { pid: 1, name: "proc".into(), swap_size: 100.0, last_cpu: None }];
-        let gpu = vec![GpuProcessInfo { pid: 1, name: "proc".into(), gpu_index: 0, gpu_memory_used_kb: 500 }];
+        let swap = vec![swap_proc(1, "proc", 100.0)];
+        let gpu = vec![GpuProcessInfo { pid: 1, name: "proc".into(), gpu_index: 0, gpu_memory_used_kb: 500, sm_util: None, mem_util: None }];
         // No NUMA data at all
         let result = merge_process_data(&swap, &gpu, &[], &[], &[]);
         assert_eq!(result.len(), 1);
@@ -457,17 +754,14 @@ mod tests {
 
     #[test]
     fn test_merge_multi_gpu_same_pid() {
-        let swap = vec![ProcessSwapInfo {
-            pid: 100, name: "train".into(), swap_size: 1024.0,
-            last_cpu: None,
-        }];
+        let swap = vec![swap_proc(100, "train", 1024.0)];
         let gpu = vec![
-            GpuProcessInfo { pid: 100, name: "train".into(), gpu_index: 0, gpu_memory_used_kb: 4096 },
-            GpuProcessInfo { pid: 100, name: "train".into(), gpu_index: 1, gpu_memory_used_kb: 2048 },
+            GpuProcessInfo { pid: 100, name: "train".into(), gpu_index: 0, gpu_memory_used_kb: 4096, sm_util: None, mem_util: None },
+            GpuProcessInfo { pid: 100, name: "train".into(), gpu_index: 1, gpu_memory_used_kb: 2048, sm_util: None, mem_util: None },
         ];
         let devices = vec![
-            GpuDevice { index: 0, name: "GPU 0".into(), memory_total_kb: 81920000, memory_used_kb: 0, memory_free_kb: 0, numa_node_id: Some(2), temperature: None, pci_bus_id: "00:01.0".into() },
-            GpuDevice { index: 1, name: "GPU 1".into(), memory_total_kb: 81920000, memory_used_kb: 0, memory_free_kb: 0, numa_node_id: Some(3), temperature: None, pci_bus_id: "00:02.0".into() },
+            gpu_device(0, 81920000, Some(2)),
+            gpu_device(1, 81920000, Some(3)),
         ];
         let result = merge_process_data(&swap, &gpu, &[], &[], &devices);
         assert_eq!(result.len(), 1);
@@ -478,10 +772,7 @@ mod tests {
 
     #[test]
     fn test_merge_carries_kb_per_node() {
-        let swap = vec![ProcessSwapInfo {
-            pid: 42, name: "app".into(), swap_size: 512.0,
-            last_cpu: None,
-        }];
+        let swap = vec![swap_proc(42, "app", 512.0)];
         let numa_infos = vec![ProcessNumaInfo {
             pid: 42, name: "app".into(),
             kb_per_node: HashMap::from([(0, 500), (1, 200)]),
@@ -494,10 +785,7 @@ mod tests {
 
     #[test]
     fn test_merge_carries_cpu_node() {
-        let swap = vec![ProcessSwapInfo {
-            pid: 42, name: "app".into(), swap_size: 512.0,
-            last_cpu: None,
-        }];
+        let swap = vec![swap_proc(42, "app", 512.0)];
         let numa_infos = vec![ProcessNumaInfo {
             pid: 42, name: "app".into(),
             kb_per_node: HashMap::new(),
@@ -509,12 +797,8 @@ mod tests {
 
     #[test]
     fn test_merge_gpu_nodes_from_device_mapping() {
-        let gpu = vec![GpuProcessInfo { pid: 200, name: "cuda".into(), gpu_index: 0, gpu_memory_used_kb: 8192 }];
-        let devices = vec![GpuDevice {
-            index: 0, name: "GPU 0".into(), memory_total_kb: 81920000,
-            memory_used_kb: 0, memory_free_kb: 0, numa_node_id: Some(2),
-            temperature: None, pci_bus_id: "00:01.0".into(),
-        }];
+        let gpu = vec![GpuProcessInfo { pid: 200, name: "cuda".into(), gpu_index: 0, gpu_memory_used_kb: 8192, sm_util: None, mem_util: None }];
+        let devices = vec![gpu_device(0, 81920000, Some(2))];
         let result = merge_process_data(&[], &gpu, &[], &[], &devices);
         assert_eq!(result[0].gpu_nodes, vec![2]);
         assert_eq!(result[0].gpu_indices, vec![0]);
@@ -523,11 +807,11 @@ mod tests {
     #[test]
     fn test_merge_gpu_only_has_gpu_nodes() {
         let gpu = vec![
-            GpuProcessInfo { pid: 300, name: "infer".into(), gpu_index: 1, gpu_memory_used_kb: 4096 },
+            GpuProcessInfo { pid: 300, name: "infer".into(), gpu_index: 1, gpu_memory_used_kb: 4096, sm_util: None, mem_util: None },
         ];
         let devices = vec![
-            GpuDevice { index: 0, name: "GPU 0".into(), memory_total_kb: 0, memory_used_kb: 0, memory_free_kb: 0, numa_node_id: Some(2), temperature: None, pci_bus_id: "00:01.0".into() },
-            GpuDevice { index: 1, name: "GPU 1".into(), memory_total_kb: 0, memory_used_kb: 0, memory_free_kb: 0, numa_node_id: Some(3), temperature: None, pci_bus_id: "00:02.0".into() },
+            gpu_device(0, 0, Some(2)),
+            gpu_device(1, 0, Some(3)),
         ];
         let result = merge_process_data(&[], &gpu, &[], &[], &devices);
         assert_eq!(result.len(), 1);
@@ -538,10 +822,7 @@ mod tests {
 
     #[test]
     fn test_merge_no_numa_info_empty_pages() {
-        let swap = vec![ProcessSwapInfo {
-            pid: 1, name: "proc".into(), swap_size: 100.0,
-            last_cpu: None,
-        }];
+        let swap = vec![swap_proc(1, "proc", 100.0)];
         let result = merge_process_data(&swap, &[], &[], &[], &[]);
         assert!(result[0].kb_per_node.is_empty());
         assert!(result[0].cpu_nodes.is_empty());
@@ -551,18 +832,15 @@ mod tests {
 
     #[test]
     fn test_merge_hbm_migration_with_new_fields() {
-        let swap = vec![ProcessSwapInfo {
-            pid: 100, name: "migrated".into(), swap_size: 1024.0,
-            last_cpu: None,
-        }];
+        let swap = vec![swap_proc(100, "migrated", 1024.0)];
         let numa_infos = vec![ProcessNumaInfo {
             pid: 100, name: "migrated".into(),
             kb_per_node: HashMap::from([(0, 500), (2, 100)]),
             total_kb: 600, cpu_node: Some(0),
         }];
         let numa_nodes = vec![
-            NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-            NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 } },
+            NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+            NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 }, distances: vec![] },
         ];
         let result = merge_process_data(&swap, &[], &numa_infos, &numa_nodes, &[]);
         assert_eq!(result[0].location, ProcessLocation::CpuAndGpu);
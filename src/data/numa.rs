@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use super::types::{NumaNode, NumaNodeType, ProcessNumaInfo};
+use super::verbose;
 
 /// Parse MemTotal and MemFree from a NUMA node's meminfo content.
 /// Expects lines like "Node 0 MemTotal:  1234 kB"
@@ -29,6 +30,24 @@ fn extract_kb_value(line: &str) -> Option<u64> {
     num_str.parse().ok()
 }
 
+/// Parse a node's `distance` file, e.g. "10 21 21 10" (one value per node,
+/// in node order). Empty when the file is missing or malformed: a partial
+/// row would put distances under the wrong nodes.
+pub fn parse_distances(content: &str) -> Vec<u32> {
+    let trimmed = content.trim();
+    let mut distances = Vec::new();
+    for token in trimmed.split_whitespace() {
+        match token.parse::<u32>() {
+            Ok(distance) => distances.push(distance),
+            Err(_) => {
+                verbose::skipped("distance", trimmed, &format!("bad distance `{}`", token));
+                return Vec::new();
+            }
+        }
+    }
+    distances
+}
+
 /// Parse a CPU list string like "0-3,8-11" into a sorted Vec of CPU IDs.
 /// Empty string returns empty vec (indicates GPU HBM node with no CPUs).
 pub fn parse_cpulist(content: &str) -> Vec<u32> {
@@ -41,19 +60,19 @@ pub fn parse_cpulist(content: &str) -> Vec<u32> {
     for part in trimmed.split(',') {
         let part = part.trim();
         if let Some(dash_pos) = part.find('-') {
-            let start: u32 = match part[..dash_pos].parse() {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-            let end: u32 = match part[dash_pos + 1..].parse() {
-                Ok(v) => v,
-                Err(_) => continue,
+            let (Ok(start), Ok(end)) =
+                (part[..dash_pos].parse::<u32>(), part[dash_pos + 1..].parse::<u32>())
+            else {
+                verbose::skipped("cpulist", trimmed, &format!("bad range `{}`", part));
+                continue;
             };
             for cpu in start..=end {
                 cpus.push(cpu);
             }
         } else if let Ok(cpu) = part.parse::<u32>() {
             cpus.push(cpu);
+        } else if !part.is_empty() {
+            verbose::skipped("cpulist", trimmed, &format!("bad cpu `{}`", part));
         }
     }
     cpus.sort();
@@ -74,25 +93,78 @@ pub fn classify_numa_node(node: &NumaNode, gpu_map: &HashMap<u32, u32>) -> NumaN
     }
 }
 
+/// How far, as a fraction of the GPU's VRAM, an HBM node's MemTotal may be
+/// from it. The driver keeps part of the HBM back from the kernel.
+const HBM_SIZE_TOLERANCE: f64 = 0.1;
+
+/// Fallback for GPUs whose PCI device reports no NUMA affinity (`numa_node`
+/// of -1), given as (index, VRAM total KB): each GPU, in index order, takes
+/// the first CPU-less node left Unknown whose memory matches its VRAM.
+/// Nodes matching no GPU (CXL expanders, PMEM) stay Unknown. GPU indices
+/// already claimed by a GpuHbm node are skipped.
+pub fn assign_unaffined_hbm_nodes(nodes: &mut [NumaNode], unaffined_gpus: &[(u32, u64)]) {
+    let claimed: Vec<u32> = nodes
+        .iter()
+        .filter_map(|n| match n.node_type {
+            NumaNodeType::GpuHbm { gpu_index } => Some(gpu_index),
+            _ => None,
+        })
+        .collect();
+    let mut gpus: Vec<(u32, u64)> = unaffined_gpus
+        .iter()
+        .copied()
+        .filter(|(g, _)| !claimed.contains(g))
+        .collect();
+    gpus.sort_unstable();
+    gpus.dedup_by_key(|(g, _)| *g);
+
+    let mut candidates: Vec<&mut NumaNode> = nodes
+        .iter_mut()
+        .filter(|n| n.node_type == NumaNodeType::Unknown && n.cpus.is_empty() && n.memory_total_kb > 0)
+        .collect();
+    candidates.sort_by_key(|n| n.id);
+
+    for (gpu_index, vram_kb) in gpus {
+        let matches_vram =
+            |n: &&mut NumaNode| n.memory_total_kb.abs_diff(vram_kb) as f64 <= vram_kb as f64 * HBM_SIZE_TOLERANCE;
+        if let Some(pos) = candidates.iter().position(matches_vram) {
+            candidates.remove(pos).node_type = NumaNodeType::GpuHbm { gpu_index };
+        }
+    }
+}
+
 /// Parse /proc/[pid]/numa_maps content into ProcessNumaInfo.
 /// Each line has format: "address policy N0=pages N1=pages ... kernelpagesize_kB=N"
 /// Page counts are multiplied by the per-line `kernelpagesize_kB` (or `default_page_size_kb`
-/// when absent) and accumulated as KB directly.
-pub fn parse_numa_maps(content: &str, pid: u32, name: &str, default_page_size_kb: u64) -> ProcessNumaInfo {
+/// when absent) and accumulated as KB directly. Lines marked `huge` (hugetlbfs
+/// mappings) that lack `kernelpagesize_kB` use `huge_page_size_kb` when given,
+/// so that `N0=10 huge` is ten huge pages rather than ten base pages. Other
+/// tokens (`anon=`, `dirty=`, `mapped=`, `interleave:` policies, file paths)
+/// do not affect the totals.
+pub fn parse_numa_maps(
+    content: &str,
+    pid: u32,
+    name: &str,
+    default_page_size_kb: u64,
+    huge_page_size_kb: Option<u64>,
+) -> ProcessNumaInfo {
     let mut kb_per_node: HashMap<u32, u64> = HashMap::new();
 
     for line in content.lines() {
-        let mut line_page_size_kb = default_page_size_kb;
+        let mut line_page_size_kb = None;
+        let mut huge = false;
         let mut line_nodes: Vec<(u32, u64)> = Vec::new();
 
         for token in line.split_whitespace() {
-            if let Some(eq_pos) = token.find('=') {
+            if token == "huge" {
+                huge = true;
+            } else if let Some(eq_pos) = token.find('=') {
                 let key = &token[..eq_pos];
                 let val = &token[eq_pos + 1..];
 
                 if key == "kernelpagesize_kB" {
                     if let Ok(kps) = val.parse::<u64>() {
-                        line_page_size_kb = kps;
+                        line_page_size_kb = Some(kps);
                     }
                 } else if let Some(node_str) = key.strip_prefix('N')
                     && let (Ok(node_id), Ok(pages)) =
@@ -103,8 +175,13 @@ pub fn parse_numa_maps(content: &str, pid: u32, name: &str, default_page_size_kb
             }
         }
 
+        let page_size_kb = match (line_page_size_kb, huge, huge_page_size_kb) {
+            (Some(kps), _, _) => kps,
+            (None, true, Some(huge_kb)) => huge_kb,
+            _ => default_page_size_kb,
+        };
         for (node_id, pages) in line_nodes {
-            *kb_per_node.entry(node_id).or_insert(0) += pages * line_page_size_kb;
+            *kb_per_node.entry(node_id).or_insert(0) += pages * page_size_kb;
         }
     }
 
@@ -128,9 +205,12 @@ pub fn cpu_to_numa_node(cpu_id: i32, numa_nodes: &[NumaNode]) -> Option<u32> {
 
 /// Discover NUMA topology by reading sysfs.
 /// sys_path should be "/sys/devices/system/node" (or a test path).
+/// `unaffined_gpus` lists GPUs without PCI NUMA affinity, with their VRAM; see
+/// [`assign_unaffined_hbm_nodes`].
 pub fn discover_numa_topology(
     sys_path: &str,
     gpu_map: &HashMap<u32, u32>,
+    unaffined_gpus: &[(u32, u64)],
 ) -> std::io::Result<Vec<NumaNode>> {
     let mut nodes = Vec::new();
 
@@ -160,18 +240,24 @@ pub fn discover_numa_topology(
         let cpulist_content = std::fs::read_to_string(&cpulist_path).unwrap_or_default();
         let cpus = parse_cpulist(&cpulist_content);
 
+        // Absent on some kernels and in containers with a partial /sys
+        let distance_content = std::fs::read_to_string(node_path.join("distance")).unwrap_or_default();
+        let distances = parse_distances(&distance_content);
+
         let mut node = NumaNode {
             id,
             memory_total_kb,
             memory_free_kb,
             cpus,
             node_type: NumaNodeType::Unknown,
+            distances,
         };
         node.node_type = classify_numa_node(&node, gpu_map);
         nodes.push(node);
     }
 
     nodes.sort_by_key(|n| n.id);
+    assign_unaffined_hbm_nodes(&mut nodes, unaffined_gpus);
     Ok(nodes)
 }
 
@@ -190,6 +276,13 @@ Node 0 MemUsed:         8192000 kB";
         assert_eq!(free, 8192000);
     }
 
+    #[test]
+    fn test_parse_distances() {
+        assert_eq!(parse_distances("10 21 21 10\n"), vec![10, 21, 21, 10]);
+        assert_eq!(parse_distances(""), Vec::<u32>::new());
+        assert_eq!(parse_distances("10 x 21"), Vec::<u32>::new());
+    }
+
     #[test]
     fn test_parse_cpulist_range() {
         assert_eq!(parse_cpulist("0-3,8-11"), vec![0, 1, 2, 3, 8, 9, 10, 11]);
@@ -213,6 +306,7 @@ Node 0 MemUsed:         8192000 kB";
             memory_free_kb: 8192000,
             cpus: vec![0, 1, 2, 3],
             node_type: NumaNodeType::Unknown,
+            distances: vec![],
         };
         let gpu_map = HashMap::new();
         assert_eq!(classify_numa_node(&node, &gpu_map), NumaNodeType::Cpu);
@@ -226,6 +320,7 @@ Node 0 MemUsed:         8192000 kB";
             memory_free_kb: 40960000,
             cpus: vec![],
             node_type: NumaNodeType::Unknown,
+            distances: vec![],
         };
         let mut gpu_map = HashMap::new();
         gpu_map.insert(2, 0); // node 2 -> GPU 0
@@ -243,17 +338,69 @@ Node 0 MemUsed:         8192000 kB";
             memory_free_kb: 0,
             cpus: vec![],
             node_type: NumaNodeType::Unknown,
+            distances: vec![],
         };
         let gpu_map = HashMap::new();
         assert_eq!(classify_numa_node(&node, &gpu_map), NumaNodeType::Unknown);
     }
 
+    fn node(id: u32, memory_total_kb: u64, cpus: Vec<u32>, node_type: NumaNodeType) -> NumaNode {
+        NumaNode { id, memory_total_kb, memory_free_kb: 0, cpus, node_type, distances: vec![] }
+    }
+
+    #[test]
+    fn test_assign_unaffined_hbm_nodes() {
+        let mut nodes = vec![
+            node(0, 1000, vec![0, 1], NumaNodeType::Cpu),
+            node(4, 800, vec![], NumaNodeType::Unknown),
+            node(2, 800, vec![], NumaNodeType::Unknown),
+            node(3, 0, vec![], NumaNodeType::Unknown),
+        ];
+        assign_unaffined_hbm_nodes(&mut nodes, &[(1, 820), (0, 820)]);
+        assert_eq!(nodes[0].node_type, NumaNodeType::Cpu);
+        assert_eq!(nodes[2].node_type, NumaNodeType::GpuHbm { gpu_index: 0 });
+        assert_eq!(nodes[1].node_type, NumaNodeType::GpuHbm { gpu_index: 1 });
+        // Memoryless nodes are never HBM
+        assert_eq!(nodes[3].node_type, NumaNodeType::Unknown);
+    }
+
+    #[test]
+    fn test_assign_unaffined_hbm_nodes_leaves_non_gpu_nodes_unknown() {
+        // A CXL memory expander or PMEM node: CPU-less with memory, but far
+        // from the size of the unaffined GPU's VRAM
+        let mut nodes = vec![
+            node(0, 1000, vec![0, 1], NumaNodeType::Cpu),
+            node(2, 4000, vec![], NumaNodeType::Unknown),
+            node(3, 790, vec![], NumaNodeType::Unknown),
+        ];
+        assign_unaffined_hbm_nodes(&mut nodes, &[(0, 820)]);
+        assert_eq!(nodes[1].node_type, NumaNodeType::Unknown);
+        assert_eq!(nodes[2].node_type, NumaNodeType::GpuHbm { gpu_index: 0 });
+
+        let mut nodes = vec![node(2, 4000, vec![], NumaNodeType::Unknown)];
+        assign_unaffined_hbm_nodes(&mut nodes, &[(0, 820)]);
+        assert_eq!(nodes[0].node_type, NumaNodeType::Unknown);
+    }
+
+    #[test]
+    fn test_assign_unaffined_hbm_nodes_skips_claimed_gpus() {
+        let mut nodes = vec![
+            node(1, 800, vec![], NumaNodeType::GpuHbm { gpu_index: 0 }),
+            node(2, 800, vec![], NumaNodeType::Unknown),
+        ];
+        assign_unaffined_hbm_nodes(&mut nodes, &[(0, 800)]);
+        assert_eq!(nodes[1].node_type, NumaNodeType::Unknown);
+
+        assign_unaffined_hbm_nodes(&mut nodes, &[(0, 800), (1, 800)]);
+        assert_eq!(nodes[1].node_type, NumaNodeType::GpuHbm { gpu_index: 1 });
+    }
+
     #[test]
     fn test_parse_numa_maps() {
         let content = "\
 00400000 default N0=10 N1=5
 00600000 default N0=3 N2=7";
-        let info = parse_numa_maps(content, 42, "test_proc", 4);
+        let info = parse_numa_maps(content, 42, "test_proc", 4, None);
         assert_eq!(info.pid, 42);
         assert_eq!(info.name, "test_proc");
         assert_eq!(info.kb_per_node.get(&0), Some(&52));  // 13 pages * 4 KB
@@ -268,14 +415,14 @@ Node 0 MemUsed:         8192000 kB";
 00400000 default N0=100
 00500000 default N0=200
 00600000 default N1=50";
-        let info = parse_numa_maps(content, 1, "proc", 4);
+        let info = parse_numa_maps(content, 1, "proc", 4, None);
         assert_eq!(info.kb_per_node.get(&0), Some(&1200));  // 300 pages * 4 KB
         assert_eq!(info.kb_per_node.get(&1), Some(&200));   // 50 pages * 4 KB
     }
 
     #[test]
     fn test_parse_numa_maps_empty() {
-        let info = parse_numa_maps("", 1, "empty", 4);
+        let info = parse_numa_maps("", 1, "empty", 4, None);
         assert_eq!(info.total_kb, 0);
         assert!(info.kb_per_node.is_empty());
     }
@@ -283,7 +430,7 @@ Node 0 MemUsed:         8192000 kB";
     #[test]
     fn test_total_kb_sum() {
         let content = "00400000 default N0=10 N1=20 N2=30";
-        let info = parse_numa_maps(content, 1, "proc", 4);
+        let info = parse_numa_maps(content, 1, "proc", 4, None);
         let manual_sum: u64 = info.kb_per_node.values().sum();
         assert_eq!(info.total_kb, manual_sum);
         assert_eq!(info.total_kb, 240);  // 60 pages * 4 KB
@@ -305,7 +452,7 @@ Node 0 MemUsed:         8192000 kB";
     fn test_parse_numa_maps_cpu_node_is_none() {
         // parse_numa_maps doesn't know about CPU scheduling, so cpu_node should be None
         let content = "00400000 default N0=10 N1=5";
-        let info = parse_numa_maps(content, 42, "test_proc", 4);
+        let info = parse_numa_maps(content, 42, "test_proc", 4, None);
         assert_eq!(info.cpu_node, None);
     }
 
@@ -317,7 +464,7 @@ Node 0 MemUsed:         8192000 kB";
         let content = "\
 00400000 default N0=8 kernelpagesize_kB=4
 00600000 default N0=512 kernelpagesize_kB=2048";
-        let info = parse_numa_maps(content, 1, "proc", 4);
+        let info = parse_numa_maps(content, 1, "proc", 4, None);
         // N0 = 8*4 + 512*2048 = 32 + 1,048,576 = 1,048,608 KB
         assert_eq!(info.kb_per_node.get(&0), Some(&1_048_608));
     }
@@ -328,7 +475,7 @@ Node 0 MemUsed:         8192000 kB";
 00400000 default N0=100 N1=200 kernelpagesize_kB=4
 00600000 default N0=200 kernelpagesize_kB=2048
 00800000 default N1=10 kernelpagesize_kB=64";
-        let info = parse_numa_maps(content, 1, "proc", 4);
+        let info = parse_numa_maps(content, 1, "proc", 4, None);
         // N0 = 100*4 + 200*2048 = 400 + 409,600 = 410,000 KB
         assert_eq!(info.kb_per_node.get(&0), Some(&410_000));
         // N1 = 200*4 + 10*64 = 800 + 640 = 1,440 KB
@@ -340,7 +487,7 @@ Node 0 MemUsed:         8192000 kB";
         // No kernelpagesize_kB, default is 64 (aarch64)
         let content = "\
 00400000 default N0=10 N1=5";
-        let info = parse_numa_maps(content, 1, "proc", 64);
+        let info = parse_numa_maps(content, 1, "proc", 64, None);
         assert_eq!(info.kb_per_node.get(&0), Some(&640));   // 10 * 64
         assert_eq!(info.kb_per_node.get(&1), Some(&320));   // 5 * 64
     }
@@ -348,7 +495,7 @@ Node 0 MemUsed:         8192000 kB";
     #[test]
     fn test_parse_numa_maps_hugepages_1gb() {
         let content = "7f000000 default N0=4 kernelpagesize_kB=1048576";
-        let info = parse_numa_maps(content, 1, "proc", 4);
+        let info = parse_numa_maps(content, 1, "proc", 4, None);
         // 4 * 1,048,576 = 4,194,304 KB
         assert_eq!(info.kb_per_node.get(&0), Some(&4_194_304));
     }
@@ -357,7 +504,7 @@ Node 0 MemUsed:         8192000 kB";
     fn test_parse_numa_maps_kernelpagesize_before_nodes() {
         // kernelpagesize_kB appears before N= tokens
         let content = "00400000 default kernelpagesize_kB=2048 N0=100";
-        let info = parse_numa_maps(content, 1, "proc", 4);
+        let info = parse_numa_maps(content, 1, "proc", 4, None);
         assert_eq!(info.kb_per_node.get(&0), Some(&204_800));  // 100 * 2048
     }
 
@@ -367,16 +514,38 @@ Node 0 MemUsed:         8192000 kB";
         let content = "\
 00400000 default kernelpagesize_kB=2048
 00600000 default N0=10 kernelpagesize_kB=4";
-        let info = parse_numa_maps(content, 1, "proc", 4);
+        let info = parse_numa_maps(content, 1, "proc", 4, None);
         assert_eq!(info.kb_per_node.get(&0), Some(&40));  // 10 * 4
         assert_eq!(info.total_kb, 40);
     }
 
+    #[test]
+    fn test_parse_numa_maps_huge_line_weighted() {
+        let content = "\
+00400000 interleave:0-1 anon=12 dirty=12 N0=6 N1=6 kernelpagesize_kB=4
+2aaaaac00000 default file=/dev/hugepages/buf huge dirty=10 N0=10";
+        // Without a huge page size the huge line falls back to the base page size
+        let info = parse_numa_maps(content, 1, "proc", 4, None);
+        assert_eq!(info.kb_per_node.get(&0), Some(&(6 * 4 + 10 * 4)));
+
+        let info = parse_numa_maps(content, 1, "proc", 4, Some(2048));
+        assert_eq!(info.kb_per_node.get(&0), Some(&(6 * 4 + 10 * 2048)));
+        assert_eq!(info.kb_per_node.get(&1), Some(&24));
+        assert_eq!(info.total_kb, 6 * 4 + 10 * 2048 + 24);
+    }
+
+    #[test]
+    fn test_parse_numa_maps_huge_line_prefers_kernelpagesize() {
+        let content = "7f0000000000 default huge N0=2 kernelpagesize_kB=1048576";
+        let info = parse_numa_maps(content, 1, "proc", 4, Some(2048));
+        assert_eq!(info.kb_per_node.get(&0), Some(&(2 * 1_048_576)));
+    }
+
     #[test]
     fn test_cpu_to_numa_node_found() {
         let nodes = vec![
-            NumaNode { id: 0, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu },
-            NumaNode { id: 1, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![4, 5, 6, 7], node_type: NumaNodeType::Cpu },
+            NumaNode { id: 0, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
+            NumaNode { id: 1, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![4, 5, 6, 7], node_type: NumaNodeType::Cpu, distances: vec![] },
         ];
         assert_eq!(cpu_to_numa_node(0, &nodes), Some(0));
         assert_eq!(cpu_to_numa_node(3, &nodes), Some(0));
@@ -387,7 +556,7 @@ Node 0 MemUsed:         8192000 kB";
     #[test]
     fn test_cpu_to_numa_node_not_found() {
         let nodes = vec![
-            NumaNode { id: 0, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
+            NumaNode { id: 0, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
         ];
         assert_eq!(cpu_to_numa_node(99, &nodes), None);
     }
@@ -396,8 +565,8 @@ Node 0 MemUsed:         8192000 kB";
     fn test_cpu_to_numa_node_gpu_hbm_skipped() {
         // GPU HBM nodes have no CPUs, so should never match
         let nodes = vec![
-            NumaNode { id: 0, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-            NumaNode { id: 2, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 } },
+            NumaNode { id: 0, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+            NumaNode { id: 2, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 }, distances: vec![] },
         ];
         assert_eq!(cpu_to_numa_node(0, &nodes), Some(0));
         assert_eq!(cpu_to_numa_node(5, &nodes), None);
@@ -407,13 +576,14 @@ Node 0 MemUsed:         8192000 kB";
     fn test_topology_sorted() {
         // We can't easily test discover_numa_topology without a real /sys,
         // but we test that the sort logic works by creating nodes and sorting
-        let mut nodes = vec![
+        let mut nodes = [
             NumaNode {
                 id: 2,
                 memory_total_kb: 0,
                 memory_free_kb: 0,
                 cpus: vec![],
                 node_type: NumaNodeType::Unknown,
+                distances: vec![],
             },
             NumaNode {
                 id: 0,
@@ -421,6 +591,7 @@ Node 0 MemUsed:         8192000 kB";
                 memory_free_kb: 0,
                 cpus: vec![0],
                 node_type: NumaNodeType::Cpu,
+                distances: vec![],
             },
             NumaNode {
                 id: 1,
@@ -428,6 +599,7 @@ Node 0 MemUsed:         8192000 kB";
                 memory_free_kb: 0,
                 cpus: vec![1],
                 node_type: NumaNodeType::Cpu,
+                distances: vec![],
             },
         ];
         nodes.sort_by_key(|n| n.id);
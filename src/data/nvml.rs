@@ -0,0 +1,145 @@
+//! NVIDIA GPUs through the NVML library (`--features nvml`), read in-process
+//! instead of spawning nvidia-smi on every refresh. When libnvidia-ml cannot
+//! be loaded or initialised, callers fall back to the nvidia-smi CSV path.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use nvml_wrapper::Nvml;
+use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+use nvml_wrapper::enums::device::UsedGpuMemory;
+use nvml_wrapper::error::NvmlError;
+
+use super::types::{GpuDevice, GpuProcessInfo};
+
+/// NVML_DEVICE_MIG_ENABLE
+const MIG_ENABLED: u32 = 1;
+
+/// Longest process name NVML is asked for, as nvidia-smi does.
+const PROCESS_NAME_LEN: usize = 64;
+
+/// An initialised NVML library handle.
+pub struct NvmlBackend {
+    nvml: Nvml,
+}
+
+impl NvmlBackend {
+    /// Load libnvidia-ml and initialise NVML. None when the library is not
+    /// installed or the driver is not loaded.
+    pub fn new() -> Option<Self> {
+        Nvml::init().ok().map(|nvml| Self { nvml })
+    }
+
+    /// All GPUs, with the fields of `nvidia-smi --query-gpu`. Optional
+    /// readings the device does not support (temperature, reserved memory,
+    /// utilisation) are left None, as on the CSV path.
+    pub fn devices(&self) -> Result<Vec<GpuDevice>, NvmlError> {
+        let mut devices = Vec::new();
+        for index in 0..self.nvml.device_count()? {
+            let device = self.nvml.device_by_index(index)?;
+            let memory = device.memory_info()?;
+            let utilization = device.utilization_rates().ok();
+            devices.push(GpuDevice {
+                index,
+                name: device.name()?,
+                memory_total_kb: memory.total / 1024,
+                memory_used_kb: memory.used / 1024,
+                memory_free_kb: memory.free / 1024,
+                numa_node_id: None, // filled later by get_gpu_numa_mapping
+                temperature: device.temperature(TemperatureSensor::Gpu).ok(),
+                pci_bus_id: device.pci_info()?.bus_id,
+                memory_reserved_kb: Some(memory.reserved / 1024),
+                mig_instances: Vec::new(),
+                utilization_percent: utilization.as_ref().map(|u| u.gpu),
+                memory_utilization_percent: utilization.as_ref().map(|u| u.memory),
+            });
+        }
+        Ok(devices)
+    }
+
+    /// Indices of GPUs with MIG mode currently enabled. Devices that do not
+    /// support MIG are left out.
+    pub fn mig_enabled(&self) -> Vec<u32> {
+        let count = self.nvml.device_count().unwrap_or(0);
+        (0..count)
+            .filter(|&index| {
+                self.nvml
+                    .device_by_index(index)
+                    .and_then(|device| device.mig_mode())
+                    .is_ok_and(|mode| mode.current == MIG_ENABLED)
+            })
+            .collect()
+    }
+
+    /// Compute processes on every GPU, as `--query-compute-apps` lists them.
+    /// With `utilization`, each process also gets its latest SM and memory
+    /// utilisation sample, which is what `nvidia-smi pmon` reports.
+    pub fn processes(&self, utilization: bool) -> Result<Vec<GpuProcessInfo>, NvmlError> {
+        let mut results = Vec::new();
+        for index in 0..self.nvml.device_count()? {
+            let device = self.nvml.device_by_index(index)?;
+            // Processes that have been idle for the whole sample window
+            // have no sample and keep None
+            let samples: HashMap<u32, (u64, u32, u32)> = if utilization {
+                latest_samples(device.process_utilization_stats(None).unwrap_or_default().into_iter().map(|s| {
+                    (s.pid, (s.timestamp, s.sm_util, s.mem_util))
+                }))
+            } else {
+                HashMap::new()
+            };
+            for proc in device.running_compute_processes()? {
+                let used_bytes = match proc.used_gpu_memory {
+                    UsedGpuMemory::Used(bytes) => bytes,
+                    UsedGpuMemory::Unavailable => 0,
+                };
+                let sample = samples.get(&proc.pid);
+                results.push(GpuProcessInfo {
+                    pid: proc.pid,
+                    // nvidia-smi's placeholder for PIDs it cannot name
+                    name: self
+                        .nvml
+                        .sys_process_name(proc.pid, PROCESS_NAME_LEN)
+                        .unwrap_or_else(|_| "[Not Found]".to_string()),
+                    gpu_index: index,
+                    gpu_memory_used_kb: used_bytes / 1024,
+                    sm_util: sample.map(|&(_, sm, _)| sm),
+                    mem_util: sample.map(|&(_, _, mem)| mem),
+                });
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Keep the newest of each PID's (timestamp, sm, mem) samples. NVML returns
+/// every sample buffered over the last few seconds.
+fn latest_samples(samples: impl Iterator<Item = (u32, (u64, u32, u32))>) -> HashMap<u32, (u64, u32, u32)> {
+    let mut latest: HashMap<u32, (u64, u32, u32)> = HashMap::new();
+    for (pid, sample) in samples {
+        let entry = latest.entry(pid).or_insert(sample);
+        if sample.0 > entry.0 {
+            *entry = sample;
+        }
+    }
+    latest
+}
+
+/// The shared backend, initialised on first use. Initialisation loads the
+/// library and is only attempted once: a missing library stays missing.
+pub fn backend() -> Option<&'static NvmlBackend> {
+    static BACKEND: OnceLock<Option<NvmlBackend>> = OnceLock::new();
+    BACKEND.get_or_init(NvmlBackend::new).as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_samples_keeps_newest_per_pid() {
+        let samples = vec![(10, (100, 5, 1)), (10, (300, 80, 20)), (10, (200, 40, 9)), (11, (150, 0, 0))];
+        let latest = latest_samples(samples.into_iter());
+        assert_eq!(latest[&10], (300, 80, 20));
+        assert_eq!(latest[&11], (150, 0, 0));
+    }
+}
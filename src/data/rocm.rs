@@ -0,0 +1,210 @@
+//! AMD GPUs via `rocm-smi --json`, used when nvidia-smi is unavailable.
+//! rocm-smi reports one object per card ("card0", "card1", ...) whose keys
+//! are human-readable labels; sizes are bytes, all values are strings.
+
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use serde_json::{Map, Value};
+
+use super::types::{GpuDevice, GpuProcessInfo};
+use super::verbose;
+
+const PIDS_QUERY: &str = "rocm-smi --showpids";
+
+/// Build a rocm-smi invocation with a fixed C locale, as for nvidia-smi.
+fn rocm_smi_command(args: &[&str]) -> Command {
+    let mut cmd = Command::new("rocm-smi");
+    cmd.args(args).env("LC_ALL", "C");
+    cmd
+}
+
+/// Run rocm-smi with given arguments and return stdout.
+pub fn run_rocm_smi(args: &[&str]) -> Result<String, std::io::Error> {
+    let output = rocm_smi_command(args).output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "rocm-smi failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Whether rocm-smi runs and lists at least one card. Probed once: it is
+/// only consulted when nvidia-smi is missing, and is slow to start.
+pub fn check_rocm_smi_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        run_rocm_smi(&["--showmeminfo", "vram", "--json"]).is_ok_and(|json| !cards(&json).is_empty())
+    })
+}
+
+/// Cards of a rocm-smi JSON document as (index, fields), sorted by index.
+/// Non-card entries such as "system" are skipped.
+fn cards(json: &str) -> Vec<(u32, Map<String, Value>)> {
+    let Ok(Value::Object(root)) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+    let mut cards: Vec<(u32, Map<String, Value>)> = root
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let index = key.strip_prefix("card")?.parse().ok()?;
+            match value {
+                Value::Object(fields) => Some((index, fields)),
+                _ => None,
+            }
+        })
+        .collect();
+    cards.sort_by_key(|(index, _)| *index);
+    cards
+}
+
+/// The first of `keys` present in `fields`, as a trimmed string.
+fn field<'a>(fields: &'a Map<String, Value>, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|key| fields.get(*key)?.as_str()).map(str::trim)
+}
+
+/// A numeric field, accepting "36.0" for integer values.
+fn number(fields: &Map<String, Value>, keys: &[&str]) -> Option<u64> {
+    let value = field(fields, keys)?;
+    value.parse().ok().or_else(|| value.parse::<f64>().ok().filter(|v| *v >= 0.0).map(|v| v as u64))
+}
+
+/// Parse `rocm-smi --showmeminfo vram --json` into devices. `details` is the
+/// optional `--showproductname --showtemp --showbus --showuse --showmemuse
+/// --json` output; fields it lacks (older rocm-smi, unsupported sensors)
+/// are left None or empty.
+pub fn parse_rocm_devices_json(meminfo: &str, details: Option<&str>) -> Vec<GpuDevice> {
+    let details: HashMap<u32, Map<String, Value>> = details.map(cards).unwrap_or_default().into_iter().collect();
+    cards(meminfo)
+        .into_iter()
+        .filter_map(|(index, fields)| {
+            let total = number(&fields, &["VRAM Total Memory (B)"])?;
+            let used = number(&fields, &["VRAM Total Used Memory (B)"]).unwrap_or(0);
+            let extra = details.get(&index);
+            let detail = |keys: &[&str]| extra.and_then(|f| field(f, keys));
+            let detail_number = |keys: &[&str]| extra.and_then(|f| number(f, keys));
+            Some(GpuDevice {
+                index,
+                name: detail(&["Card series", "Card SKU", "Card model"]).unwrap_or("AMD GPU").to_string(),
+                memory_total_kb: total / 1024,
+                memory_used_kb: used / 1024,
+                memory_free_kb: total.saturating_sub(used) / 1024,
+                numa_node_id: None, // filled later by get_gpu_numa_mapping
+                temperature: detail_number(&["Temperature (Sensor edge) (C)", "Temperature (Sensor junction) (C)"])
+                    .map(|t| t as u32),
+                // sysfs names PCI devices in lower case
+                pci_bus_id: detail(&["PCI Bus"]).unwrap_or_default().to_lowercase(),
+                memory_reserved_kb: None,
+                mig_instances: Vec::new(),
+                utilization_percent: detail_number(&["GPU use (%)"]).map(|v| v as u32),
+                memory_utilization_percent: detail_number(&["GPU Memory Allocated (VRAM%)", "GPU memory use (%)"])
+                    .map(|v| v as u32),
+            })
+        })
+        .collect()
+}
+
+/// Parse `rocm-smi --showpids --json`, whose "system" entries map "PID<n>"
+/// to "name, gpu count, VRAM bytes, SDMA bytes, CU occupancy". rocm-smi
+/// reports one VRAM figure per process, so a process is listed once, on the
+/// first GPU `--showpidgpus` names for it (GPU 0 if unknown).
+pub fn parse_rocm_pids_json(pids: &str, pid_gpus: Option<&str>) -> Vec<GpuProcessInfo> {
+    let system = |json: &str| match serde_json::from_str::<Value>(json) {
+        Ok(Value::Object(mut root)) => match root.remove("system") {
+            Some(Value::Object(system)) => system,
+            _ => Map::new(),
+        },
+        _ => Map::new(),
+    };
+    let gpus = pid_gpus.map(system).unwrap_or_default();
+    let mut results: Vec<GpuProcessInfo> = system(pids)
+        .iter()
+        .filter_map(|(key, value)| {
+            let pid = key.strip_prefix("PID")?.parse::<u32>().ok()?;
+            let line = value.as_str()?;
+            let fields: Vec<&str> = line.split(", ").map(str::trim).collect();
+            let (Some(name), Some(vram)) = (fields.first(), fields.get(2)) else {
+                verbose::skipped(PIDS_QUERY, line, &format!("expected 5 fields, got {}", fields.len()));
+                return None;
+            };
+            let Ok(vram_bytes) = vram.parse::<u64>() else {
+                verbose::skipped(PIDS_QUERY, line, &format!("bad VRAM value `{}`", vram));
+                return None;
+            };
+            let gpu_index = gpus
+                .get(key)
+                .and_then(Value::as_str)
+                .and_then(|list| {
+                    list.split(|c: char| !c.is_ascii_digit())
+                        .find_map(|n| n.parse().ok())
+                })
+                .unwrap_or(0);
+            Some(GpuProcessInfo {
+                pid,
+                name: name.to_string(),
+                gpu_index,
+                gpu_memory_used_kb: vram_bytes / 1024,
+                sm_util: None,
+                mem_util: None,
+            })
+        })
+        .collect();
+    results.sort_by_key(|p| p.pid);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // rocm-smi --json layout for a two-GPU MI210 box
+    const MEMINFO: &str = r#"{"card0": {"VRAM Total Memory (B)": "68702699520", "VRAM Total Used Memory (B)": "21474836480"}, "card1": {"VRAM Total Memory (B)": "68702699520", "VRAM Total Used Memory (B)": "10960896"}}"#;
+    const DETAILS: &str = r#"{"card0": {"Card series": "Instinct MI210", "Card model": "0x0c34", "Card vendor": "Advanced Micro Devices, Inc. [AMD/ATI]", "Card SKU": "D67301", "Temperature (Sensor edge) (C)": "41.0", "Temperature (Sensor junction) (C)": "44.0", "PCI Bus": "0000:C3:00.0", "GPU use (%)": "87", "GPU Memory Allocated (VRAM%)": "31"}, "card1": {"Card series": "Instinct MI210", "Temperature (Sensor edge) (C)": "N/A", "PCI Bus": "0000:83:00.0", "GPU use (%)": "0", "GPU Memory Allocated (VRAM%)": "0"}}"#;
+    const PIDS: &str = r#"{"system": {"PID3476": "python3, 2, 21474836480, 0, 0", "PID9120": "rccl-tests, 1, 4194304, 0, unknown"}}"#;
+    const PID_GPUS: &str = r#"{"system": {"PID3476": "[0, 1]", "PID9120": "[1]"}}"#;
+
+    #[test]
+    fn test_parse_rocm_devices_two_gpus() {
+        let devices = parse_rocm_devices_json(MEMINFO, Some(DETAILS));
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].index, 0);
+        assert_eq!(devices[0].name, "Instinct MI210");
+        assert_eq!(devices[0].memory_total_kb, 67_092_480);
+        assert_eq!(devices[0].memory_used_kb, 20_971_520);
+        assert_eq!(devices[0].memory_free_kb, 67_092_480 - 20_971_520);
+        assert_eq!(devices[0].temperature, Some(41));
+        assert_eq!(devices[0].pci_bus_id, "0000:c3:00.0");
+        assert_eq!(devices[0].utilization_percent, Some(87));
+        assert_eq!(devices[0].memory_utilization_percent, Some(31));
+        // "N/A" sensor
+        assert_eq!(devices[1].temperature, None);
+        assert_eq!(devices[1].memory_used_kb, 10_704);
+    }
+
+    #[test]
+    fn test_parse_rocm_devices_without_details() {
+        let devices = parse_rocm_devices_json(MEMINFO, None);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[1].name, "AMD GPU");
+        assert_eq!(devices[1].pci_bus_id, "");
+        assert_eq!(devices[1].utilization_percent, None);
+        assert!(parse_rocm_devices_json("WARNING: No AMD GPUs specified", None).is_empty());
+    }
+
+    #[test]
+    fn test_parse_rocm_pids() {
+        let procs = parse_rocm_pids_json(PIDS, Some(PID_GPUS));
+        assert_eq!(procs.len(), 2);
+        assert_eq!((procs[0].pid, procs[0].name.as_str()), (3476, "python3"));
+        assert_eq!(procs[0].gpu_index, 0);
+        assert_eq!(procs[0].gpu_memory_used_kb, 20_971_520);
+        assert_eq!((procs[1].pid, procs[1].gpu_index, procs[1].gpu_memory_used_kb), (9120, 1, 4096));
+
+        // Without --showpidgpus everything lands on GPU 0
+        assert!(parse_rocm_pids_json(PIDS, None).iter().all(|p| p.gpu_index == 0));
+        assert!(parse_rocm_pids_json(r#"{"system": {}}"#, None).is_empty());
+    }
+}
@@ -1,25 +1,174 @@
 use super::types::*;
 
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+
 use proc_mounts::SwapIter;
 use procfs::{self, Current, Meminfo};
 
+/// List swap devices via `proc_mounts`, falling back to parsing /proc/swaps
+/// directly when the crate's parser fails on an unusual kernel.
 pub fn get_swap_devices(unit: SizeUnits) -> std::io::Result<Vec<InfoSwap>> {
+    let mut devices = match get_swap_devices_proc_mounts(unit.clone()) {
+        Ok(devices) => devices,
+        Err(_) => {
+            let content = std::fs::read("/proc/swaps")?;
+            parse_proc_swaps(&content, unit)
+        }
+    };
+    for device in &mut devices {
+        // Devices without a sysfs entry (e.g. swapfiles on btrfs, which uses
+        // anonymous device numbers) keep Unknown media and no queue settings
+        let Some(sys_dev) = sysfs_block_dir(&device.path) else {
+            continue;
+        };
+        let name = sys_dev.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let rotational = read_queue_attr(&sys_dev, "rotational").map(|v| v.trim() == "1");
+        device.media = classify_media(name, rotational);
+        device.scheduler = read_queue_attr(&sys_dev, "scheduler").and_then(|v| parse_scheduler(&v));
+        device.read_ahead_kb = read_queue_attr(&sys_dev, "read_ahead_kb").and_then(|v| v.trim().parse().ok());
+    }
+    Ok(devices)
+}
+
+/// /sys/dev/block/MAJ:MIN (resolved) of the block device backing a swap
+/// partition or swapfile.
+fn sysfs_block_dir(path: &std::path::Path) -> Option<std::path::PathBuf> {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+    let meta = std::fs::metadata(path).ok()?;
+    let dev = if meta.file_type().is_block_device() { meta.rdev() } else { meta.dev() };
+    let (major, minor) = dev_major_minor(dev);
+    std::path::Path::new(&format!("/sys/dev/block/{}:{}", major, minor)).canonicalize().ok()
+}
+
+/// A queue/ attribute of a block device. Partitions have no queue/ of their
+/// own; it lives on the parent disk.
+fn read_queue_attr(sys_dev: &std::path::Path, attr: &str) -> Option<String> {
+    std::fs::read_to_string(sys_dev.join("queue").join(attr))
+        .or_else(|_| std::fs::read_to_string(sys_dev.join("../queue").join(attr)))
+        .ok()
+}
+
+/// Active scheduler from queue/scheduler, e.g. "mq-deadline kyber [bfq] none"
+/// -> "bfq". Queues with a single choice list it without brackets.
+fn parse_scheduler(content: &str) -> Option<String> {
+    let choices: Vec<&str> = content.split_whitespace().collect();
+    if let Some(active) = choices.iter().find_map(|c| c.strip_prefix('[').and_then(|c| c.strip_suffix(']'))) {
+        return Some(active.to_string());
+    }
+    match choices.as_slice() {
+        [only] => Some(only.to_string()),
+        _ => None,
+    }
+}
+
+/// Split a Linux `dev_t` into (major, minor), as glibc's gnu_dev_major/minor.
+fn dev_major_minor(dev: u64) -> (u64, u64) {
+    let major = ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff);
+    let minor = (dev & 0xff) | ((dev >> 12) & !0xff);
+    (major, minor)
+}
+
+fn classify_media(block_name: &str, rotational: Option<bool>) -> MediaClass {
+    if block_name.starts_with("nvme") {
+        return MediaClass::Nvme;
+    }
+    if block_name.starts_with("zram") {
+        return MediaClass::Zram;
+    }
+    match rotational {
+        Some(true) => MediaClass::Hdd,
+        Some(false) => MediaClass::Ssd,
+        None => MediaClass::Unknown,
+    }
+}
+
+fn get_swap_devices_proc_mounts(unit: SizeUnits) -> std::io::Result<Vec<InfoSwap>> {
     let mut out = Vec::new();
     for swap in SwapIter::new()? {
         let s = swap?;
         out.push(InfoSwap {
             name: s.source.to_string_lossy().into_owned(),
+            path: s.source,
             kind: s.kind.to_string_lossy().into_owned(),
-            size_kb: convert_swap(s.size as u64, unit.to_owned()),
-            used_kb: convert_swap(s.used as u64, unit.to_owned()),
+            size_kb: convert_swap(s.size as u64, unit.to_owned(), UnitBase::Binary),
+            used_kb: convert_swap(s.used as u64, unit.to_owned(), UnitBase::Binary),
             priority: s.priority,
+            media: MediaClass::Unknown,
+            scheduler: None,
+            read_ahead_kb: None,
         });
     }
     Ok(out)
 }
 
+/// Parse /proc/swaps content. Columns: Filename Type Size Used Priority,
+/// with Size and Used in KB. Fields are taken from the end of the line so
+/// that the filename is whatever precedes them. Filenames are raw bytes and
+/// need not be UTF-8, so the content is parsed as bytes.
+pub fn parse_proc_swaps(content: impl AsRef<[u8]>, unit: SizeUnits) -> Vec<InfoSwap> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut out = Vec::new();
+    for line in content.as_ref().split(|&b| b == b'\n') {
+        if line.starts_with(b"Filename") {
+            continue;
+        }
+        let fields: Vec<&[u8]> = line.split(u8::is_ascii_whitespace).filter(|f| !f.is_empty()).collect();
+        if fields.len() < 5 {
+            continue;
+        }
+        let n = fields.len();
+        let (Some(size), Some(used), Some(priority)) = (
+            parse_field::<u64>(fields[n - 3]),
+            parse_field::<u64>(fields[n - 2]),
+            parse_field::<isize>(fields[n - 1]),
+        ) else {
+            continue;
+        };
+        let raw_name = unescape_space(&fields[..n - 4].join(&b' '));
+        let path = std::path::PathBuf::from(std::ffi::OsStr::from_bytes(&raw_name));
+        out.push(InfoSwap {
+            name: path.to_string_lossy().into_owned(),
+            path,
+            kind: String::from_utf8_lossy(fields[n - 4]).into_owned(),
+            size_kb: convert_swap(size, unit.clone(), UnitBase::Binary),
+            used_kb: convert_swap(used, unit.clone(), UnitBase::Binary),
+            priority,
+            media: MediaClass::Unknown,
+            scheduler: None,
+            read_ahead_kb: None,
+        });
+    }
+    out
+}
+
+fn parse_field<T: std::str::FromStr>(field: &[u8]) -> Option<T> {
+    std::str::from_utf8(field).ok()?.parse().ok()
+}
+
+/// Undo the kernel's `\040` escaping of spaces in /proc/swaps filenames.
+fn unescape_space(name: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(&b) = rest.first() {
+        if rest.starts_with(b"\\040") {
+            out.push(b' ');
+            rest = &rest[4..];
+        } else {
+            out.push(b);
+            rest = &rest[1..];
+        }
+    }
+    out
+}
+
 pub fn get_processes_using_swap(unit: SizeUnits) -> Result<Vec<ProcessSwapInfo>, SwapDataError> {
     let mut swap_processes = Vec::new();
+    let mut ns_cache = MNT_NS_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let ns_cache = ns_cache.get_or_insert_with(HashMap::new);
+    let mut seen = HashSet::new();
 
     for process in (procfs::process::all_processes()?).flatten() {
         let pid = process.pid;
@@ -27,27 +176,73 @@ pub fn get_processes_using_swap(unit: SizeUnits) -> Result<Vec<ProcessSwapInfo>,
             && let Some(swap_kb) = status.vmswap
             && swap_kb > 0
         {
-            let (name, last_cpu) = match process.stat() {
-                Ok(stat) => (stat.comm, stat.processor),
-                Err(_) => ("unknown".to_string(), None),
+            let (name, last_cpu, start_time) = match process.stat() {
+                Ok(stat) => (stat.comm, stat.processor, Some(stat.starttime)),
+                Err(_) => ("unknown".to_string(), None, None),
+            };
+            let mnt_ns = match start_time {
+                Some(start_time) => {
+                    seen.insert((pid, start_time));
+                    *ns_cache.entry((pid, start_time)).or_insert_with(|| read_mnt_ns(pid))
+                }
+                None => read_mnt_ns(pid),
             };
-            let swap_size = convert_swap(swap_kb, unit.clone());
+            let swap_size = convert_swap(swap_kb, unit.clone(), UnitBase::Binary);
             let info = ProcessSwapInfo {
                 pid: pid as u32,
                 name,
                 swap_size,
                 last_cpu,
+                rss_kb: status.vmrss,
+                mnt_ns,
+                count: 1,
             };
             swap_processes.push(info);
         }
     }
+    ns_cache.retain(|key, _| seen.contains(key));
 
     Ok(swap_processes)
 }
 
+/// Mount namespace per (pid, start time). A process rarely changes namespace,
+/// so the link is read once per process instead of on every refresh; the
+/// start time keeps a reused PID from inheriting a stale entry.
+static MNT_NS_CACHE: Mutex<Option<MntNsCache>> = Mutex::new(None);
+
+type MntNsCache = HashMap<(i32, u64), Option<u64>>;
+
+fn read_mnt_ns(pid: i32) -> Option<u64> {
+    let link = std::fs::read_link(format!("/proc/{}/ns/mnt", pid)).ok()?;
+    parse_ns_link(&link.to_string_lossy())
+}
+
+/// Inode from a namespace link target such as "mnt:[4026531841]".
+fn parse_ns_link(target: &str) -> Option<u64> {
+    let (_, rest) = target.split_once(":[")?;
+    rest.strip_suffix(']')?.parse().ok()
+}
+
+/// Mount namespace of the host: PID 1's, or our own when PID 1's link is
+/// unreadable (unprivileged). Read once.
+pub fn host_mnt_ns() -> Option<u64> {
+    static HOST_MNT_NS: OnceLock<Option<u64>> = OnceLock::new();
+    *HOST_MNT_NS.get_or_init(|| {
+        read_mnt_ns(1).or_else(|| {
+            let link = std::fs::read_link("/proc/self/ns/mnt").ok()?;
+            parse_ns_link(&link.to_string_lossy())
+        })
+    })
+}
+
 pub fn find_mount_device(path: &std::path::Path) -> Option<String> {
     let abs_path = path.canonicalize().ok()?;
 
+    // A zvol is a /dev/zdN block device; name it by its /dev/zvol/<pool>/<vol> link
+    if let Some(zvol) = find_zvol_name(&abs_path) {
+        return Some(format!("zvol:{}", zvol));
+    }
+
     let mountinfo = procfs::process::Process::myself()
         .and_then(|p| p.mountinfo())
         .ok()?;
@@ -57,22 +252,347 @@ pub fn find_mount_device(path: &std::path::Path) -> Option<String> {
         .filter(|m| abs_path.starts_with(&m.mount_point))
         .max_by_key(|m| m.mount_point.components().count())?;
 
-    Some(if best_mount.fs_type == "devtmpfs" {
-        "RAM".to_owned()
-    } else {
-        best_mount.mount_source?
-    })
+    mount_source_label(&best_mount)
+}
+
+/// Fill `sources` with the mount source of each swap device, keyed by device
+/// name, so rendering never resolves paths. Names already cached are not
+/// resolved again and devices no longer listed are dropped; clear the map to
+/// force a re-read. Unresolvable devices show as "RAM", like zram.
+pub fn resolve_device_sources(
+    sources: &mut HashMap<String, String>,
+    devices: &[InfoSwap],
+    mut resolve: impl FnMut(&std::path::Path) -> Option<String>,
+) {
+    sources.retain(|name, _| devices.iter().any(|d| &d.name == name));
+    for device in devices {
+        if !sources.contains_key(&device.name) {
+            let source = resolve(&device.path).unwrap_or_else(|| "RAM".into());
+            sources.insert(device.name.clone(), source);
+        }
+    }
+}
+
+/// Display name for the source of a mount. Btrfs subvolumes share one block
+/// device, so the subvolume is appended as `device[/subvol]` (as findmnt does);
+/// ZFS sources are dataset names, not devices, so they get a `zfs:` prefix.
+pub fn mount_source_label(mount: &procfs::process::MountInfo) -> Option<String> {
+    let source = mount.mount_source.clone();
+    match mount.fs_type.as_str() {
+        "devtmpfs" => Some("RAM".to_owned()),
+        "btrfs" if mount.root != "/" => Some(format!("{}[{}]", source?, mount.root)),
+        "zfs" => Some(format!("zfs:{}", source?)),
+        _ => source,
+    }
+}
+
+fn find_zvol_name(dev: &std::path::Path) -> Option<String> {
+    let is_zd = dev
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with("zd") && dev.starts_with("/dev"));
+    if !is_zd {
+        return None;
+    }
+    find_link_to(std::path::Path::new("/dev/zvol"), dev)
+        .and_then(|link| zvol_name_from_link(&link))
+}
+
+/// Depth-first search under `dir` for a symlink resolving to `target`.
+fn find_link_to(dir: &std::path::Path, target: &std::path::Path) -> Option<std::path::PathBuf> {
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        let file_type = entry.file_type().ok()?;
+        if file_type.is_dir() {
+            if let Some(found) = find_link_to(&path, target) {
+                return Some(found);
+            }
+        } else if file_type.is_symlink() && path.canonicalize().ok().as_deref() == Some(target) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// "/dev/zvol/rpool/swap" -> "rpool/swap"
+fn zvol_name_from_link(link: &std::path::Path) -> Option<String> {
+    link.strip_prefix("/dev/zvol")
+        .ok()
+        .and_then(|rest| rest.to_str())
+        .filter(|rest| !rest.is_empty())
+        .map(str::to_owned)
+}
+
+/// Parse the cumulative `pswpin`/`pswpout` page counters from /proc/vmstat content.
+pub fn parse_vmstat_swap(content: &str) -> (Option<u64>, Option<u64>) {
+    let mut swap_in = None;
+    let mut swap_out = None;
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next().and_then(|v| v.parse::<u64>().ok())) {
+            (Some("pswpin"), Some(v)) => swap_in = Some(v),
+            (Some("pswpout"), Some(v)) => swap_out = Some(v),
+            _ => {}
+        }
+    }
+    (swap_in, swap_out)
+}
+
+/// Parse the `full avg10` percentage from /proc/pressure/memory content.
+/// Expects lines like "full avg10=1.23 avg60=0.50 avg300=0.10 total=12345"
+pub fn parse_psi_full_avg10(content: &str) -> Option<f64> {
+    let line = content.lines().find(|l| l.starts_with("full "))?;
+    line.split_whitespace()
+        .find_map(|token| token.strip_prefix("avg10="))
+        .and_then(|v| v.parse().ok())
 }
 
 pub fn get_chart_info(unit: SizeUnits) -> Result<SwapUpdate, SwapDataError> {
     let meminfo = Meminfo::current()?;
 
     let total_swap_kb = meminfo.swap_total / 1024;
-    let used_swap_kb = meminfo.swap_total.saturating_sub(meminfo.swap_free) / 1024;
+    let (used_swap_kb, torn_read) = used_swap_kb(meminfo.swap_total, meminfo.swap_free);
+
+    // Both files are optional: vmstat may be restricted, PSI needs CONFIG_PSI
+    let (swap_in_pages, swap_out_pages) = std::fs::read_to_string("/proc/vmstat")
+        .map(|c| parse_vmstat_swap(&c))
+        .unwrap_or((None, None));
+    let memory_full_avg10 = std::fs::read_to_string("/proc/pressure/memory")
+        .ok()
+        .and_then(|c| parse_psi_full_avg10(&c));
+    let swappiness = std::fs::read_to_string("/proc/sys/vm/swappiness")
+        .ok()
+        .and_then(|c| c.trim().parse().ok());
 
     Ok(SwapUpdate {
         swap_devices: get_swap_devices(unit)?,
         total_swap: total_swap_kb,
         used_swap: used_swap_kb,
+        swap_in_pages,
+        swap_out_pages,
+        page_size_kb: Some(procfs::page_size() / 1024),
+        memory_full_avg10,
+        swap_cached: Some(meminfo.swap_cached / 1024),
+        mem_total_kb: Some(meminfo.mem_total / 1024),
+        mem_available_kb: meminfo.mem_available.map(|bytes| bytes / 1024),
+        swappiness,
+        torn_read,
     })
 }
+
+/// Used swap in KB from SwapTotal and SwapFree in bytes, clamped to 0, and
+/// whether SwapFree exceeded SwapTotal (a torn /proc/meminfo read).
+fn used_swap_kb(total_bytes: u64, free_bytes: u64) -> (u64, bool) {
+    (total_bytes.saturating_sub(free_bytes) / 1024, free_bytes > total_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use procfs::process::MountInfo;
+
+    #[test]
+    fn test_used_swap_kb_flags_free_above_total() {
+        assert_eq!(used_swap_kb(4096 * 1024, 1024 * 1024), (3072, false));
+        assert_eq!(used_swap_kb(0, 0), (0, false));
+        // Torn read: clamped rather than wrapping, and flagged
+        assert_eq!(used_swap_kb(1024 * 1024, 1025 * 1024), (0, true));
+    }
+
+    #[test]
+    fn test_parse_ns_link() {
+        assert_eq!(parse_ns_link("mnt:[4026531841]"), Some(4026531841));
+        assert_eq!(parse_ns_link("mnt:[]"), None);
+        assert_eq!(parse_ns_link("/proc/1/ns/mnt"), None);
+    }
+
+    #[test]
+    fn test_parse_scheduler() {
+        assert_eq!(parse_scheduler("mq-deadline kyber [bfq] none\n").as_deref(), Some("bfq"));
+        assert_eq!(parse_scheduler("[none] mq-deadline\n").as_deref(), Some("none"));
+        assert_eq!(parse_scheduler("none\n").as_deref(), Some("none"));
+        assert_eq!(parse_scheduler("mq-deadline none"), None);
+        assert_eq!(parse_scheduler(""), None);
+    }
+
+    #[test]
+    fn test_classify_media() {
+        assert_eq!(classify_media("nvme0n1p3", Some(false)), MediaClass::Nvme);
+        assert_eq!(classify_media("sda2", Some(true)), MediaClass::Hdd);
+        assert_eq!(classify_media("sdb1", Some(false)), MediaClass::Ssd);
+        assert_eq!(classify_media("zram0", Some(false)), MediaClass::Zram);
+        assert_eq!(classify_media("loop0", None), MediaClass::Unknown);
+    }
+
+    #[test]
+    fn test_dev_major_minor() {
+        // 8:2 (sda2) and 259:3 (nvme partitions use the extended major range)
+        assert_eq!(dev_major_minor(0x802), (8, 2));
+        assert_eq!(dev_major_minor((259 << 8) | 3), (259, 3));
+        // minor > 255 spills into the high bits
+        assert_eq!(dev_major_minor((0x100 << 12) | (8 << 8)), (8, 256));
+    }
+
+    #[test]
+    fn test_mount_source_label_btrfs_subvolume() {
+        let mount = MountInfo::from_line(
+            "45 1 0:38 /@swap /swap rw,noatime shared:3 - btrfs /dev/nvme0n1p2 rw,ssd,space_cache=v2,subvolid=260,subvol=/@swap",
+        )
+        .unwrap();
+        assert_eq!(mount_source_label(&mount).as_deref(), Some("/dev/nvme0n1p2[/@swap]"));
+    }
+
+    #[test]
+    fn test_mount_source_label_btrfs_top_level() {
+        let mount = MountInfo::from_line(
+            "30 1 0:27 / / rw,relatime shared:1 - btrfs /dev/sda2 rw,subvolid=5,subvol=/",
+        )
+        .unwrap();
+        assert_eq!(mount_source_label(&mount).as_deref(), Some("/dev/sda2"));
+    }
+
+    #[test]
+    fn test_mount_source_label_zfs_dataset() {
+        let mount = MountInfo::from_line(
+            "60 1 0:52 / /var/swap rw,noatime shared:30 - zfs rpool/swap rw,xattr,noacl",
+        )
+        .unwrap();
+        assert_eq!(mount_source_label(&mount).as_deref(), Some("zfs:rpool/swap"));
+    }
+
+    #[test]
+    fn test_mount_source_label_plain_and_devtmpfs() {
+        let ext4 = MountInfo::from_line("29 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw").unwrap();
+        assert_eq!(mount_source_label(&ext4).as_deref(), Some("/dev/sda1"));
+        let dev = MountInfo::from_line("22 29 0:5 / /dev rw,nosuid shared:2 - devtmpfs udev rw,size=8G").unwrap();
+        assert_eq!(mount_source_label(&dev).as_deref(), Some("RAM"));
+    }
+
+    #[test]
+    fn test_zvol_name_from_link() {
+        assert_eq!(
+            zvol_name_from_link(std::path::Path::new("/dev/zvol/rpool/swap")).as_deref(),
+            Some("rpool/swap")
+        );
+        assert_eq!(zvol_name_from_link(std::path::Path::new("/dev/zvol")), None);
+        assert_eq!(zvol_name_from_link(std::path::Path::new("/dev/sda1")), None);
+    }
+
+    #[test]
+    fn test_parse_proc_swaps() {
+        let content = "\
+Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority
+/dev/nvme0n1p3                          partition\t16777212\t1048576\t-2
+/swapfile                               file\t\t8388604\t\t0\t\t10
+/dev/zram0                              partition\t4194300\t\t2048\t\t100
+";
+        let devices = parse_proc_swaps(content, SizeUnits::KB);
+        assert_eq!(devices.len(), 3);
+        assert_eq!(devices[0].name, "/dev/nvme0n1p3");
+        assert_eq!(devices[0].kind, "partition");
+        assert_eq!(devices[0].size_kb, 16777212.0);
+        assert_eq!(devices[0].used_kb, 1048576.0);
+        assert_eq!(devices[0].priority, -2);
+        assert_eq!(devices[1].name, "/swapfile");
+        assert_eq!(devices[1].kind, "file");
+        assert_eq!(devices[2].priority, 100);
+    }
+
+    #[test]
+    fn test_parse_proc_swaps_unit_and_escaped_space() {
+        let content = "/mnt/my\\040swap file 1048576 524288 -3\n";
+        let devices = parse_proc_swaps(content, SizeUnits::MB);
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, "/mnt/my swap");
+        assert_eq!(devices[0].size_kb, 1024.0);
+        assert_eq!(devices[0].used_kb, 512.0);
+    }
+
+    #[test]
+    fn test_parse_proc_swaps_non_utf8_path() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let content = b"/mnt/swap\xff\\040file file 1024 0 -2\n";
+        let devices = parse_proc_swaps(content, SizeUnits::KB);
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].path.as_os_str().as_bytes(), b"/mnt/swap\xff file");
+        assert_eq!(devices[0].name, "/mnt/swap\u{FFFD} file");
+    }
+
+    #[test]
+    fn test_find_mount_device_non_utf8_path() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = std::env::temp_dir().join(format!("nv-swaptop-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join(std::ffi::OsStr::from_bytes(b"swap\xfffile"));
+        std::fs::write(&file, b"").unwrap();
+
+        let resolved = find_mount_device(&file);
+        let lossy = find_mount_device(std::path::Path::new(&*file.to_string_lossy()));
+        let expected = find_mount_device(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(expected.is_some());
+        assert_eq!(resolved, expected);
+        // The display name no longer names the file
+        assert_eq!(lossy, None);
+    }
+
+    #[test]
+    fn test_resolve_device_sources_cached_by_name() {
+        let content = b"Filename Type Size Used Priority\n/dev/sda2 partition 1024 0 -2\n/swapfile file 2048 0 -3\n";
+        let devices = parse_proc_swaps(content, SizeUnits::KB);
+        let mut sources = HashMap::new();
+        let calls = std::cell::Cell::new(0);
+        let resolve = |path: &std::path::Path| {
+            calls.set(calls.get() + 1);
+            (path == std::path::Path::new("/swapfile")).then(|| "/dev/nvme0n1p2".to_string())
+        };
+
+        resolve_device_sources(&mut sources, &devices, resolve);
+        resolve_device_sources(&mut sources, &devices, resolve);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(sources["/swapfile"], "/dev/nvme0n1p2");
+        assert_eq!(sources["/dev/sda2"], "RAM");
+
+        // A swapoff drops the entry; nothing is re-read
+        resolve_device_sources(&mut sources, &devices[1..], resolve);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(sources.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_proc_swaps_header_only_and_malformed() {
+        assert!(parse_proc_swaps("Filename Type Size Used Priority\n", SizeUnits::KB).is_empty());
+        assert!(parse_proc_swaps("/dev/sda2 partition lots 0 -2\n", SizeUnits::KB).is_empty());
+    }
+
+    #[test]
+    fn test_parse_vmstat_swap() {
+        let content = "\
+nr_free_pages 123456
+pswpin 1000
+pswpout 2500
+pgfault 99";
+        assert_eq!(parse_vmstat_swap(content), (Some(1000), Some(2500)));
+    }
+
+    #[test]
+    fn test_parse_vmstat_swap_missing() {
+        assert_eq!(parse_vmstat_swap("nr_free_pages 1\n"), (None, None));
+    }
+
+    #[test]
+    fn test_parse_psi_full_avg10() {
+        let content = "\
+some avg10=4.50 avg60=2.00 avg300=1.00 total=100
+full avg10=1.25 avg60=0.50 avg300=0.10 total=50";
+        assert_eq!(parse_psi_full_avg10(content), Some(1.25));
+    }
+
+    #[test]
+    fn test_parse_psi_full_avg10_missing_full_line() {
+        assert_eq!(parse_psi_full_avg10("some avg10=4.50 avg60=2.00 avg300=1.00 total=100"), None);
+    }
+}
@@ -1,21 +1,114 @@
+use serde::Serialize;
 use std::collections::HashMap;
 use thiserror::Error;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ProcessSwapInfo {
     pub pid: u32,
     pub name: String,
     pub swap_size: f64,
     pub last_cpu: Option<i32>,
+    /// Resident set size (VmRSS), in KB regardless of the display unit
+    pub rss_kb: Option<u64>,
+    /// Mount namespace inode (`/proc/[pid]/ns/mnt`); None when unreadable
+    pub mnt_ns: Option<u64>,
+    /// Processes the row stands for: 1 for a single process, the group size
+    /// for rows built by `aggregate_processes` (whose `pid` is 0)
+    pub count: u32,
 }
 
 #[derive(Debug, Clone)]
 pub struct InfoSwap {
+    /// `path` for display, with invalid UTF-8 replaced
     pub name: String,
+    /// Path as the kernel reports it; used for every filesystem lookup since
+    /// `name` may not round-trip
+    pub path: std::path::PathBuf,
     pub kind: String,
     pub size_kb: f64,
     pub used_kb: f64,
     pub priority: isize,
+    /// Storage class of the backing block device
+    pub media: MediaClass,
+    /// Active I/O scheduler of the backing disk's queue
+    pub scheduler: Option<String>,
+    /// Read-ahead of the backing disk's queue
+    pub read_ahead_kb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MediaClass {
+    Hdd,
+    Ssd,
+    Nvme,
+    /// Compressed RAM block device
+    Zram,
+    #[default]
+    Unknown,
+}
+
+impl MediaClass {
+    pub fn label(self) -> &'static str {
+        match self {
+            MediaClass::Hdd => "hdd",
+            MediaClass::Ssd => "ssd",
+            MediaClass::Nvme => "nvme",
+            MediaClass::Zram => "zram",
+            MediaClass::Unknown => "-",
+        }
+    }
+}
+
+/// Capacity and usage of swap grouped by media class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaShare {
+    pub media: MediaClass,
+    /// Share of total swap capacity, 0..=100
+    pub capacity_percent: f64,
+    /// Used fraction of this class's own capacity, 0..=100
+    pub used_percent: f64,
+}
+
+/// Group `devices` by media class, largest capacity first.
+pub fn media_summary(devices: &[InfoSwap]) -> Vec<MediaShare> {
+    let total: f64 = devices.iter().map(|d| d.size_kb).sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    let mut groups: Vec<(MediaClass, f64, f64)> = Vec::new();
+    for device in devices {
+        match groups.iter_mut().find(|(media, _, _)| *media == device.media) {
+            Some(group) => {
+                group.1 += device.size_kb;
+                group.2 += device.used_kb;
+            }
+            None => groups.push((device.media, device.size_kb, device.used_kb)),
+        }
+    }
+    groups.sort_by(|a, b| b.1.total_cmp(&a.1));
+    groups
+        .into_iter()
+        .map(|(media, size, used)| MediaShare {
+            media,
+            capacity_percent: size / total * 100.0,
+            used_percent: if size > 0.0 { used / size * 100.0 } else { 0.0 },
+        })
+        .collect()
+}
+
+/// Flag swap devices on rotational disks that have a higher priority than
+/// some SSD/NVMe device: the kernel fills higher priorities first, so the
+/// slow disk is used before the fast one.
+pub fn priority_warnings(devices: &[InfoSwap]) -> Vec<bool> {
+    let fastest_priority = devices
+        .iter()
+        .filter(|d| matches!(d.media, MediaClass::Ssd | MediaClass::Nvme))
+        .map(|d| d.priority)
+        .min();
+    devices
+        .iter()
+        .map(|d| d.media == MediaClass::Hdd && fastest_priority.is_some_and(|p| d.priority > p))
+        .collect()
 }
 
 #[derive(Debug, Clone, Default)]
@@ -23,6 +116,101 @@ pub struct SwapUpdate {
     pub swap_devices: Vec<InfoSwap>,
     pub total_swap: u64,
     pub used_swap: u64,
+    /// Cumulative pages swapped in since boot (`pswpin` in /proc/vmstat)
+    pub swap_in_pages: Option<u64>,
+    /// Cumulative pages swapped out since boot (`pswpout` in /proc/vmstat)
+    pub swap_out_pages: Option<u64>,
+    /// Size of the pages counted by `swap_in_pages`/`swap_out_pages`, in KB
+    pub page_size_kb: Option<u64>,
+    /// PSI memory "full" avg10 percentage (/proc/pressure/memory)
+    pub memory_full_avg10: Option<f64>,
+    /// Swapped-out pages that still have a copy in RAM (`SwapCached`, KB)
+    pub swap_cached: Option<u64>,
+    /// `MemTotal` in KB
+    pub mem_total_kb: Option<u64>,
+    /// `MemAvailable` in KB (kernels before 3.14 lack it)
+    pub mem_available_kb: Option<u64>,
+    /// /proc/sys/vm/swappiness
+    pub swappiness: Option<u32>,
+    /// SwapFree was read larger than SwapTotal. /proc/meminfo is not read
+    /// atomically, so the two can come from different moments; `used_swap`
+    /// (clamped to 0) is then not a real value.
+    pub torn_read: bool,
+}
+
+/// Definition of "used swap" for display.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SwapUsedMode {
+    /// SwapTotal - SwapFree, as reported by free(1)
+    #[default]
+    IncludeCached,
+    /// Additionally subtract SwapCached: pages that are also still in RAM
+    /// and can be dropped from swap without I/O
+    ExcludeCached,
+}
+
+impl SwapUpdate {
+    /// Used swap in KB under `mode`. Every displayed "used" value (chart,
+    /// titles, percentages, alerts) goes through here.
+    pub fn used_kb(&self, mode: SwapUsedMode) -> u64 {
+        match mode {
+            SwapUsedMode::IncludeCached => self.used_swap,
+            SwapUsedMode::ExcludeCached => self.used_swap.saturating_sub(self.swap_cached.unwrap_or(0)),
+        }
+    }
+
+    /// This sample, or for a torn read the swap totals of `prev`, so one bad
+    /// read does not drop used swap (and the chart) to 0 for a tick. A torn
+    /// first sample has nothing to fall back on and is kept as is.
+    pub fn or_previous(mut self, prev: &SwapUpdate) -> SwapUpdate {
+        if self.torn_read && !prev.torn_read && prev.total_swap > 0 {
+            self.total_swap = prev.total_swap;
+            self.used_swap = prev.used_swap;
+            self.swap_cached = prev.swap_cached;
+            self.torn_read = false;
+        }
+        self
+    }
+
+    /// KB swapped in and out since boot, when vmstat and the page size are
+    /// known.
+    pub fn swapped_since_boot_kb(&self) -> Option<(u64, u64)> {
+        let page_kb = self.page_size_kb?;
+        Some((self.swap_in_pages?.saturating_mul(page_kb), self.swap_out_pages?.saturating_mul(page_kb)))
+    }
+
+    /// MemAvailable as a percentage of MemTotal, when both are known.
+    pub fn mem_available_percent(&self) -> Option<f64> {
+        match (self.mem_available_kb, self.mem_total_kb) {
+            (Some(available), Some(total)) if total > 0 => Some(available as f64 / total as f64 * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// When to flag swap use despite free RAM (`--swappiness-hint-available`,
+/// `--swappiness-hint-min-mb`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwappinessHintThresholds {
+    /// MemAvailable at or above this percentage of MemTotal counts as plenty of RAM
+    pub available_percent: f64,
+    /// Used swap below this is ignored, in KB
+    pub min_used_kb: u64,
+}
+
+impl Default for SwappinessHintThresholds {
+    fn default() -> Self {
+        Self { available_percent: 50.0, min_used_kb: 1024 }
+    }
+}
+
+/// Swap in use while plenty of RAM is available: the kernel preferred
+/// swapping anonymous pages out over dropping page cache, which usually
+/// points at vm.swappiness. `used_kb` is the displayed used swap.
+pub fn swapping_with_free_ram(update: &SwapUpdate, used_kb: u64, thresholds: &SwappinessHintThresholds) -> bool {
+    used_kb > 0
+        && used_kb >= thresholds.min_used_kb
+        && update.mem_available_percent().is_some_and(|percent| percent >= thresholds.available_percent)
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -31,6 +219,67 @@ pub enum SizeUnits {
     KB,
     MB,
     GB,
+    TB,
+}
+
+impl SizeUnits {
+    /// Power of the base: 1 for KB up to 4 for TB.
+    fn exponent(&self) -> i32 {
+        match self {
+            SizeUnits::KB => 1,
+            SizeUnits::MB => 2,
+            SizeUnits::GB => 3,
+            SizeUnits::TB => 4,
+        }
+    }
+}
+
+/// Whether sizes are shown in powers of 1024 (KiB, MiB, ...), as the kernel
+/// and nvidia-smi count, or powers of 1000 (kB, MB, ...), as `free --si` and
+/// most vendor dashboards do (`B`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum UnitBase {
+    #[default]
+    Binary,
+    Decimal,
+}
+
+impl UnitBase {
+    pub fn toggle(self) -> Self {
+        match self {
+            UnitBase::Binary => UnitBase::Decimal,
+            UnitBase::Decimal => UnitBase::Binary,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UnitBase::Binary => "binary",
+            UnitBase::Decimal => "decimal",
+        }
+    }
+
+    /// Symbol for `unit` in this base, e.g. "MiB" or "MB".
+    pub fn suffix(self, unit: &SizeUnits) -> &'static str {
+        match (self, unit) {
+            (UnitBase::Binary, SizeUnits::KB) => "KiB",
+            (UnitBase::Binary, SizeUnits::MB) => "MiB",
+            (UnitBase::Binary, SizeUnits::GB) => "GiB",
+            (UnitBase::Binary, SizeUnits::TB) => "TiB",
+            (UnitBase::Decimal, SizeUnits::KB) => "kB",
+            (UnitBase::Decimal, SizeUnits::MB) => "MB",
+            (UnitBase::Decimal, SizeUnits::GB) => "GB",
+            (UnitBase::Decimal, SizeUnits::TB) => "TB",
+        }
+    }
+}
+
+/// Combined swap-thrash severity derived from PSI and swap I/O rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThrashLevel {
+    Low,
+    Medium,
+    High,
 }
 
 #[derive(Debug, Error)]
@@ -57,6 +306,9 @@ pub struct NumaNode {
     pub memory_free_kb: u64,
     pub cpus: Vec<u32>,
     pub node_type: NumaNodeType,
+    /// Relative access cost from this node to each node, in node order, as
+    /// `nodeN/distance` lists it (10 = local); empty when unavailable
+    pub distances: Vec<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -68,6 +320,34 @@ pub struct ProcessNumaInfo {
     pub cpu_node: Option<u32>,
 }
 
+impl ProcessNumaInfo {
+    /// Node holding the most of this process's memory.
+    pub fn dominant_node(&self) -> Option<u32> {
+        self.kb_per_node
+            .iter()
+            .max_by_key(|(_, v)| **v)
+            .map(|(k, _)| *k)
+    }
+
+    /// KB on nodes missing from `nodes`, e.g. a hotplugged node that appeared
+    /// after the topology was last read. Already included in `total_kb`.
+    pub fn kb_outside(&self, nodes: &[NumaNode]) -> u64 {
+        self.kb_per_node
+            .iter()
+            .filter(|(id, _)| !nodes.iter().any(|n| n.id == **id))
+            .map(|(_, kb)| kb)
+            .sum()
+    }
+
+    /// The process last ran on a CPU outside its dominant memory node.
+    pub fn is_misaligned(&self) -> bool {
+        match (self.cpu_node, self.dominant_node()) {
+            (Some(cpu), Some(mem)) => cpu != mem,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum ActiveView {
     #[default]
@@ -79,15 +359,31 @@ pub enum ActiveView {
 
 // --- GPU types ---
 
-#[derive(Debug, Clone)]
+/// Whether GPU data can be collected, and if not, why.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum GpuStatus {
+    Available,
+    /// nvidia-smi is not installed / not in PATH, and rocm-smi is not
+    /// usable either
+    #[default]
+    NotFound,
+    /// nvidia-smi exists but exited with an error (e.g. driver/library mismatch)
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct GpuProcessInfo {
     pub pid: u32,
     pub name: String,
     pub gpu_index: u32,
     pub gpu_memory_used_kb: u64,
+    /// SM (compute) utilisation % from `nvidia-smi pmon` (`--gpu-pmon`)
+    pub sm_util: Option<u32>,
+    /// Memory controller utilisation % from `nvidia-smi pmon`
+    pub mem_util: Option<u32>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GpuDevice {
     pub index: u32,
     pub name: String,
@@ -97,18 +393,287 @@ pub struct GpuDevice {
     pub numa_node_id: Option<u32>,
     pub temperature: Option<u32>,
     pub pci_bus_id: String,
+    /// Memory reserved by the driver (`memory.reserved`); None on drivers
+    /// that do not report it
+    pub memory_reserved_kb: Option<u64>,
+    /// MIG instances carved out of this physical GPU (empty when MIG is off)
+    pub mig_instances: Vec<MigInstance>,
+    /// Compute utilisation (`utilization.gpu`); None when not reported
+    pub utilization_percent: Option<u32>,
+    /// Memory controller (bandwidth) utilisation (`utilization.memory`):
+    /// how busy the memory bus is, unrelated to how much memory is used
+    pub memory_utilization_percent: Option<u32>,
+}
+
+/// A MIG (Multi-Instance GPU) partition. Owned by its parent `GpuDevice`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MigInstance {
+    /// MIG device index within the parent GPU
+    pub device: u32,
+    pub gpu_instance: u32,
+    pub compute_instance: u32,
+    pub memory_total_kb: u64,
+    pub memory_used_kb: u64,
+}
+
+impl GpuDevice {
+    /// (total, used, free) memory in KB, with MIG instances folded back into
+    /// the physical GPU: used is the sum over instances, total stays physical.
+    pub fn flattened_memory_kb(&self) -> (u64, u64, u64) {
+        if self.mig_instances.is_empty() {
+            (self.memory_total_kb, self.memory_used_kb, self.memory_free_kb)
+        } else {
+            let used: u64 = self.mig_instances.iter().map(|m| m.memory_used_kb).sum();
+            (self.memory_total_kb, used, self.memory_total_kb.saturating_sub(used))
+        }
+    }
+
+    /// Held but idle: at least one process has memory on this GPU while its
+    /// utilisation is at or below `max_utilization_percent`. GPUs that do
+    /// not report utilisation are never idle.
+    pub fn is_idle_but_held(&self, processes: &[GpuProcessInfo], max_utilization_percent: u32) -> bool {
+        self.utilization_percent.is_some_and(|u| u <= max_utilization_percent)
+            && processes.iter().any(|p| p.gpu_index == self.index)
+    }
+}
+
+// --- Baseline types ---
+
+/// Per-process memory snapshot taken when the user "zeroes" the display.
+/// All values are in KB so the baseline is independent of the display unit.
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    pub swap_kb: HashMap<u32, u64>,
+    pub swap_kb_by_name: HashMap<String, u64>,
+    pub gpu_kb: HashMap<u32, u64>,
+    /// By the name the unified view shows: the swap scan's when the PID
+    /// also swaps, else the GPU tool's
+    pub gpu_kb_by_name: HashMap<String, u64>,
+}
+
+impl Baseline {
+    /// `swap_procs` must have been collected with `SizeUnits::KB`.
+    pub fn capture(swap_procs: &[ProcessSwapInfo], gpu_procs: &[GpuProcessInfo]) -> Self {
+        let mut baseline = Baseline::default();
+        for p in swap_procs {
+            baseline.swap_kb.insert(p.pid, p.swap_size as u64);
+            *baseline.swap_kb_by_name.entry(p.name.clone()).or_insert(0) += p.swap_size as u64;
+        }
+        let swap_names: HashMap<u32, &str> = swap_procs.iter().map(|p| (p.pid, p.name.as_str())).collect();
+        for gp in gpu_procs {
+            *baseline.gpu_kb.entry(gp.pid).or_insert(0) += gp.gpu_memory_used_kb;
+            let name = swap_names.get(&gp.pid).copied().unwrap_or(&gp.name);
+            *baseline.gpu_kb_by_name.entry(name.to_string()).or_insert(0) += gp.gpu_memory_used_kb;
+        }
+        baseline
+    }
+}
+
+/// Last-seen name per PID, used to notice when a process execs: the PID
+/// (and everything keyed on it, such as baseline deltas) is kept, only the
+/// displayed name changes. PIDs that disappear are forgotten.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessNames {
+    current: HashMap<u32, String>,
+    /// PID -> name before the most recent exec
+    previous: HashMap<u32, String>,
+}
+
+impl ProcessNames {
+    pub fn observe(&mut self, procs: &[ProcessSwapInfo]) {
+        let mut current = HashMap::with_capacity(procs.len());
+        for p in procs {
+            if let Some(old) = self.current.get(&p.pid)
+                && *old != p.name
+            {
+                self.previous.insert(p.pid, old.clone());
+            }
+            current.insert(p.pid, p.name.clone());
+        }
+        self.previous.retain(|pid, _| current.contains_key(pid));
+        self.current = current;
+    }
+
+    /// The name a PID had before it exec'd, if a change was seen.
+    pub fn previous_name(&self, pid: u32) -> Option<&str> {
+        self.previous.get(&pid).map(String::as_str)
+    }
+}
+
+/// Per-process swap growth between refresh ticks: the `SWP I/O` column's
+/// KB/s (positive while a process is being swapped out, negative while it is
+/// swapped back in) and the `TREND` column's change. Rows are keyed by PID,
+/// or by name when aggregated; a row's change is unknown until it has been
+/// seen on two ticks.
+#[derive(Debug, Clone, Default)]
+pub struct SwapDeltas {
+    prev_kb: HashMap<u32, f64>,
+    prev_kb_by_name: HashMap<String, f64>,
+    changes: HashMap<u32, f64>,
+    changes_by_name: HashMap<String, f64>,
+    elapsed_secs: f64,
+    /// Mode of the last tick; None before the first
+    last_aggregated: Option<bool>,
+    /// Whether the last tick had a previous one in the same mode
+    has_previous: bool,
+}
+
+/// How a row's swap moved since the previous tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SwapTrend {
+    /// Not listed on the previous tick
+    New,
+    /// KB gained (negative: lost)
+    Changed(f64),
+}
+
+impl SwapDeltas {
+    /// Record one tick's rows (in `unit`), taken `elapsed_secs` after the
+    /// previous tick. Switching between aggregated and per-PID rows starts
+    /// over.
+    pub fn update(&mut self, rows: &[ProcessSwapInfo], unit: &SizeUnits, aggregated: bool, elapsed_secs: f64) {
+        self.elapsed_secs = elapsed_secs;
+        self.has_previous = self.last_aggregated == Some(aggregated);
+        self.last_aggregated = Some(aggregated);
+        if aggregated {
+            let curr: HashMap<String, f64> = rows.iter().map(|p| (p.name.clone(), unit_to_kb(p.swap_size, unit) as f64)).collect();
+            self.changes_by_name = delta_kb(&self.prev_kb_by_name, &curr);
+            self.prev_kb_by_name = curr;
+            self.prev_kb.clear();
+            self.changes.clear();
+        } else {
+            let curr: HashMap<u32, f64> = rows.iter().map(|p| (p.pid, unit_to_kb(p.swap_size, unit) as f64)).collect();
+            self.changes = delta_kb(&self.prev_kb, &curr);
+            self.prev_kb = curr;
+            self.prev_kb_by_name.clear();
+            self.changes_by_name.clear();
+        }
+    }
+
+    /// KB/s for a row, if it was present on the previous tick too.
+    pub fn rate(&self, row: &ProcessSwapInfo, aggregated: bool) -> Option<f64> {
+        if self.elapsed_secs <= 0.0 {
+            return None;
+        }
+        self.change(row, aggregated).map(|kb| kb / self.elapsed_secs)
+    }
+
+    /// The row's change since the previous tick; None on the first tick,
+    /// when every row would otherwise count as new.
+    pub fn trend(&self, row: &ProcessSwapInfo, aggregated: bool) -> Option<SwapTrend> {
+        if !self.has_previous {
+            return None;
+        }
+        Some(self.change(row, aggregated).map_or(SwapTrend::New, SwapTrend::Changed))
+    }
+
+    fn change(&self, row: &ProcessSwapInfo, aggregated: bool) -> Option<f64> {
+        if aggregated {
+            self.changes_by_name.get(&row.name).copied()
+        } else {
+            self.changes.get(&row.pid).copied()
+        }
+    }
+}
+
+fn delta_kb<K: Clone + Eq + std::hash::Hash>(prev: &HashMap<K, f64>, curr: &HashMap<K, f64>) -> HashMap<K, f64> {
+    curr.iter()
+        .filter_map(|(key, kb)| prev.get(key).map(|prev_kb| (key.clone(), kb - prev_kb)))
+        .collect()
+}
+
+/// Number of refresh ticks an exited watched PID stays listed as "(exited)".
+pub const EXITED_TICKS: u64 = 3;
+
+/// `--pid` focus mode: restricts the views to a fixed set of PIDs and keeps
+/// watched processes that exit on screen briefly so the exit is noticed.
+#[derive(Debug, Clone, Default)]
+pub struct PidWatch {
+    pids: Vec<u32>,
+    /// Last name seen per watched PID
+    names: HashMap<u32, String>,
+    /// PID -> tick at which it was found to have exited
+    exited: HashMap<u32, u64>,
+}
+
+impl PidWatch {
+    pub fn new(pids: Vec<u32>) -> Self {
+        Self { pids, ..Default::default() }
+    }
+
+    pub fn pids(&self) -> &[u32] {
+        &self.pids
+    }
+
+    /// True for watched PIDs that are still running.
+    pub fn contains(&self, pid: u32) -> bool {
+        self.pids.contains(&pid) && !self.exited.contains_key(&pid)
+    }
+
+    /// Keep only rows for watched, running PIDs and remember their names.
+    pub fn filter_swap(&mut self, procs: &mut Vec<ProcessSwapInfo>) {
+        procs.retain(|p| self.contains(p.pid));
+        for p in procs.iter() {
+            self.names.insert(p.pid, p.name.clone());
+        }
+    }
+
+    pub fn filter_gpu(&mut self, procs: &mut Vec<GpuProcessInfo>) {
+        procs.retain(|p| self.contains(p.pid));
+        for p in procs.iter() {
+            self.names.entry(p.pid).or_insert_with(|| p.name.clone());
+        }
+    }
+
+    /// Once per refresh tick: note newly exited PIDs and stop watching those
+    /// that have been shown as exited for `EXITED_TICKS` ticks.
+    pub fn update(&mut self, tick: u64, is_alive: impl Fn(u32) -> bool) {
+        for &pid in &self.pids {
+            if !self.exited.contains_key(&pid) && !is_alive(pid) {
+                self.exited.insert(pid, tick);
+            }
+        }
+        let expired: Vec<u32> = self
+            .exited
+            .iter()
+            .filter(|&(_, &at)| tick.saturating_sub(at) >= EXITED_TICKS)
+            .map(|(&pid, _)| pid)
+            .collect();
+        for pid in expired {
+            self.exited.remove(&pid);
+            self.names.remove(&pid);
+            self.pids.retain(|&p| p != pid);
+        }
+    }
+
+    /// Placeholder rows for recently exited PIDs, named "<name> (exited)".
+    pub fn exited_rows(&self) -> Vec<ProcessSwapInfo> {
+        let mut pids: Vec<u32> = self.exited.keys().copied().collect();
+        pids.sort_unstable();
+        pids.into_iter()
+            .map(|pid| ProcessSwapInfo {
+                pid,
+                name: format!("{} (exited)", self.names.get(&pid).map(String::as_str).unwrap_or("?")),
+                swap_size: 0.0,
+                last_cpu: None,
+                rss_kb: None,
+                mnt_ns: None,
+                count: 1,
+            })
+            .collect()
+    }
 }
 
 // --- Unified types ---
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum ProcessLocation {
     CpuOnly,
     GpuOnly,
     CpuAndGpu,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct UnifiedProcessInfo {
     pub pid: u32,
     pub name: String,
@@ -119,33 +684,187 @@ pub struct UnifiedProcessInfo {
     pub gpu_memory_kb: Option<u64>,
     pub gpu_indices: Vec<u32>,
     pub location: ProcessLocation,
+    /// Resident set size, from the swap scan; None for GPU-only entries
+    pub rss_kb: Option<u64>,
+    /// Processes in the row: 1, or the group size when aggregated by name
+    pub count: u32,
+}
+
+impl UnifiedProcessInfo {
+    /// Overall memory footprint: swap plus resident memory.
+    pub fn committed_kb(&self) -> u64 {
+        self.swap_kb + self.rss_kb.unwrap_or(0)
+    }
+}
+
+/// `kb` (KiB, as /proc and nvidia-smi report) in `unit` of `base`. Data
+/// providers convert in the binary base; decimal is applied when displaying,
+/// see `rebase`.
+pub fn convert_swap(kb: u64, unit: SizeUnits, base: UnitBase) -> f64 {
+    match base {
+        UnitBase::Binary => kb as f64 / 1024f64.powi(unit.exponent() - 1),
+        UnitBase::Decimal => kb as f64 * 1024.0 / 1000f64.powi(unit.exponent()),
+    }
 }
 
-pub fn convert_swap(kb: u64, unit: SizeUnits) -> f64 {
+/// A value in binary `unit` (as process rows and swap devices hold them)
+/// expressed in `base` instead.
+pub fn rebase(value: f64, unit: &SizeUnits, base: UnitBase) -> f64 {
+    match base {
+        UnitBase::Binary => value,
+        UnitBase::Decimal => value * (1024f64 / 1000.0).powi(unit.exponent()),
+    }
+}
+
+/// Back from a binary `convert_swap` value to KB. Exact below 2^53 KB: the
+/// conversion only divides by powers of two.
+pub fn unit_to_kb(value: f64, unit: &SizeUnits) -> u64 {
+    let kb = match unit {
+        SizeUnits::KB => value,
+        SizeUnits::MB => value * 1024.0,
+        SizeUnits::GB => value * 1024.0 * 1024.0,
+        SizeUnits::TB => value * 1024.0 * 1024.0 * 1024.0,
+    };
+    kb.round() as u64
+}
+
+/// Parse a size such as "512M", "1.5GiB" or "2GB" into KB. The suffix is a
+/// `UnitBase::suffix` symbol in either base (case-insensitive), or a bare
+/// K/M/G/T for binary units; a plain number is in binary `default_unit`.
+pub fn parse_size_kb(text: &str, default_unit: &SizeUnits) -> Option<u64> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let value: f64 = text[..split].parse().ok().filter(|v: &f64| v.is_finite())?;
+    let suffix = text[split..].trim();
+    if suffix.is_empty() {
+        return Some(unit_to_kb(value, default_unit));
+    }
+    for unit in [SizeUnits::KB, SizeUnits::MB, SizeUnits::GB, SizeUnits::TB] {
+        let binary = UnitBase::Binary.suffix(&unit);
+        if suffix.eq_ignore_ascii_case(binary) || suffix.eq_ignore_ascii_case(&binary[..1]) {
+            return Some(unit_to_kb(value, &unit));
+        }
+        if suffix.eq_ignore_ascii_case(UnitBase::Decimal.suffix(&unit)) {
+            return Some((value * 1000f64.powi(unit.exponent()) / 1024.0).round() as u64);
+        }
+    }
+    None
+}
+
+/// "total: X | used: Y" in `unit` of `base`. KB figures are printed from
+/// the u64 counts: an f64 holds integers exactly only up to 2^53.
+pub fn total_used_label(total_kb: u64, used_kb: u64, unit: &SizeUnits, base: UnitBase) -> String {
+    match (unit, base) {
+        (SizeUnits::KB, UnitBase::Binary) => format!("total: {} | used: {}", total_kb, used_kb),
+        (SizeUnits::KB, UnitBase::Decimal) => {
+            let decimal_kb = |kb: u64| (kb as u128 * 1024 + 500) / 1000;
+            format!("total: {} | used: {}", decimal_kb(total_kb), decimal_kb(used_kb))
+        }
+        (SizeUnits::MB, _) => format!(
+            "total: {} | used: {:.2}",
+            convert_swap(total_kb, SizeUnits::MB, base).round(),
+            convert_swap(used_kb, SizeUnits::MB, base)
+        ),
+        (SizeUnits::GB | SizeUnits::TB, _) => format!(
+            "total: {:.2} | used: {:.2}",
+            convert_swap(total_kb, unit.clone(), base),
+            convert_swap(used_kb, unit.clone(), base)
+        ),
+    }
+}
+
+/// A value already converted to `unit`: a plain integer for KB, two
+/// decimals otherwise.
+pub fn format_unit_value(value: f64, unit: &SizeUnits) -> String {
     match unit {
-        SizeUnits::KB => kb as f64,
-        SizeUnits::MB => kb as f64 / 1024.0,
-        SizeUnits::GB => kb as f64 / (1024.0 * 1024.0),
+        SizeUnits::KB => format!("{:.0}", value),
+        _ => format!("{:.2}", value),
+    }
+}
+
+/// Per-second rate between two samples of a cumulative counter.
+/// A counter that went backwards (reset) yields 0.
+pub fn counter_rate(prev: u64, curr: u64, elapsed_secs: f64) -> f64 {
+    if elapsed_secs <= 0.0 {
+        return 0.0;
+    }
+    curr.saturating_sub(prev) as f64 / elapsed_secs
+}
+
+// Thrash thresholds. PSI "full" avg10 is the share of the last 10s in which
+// all non-idle tasks were stalled on memory; swap rate is pswpin + pswpout
+// pages per second.
+const PSI_FULL_MEDIUM: f64 = 1.0;
+const PSI_FULL_HIGH: f64 = 10.0;
+const SWAP_RATE_MEDIUM: f64 = 100.0;
+const SWAP_RATE_HIGH: f64 = 1000.0;
+
+/// Score swap thrashing from PSI memory-full pressure and swap I/O rate.
+///
+/// Each metric scores 0 (below medium), 1 (medium) or 2 (high):
+///   PSI full avg10:  1% medium, 10% high
+///   swap pages/sec:  100 medium, 1000 high
+///
+/// Either signal alone can mislead (stalls from page-cache reclaim, or a
+/// steady background swap-out with no stalls), so both must be elevated:
+///   High   — both scores > 0 and their sum >= 3
+///   Medium — sum >= 2 (one signal high, or both medium)
+///   Low    — otherwise
+///
+/// Without PSI (kernel built without CONFIG_PSI) the rate score alone is
+/// used, capped at Medium.
+pub fn thrash_level(psi_full_avg10: Option<f64>, swap_pages_per_sec: f64) -> ThrashLevel {
+    let score = |v: f64, medium: f64, high: f64| -> u8 {
+        if v >= high {
+            2
+        } else if v >= medium {
+            1
+        } else {
+            0
+        }
+    };
+    let rate_score = score(swap_pages_per_sec, SWAP_RATE_MEDIUM, SWAP_RATE_HIGH);
+
+    let Some(psi) = psi_full_avg10 else {
+        return if rate_score > 0 { ThrashLevel::Medium } else { ThrashLevel::Low };
+    };
+    let psi_score = score(psi, PSI_FULL_MEDIUM, PSI_FULL_HIGH);
+
+    match psi_score + rate_score {
+        sum if sum >= 3 && psi_score > 0 && rate_score > 0 => ThrashLevel::High,
+        sum if sum >= 2 => ThrashLevel::Medium,
+        _ => ThrashLevel::Low,
     }
 }
 
 pub fn aggregate_processes(processes: Vec<ProcessSwapInfo>) -> Vec<ProcessSwapInfo> {
-    let mut name_to_info: HashMap<String, (f64, u32)> = HashMap::new();
+    // (swap, count, rss, mount namespace shared by every member)
+    let mut name_to_info: HashMap<String, (f64, u32, Option<u64>, Option<u64>)> = HashMap::new();
 
     for process in processes {
-        let entry = name_to_info.entry(process.name).or_insert((0.0, 0));
+        let first = !name_to_info.contains_key(&process.name);
+        let entry = name_to_info.entry(process.name).or_insert((0.0, 0, None, process.mnt_ns));
         entry.0 += process.swap_size;
-        entry.1 += 1;
+        entry.1 = entry.1.saturating_add(process.count);
+        if let Some(rss) = process.rss_kb {
+            entry.2 = Some(entry.2.unwrap_or(0) + rss);
+        }
+        if !first && entry.3 != process.mnt_ns {
+            entry.3 = None;
+        }
     }
 
     let mut aggregated_processes: Vec<ProcessSwapInfo> = name_to_info
         .into_iter()
-        .map(|(name, (swap_size, count))| ProcessSwapInfo {
-            pid: count,
+        .map(|(name, (swap_size, count, rss_kb, mnt_ns))| ProcessSwapInfo {
+            pid: 0,
             name,
             swap_size,
             #[cfg(target_os = "linux")]
             last_cpu: None,
+            rss_kb,
+            mnt_ns,
+            count,
         })
         .collect();
 
@@ -157,23 +876,176 @@ pub fn aggregate_processes(processes: Vec<ProcessSwapInfo>) -> Vec<ProcessSwapIn
     aggregated_processes
 }
 
+/// Unified rows grouped by name, as `aggregate_processes` groups swap rows:
+/// swap, RSS, GPU memory and per-node memory are summed, node and GPU lists
+/// merged, and the location is the one holding most of the group's swap and
+/// GPU memory.
+pub fn aggregate_unified(processes: Vec<UnifiedProcessInfo>) -> Vec<UnifiedProcessInfo> {
+    let mut groups: Vec<UnifiedProcessInfo> = Vec::new();
+    // Per group: KB held by each location, in first-seen order
+    let mut weights: Vec<Vec<(ProcessLocation, u64)>> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for process in processes {
+        let weight = process.swap_kb + process.gpu_memory_kb.unwrap_or(0);
+        let Some(&i) = index.get(&process.name) else {
+            index.insert(process.name.clone(), groups.len());
+            weights.push(vec![(process.location.clone(), weight)]);
+            groups.push(UnifiedProcessInfo { pid: 0, ..process });
+            continue;
+        };
+        let group = &mut groups[i];
+        group.swap_kb += process.swap_kb;
+        group.count = group.count.saturating_add(process.count);
+        if let Some(rss) = process.rss_kb {
+            group.rss_kb = Some(group.rss_kb.unwrap_or(0) + rss);
+        }
+        if let Some(gpu) = process.gpu_memory_kb {
+            group.gpu_memory_kb = Some(group.gpu_memory_kb.unwrap_or(0) + gpu);
+        }
+        for (node, kb) in process.kb_per_node {
+            *group.kb_per_node.entry(node).or_insert(0) += kb;
+        }
+        for (merged, more) in [
+            (&mut group.cpu_nodes, process.cpu_nodes),
+            (&mut group.gpu_nodes, process.gpu_nodes),
+            (&mut group.gpu_indices, process.gpu_indices),
+        ] {
+            merged.extend(more);
+            merged.sort();
+            merged.dedup();
+        }
+        match weights[i].iter_mut().find(|(location, _)| *location == process.location) {
+            Some((_, kb)) => *kb += weight,
+            None => weights[i].push((process.location, weight)),
+        }
+    }
+
+    for (group, weights) in groups.iter_mut().zip(weights) {
+        // Ties go to the location seen first
+        let mut dominant = weights[0].clone();
+        for (location, kb) in weights {
+            if kb > dominant.1 {
+                dominant = (location, kb);
+            }
+        }
+        group.location = dominant.0;
+    }
+    groups
+}
+
+/// "host" for the host's mount namespace, "mnt:<inode>" for any other
+/// (typically a container), "-" when unknown.
+pub fn mnt_ns_label(mnt_ns: Option<u64>, host_mnt_ns: Option<u64>) -> String {
+    match mnt_ns {
+        None => "-".to_string(),
+        Some(ns) if Some(ns) == host_mnt_ns => "host".to_string(),
+        Some(ns) => format!("mnt:{}", ns),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_mnt_ns_label() {
+        assert_eq!(mnt_ns_label(Some(4026531841), Some(4026531841)), "host");
+        assert_eq!(mnt_ns_label(Some(4026532301), Some(4026531841)), "mnt:4026532301");
+        assert_eq!(mnt_ns_label(Some(4026532301), None), "mnt:4026532301");
+        assert_eq!(mnt_ns_label(None, Some(4026531841)), "-");
+    }
+
+    #[test]
+    fn test_aggregate_keeps_shared_mnt_ns_only() {
+        let row = |pid, name: &str, mnt_ns| ProcessSwapInfo { pid, name: name.into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns, count: 1 };
+        let rows = aggregate_processes(vec![
+            row(1, "nginx", Some(7)), row(2, "nginx", Some(7)),
+            row(3, "bash", Some(7)), row(4, "bash", Some(9)),
+        ]);
+        let ns = |name: &str| rows.iter().find(|p| p.name == name).unwrap().mnt_ns;
+        assert_eq!(ns("nginx"), Some(7));
+        assert_eq!(ns("bash"), None);
+    }
+
     #[test]
     fn test_convert_swap_kb() {
-        assert_eq!(convert_swap(1024, SizeUnits::KB), 1024.0);
+        assert_eq!(convert_swap(1024, SizeUnits::KB, UnitBase::Binary), 1024.0);
     }
 
     #[test]
     fn test_convert_swap_mb() {
-        assert_eq!(convert_swap(1024, SizeUnits::MB), 1.0);
+        assert_eq!(convert_swap(1024, SizeUnits::MB, UnitBase::Binary), 1.0);
     }
 
     #[test]
     fn test_convert_swap_gb() {
-        assert_eq!(convert_swap(1048576, SizeUnits::GB), 1.0);
+        assert_eq!(convert_swap(1048576, SizeUnits::GB, UnitBase::Binary), 1.0);
+    }
+
+    #[test]
+    fn test_convert_swap_tb() {
+        assert_eq!(convert_swap(1073741824, SizeUnits::TB, UnitBase::Binary), 1.0);
+    }
+
+    #[test]
+    fn test_convert_swap_decimal() {
+        assert_eq!(convert_swap(1_000_000, SizeUnits::MB, UnitBase::Binary), 976.5625);
+        assert_eq!(convert_swap(1_000_000, SizeUnits::MB, UnitBase::Decimal), 1024.0);
+        assert_eq!(convert_swap(1000, SizeUnits::KB, UnitBase::Decimal), 1024.0);
+        assert!((rebase(976.5625, &SizeUnits::MB, UnitBase::Decimal) - 1024.0).abs() < 1e-9);
+        assert_eq!(rebase(976.5625, &SizeUnits::MB, UnitBase::Binary), 976.5625);
+    }
+
+    #[test]
+    fn test_unit_base_suffix_and_toggle() {
+        assert_eq!(UnitBase::default().suffix(&SizeUnits::GB), "GiB");
+        assert_eq!(UnitBase::Binary.toggle().suffix(&SizeUnits::GB), "GB");
+        assert_eq!(UnitBase::Decimal.suffix(&SizeUnits::KB), "kB");
+        assert_eq!(UnitBase::Decimal.toggle(), UnitBase::Binary);
+    }
+
+    #[test]
+    fn test_unit_to_kb_round_trips() {
+        for kb in [0, 1, 1023, 12_884_901, (1 << 53) - 1] {
+            for unit in [SizeUnits::KB, SizeUnits::MB, SizeUnits::GB, SizeUnits::TB] {
+                assert_eq!(unit_to_kb(convert_swap(kb, unit.clone(), UnitBase::Binary), &unit), kb);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_size_kb() {
+        assert_eq!(parse_size_kb("1024", &SizeUnits::MB), Some(1024 * 1024));
+        assert_eq!(parse_size_kb("512M", &SizeUnits::MB), Some(512 * 1024));
+        assert_eq!(parse_size_kb("1G", &SizeUnits::MB), Some(1024 * 1024));
+        assert_eq!(parse_size_kb("1.5GiB", &SizeUnits::MB), Some(1536 * 1024));
+        assert_eq!(parse_size_kb("64k", &SizeUnits::MB), Some(64));
+        // Decimal symbols convert as the decimal display does
+        assert_eq!(parse_size_kb("1GB", &SizeUnits::MB), Some(976_563));
+        assert_eq!(parse_size_kb("1024kB", &SizeUnits::MB), Some(1000));
+        assert_eq!(parse_size_kb("", &SizeUnits::MB), None);
+        assert_eq!(parse_size_kb("1X", &SizeUnits::MB), None);
+        assert_eq!(parse_size_kb("G", &SizeUnits::MB), None);
+    }
+
+    #[test]
+    fn test_total_used_label_exact_above_2_pow_53() {
+        let total = (1u64 << 53) + 1;
+        assert_eq!(
+            total_used_label(total, total - 2, &SizeUnits::KB, UnitBase::Binary),
+            "total: 9007199254740993 | used: 9007199254740991"
+        );
+        assert_eq!(total_used_label(2048, 1024, &SizeUnits::MB, UnitBase::Binary), "total: 2 | used: 1.00");
+        assert_eq!(total_used_label(1048576, 0, &SizeUnits::GB, UnitBase::Binary), "total: 1.00 | used: 0.00");
+    }
+
+    #[test]
+    fn test_format_unit_value_plain_integers() {
+        // No exponent or fractional part for huge KB values
+        assert_eq!(format_unit_value(1e20, &SizeUnits::KB), "100000000000000000000");
+        assert_eq!(format_unit_value(((1u64 << 53) + 2) as f64, &SizeUnits::KB), "9007199254740994");
+        assert_eq!(format_unit_value(1.5, &SizeUnits::MB), "1.50");
     }
 
     #[test]
@@ -185,22 +1057,63 @@ mod tests {
     #[test]
     fn test_aggregate_dedup() {
         let procs = vec![
-            ProcessSwapInfo { pid: 1, name: "firefox".into(), swap_size: 100.0, last_cpu: None },
-            ProcessSwapInfo { pid: 2, name: "firefox".into(), swap_size: 200.0, last_cpu: None },
+            ProcessSwapInfo { pid: 1, name: "firefox".into(), swap_size: 100.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
+            ProcessSwapInfo { pid: 2, name: "firefox".into(), swap_size: 200.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
         ];
         let result = aggregate_processes(procs);
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].name, "firefox");
         assert_eq!(result[0].swap_size, 300.0);
-        assert_eq!(result[0].pid, 2); // count
+        assert_eq!(result[0].count, 2);
+        assert_eq!(result[0].pid, 0);
+    }
+
+    #[test]
+    fn test_aggregate_regroups_counts() {
+        // Re-aggregating keeps the member counts instead of counting rows
+        let procs = vec![
+            ProcessSwapInfo { pid: 0, name: "host".into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 3 },
+            ProcessSwapInfo { pid: 0, name: "host".into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: u32::MAX },
+        ];
+        let result = aggregate_processes(procs);
+        assert_eq!(result[0].count, u32::MAX);
+    }
+
+    #[test]
+    fn test_aggregate_sums_known_rss() {
+        let procs = vec![
+            ProcessSwapInfo { pid: 1, name: "worker".into(), swap_size: 1.0, last_cpu: None, rss_kb: Some(100), mnt_ns: None, count: 1 },
+            ProcessSwapInfo { pid: 2, name: "worker".into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
+            ProcessSwapInfo { pid: 3, name: "worker".into(), swap_size: 1.0, last_cpu: None, rss_kb: Some(50), mnt_ns: None, count: 1 },
+            ProcessSwapInfo { pid: 4, name: "ghost".into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
+        ];
+        let result = aggregate_processes(procs);
+        let rss = |name: &str| result.iter().find(|p| p.name == name).unwrap().rss_kb;
+        assert_eq!(rss("worker"), Some(150));
+        assert_eq!(rss("ghost"), None);
+    }
+
+    #[test]
+    fn test_kb_outside_topology() {
+        let node = |id| NumaNode { id, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![], node_type: NumaNodeType::Cpu, distances: vec![] };
+        let info = ProcessNumaInfo {
+            pid: 1,
+            name: "p".into(),
+            kb_per_node: HashMap::from([(0, 10), (2, 20), (9, 5)]),
+            total_kb: 35,
+            cpu_node: None,
+        };
+        assert_eq!(info.kb_outside(&[node(0), node(2)]), 5);
+        assert_eq!(info.kb_outside(&[node(0)]), 25);
+        assert_eq!(info.kb_outside(&[node(0), node(2), node(9)]), 0);
     }
 
     #[test]
     fn test_aggregate_sorted() {
         let procs = vec![
-            ProcessSwapInfo { pid: 1, name: "small".into(), swap_size: 10.0, last_cpu: None },
-            ProcessSwapInfo { pid: 2, name: "big".into(), swap_size: 500.0, last_cpu: None },
-            ProcessSwapInfo { pid: 3, name: "medium".into(), swap_size: 100.0, last_cpu: None },
+            ProcessSwapInfo { pid: 1, name: "small".into(), swap_size: 10.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
+            ProcessSwapInfo { pid: 2, name: "big".into(), swap_size: 500.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
+            ProcessSwapInfo { pid: 3, name: "medium".into(), swap_size: 100.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
         ];
         let result = aggregate_processes(procs);
         assert_eq!(result[0].name, "big");
@@ -208,6 +1121,339 @@ mod tests {
         assert_eq!(result[2].name, "small");
     }
 
+    #[test]
+    fn test_aggregate_unified_sums_same_name() {
+        let unified = |pid, name: &str, swap_kb, gpu_memory_kb, location| UnifiedProcessInfo {
+            pid, name: name.into(), swap_kb, cpu_nodes: vec![pid % 2], gpu_nodes: vec![],
+            kb_per_node: HashMap::from([(0, swap_kb)]), gpu_memory_kb, gpu_indices: gpu_memory_kb.map(|_| vec![pid]).unwrap_or_default(),
+            location, rss_kb: Some(10), count: 1,
+        };
+        let procs = vec![
+            unified(1, "loader", 100, None, ProcessLocation::CpuOnly),
+            unified(2, "other", 5, None, ProcessLocation::CpuOnly),
+            unified(3, "loader", 50, Some(400), ProcessLocation::CpuAndGpu),
+        ];
+        let result = aggregate_unified(procs);
+        assert_eq!(result.len(), 2);
+        let loader = result.iter().find(|p| p.name == "loader").unwrap();
+        assert_eq!((loader.pid, loader.count), (0, 2));
+        assert_eq!(loader.swap_kb, 150);
+        assert_eq!(loader.gpu_memory_kb, Some(400));
+        assert_eq!(loader.rss_kb, Some(20));
+        assert_eq!(loader.kb_per_node[&0], 150);
+        assert_eq!(loader.cpu_nodes, vec![1]);
+        assert_eq!(loader.gpu_indices, vec![3]);
+        // 450 KB on CPU+GPU against 100 KB CPU-only
+        assert_eq!(loader.location, ProcessLocation::CpuAndGpu);
+        assert_eq!(result.iter().find(|p| p.name == "other").unwrap().count, 1);
+    }
+
+    #[test]
+    fn test_counter_rate() {
+        assert_eq!(counter_rate(1000, 1500, 1.0), 500.0);
+        assert_eq!(counter_rate(1000, 1500, 2.0), 250.0);
+        assert_eq!(counter_rate(1500, 1000, 1.0), 0.0); // counter reset
+        assert_eq!(counter_rate(0, 100, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_thrash_level_quiet_system() {
+        assert_eq!(thrash_level(Some(0.0), 0.0), ThrashLevel::Low);
+    }
+
+    #[test]
+    fn test_thrash_level_single_signal_is_not_high() {
+        // Stalls without swap I/O (page-cache reclaim)
+        assert_eq!(thrash_level(Some(50.0), 0.0), ThrashLevel::Medium);
+        // Heavy swap-out without stalls
+        assert_eq!(thrash_level(Some(0.0), 5000.0), ThrashLevel::Medium);
+        // One signal only medium
+        assert_eq!(thrash_level(Some(2.0), 0.0), ThrashLevel::Low);
+    }
+
+    #[test]
+    fn test_thrash_level_both_elevated() {
+        assert_eq!(thrash_level(Some(2.0), 200.0), ThrashLevel::Medium);
+        assert_eq!(thrash_level(Some(15.0), 200.0), ThrashLevel::High);
+        assert_eq!(thrash_level(Some(2.0), 2000.0), ThrashLevel::High);
+        assert_eq!(thrash_level(Some(15.0), 2000.0), ThrashLevel::High);
+    }
+
+    #[test]
+    fn test_thrash_level_without_psi() {
+        assert_eq!(thrash_level(None, 0.0), ThrashLevel::Low);
+        assert_eq!(thrash_level(None, 200.0), ThrashLevel::Medium);
+        assert_eq!(thrash_level(None, 5000.0), ThrashLevel::Medium);
+    }
+
+    #[test]
+    fn test_baseline_capture() {
+        let swap = vec![
+            ProcessSwapInfo { pid: 1, name: "worker".into(), swap_size: 100.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
+            ProcessSwapInfo { pid: 2, name: "worker".into(), swap_size: 50.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
+        ];
+        let gpu = vec![
+            GpuProcessInfo { pid: 1, name: "worker".into(), gpu_index: 0, gpu_memory_used_kb: 10, sm_util: None, mem_util: None },
+            GpuProcessInfo { pid: 1, name: "worker".into(), gpu_index: 1, gpu_memory_used_kb: 20, sm_util: None, mem_util: None },
+            // GPU-only: keyed by the GPU tool's name
+            GpuProcessInfo { pid: 9, name: "cuda-app".into(), gpu_index: 0, gpu_memory_used_kb: 5, sm_util: None, mem_util: None },
+        ];
+        let baseline = Baseline::capture(&swap, &gpu);
+        assert_eq!(baseline.swap_kb.get(&1), Some(&100));
+        assert_eq!(baseline.swap_kb_by_name.get("worker"), Some(&150));
+        assert_eq!(baseline.gpu_kb.get(&1), Some(&30));
+        assert_eq!(baseline.gpu_kb_by_name.get("worker"), Some(&30));
+        assert_eq!(baseline.gpu_kb_by_name.get("cuda-app"), Some(&5));
+    }
+
+    #[test]
+    fn test_process_names_detects_exec() {
+        let mut names = ProcessNames::default();
+        names.observe(&[ProcessSwapInfo { pid: 7, name: "bash".into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 }]);
+        assert_eq!(names.previous_name(7), None);
+
+        names.observe(&[ProcessSwapInfo { pid: 7, name: "python3".into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 }]);
+        assert_eq!(names.previous_name(7), Some("bash"));
+
+        // Mark persists while the PID lives, and is dropped when it exits
+        names.observe(&[ProcessSwapInfo { pid: 7, name: "python3".into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 }]);
+        assert_eq!(names.previous_name(7), Some("bash"));
+        names.observe(&[]);
+        assert_eq!(names.previous_name(7), None);
+    }
+
+    #[test]
+    fn test_flattened_memory_sums_mig_instances() {
+        let mig = |device, used| MigInstance {
+            device, gpu_instance: device + 1, compute_instance: 0,
+            memory_total_kb: 20_000, memory_used_kb: used,
+        };
+        let mut dev = GpuDevice {
+            index: 0, name: "A100".into(), memory_total_kb: 40_960, memory_used_kb: 100,
+            memory_free_kb: 40_860, numa_node_id: None, temperature: None,
+            pci_bus_id: String::new(), memory_reserved_kb: None, mig_instances: Vec::new(), utilization_percent: None, memory_utilization_percent: None,
+        };
+        assert_eq!(dev.flattened_memory_kb(), (40_960, 100, 40_860));
+        dev.mig_instances = vec![mig(0, 300), mig(1, 700)];
+        assert_eq!(dev.flattened_memory_kb(), (40_960, 1000, 39_960));
+    }
+
+    #[test]
+    fn test_is_idle_but_held() {
+        let mut dev = GpuDevice {
+            index: 1, name: "H100".into(), memory_total_kb: 81_920, memory_used_kb: 40_960,
+            memory_free_kb: 40_960, numa_node_id: None, temperature: None,
+            pci_bus_id: String::new(), memory_reserved_kb: None, mig_instances: Vec::new(),
+            utilization_percent: Some(0),
+            memory_utilization_percent: None,
+        };
+        let on = |gpu_index| GpuProcessInfo { pid: 7, name: "train".into(), gpu_index, gpu_memory_used_kb: 40_960, sm_util: None, mem_util: None };
+        assert!(dev.is_idle_but_held(&[on(1)], 0));
+        // No process on this GPU: free, not held
+        assert!(!dev.is_idle_but_held(&[on(0)], 0));
+        dev.utilization_percent = Some(3);
+        assert!(!dev.is_idle_but_held(&[on(1)], 0));
+        assert!(dev.is_idle_but_held(&[on(1)], 5));
+        dev.utilization_percent = None;
+        assert!(!dev.is_idle_but_held(&[on(1)], 100));
+    }
+
+    #[test]
+    fn test_swap_deltas_signed_rates() {
+        let row = |pid, name: &str, swap_size| ProcessSwapInfo { pid, name: name.into(), swap_size, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 };
+        let mut deltas = SwapDeltas::default();
+        deltas.update(&[row(1, "a", 1000.0), row(2, "b", 500.0)], &SizeUnits::KB, false, 1.0);
+        // First sighting: no rate yet
+        assert_eq!(deltas.rate(&row(1, "a", 1000.0), false), None);
+
+        deltas.update(&[row(1, "a", 1400.0), row(2, "b", 300.0), row(3, "c", 10.0)], &SizeUnits::KB, false, 2.0);
+        assert_eq!(deltas.rate(&row(1, "a", 0.0), false), Some(200.0));
+        assert_eq!(deltas.rate(&row(2, "b", 0.0), false), Some(-100.0));
+        assert_eq!(deltas.rate(&row(3, "c", 0.0), false), None);
+
+        // Rows in MB are converted back to KB
+        deltas.update(&[row(1, "a", 2.0)], &SizeUnits::MB, false, 1.0);
+        assert_eq!(deltas.rate(&row(1, "a", 0.0), false), Some(2048.0 - 1400.0));
+    }
+
+    #[test]
+    fn test_swap_deltas_aggregated_by_name() {
+        let row = |pid, name: &str, swap_size| ProcessSwapInfo { pid, name: name.into(), swap_size, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 };
+        let mut deltas = SwapDeltas::default();
+        deltas.update(&[row(1, "a", 100.0)], &SizeUnits::KB, false, 1.0);
+        // Switching modes starts over
+        deltas.update(&[row(3, "a", 100.0)], &SizeUnits::KB, true, 1.0);
+        assert_eq!(deltas.rate(&row(3, "a", 0.0), true), None);
+        deltas.update(&[row(2, "a", 160.0)], &SizeUnits::KB, true, 2.0);
+        assert_eq!(deltas.rate(&row(2, "a", 0.0), true), Some(30.0));
+        assert_eq!(deltas.rate(&row(2, "a", 0.0), false), None);
+    }
+
+    #[test]
+    fn test_swap_deltas_trend() {
+        let row = |pid, name: &str, swap_size| ProcessSwapInfo { pid, name: name.into(), swap_size, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 };
+        let mut deltas = SwapDeltas::default();
+        deltas.update(&[row(1, "a", 100.0)], &SizeUnits::KB, false, 1.0);
+        // Nothing to compare against yet
+        assert_eq!(deltas.trend(&row(1, "a", 0.0), false), None);
+
+        deltas.update(&[row(1, "a", 40.0), row(2, "b", 5.0)], &SizeUnits::KB, false, 0.0);
+        assert_eq!(deltas.trend(&row(1, "a", 0.0), false), Some(SwapTrend::Changed(-60.0)));
+        assert_eq!(deltas.trend(&row(2, "b", 0.0), false), Some(SwapTrend::New));
+        // No time passed: a change but no rate
+        assert_eq!(deltas.rate(&row(1, "a", 0.0), false), None);
+
+        // Aggregated rows start over, then compare by name
+        deltas.update(&[row(7, "a", 40.0)], &SizeUnits::KB, true, 1.0);
+        assert_eq!(deltas.trend(&row(7, "a", 0.0), true), None);
+        deltas.update(&[row(8, "a", 50.0)], &SizeUnits::KB, true, 1.0);
+        assert_eq!(deltas.trend(&row(8, "a", 0.0), true), Some(SwapTrend::Changed(10.0)));
+    }
+
+    #[test]
+    fn test_pid_watch_filters_to_watched_pids() {
+        let mut watch = PidWatch::new(vec![2]);
+        let mut procs = vec![
+            ProcessSwapInfo { pid: 1, name: "other".into(), swap_size: 9.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
+            ProcessSwapInfo { pid: 2, name: "job".into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 },
+        ];
+        watch.filter_swap(&mut procs);
+        assert_eq!(procs.len(), 1);
+        assert_eq!(procs[0].pid, 2);
+    }
+
+    #[test]
+    fn test_pid_watch_shows_exited_for_a_few_ticks() {
+        let mut watch = PidWatch::new(vec![2, 3]);
+        let mut procs = vec![ProcessSwapInfo { pid: 2, name: "job".into(), swap_size: 1.0, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 }];
+        watch.filter_swap(&mut procs);
+        watch.update(1, |_| true);
+        assert!(watch.exited_rows().is_empty());
+
+        watch.update(2, |pid| pid != 2);
+        assert!(!watch.contains(2));
+        assert!(watch.contains(3));
+        let rows = watch.exited_rows();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].name, "job (exited)");
+
+        watch.update(2 + EXITED_TICKS - 1, |pid| pid != 2);
+        assert_eq!(watch.exited_rows().len(), 1);
+        watch.update(2 + EXITED_TICKS, |pid| pid != 2);
+        assert!(watch.exited_rows().is_empty());
+        // Dropped for good, even if the PID is reused later
+        watch.update(10, |_| true);
+        assert!(!watch.contains(2));
+    }
+
+    #[test]
+    fn test_used_kb_modes() {
+        let update = SwapUpdate { used_swap: 1000, swap_cached: Some(300), ..Default::default() };
+        assert_eq!(update.used_kb(SwapUsedMode::IncludeCached), 1000);
+        assert_eq!(update.used_kb(SwapUsedMode::ExcludeCached), 700);
+
+        // Unknown SwapCached: nothing subtracted; never underflows
+        let update = SwapUpdate { used_swap: 1000, ..Default::default() };
+        assert_eq!(update.used_kb(SwapUsedMode::ExcludeCached), 1000);
+        let update = SwapUpdate { used_swap: 10, swap_cached: Some(50), ..Default::default() };
+        assert_eq!(update.used_kb(SwapUsedMode::ExcludeCached), 0);
+    }
+
+    #[test]
+    fn test_swapped_since_boot_kb() {
+        let mut update = SwapUpdate { swap_in_pages: Some(100), swap_out_pages: Some(250), ..Default::default() };
+        assert_eq!(update.swapped_since_boot_kb(), None);
+        update.page_size_kb = Some(4);
+        assert_eq!(update.swapped_since_boot_kb(), Some((400, 1000)));
+        update.swap_out_pages = None;
+        assert_eq!(update.swapped_since_boot_kb(), None);
+    }
+
+    #[test]
+    fn test_or_previous_carries_forward_torn_read() {
+        let prev = SwapUpdate { total_swap: 8000, used_swap: 2000, swap_cached: Some(100), ..Default::default() };
+        let torn = SwapUpdate { total_swap: 8000, used_swap: 0, torn_read: true, swappiness: Some(10), ..Default::default() };
+        let smoothed = torn.clone().or_previous(&prev);
+        assert_eq!((smoothed.used_swap, smoothed.swap_cached, smoothed.torn_read), (2000, Some(100), false));
+        // Other fields still come from the new read
+        assert_eq!(smoothed.swappiness, Some(10));
+
+        // Consistent reads replace the previous sample, even when used drops to 0
+        let fine = SwapUpdate { total_swap: 8000, used_swap: 0, ..Default::default() };
+        assert_eq!(fine.or_previous(&prev).used_swap, 0);
+        // Nothing valid to fall back on
+        assert!(torn.clone().or_previous(&SwapUpdate::default()).torn_read);
+    }
+
+    fn swap_dev(name: &str, priority: isize, media: MediaClass) -> InfoSwap {
+        InfoSwap {
+            name: name.into(), path: name.into(), kind: "partition".into(), size_kb: 1.0, used_kb: 0.0, priority, media,
+            scheduler: None, read_ahead_kb: None,
+        }
+    }
+
+    #[test]
+    fn test_swapping_with_free_ram() {
+        let thresholds = SwappinessHintThresholds::default();
+        let update = SwapUpdate {
+            used_swap: 4096, mem_total_kb: Some(1000), mem_available_kb: Some(600), ..Default::default()
+        };
+        assert!(swapping_with_free_ram(&update, 4096, &thresholds));
+        // Below the minimum, or nothing swapped at all
+        assert!(!swapping_with_free_ram(&update, 512, &thresholds));
+        let none = SwappinessHintThresholds { min_used_kb: 0, ..thresholds };
+        assert!(!swapping_with_free_ram(&update, 0, &none));
+        // RAM is actually short
+        let tight = SwapUpdate { mem_available_kb: Some(100), ..update.clone() };
+        assert!(!swapping_with_free_ram(&tight, 4096, &thresholds));
+        // MemAvailable unknown (old kernel)
+        let unknown = SwapUpdate { mem_available_kb: None, ..update };
+        assert!(!swapping_with_free_ram(&unknown, 4096, &thresholds));
+    }
+
+    #[test]
+    fn test_priority_warnings_flags_hdd_above_ssd() {
+        let devices = vec![
+            swap_dev("/dev/sda2", 10, MediaClass::Hdd),
+            swap_dev("/dev/nvme0n1p3", -2, MediaClass::Nvme),
+        ];
+        assert_eq!(priority_warnings(&devices), vec![true, false]);
+    }
+
+    #[test]
+    fn test_media_summary_groups_by_class() {
+        let mut hdd1 = swap_dev("/dev/sda2", 0, MediaClass::Hdd);
+        hdd1.size_kb = 600.0;
+        hdd1.used_kb = 300.0;
+        let mut hdd2 = swap_dev("/dev/sdb2", 0, MediaClass::Hdd);
+        hdd2.size_kb = 200.0;
+        let mut zram = swap_dev("/dev/zram0", 100, MediaClass::Zram);
+        zram.size_kb = 200.0;
+        zram.used_kb = 200.0;
+
+        let summary = media_summary(&[zram, hdd1, hdd2]);
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0], MediaShare { media: MediaClass::Hdd, capacity_percent: 80.0, used_percent: 37.5 });
+        assert_eq!(summary[1], MediaShare { media: MediaClass::Zram, capacity_percent: 20.0, used_percent: 100.0 });
+    }
+
+    #[test]
+    fn test_media_summary_empty() {
+        assert!(media_summary(&[]).is_empty());
+        assert!(media_summary(&[InfoSwap { size_kb: 0.0, ..swap_dev("x", 0, MediaClass::Ssd) }]).is_empty());
+    }
+
+    #[test]
+    fn test_priority_warnings_ok_configs() {
+        // Fast device first, equal priorities, or no fast device at all
+        let ordered = vec![swap_dev("hdd", -3, MediaClass::Hdd), swap_dev("ssd", 5, MediaClass::Ssd)];
+        assert_eq!(priority_warnings(&ordered), vec![false, false]);
+        let equal = vec![swap_dev("hdd", 1, MediaClass::Hdd), swap_dev("ssd", 1, MediaClass::Ssd)];
+        assert_eq!(priority_warnings(&equal), vec![false, false]);
+        let unknown = vec![swap_dev("hdd", 5, MediaClass::Hdd), swap_dev("zram0", -2, MediaClass::Unknown)];
+        assert_eq!(priority_warnings(&unknown), vec![false, false]);
+    }
+
     #[test]
     fn test_size_units_default() {
         assert_eq!(SizeUnits::default(), SizeUnits::KB);
@@ -220,6 +1466,9 @@ mod tests {
             name: "test".into(),
             swap_size: 100.0,
             last_cpu: Some(3),
+            rss_kb: None,
+            mnt_ns: None,
+            count: 1,
         };
         assert_eq!(info.last_cpu, Some(3));
     }
@@ -0,0 +1,47 @@
+//! `--verbose` diagnostics: report input lines the parsers skip, so a format
+//! mismatch (e.g. a different nvidia-smi version) can be told apart from
+//! "no data". Silent unless enabled.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Messages already printed; parsers run every refresh and would otherwise
+/// repeat the same warning each tick.
+static REPORTED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+pub fn set_verbose(enabled: bool) {
+    VERBOSE.store(enabled, Ordering::Relaxed);
+}
+
+/// Log to stderr that `source` skipped `line` because of `reason`. Each
+/// distinct message is printed once.
+pub fn skipped(source: &str, line: &str, reason: &str) {
+    if !VERBOSE.load(Ordering::Relaxed) {
+        return;
+    }
+    let message = skip_message(source, line, reason);
+    let mut reported = REPORTED.lock().unwrap_or_else(|e| e.into_inner());
+    if reported.get_or_insert_with(HashSet::new).insert(message.clone()) {
+        eprintln!("{}", message);
+    }
+}
+
+fn skip_message(source: &str, line: &str, reason: &str) -> String {
+    format!("nv-swaptop: {}: skipped {:?}: {}", source, line, reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_message() {
+        assert_eq!(
+            skip_message("nvidia-smi --query-compute-apps", "0, abc, x, 1 MiB", "bad pid `abc`"),
+            "nv-swaptop: nvidia-smi --query-compute-apps: skipped \"0, abc, x, 1 MiB\": bad pid `abc`"
+        );
+    }
+}
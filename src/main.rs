@@ -1,6 +1,13 @@
+mod alerts;
 mod app;
 mod cli;
+mod config;
 mod data;
+mod numa_csv;
+mod record;
+#[cfg(feature = "serve")]
+mod serve;
+mod snapshot;
 mod theme;
 mod ui;
 
@@ -9,13 +16,85 @@ mod smoke_tests;
 
 use app::App;
 use clap::Parser;
-use data::ProcDataProvider;
 
 fn main() -> color_eyre::Result<()> {
     let cli = cli::Cli::parse();
     color_eyre::install()?;
+    data::verbose::set_verbose(cli.verbose);
+    data::gpu::set_pmon_enabled(cli.gpu_pmon);
+    if let Some(path) = &cli.nvidia_smi {
+        data::gpu::set_nvidia_smi_path(path);
+    }
+    let provider_kind = if cli.demo { data::ProviderKind::Demo } else { data::ProviderKind::Proc };
+    let provider = provider_kind.build();
+    if cli.dump_numa_csv {
+        if !provider.is_numa_available() {
+            return Err(color_eyre::eyre::eyre!("NUMA topology not available (/sys/devices/system/node)"));
+        }
+        let (nodes, infos) = numa_csv::collect(provider.as_ref(), cli.top_n);
+        print!("{}", numa_csv::to_csv(&nodes, &infos));
+        return Ok(());
+    }
+    let thresholds = alerts::AlertThresholds {
+        swap_percent: cli.swap_alert,
+        gpu_percent: cli.gpu_alert,
+        thrash_pages_per_sec: cli.thrash_alert,
+    };
+    let used_mode = if cli.exclude_swap_cache {
+        data::SwapUsedMode::ExcludeCached
+    } else {
+        data::SwapUsedMode::IncludeCached
+    };
+    if cli.once {
+        let snapshot = snapshot::collect_snapshot_with(provider.as_ref(), &cli.pid, used_mode);
+        print!("{}", snapshot::render(&snapshot, cli.format));
+        return Ok(());
+    }
+    let app = App::new(provider, cli.demo)
+        .with_theme(cli.theme)
+        .with_max_processes(cli.max_processes)
+        .with_top_n(cli.top_n)
+        .with_history_len(cli.history_len)
+        .with_resort_interval(cli.resort_interval)
+        .with_used_mode(used_mode)
+        .with_watched_pids(cli.pid)
+        .with_alerts(thresholds, cli.alert_log)
+        .with_idle_gpu_highlight(ui::gpu_view::IdleGpuHighlight {
+            max_utilization_percent: cli.idle_gpu_util,
+            color: cli.idle_gpu_color,
+        })
+        .with_swappiness_hint(data::SwappinessHintThresholds {
+            available_percent: cli.swappiness_hint_available,
+            min_used_kb: cli.swappiness_hint_min_mb.saturating_mul(1024),
+        })
+        .with_swap_highlight(cli.swap_highlight_kb)
+        .with_debug(cli.debug);
+    let app = match &cli.record {
+        Some(path) => app.with_recorder(record::Recorder::new(
+            path,
+            cli.record_max_mb.saturating_mul(1024 * 1024),
+            cli.record_keep,
+        )),
+        None => app,
+    };
+    if cli.print_config {
+        eprint!("{}", app.effective_config().to_toml());
+        return Ok(());
+    }
+    #[cfg(feature = "serve")]
+    if let Some(addr) = &cli.serve {
+        let server = serve::spawn(addr, provider_kind)?;
+        if cli.headless {
+            return server.join().map_err(|_| color_eyre::eyre::eyre!("--serve thread panicked"));
+        }
+    }
     let terminal = ratatui::init();
-    let result = App::new(Box::new(ProcDataProvider), cli.demo).run(terminal);
+    crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture)?;
+    let result = app.run(terminal);
+    let _ = crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture);
     ratatui::restore();
-    result
+    if let Some(config) = result? {
+        eprint!("{}", config);
+    }
+    Ok(())
 }
@@ -0,0 +1,119 @@
+//! `--dump-numa-csv`: one-shot export of the NUMA view's per-process memory
+//! distribution, one row per process and one column per NUMA node.
+
+use std::fmt::Write;
+
+use crate::data::{DataProvider, NumaNode, ProcessNumaInfo, SizeUnits, collect_process_numa_infos};
+
+/// Read the topology and numa_maps of the `top_n` largest swap consumers, as
+/// the NUMA view does.
+pub fn collect(provider: &dyn DataProvider, top_n: usize) -> (Vec<NumaNode>, Vec<ProcessNumaInfo>) {
+    let mut nodes = provider.get_numa_topology().unwrap_or_default();
+    nodes.sort_by_key(|n| n.id);
+    let swap_procs = provider.get_processes_swap(&SizeUnits::KB).unwrap_or_default();
+    let infos = collect_process_numa_infos(provider, &swap_procs, &nodes, top_n);
+    (nodes, infos)
+}
+
+/// Header `pid,name,cpu_node,misaligned,total_kb,n<id>_kb...,other_kb`. Per-node
+/// values are KB, since numa_maps page counts of different page sizes are not
+/// comparable; `other_kb` is memory on nodes missing from the topology.
+pub fn to_csv(nodes: &[NumaNode], infos: &[ProcessNumaInfo]) -> String {
+    let mut out = String::from("pid,name,cpu_node,misaligned,total_kb");
+    for node in nodes {
+        let _ = write!(out, ",n{}_kb", node.id);
+    }
+    out.push_str(",other_kb\n");
+
+    for info in infos {
+        let cpu_node = info.cpu_node.map(|n| n.to_string()).unwrap_or_default();
+        let _ = write!(
+            out,
+            "{},{},{},{},{}",
+            info.pid,
+            csv_field(&info.name),
+            cpu_node,
+            info.is_misaligned(),
+            info.total_kb
+        );
+        for node in nodes {
+            let _ = write!(out, ",{}", info.kb_per_node.get(&node.id).copied().unwrap_or(0));
+        }
+        let _ = writeln!(out, ",{}", info.kb_outside(nodes));
+    }
+    out
+}
+
+/// Quote a field per RFC 4180 when it contains a separator, quote or newline.
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{MockDataProvider, NUMA_MAPS_TOP_N, NumaNodeType};
+    use std::collections::HashMap;
+
+    fn node(id: u32) -> NumaNode {
+        NumaNode { id, memory_total_kb: 0, memory_free_kb: 0, cpus: vec![], node_type: NumaNodeType::Cpu, distances: vec![] }
+    }
+
+    #[test]
+    fn test_to_csv_matrix() {
+        let infos = vec![ProcessNumaInfo {
+            pid: 42,
+            name: "trainer".into(),
+            kb_per_node: HashMap::from([(0, 100), (1, 900)]),
+            total_kb: 1000,
+            cpu_node: Some(0),
+        }];
+        let csv = to_csv(&[node(0), node(1)], &infos);
+        assert_eq!(csv, "pid,name,cpu_node,misaligned,total_kb,n0_kb,n1_kb,other_kb\n42,trainer,0,true,1000,100,900,0\n");
+    }
+
+    #[test]
+    fn test_to_csv_unknown_cpu_and_missing_node() {
+        let infos = vec![ProcessNumaInfo {
+            pid: 7,
+            name: "idle".into(),
+            kb_per_node: HashMap::from([(0, 64)]),
+            total_kb: 64,
+            cpu_node: None,
+        }];
+        let csv = to_csv(&[node(0), node(1)], &infos);
+        assert!(csv.ends_with("7,idle,,false,64,64,0,0\n"));
+    }
+
+    #[test]
+    fn test_to_csv_counts_nodes_outside_topology() {
+        let infos = vec![ProcessNumaInfo {
+            pid: 9,
+            name: "hotplug".into(),
+            kb_per_node: HashMap::from([(0, 10), (5, 30)]),
+            total_kb: 40,
+            cpu_node: Some(0),
+        }];
+        let csv = to_csv(&[node(0)], &infos);
+        assert!(csv.ends_with("9,hotplug,0,true,40,10,30\n"));
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_collect_uses_mock_topology() {
+        let (nodes, infos) = collect(&MockDataProvider::new(), NUMA_MAPS_TOP_N);
+        assert!(!nodes.is_empty());
+        assert!(nodes.windows(2).all(|w| w[0].id < w[1].id));
+        assert!(infos.len() <= NUMA_MAPS_TOP_N);
+    }
+}
@@ -0,0 +1,224 @@
+//! `--record PATH`: append one CSV row per refresh tick for long captures.
+//! When the file would grow past its size limit it is rotated by renaming:
+//! PATH becomes PATH.1, PATH.1 becomes PATH.2 and so on, keeping at most
+//! `keep` rotated files; the oldest is deleted.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::numa_csv::csv_field;
+
+pub const DEFAULT_MAX_MB: u64 = 100;
+pub const DEFAULT_KEEP: usize = 5;
+
+pub const HEADER: &str = "timestamp_ms,total_swap_kb,used_swap_kb,top_pid,top_name,top_swap_kb,gpu_used_kb,gpu_total_kb\n";
+
+/// One tick's worth of data. All sizes are KB.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordRow {
+    pub timestamp_ms: u128,
+    pub total_swap_kb: u64,
+    pub used_swap_kb: u64,
+    /// (pid, name, swap KB) of the largest swap row; pid is None for
+    /// aggregated rows
+    pub top_process: Option<(Option<u32>, String, u64)>,
+    /// (used, total) summed over all GPUs; None without GPUs
+    pub gpu: Option<(u64, u64)>,
+}
+
+impl RecordRow {
+    /// Empty fields for unknown values, so the column count never changes.
+    pub fn to_csv(&self) -> String {
+        let (top_pid, top_name, top_swap) = match &self.top_process {
+            Some((pid, name, kb)) => (pid.map(|p| p.to_string()).unwrap_or_default(), csv_field(name), kb.to_string()),
+            None => Default::default(),
+        };
+        let (gpu_used, gpu_total) = match self.gpu {
+            Some((used, total)) => (used.to_string(), total.to_string()),
+            None => Default::default(),
+        };
+        format!(
+            "{},{},{},{},{},{},{},{}\n",
+            self.timestamp_ms, self.total_swap_kb, self.used_swap_kb, top_pid, top_name, top_swap, gpu_used, gpu_total
+        )
+    }
+}
+
+/// Milliseconds since the Unix epoch.
+pub fn now_ms() -> u128 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0)
+}
+
+pub struct Recorder {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+    /// Opened on the first row, so `--print-config` creates no file
+    file: Option<File>,
+    /// Bytes in the current file
+    size: u64,
+}
+
+impl Recorder {
+    /// Append to `path`, writing the header first if the file is new or
+    /// empty. Rotates once the file would exceed `max_bytes`.
+    pub fn new(path: impl Into<PathBuf>, max_bytes: u64, keep: usize) -> Self {
+        Self { path: path.into(), max_bytes, keep, file: None, size: 0 }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    pub fn keep(&self) -> usize {
+        self.keep
+    }
+
+    pub fn record(&mut self, row: &RecordRow) -> io::Result<()> {
+        let line = row.to_csv();
+        if self.file.is_none() {
+            self.reopen()?;
+        }
+        // A file holding only the header is never rotated, so a row larger
+        // than the limit is still written
+        if self.size > HEADER.len() as u64 && self.size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(line.as_bytes())?;
+        }
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    fn reopen(&mut self) -> io::Result<()> {
+        let (file, size) = open_with_header(&self.path)?;
+        self.file = Some(file);
+        self.size = size;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.keep == 0 {
+            std::fs::remove_file(&self.path)?;
+        } else {
+            match std::fs::remove_file(rotated_path(&self.path, self.keep)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+            for n in (1..self.keep).rev() {
+                let from = rotated_path(&self.path, n);
+                if from.exists() {
+                    std::fs::rename(&from, rotated_path(&self.path, n + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        }
+        self.reopen()
+    }
+}
+
+fn open_with_header(path: &Path) -> io::Result<(File, u64)> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut size = file.metadata()?.len();
+    if size == 0 {
+        file.write_all(HEADER.as_bytes())?;
+        size = HEADER.len() as u64;
+    }
+    Ok((file, size))
+}
+
+/// PATH.n
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nv-swaptop-record-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn row(timestamp_ms: u128) -> RecordRow {
+        RecordRow { timestamp_ms, total_swap_kb: 1000, used_swap_kb: 250, ..Default::default() }
+    }
+
+    #[test]
+    fn test_row_to_csv() {
+        let full = RecordRow {
+            timestamp_ms: 1_700_000_000_123,
+            total_swap_kb: 8_000_000,
+            used_swap_kb: 2_000_000,
+            top_process: Some((Some(42), "python, worker".into(), 1_500_000)),
+            gpu: Some((40_960, 81_920)),
+        };
+        assert_eq!(full.to_csv(), "1700000000123,8000000,2000000,42,\"python, worker\",1500000,40960,81920\n");
+        // Aggregated top row, no GPUs
+        let sparse = RecordRow { top_process: Some((None, "firefox".into(), 10)), ..row(5) };
+        assert_eq!(sparse.to_csv(), "5,1000,250,,firefox,10,,\n");
+        assert_eq!(row(5).to_csv().matches(',').count(), HEADER.matches(',').count());
+    }
+
+    #[test]
+    fn test_recorder_appends_with_single_header() {
+        let dir = temp_dir("append");
+        let path = dir.join("swap.csv");
+        Recorder::new(&path, 1 << 20, 2).record(&row(1)).unwrap();
+        Recorder::new(&path, 1 << 20, 2).record(&row(2)).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(content, format!("{}{}{}", HEADER, row(1).to_csv(), row(2).to_csv()));
+    }
+
+    #[test]
+    fn test_recorder_rotates_and_keeps_bounded_files() {
+        let dir = temp_dir("rotate");
+        let path = dir.join("swap.csv");
+        let line_len = row(1).to_csv().len() as u64;
+        // Room for the header and two rows per file
+        let mut recorder = Recorder::new(&path, HEADER.len() as u64 + 2 * line_len, 2);
+        for ts in 1..=7 {
+            recorder.record(&row(ts)).unwrap();
+        }
+        let read = |p: PathBuf| std::fs::read_to_string(p).ok();
+        let current = read(path.clone());
+        let first = read(rotated_path(&path, 1));
+        let second = read(rotated_path(&path, 2));
+        let third = read(rotated_path(&path, 3));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(current, Some(format!("{}{}", HEADER, row(7).to_csv())));
+        assert_eq!(first, Some(format!("{}{}{}", HEADER, row(5).to_csv(), row(6).to_csv())));
+        assert_eq!(second, Some(format!("{}{}{}", HEADER, row(3).to_csv(), row(4).to_csv())));
+        // Rows 1-2 were in the oldest file, beyond `keep`
+        assert_eq!(third, None);
+    }
+
+    #[test]
+    fn test_recorder_without_kept_files_starts_over() {
+        let dir = temp_dir("keep0");
+        let path = dir.join("swap.csv");
+        let line_len = row(1).to_csv().len() as u64;
+        let mut recorder = Recorder::new(&path, HEADER.len() as u64 + line_len, 0);
+        recorder.record(&row(1)).unwrap();
+        recorder.record(&row(2)).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let rotated = rotated_path(&path, 1).exists();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(content, format!("{}{}", HEADER, row(2).to_csv()));
+        assert!(!rotated);
+    }
+}
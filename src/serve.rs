@@ -0,0 +1,86 @@
+//! Minimal HTTP endpoint for scraping (`--serve <addr>`, `serve` feature).
+//!
+//! Routes:
+//!   /metrics        Prometheus text exposition format
+//!   /snapshot.json  Unified swap + GPU snapshot as JSON
+
+use std::thread::{self, JoinHandle};
+
+use tiny_http::{Header, Response, Server};
+
+use crate::data::{DataProvider, ProviderKind};
+use crate::snapshot;
+
+/// Bind `addr` and serve requests on a background thread.
+/// Each request collects a fresh snapshot from the thread's own provider of
+/// `kind`, so the server is independent of the TUI refresh loop.
+pub fn spawn(addr: &str, kind: ProviderKind) -> std::io::Result<JoinHandle<()>> {
+    let server = Server::http(addr).map_err(|e| std::io::Error::other(e.to_string()))?;
+    Ok(thread::spawn(move || {
+        let provider = kind.build();
+        for request in server.incoming_requests() {
+            let (status, content_type, body) = route(request.url(), provider.as_ref());
+            let header = Header::from_bytes("Content-Type", content_type)
+                .expect("static header is valid");
+            let response = Response::from_string(body)
+                .with_status_code(status)
+                .with_header(header);
+            let _ = request.respond(response);
+        }
+    }))
+}
+
+/// Map a request path to (status, content type, body).
+fn route(url: &str, provider: &dyn DataProvider) -> (u16, &'static str, String) {
+    let path = url.split('?').next().unwrap_or(url);
+    match path {
+        "/metrics" => (
+            200,
+            "text/plain; version=0.0.4",
+            snapshot::to_prometheus(&snapshot::collect_snapshot(provider)),
+        ),
+        "/snapshot.json" => (
+            200,
+            "application/json",
+            snapshot::to_json(&snapshot::collect_snapshot(provider)),
+        ),
+        _ => (404, "text/plain", "not found\n".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MockDataProvider;
+
+    #[test]
+    fn test_route_metrics() {
+        let (status, content_type, body) = route("/metrics", &MockDataProvider::new());
+        assert_eq!(status, 200);
+        assert!(content_type.starts_with("text/plain"));
+        assert!(body.contains("nv_swaptop_swap_total_bytes"));
+    }
+
+    #[test]
+    fn test_route_snapshot_json_ignores_query() {
+        let (status, content_type, body) = route("/snapshot.json?x=1", &MockDataProvider::new());
+        assert_eq!(status, 200);
+        assert_eq!(content_type, "application/json");
+        assert!(serde_json::from_str::<serde_json::Value>(&body).is_ok());
+    }
+
+    #[test]
+    fn test_route_serves_demo_data_for_demo_provider() {
+        let (_, _, body) = route("/snapshot.json", ProviderKind::Demo.build().as_ref());
+        let demo = snapshot::to_json(&snapshot::collect_snapshot(&crate::data::demo::DemoDataProvider));
+        let value = |json: &str| serde_json::from_str::<serde_json::Value>(json).unwrap()["swap_processes"].clone();
+        assert_eq!(value(&body), value(&demo));
+        assert!(!value(&body).as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_route_unknown_is_404() {
+        let (status, _, _) = route("/", &MockDataProvider::new());
+        assert_eq!(status, 404);
+    }
+}
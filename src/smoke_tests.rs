@@ -9,12 +9,14 @@ use ratatui::{Terminal, backend::TestBackend};
 
 use crate::app::{App, SortColumn};
 use crate::data::{
-    DataProvider, GpuDevice, GpuProcessInfo, MockDataProvider, ProcDataProvider, ProcessLocation,
-    SizeUnits, SwapUpdate, merge_process_data,
+    DataProvider, GpuDevice, GpuProcessInfo, GpuStatus, MigInstance, MockDataProvider, NUMA_MAPS_TOP_N, ProcDataProvider,
+    ProcessLocation, SizeUnits, SwapUpdate, UnitBase, merge_process_data,
 };
 use crate::data::types::{ActiveView, NumaNode, NumaNodeType, ProcessNumaInfo, UnifiedProcessInfo};
 use crate::theme::{Theme, ThemeType};
 use crate::ui;
+use crate::ui::process_list::ListScroll;
+use crate::ui::unified_view::LocationFilter;
 
 // ─── Helpers ──────────────────────────────────────────────────────────────
 
@@ -30,6 +32,7 @@ fn make_rich_mock() -> MockDataProvider {
         swap_devices: vec![],
         total_swap: 16_000_000,
         used_swap: 4_000_000,
+        ..Default::default()
     };
     mock.processes = vec![
         crate::data::ProcessSwapInfo {
@@ -37,18 +40,27 @@ fn make_rich_mock() -> MockDataProvider {
             name: "train_model".into(),
             swap_size: 2048.0,
             last_cpu: Some(0),
+            rss_kb: None,
+            mnt_ns: None,
+            count: 1,
         },
         crate::data::ProcessSwapInfo {
             pid: 200,
             name: "data_loader".into(),
             swap_size: 1024.0,
             last_cpu: Some(4),
+            rss_kb: None,
+            mnt_ns: None,
+            count: 1,
         },
         crate::data::ProcessSwapInfo {
             pid: 300,
             name: "monitor".into(),
             swap_size: 256.0,
             last_cpu: Some(1),
+            rss_kb: None,
+            mnt_ns: None,
+            count: 1,
         },
     ];
     mock.numa_nodes = vec![
@@ -58,6 +70,7 @@ fn make_rich_mock() -> MockDataProvider {
             memory_free_kb: 8_000_000,
             cpus: vec![0, 1, 2, 3],
             node_type: NumaNodeType::Cpu,
+            distances: vec![],
         },
         NumaNode {
             id: 1,
@@ -65,6 +78,7 @@ fn make_rich_mock() -> MockDataProvider {
             memory_free_kb: 10_000_000,
             cpus: vec![4, 5, 6, 7],
             node_type: NumaNodeType::Cpu,
+            distances: vec![],
         },
         NumaNode {
             id: 2,
@@ -72,6 +86,7 @@ fn make_rich_mock() -> MockDataProvider {
             memory_free_kb: 40_960_000,
             cpus: vec![],
             node_type: NumaNodeType::GpuHbm { gpu_index: 0 },
+            distances: vec![],
         },
     ];
     mock.numa_available = true;
@@ -85,6 +100,10 @@ fn make_rich_mock() -> MockDataProvider {
             numa_node_id: Some(2),
             temperature: Some(55),
             pci_bus_id: "00:01.0".into(),
+            memory_reserved_kb: None,
+            mig_instances: Vec::new(),
+            utilization_percent: None,
+            memory_utilization_percent: None,
         },
         GpuDevice {
             index: 1,
@@ -95,6 +114,10 @@ fn make_rich_mock() -> MockDataProvider {
             numa_node_id: Some(3),
             temperature: Some(42),
             pci_bus_id: "00:02.0".into(),
+            memory_reserved_kb: None,
+            mig_instances: Vec::new(),
+            utilization_percent: None,
+            memory_utilization_percent: None,
         },
     ];
     mock.gpu_processes = vec![
@@ -103,12 +126,16 @@ fn make_rich_mock() -> MockDataProvider {
             name: "train_model".into(),
             gpu_index: 0,
             gpu_memory_used_kb: 30_000_000,
+            sm_util: None,
+            mem_util: None,
         },
         GpuProcessInfo {
             pid: 100,
             name: "train_model".into(),
             gpu_index: 1,
             gpu_memory_used_kb: 15_000_000,
+            sm_util: None,
+            mem_util: None,
         },
     ];
     mock.gpu_available = true;
@@ -245,7 +272,7 @@ fn test_numa_maps_to_merge_kb_pipeline() {
 00400000 default N0=100 N1=50 kernelpagesize_kB=4
 00600000 default N0=10 kernelpagesize_kB=2048";
 
-    let info = crate::data::numa::parse_numa_maps(numa_maps_content, 42, "mixed_app", 4);
+    let info = crate::data::numa::parse_numa_maps(numa_maps_content, 42, "mixed_app", 4, None);
     // N0 = 100*4 + 10*2048 = 400 + 20480 = 20880
     assert_eq!(info.kb_per_node.get(&0), Some(&20_880));
     // N1 = 50*4 = 200
@@ -256,11 +283,14 @@ fn test_numa_maps_to_merge_kb_pipeline() {
         name: "mixed_app".into(),
         swap_size: 512.0,
         last_cpu: Some(0),
+        rss_kb: None,
+        mnt_ns: None,
+        count: 1,
     }];
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
     ];
 
     let result = merge_process_data(&swap_procs, &[], &[info], &numa_nodes, &[]);
@@ -277,6 +307,9 @@ fn test_hbm_migration_full_pipeline() {
         name: "migrated_app".into(),
         swap_size: 1024.0,
         last_cpu: Some(0),
+        rss_kb: None,
+        mnt_ns: None,
+        count: 1,
     }];
 
     let numa_infos = vec![ProcessNumaInfo {
@@ -288,8 +321,8 @@ fn test_hbm_migration_full_pipeline() {
     }];
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 } },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 }, distances: vec![] },
     ];
 
     let result = merge_process_data(&swap_procs, &[], &numa_infos, &numa_nodes, &[]);
@@ -305,8 +338,8 @@ fn test_render_numa_view_smoke() {
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![4, 5, 6, 7], node_type: NumaNodeType::Cpu },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![4, 5, 6, 7], node_type: NumaNodeType::Cpu, distances: vec![] },
     ];
     let process_infos = vec![ProcessNumaInfo {
         pid: 42,
@@ -326,6 +359,11 @@ fn test_render_numa_view_smoke() {
                 &process_infos,
                 true,
                 &SizeUnits::KB,
+                UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                true,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -334,6 +372,159 @@ fn test_render_numa_view_smoke() {
     // Buffer should be non-empty (has content beyond just spaces)
     let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
     assert!(content.contains("NUMA"));
+    // Node 0 is half full, node 1 three eighths
+    assert!(content.contains("[█████░░░░░]  50.0%"));
+    assert!(content.contains("[████░░░░░░]  37.5%"));
+}
+
+#[test]
+fn test_render_numa_view_sort_by_used() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+    let node = |id, free| NumaNode {
+        id, memory_total_kb: 16_000_000, memory_free_kb: free, cpus: vec![], node_type: NumaNodeType::Cpu, distances: vec![],
+    };
+    // Node 2 is the fullest, node 0 the emptiest
+    let numa_nodes = vec![node(0, 15_000_000), node(1, 8_000_000), node(2, 1_000_000)];
+    let process_infos = vec![ProcessNumaInfo {
+        pid: 42,
+        name: "test_proc".into(),
+        kb_per_node: HashMap::from([(0, 500)]),
+        total_kb: 500,
+        cpu_node: Some(0),
+    }];
+
+    let row_order = |terminal: &mut Terminal<TestBackend>, sort| {
+        terminal
+            .draw(|frame| {
+                ui::numa_view::render_numa_view(
+                    frame, frame.area(), &theme, &numa_nodes, &process_infos, true, &SizeUnits::KB, UnitBase::Binary, sort, true,
+                    NUMA_MAPS_TOP_N,
+                    &mut ListScroll::default(),
+                );
+            })
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buf.area.height)
+            .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol().to_string()).collect())
+            .collect();
+        let node_row = |id: u32| rows.iter().position(|r| r.contains(&format!("{:>6} | CPU", id))).unwrap();
+        let header = rows.iter().find(|r| r.contains("PROCESS")).unwrap().clone();
+        ((node_row(0), node_row(1), node_row(2)), header)
+    };
+
+    let ((n0, n1, n2), _) = row_order(&mut terminal, ui::numa_view::NumaNodeSort::Id);
+    assert!(n0 < n1 && n1 < n2);
+    let ((n0, n1, n2), header) = row_order(&mut terminal, ui::numa_view::NumaNodeSort::Used);
+    assert!(n2 < n1 && n1 < n0);
+    // Per-process columns stay in id order
+    assert!(header.find("N0").unwrap() < header.find("N1").unwrap());
+    assert!(header.find("N1").unwrap() < header.find("N2").unwrap());
+}
+
+#[test]
+fn test_render_numa_view_multibyte_name() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+    let numa_nodes = vec![
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0], node_type: NumaNodeType::Cpu, distances: vec![] },
+    ];
+    // 22 chars, 66 bytes: the 20-char cut lands inside a 3-byte char
+    let process_infos = vec![ProcessNumaInfo {
+        pid: 42,
+        name: "绘图程序渲染器".repeat(3) + "x",
+        kb_per_node: HashMap::from([(0, 500)]),
+        total_kb: 500,
+        cpu_node: Some(0),
+    }];
+
+    terminal
+        .draw(|frame| {
+            ui::numa_view::render_numa_view(
+                frame, frame.area(), &theme, &numa_nodes, &process_infos, true, &SizeUnits::KB, UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                true,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    let buf = terminal.backend().buffer();
+    let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
+    assert!(content.contains('…'));
+}
+
+#[test]
+fn test_render_numa_view_distance_matrix() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Default);
+    let node = |id, distances| NumaNode {
+        id, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![id], node_type: NumaNodeType::Cpu, distances,
+    };
+    let render = |terminal: &mut Terminal<TestBackend>, numa_nodes: &[NumaNode]| {
+        terminal
+            .draw(|frame| {
+                ui::numa_view::render_numa_view(
+                    frame, frame.area(), &theme, numa_nodes, &[], true, &SizeUnits::KB, UnitBase::Binary,
+                    ui::numa_view::NumaNodeSort::Id,
+                    false,
+                    NUMA_MAPS_TOP_N,
+                    &mut ListScroll::default(),
+                );
+            })
+            .unwrap();
+        terminal.backend().buffer().content().iter().map(|c| c.symbol().to_string()).collect::<String>()
+    };
+
+    let content = render(&mut terminal, &[node(0, vec![10, 21]), node(1, vec![21, 10])]);
+    assert!(content.contains("Distances"));
+    assert!(content.contains("N0     10   21"));
+    assert!(content.contains("N1     21   10"));
+
+    // A node without a distance file shows dashes
+    let content = render(&mut terminal, &[node(0, vec![10, 21]), node(1, vec![])]);
+    assert!(content.contains("N1      -    -"));
+
+    // No distances at all: no panel
+    assert!(!render(&mut terminal, &[node(0, vec![])]).contains("Distances"));
+}
+
+#[test]
+fn test_render_numa_view_topology_only() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+    let numa_nodes = vec![
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+    ];
+    let process_infos = vec![ProcessNumaInfo {
+        pid: 42,
+        name: "test_proc".into(),
+        kb_per_node: HashMap::from([(0, 500)]),
+        total_kb: 500,
+        cpu_node: Some(0),
+    }];
+
+    terminal
+        .draw(|frame| {
+            ui::numa_view::render_numa_view(
+                frame, frame.area(), &theme, &numa_nodes, &process_infos, true, &SizeUnits::KB, UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                false,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    let buf = terminal.backend().buffer();
+    let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
+    assert!(content.contains("P for processes"));
+    assert!(!content.contains("PROCESS"));
+    assert!(!content.contains("test_proc"));
+    // The topology block spans the full height: its bottom border is on the last row
+    let last_row: String = (0..buf.area.width).map(|x| buf[(x, buf.area.height - 1)].symbol().to_string()).collect();
+    assert!(last_row.starts_with('╰'));
 }
 
 #[test]
@@ -351,6 +542,11 @@ fn test_render_numa_view_unavailable() {
                 &[],
                 false,
                 &SizeUnits::KB,
+                UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                true,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -375,12 +571,18 @@ fn test_render_gpu_view_smoke() {
         numa_node_id: Some(2),
         temperature: Some(55),
         pci_bus_id: "00:01.0".into(),
+        memory_reserved_kb: Some(512 * 1024),
+        mig_instances: Vec::new(),
+        utilization_percent: None,
+        memory_utilization_percent: None,
     }];
     let processes = vec![GpuProcessInfo {
         pid: 100,
         name: "train_model".into(),
         gpu_index: 0,
         gpu_memory_used_kb: 30_000_000,
+        sm_util: None,
+        mem_util: None,
     }];
 
     terminal
@@ -391,8 +593,17 @@ fn test_render_gpu_view_smoke() {
                 &theme,
                 &devices,
                 &processes,
-                true,
+                &GpuStatus::Available,
                 &SizeUnits::KB,
+                UnitBase::Binary,
+                false,
+                &ui::gpu_view::IdleGpuHighlight::default(),
+                &HashMap::new(),
+                false,
+                false,
+                false,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -400,6 +611,344 @@ fn test_render_gpu_view_smoke() {
     let buf = terminal.backend().buffer().clone();
     let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
     assert!(content.contains("GPU"));
+    assert!(content.contains("RESERVED"));
+    assert!(content.contains("524288 KiB"));
+}
+
+#[test]
+fn test_render_gpu_view_sm_column_only_with_pmon_data() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Default);
+    let mock = make_rich_mock();
+    let render = |terminal: &mut Terminal<TestBackend>, processes: &[GpuProcessInfo]| {
+        terminal
+            .draw(|frame| {
+                ui::gpu_view::render_gpu_view(
+                    frame,
+                    frame.area(),
+                    &theme,
+                    &mock.gpu_devices,
+                    processes,
+                    &GpuStatus::Available,
+                    &SizeUnits::KB,
+                    UnitBase::Binary,
+                    false,
+                    &ui::gpu_view::IdleGpuHighlight::default(),
+                    &HashMap::new(),
+                    false,
+                    false,
+                    false,
+                    None,
+                    &mut ListScroll::default(),
+                );
+            })
+            .unwrap();
+        terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect::<String>()
+    };
+
+    // The device table has its own SM% column; check the process header
+    assert!(!render(&mut terminal, &mock.gpu_processes).contains("VRAM USED |  SM%"));
+
+    let mut processes = mock.gpu_processes.clone();
+    processes[0].sm_util = Some(87);
+    let content = render(&mut terminal, &processes);
+    assert!(content.contains("VRAM USED |  SM%"));
+    assert!(content.contains("|   87"));
+    // Unsampled processes show a dash
+    assert!(content.contains("|    -"));
+}
+
+#[test]
+fn test_render_gpu_view_prefers_cmdline() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+    let devices = vec![GpuDevice {
+        index: 0, name: "GPU 0".into(), memory_total_kb: 81_920_000, memory_used_kb: 40_000_000,
+        memory_free_kb: 41_920_000, numa_node_id: None, temperature: None, pci_bus_id: "00:01.0".into(),
+        memory_reserved_kb: None, mig_instances: Vec::new(), utilization_percent: None, memory_utilization_percent: None,
+    }];
+    let processes = vec![
+        GpuProcessInfo { pid: 100, name: "python3".into(), gpu_index: 0, gpu_memory_used_kb: 1024, sm_util: None, mem_util: None },
+        GpuProcessInfo { pid: 200, name: "mps_client".into(), gpu_index: 0, gpu_memory_used_kb: 1024, sm_util: None, mem_util: None },
+    ];
+    // pid 200 is not local (e.g. an MPS client): keeps the nvidia-smi name
+    let cmdlines = HashMap::from([(100, "python3 train.py".to_string())]);
+
+    terminal
+        .draw(|frame| {
+            ui::gpu_view::render_gpu_view(
+                frame,
+                frame.area(),
+                &theme,
+                &devices,
+                &processes,
+                &GpuStatus::Available,
+                &SizeUnits::KB,
+                UnitBase::Binary,
+                false,
+                &ui::gpu_view::IdleGpuHighlight::default(),
+                &cmdlines,
+                false,
+                false,
+                false,
+                None,
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    let buf = terminal.backend().buffer().clone();
+    let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
+    assert!(content.contains("python3 train.py"));
+    assert!(content.contains("mps_client"));
+}
+
+#[test]
+fn test_render_gpu_view_mig_toggle() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+
+    let mig = |device, gpu_instance| MigInstance {
+        device,
+        gpu_instance,
+        compute_instance: 0,
+        memory_total_kb: 20_096 * 1024,
+        memory_used_kb: 1024 * 1024,
+    };
+    let devices = vec![GpuDevice {
+        index: 0,
+        name: "NVIDIA A100".into(),
+        memory_total_kb: 40_960 * 1024,
+        memory_used_kb: 0,
+        memory_free_kb: 40_960 * 1024,
+        numa_node_id: None,
+        temperature: Some(40),
+        pci_bus_id: "00:01.0".into(),
+        memory_reserved_kb: None,
+        mig_instances: vec![mig(0, 1), mig(1, 2)],
+        utilization_percent: None,
+        memory_utilization_percent: None,
+    }];
+
+    let render = |terminal: &mut Terminal<TestBackend>, show_mig_instances| {
+        terminal
+            .draw(|frame| {
+                ui::gpu_view::render_gpu_view(
+                    frame,
+                    frame.area(),
+                    &theme,
+                    &devices,
+                    &[],
+                    &GpuStatus::Available,
+                    &SizeUnits::MB,
+                    UnitBase::Binary,
+                    show_mig_instances,
+                    &ui::gpu_view::IdleGpuHighlight::default(),
+                    &HashMap::new(),
+                    false,
+                    false,
+                    false,
+                    None,
+                    &mut ListScroll::default(),
+                );
+            })
+            .unwrap();
+        let buf = terminal.backend().buffer().clone();
+        buf.content().iter().map(|c| c.symbol().to_string()).collect::<String>()
+    };
+
+    // Flattened: one physical row with instance usage summed
+    let content = render(&mut terminal, false);
+    assert!(content.contains("NVIDIA A100"));
+    assert!(content.contains("2048.00 MiB"));
+    assert!(!content.contains("MIG GI"));
+    assert!(content.contains("i to show MIG instances"));
+
+    let content = render(&mut terminal, true);
+    assert!(content.contains("MIG GI 1 CI 0"));
+    assert!(content.contains("MIG GI 2 CI 0"));
+    assert!(content.contains("0:1"));
+}
+
+#[test]
+fn test_render_gpu_view_idle_gpu_highlight() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+
+    let device = |index, utilization_percent| GpuDevice {
+        index,
+        name: format!("GPU {}", index),
+        memory_total_kb: 81_920_000,
+        memory_used_kb: 40_000_000,
+        memory_free_kb: 41_920_000,
+        numa_node_id: None,
+        temperature: Some(40),
+        pci_bus_id: "00:01.0".into(),
+        memory_reserved_kb: None,
+        mig_instances: Vec::new(),
+        utilization_percent,
+        memory_utilization_percent: Some(35),
+    };
+    let devices = vec![device(0, Some(0)), device(1, Some(2))];
+    let processes = vec![
+        GpuProcessInfo { pid: 100, name: "held".into(), gpu_index: 0, gpu_memory_used_kb: 40_000_000, sm_util: None, mem_util: None },
+        GpuProcessInfo { pid: 101, name: "busy".into(), gpu_index: 1, gpu_memory_used_kb: 40_000_000, sm_util: None, mem_util: None },
+    ];
+    let magenta = ratatui::style::Color::Magenta;
+
+    let render = |terminal: &mut Terminal<TestBackend>, highlight: ui::gpu_view::IdleGpuHighlight| {
+        terminal
+            .draw(|frame| {
+                ui::gpu_view::render_gpu_view(
+                    frame,
+                    frame.area(),
+                    &theme,
+                    &devices,
+                    &processes,
+                    &GpuStatus::Available,
+                    &SizeUnits::KB,
+                    UnitBase::Binary,
+                    false,
+                    &highlight,
+                    &HashMap::new(),
+                    false,
+                    false,
+                    false,
+                    None,
+                    &mut ListScroll::default(),
+                );
+            })
+            .unwrap();
+        terminal.backend().buffer().clone()
+    };
+    let row_fg = |buf: &ratatui::buffer::Buffer, name: &str| {
+        let width = buf.area.width as usize;
+        let symbols: Vec<&str> = buf.content().iter().map(|c| c.symbol()).collect();
+        let row = symbols.chunks(width).position(|r| r.concat().contains(name)).unwrap();
+        let col = symbols[row * width..(row + 1) * width].concat().find(name).unwrap();
+        buf[(col as u16, row as u16)].fg
+    };
+
+    let buf = render(&mut terminal, ui::gpu_view::IdleGpuHighlight { max_utilization_percent: 0, color: Some(magenta) });
+    let content: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(content.contains("SM%/MEM%"));
+    assert!(content.contains("0%/35%"));
+    assert!(content.contains("util <= 0%"));
+    assert_eq!(row_fg(&buf, "GPU 0"), magenta);
+    assert_ne!(row_fg(&buf, "GPU 1"), magenta);
+
+    // Raising the threshold catches the nearly idle GPU too
+    let buf = render(&mut terminal, ui::gpu_view::IdleGpuHighlight { max_utilization_percent: 5, color: Some(magenta) });
+    assert_eq!(row_fg(&buf, "GPU 1"), magenta);
+}
+
+#[test]
+fn test_render_gpu_view_hidden_processes_note() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+    let devices = crate::data::gpu::parse_gpu_devices_csv("0, H100, 81920 MiB, 40960 MiB, 40960 MiB, 45, 00000000:01:00.0\n");
+
+    let render = |terminal: &mut Terminal<TestBackend>, hidden| {
+        terminal
+            .draw(|frame| {
+                ui::gpu_view::render_gpu_view(
+                    frame,
+                    frame.area(),
+                    &theme,
+                    &devices,
+                    &[],
+                    &GpuStatus::Available,
+                    &SizeUnits::KB,
+                    UnitBase::Binary,
+                    false,
+                    &ui::gpu_view::IdleGpuHighlight::default(),
+                    &HashMap::new(),
+                    false,
+                    false,
+                    hidden,
+                    None,
+                    &mut ListScroll::default(),
+                );
+            })
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        buf.content().iter().map(|c| c.symbol()).collect::<String>()
+    };
+
+    let content = render(&mut terminal, true);
+    assert!(content.contains("No GPU processes visible"));
+    assert!(content.contains("host PID namespace"));
+    assert!(!content.contains("No GPU processes running"));
+
+    let content = render(&mut terminal, false);
+    assert!(content.contains("No GPU processes running"));
+}
+
+#[test]
+fn test_render_gpu_view_collapsed_gpus() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+    let device = |index, used_kb, temperature, utilization| GpuDevice {
+        index,
+        name: format!("GPU {}", index),
+        memory_total_kb: 4096,
+        memory_used_kb: used_kb,
+        memory_free_kb: 4096 - used_kb,
+        numa_node_id: None,
+        temperature: Some(temperature),
+        pci_bus_id: String::new(),
+        memory_reserved_kb: None,
+        mig_instances: Vec::new(),
+        utilization_percent: Some(utilization),
+        memory_utilization_percent: None,
+    };
+    let devices = vec![device(0, 1024, 40, 20), device(1, 2048, 71, 60)];
+    let processes = vec![
+        GpuProcessInfo { pid: 100, name: "trainer".into(), gpu_index: 0, gpu_memory_used_kb: 1024, sm_util: None, mem_util: None },
+        GpuProcessInfo { pid: 100, name: "trainer".into(), gpu_index: 1, gpu_memory_used_kb: 1024, sm_util: None, mem_util: None },
+        GpuProcessInfo { pid: 101, name: "eval".into(), gpu_index: 1, gpu_memory_used_kb: 1024, sm_util: None, mem_util: None },
+    ];
+
+    let render = |terminal: &mut Terminal<TestBackend>, collapse| {
+        terminal
+            .draw(|frame| {
+                ui::gpu_view::render_gpu_view(
+                    frame,
+                    frame.area(),
+                    &theme,
+                    &devices,
+                    &processes,
+                    &GpuStatus::Available,
+                    &SizeUnits::KB,
+                    UnitBase::Binary,
+                    false,
+                    &ui::gpu_view::IdleGpuHighlight::default(),
+                    &HashMap::new(),
+                    collapse,
+                    false,
+                    false,
+                    None,
+                    &mut ListScroll::default(),
+                );
+            })
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        buf.content().iter().map(|c| c.symbol()).collect::<String>()
+    };
+
+    let content = render(&mut terminal, true);
+    assert!(content.contains("2 GPUs, 2 procs"));
+    assert!(content.contains("8192 KiB")); // summed total
+    assert!(content.contains("3072 KiB")); // summed used
+    assert!(content.contains("71°C")); // hottest
+    assert!(content.contains("40%/-")); // mean SM%
+    assert!(!content.contains("GPU 0"));
+    // The process list is unaffected
+    assert!(content.contains("trainer") && content.contains("eval"));
+
+    let content = render(&mut terminal, false);
+    assert!(content.contains("GPU 0") && content.contains("GPU 1"));
+    assert!(content.contains("A to collapse GPUs"));
 }
 
 #[test]
@@ -415,8 +964,17 @@ fn test_render_gpu_view_no_gpu() {
                 &theme,
                 &[],
                 &[],
-                false,
+                &GpuStatus::NotFound,
                 &SizeUnits::KB,
+                UnitBase::Binary,
+                false,
+                &ui::gpu_view::IdleGpuHighlight::default(),
+                &HashMap::new(),
+                false,
+                false,
+                false,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -426,14 +984,48 @@ fn test_render_gpu_view_no_gpu() {
     assert!(content.contains("No NVIDIA GPU"));
 }
 
+#[test]
+fn test_render_gpu_view_nvidia_smi_failed() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+    let status = GpuStatus::Failed("Driver/library version mismatch".into());
+
+    terminal
+        .draw(|frame| {
+            ui::gpu_view::render_gpu_view(
+                frame,
+                frame.area(),
+                &theme,
+                &[],
+                &[],
+                &status,
+                &SizeUnits::KB,
+                UnitBase::Binary,
+                false,
+                &ui::gpu_view::IdleGpuHighlight::default(),
+                &HashMap::new(),
+                false,
+                false,
+                false,
+                None,
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    let buf = terminal.backend().buffer().clone();
+    let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
+    assert!(content.contains("nvidia-smi present but failed: Driver/library version mismatch"));
+}
+
 #[test]
 fn test_render_unified_view_smoke() {
     let mut terminal = make_test_terminal();
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![4, 5, 6, 7], node_type: NumaNodeType::Cpu },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![4, 5, 6, 7], node_type: NumaNodeType::Cpu, distances: vec![] },
     ];
 
     let procs = vec![
@@ -447,6 +1039,8 @@ fn test_render_unified_view_smoke() {
             gpu_memory_kb: Some(30_000_000),
             gpu_indices: vec![0],
             location: ProcessLocation::CpuAndGpu,
+            rss_kb: None,
+            count: 1,
         },
         UnifiedProcessInfo {
             pid: 200,
@@ -458,6 +1052,8 @@ fn test_render_unified_view_smoke() {
             gpu_memory_kb: None,
             gpu_indices: vec![],
             location: ProcessLocation::CpuOnly,
+            rss_kb: None,
+            count: 1,
         },
     ];
 
@@ -469,7 +1065,19 @@ fn test_render_unified_view_smoke() {
                 &theme,
                 &procs,
                 &SizeUnits::KB,
+                UnitBase::Binary,
                 &numa_nodes,
+                false,
+                None,
+                SortColumn::Swap,
+                false,
+                false,
+                &[],
+                false,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -479,6 +1087,256 @@ fn test_render_unified_view_smoke() {
     assert!(content.contains("Unified"));
 }
 
+#[test]
+fn test_render_unified_view_header_aligned_when_sorted_by_node() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Default);
+    let procs = vec![UnifiedProcessInfo {
+        pid: 200,
+        name: "bash".into(),
+        swap_kb: 512,
+        cpu_nodes: vec![1],
+        gpu_nodes: vec![0],
+        kb_per_node: HashMap::new(),
+        gpu_memory_kb: None,
+        gpu_indices: vec![],
+        location: ProcessLocation::CpuOnly,
+        rss_kb: None,
+        count: 1,
+    }];
+    terminal
+        .draw(|frame| {
+            ui::unified_view::render_unified_view(
+                frame, frame.area(), &theme, &procs, &SizeUnits::KB, UnitBase::Binary, &[], false, None, SortColumn::NumaNode, false, false,
+                &[], false, LocationFilter::All, 0, None,
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    // One cell per char, so char positions are columns
+    let buf = terminal.backend().buffer();
+    let lines: Vec<Vec<char>> = (0..buf.area.height)
+        .map(|y| (0..buf.area.width).flat_map(|x| buf[(x, y)].symbol().chars().collect::<Vec<_>>()).collect())
+        .collect();
+    let end_of = |line: &[char], text: &str| -> Option<usize> {
+        let text: Vec<char> = text.chars().collect();
+        line.windows(text.len()).position(|w| w == text.as_slice()).map(|i| i + text.len())
+    };
+    let header = lines.iter().find(|l| end_of(l, "CPU→N▲").is_some()).expect("header with sort arrow");
+    let row = lines.iter().find(|l| end_of(l, "bash").is_some()).expect("process row");
+    // Right-aligned columns end where their header labels end
+    assert_eq!(end_of(header, "CPU→N▲"), end_of(row, "     1"));
+    assert_eq!(end_of(header, "GPU→N"), end_of(row, "     0"));
+    assert_eq!(end_of(header, "SWAP"), end_of(row, "512 KiB"));
+}
+
+#[test]
+fn test_render_unified_view_location_swap_shares() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Default);
+    let proc = |pid, swap_kb, location| UnifiedProcessInfo {
+        pid,
+        name: format!("p{}", pid),
+        swap_kb,
+        cpu_nodes: vec![],
+        gpu_nodes: vec![],
+        kb_per_node: HashMap::new(),
+        gpu_memory_kb: None,
+        gpu_indices: vec![],
+        location,
+        rss_kb: None,
+        count: 1,
+    };
+    let procs = vec![proc(1, 2_500, ProcessLocation::CpuOnly), proc(2, 1_000, ProcessLocation::CpuAndGpu)];
+    let render = |terminal: &mut Terminal<TestBackend>, total_swap_kb| {
+        terminal
+            .draw(|frame| {
+                ui::unified_view::render_unified_view(
+                    frame, frame.area(), &theme, &procs, &SizeUnits::KB, UnitBase::Binary, &[], false, None, SortColumn::Swap, false, false,
+                    &[], false, LocationFilter::All, total_swap_kb, None,
+                    &mut ListScroll::default(),
+                );
+            })
+            .unwrap();
+        terminal.backend().buffer().content().iter().map(|c| c.symbol().to_string()).collect::<String>()
+    };
+
+    assert!(render(&mut terminal, 10_000).contains("swap held: CPU-only 25.0%  CPU+GPU 10.0%"));
+    // No swap configured: the summary is left out
+    assert!(!render(&mut terminal, 0).contains("swap held"));
+}
+
+#[test]
+fn test_render_unified_view_location_filter() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Default);
+    let proc = |name: &str, location| UnifiedProcessInfo {
+        pid: 1,
+        name: name.into(),
+        swap_kb: 100,
+        cpu_nodes: vec![],
+        gpu_nodes: vec![],
+        kb_per_node: HashMap::new(),
+        gpu_memory_kb: None,
+        gpu_indices: vec![],
+        location,
+        rss_kb: None,
+        count: 1,
+    };
+    let procs = vec![proc("cpu_worker", ProcessLocation::CpuOnly), proc("migrated", ProcessLocation::CpuAndGpu)];
+    let render = |terminal: &mut Terminal<TestBackend>, procs: &[UnifiedProcessInfo], filter| {
+        terminal
+            .draw(|frame| {
+                ui::unified_view::render_unified_view(
+                    frame, frame.area(), &theme, procs, &SizeUnits::KB, UnitBase::Binary, &[], false, None, SortColumn::Swap, false, false,
+                    &[], false, filter, 0, None,
+                    &mut ListScroll::default(),
+                );
+            })
+            .unwrap();
+        terminal.backend().buffer().content().iter().map(|c| c.symbol().to_string()).collect::<String>()
+    };
+
+    let content = render(&mut terminal, &procs, LocationFilter::All);
+    assert!(content.contains("cpu_worker") && content.contains("migrated"));
+
+    let content = render(&mut terminal, &procs, LocationFilter::CpuAndGpu);
+    assert!(content.contains("migrated"));
+    assert!(!content.contains("cpu_worker"));
+    assert!(content.contains("Unified CPU+GPU+NUMA View [CPU+GPU only]"));
+
+    let content = render(&mut terminal, &procs[..1], LocationFilter::CpuAndGpu);
+    assert!(content.contains("No processes in this location (F to change)"));
+}
+
+#[test]
+fn test_render_unified_view_rss_column() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Default);
+    let proc = |pid, rss_kb| UnifiedProcessInfo {
+        pid,
+        name: format!("p{}", pid),
+        swap_kb: 100,
+        cpu_nodes: vec![],
+        gpu_nodes: vec![],
+        kb_per_node: HashMap::new(),
+        gpu_memory_kb: None,
+        gpu_indices: vec![],
+        location: ProcessLocation::CpuOnly,
+        rss_kb,
+        count: 1,
+    };
+    let procs = vec![proc(1, Some(41_943_040)), proc(2, None)];
+    terminal
+        .draw(|frame| {
+            ui::unified_view::render_unified_view(
+                frame, frame.area(), &theme, &procs, &SizeUnits::GB, UnitBase::Binary, &[], false, None, SortColumn::Swap, false, false,
+                &[], false, LocationFilter::All, 0, None,
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    let buf = terminal.backend().buffer();
+    let row = |text: &str| {
+        (0..buf.area.height)
+            .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect::<String>())
+            .find(|line| line.contains(text))
+            .unwrap()
+    };
+    assert!(row("SWAP").contains("RSS"));
+    // 40 GiB resident; no RSS reading shows "-"
+    assert!(row(" p1 ").contains("40.00 GiB"));
+    assert!(row(" p2 ").trim_end_matches(['│', ' ']).ends_with('-'));
+}
+
+#[test]
+fn test_render_unified_view_swap_highlight() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Default);
+    let proc = |pid, swap_kb| UnifiedProcessInfo {
+        pid,
+        name: format!("proc{}", pid),
+        swap_kb,
+        cpu_nodes: vec![],
+        gpu_nodes: vec![],
+        kb_per_node: HashMap::new(),
+        gpu_memory_kb: None,
+        gpu_indices: vec![],
+        location: ProcessLocation::CpuOnly,
+        rss_kb: None,
+        count: 1,
+    };
+    let procs = vec![proc(1, 4_000_000), proc(2, 1_000)];
+    terminal
+        .draw(|frame| {
+            ui::unified_view::render_unified_view(
+                frame, frame.area(), &theme, &procs, &SizeUnits::KB, UnitBase::Binary, &[], false, None, SortColumn::Swap, false, false,
+                &[], false, LocationFilter::All, 0, Some(1_048_576),
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    let buf = terminal.backend().buffer();
+    let name_fg = |name: &str| {
+        let y = (0..buf.area.height)
+            .find(|&y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect::<String>().contains(name))
+            .unwrap();
+        let x = (0..buf.area.width).find(|&x| buf[(x, y)].symbol() == "p" && buf[(x + 1, y)].symbol() == "r").unwrap();
+        buf[(x, y)].fg
+    };
+    assert_eq!(name_fg("proc1"), theme.critical);
+    assert_ne!(name_fg("proc2"), theme.critical);
+}
+
+#[test]
+fn test_render_unified_view_gpu_percent_column() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Default);
+    let devices = vec![GpuDevice {
+        index: 0, name: "H100".into(), memory_total_kb: 80_000_000, memory_used_kb: 20_000_000,
+        memory_free_kb: 60_000_000, numa_node_id: None, temperature: None, pci_bus_id: String::new(),
+        memory_reserved_kb: None, mig_instances: Vec::new(), utilization_percent: None, memory_utilization_percent: None,
+    }];
+    let procs = vec![UnifiedProcessInfo {
+        pid: 100, name: "trainer".into(), swap_kb: 0, cpu_nodes: vec![], gpu_nodes: vec![],
+        kb_per_node: HashMap::new(), gpu_memory_kb: Some(20_000_000), gpu_indices: vec![0],
+        location: ProcessLocation::GpuOnly, rss_kb: None, count: 1,
+    }];
+
+    terminal
+        .draw(|frame| {
+            ui::unified_view::render_unified_view(
+                frame,
+                frame.area(),
+                &theme,
+                &procs,
+                &SizeUnits::KB,
+                UnitBase::Binary,
+                &[],
+                false,
+                None,
+                SortColumn::Swap,
+                false,
+                false,
+                &devices,
+                true,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    let buf = terminal.backend().buffer().clone();
+    let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
+    assert!(content.contains("GPU%"));
+    assert!(content.contains("25.0%"));
+}
+
 #[test]
 fn test_render_unified_view_empty() {
     let mut terminal = make_test_terminal();
@@ -492,7 +1350,19 @@ fn test_render_unified_view_empty() {
                 &theme,
                 &[],
                 &SizeUnits::KB,
+                UnitBase::Binary,
                 &[],
+                false,
+                None,
+                SortColumn::Swap,
+                false,
+                false,
+                &[],
+                false,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -509,12 +1379,12 @@ fn test_render_unified_view_gb200() {
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 128_000_000, memory_free_kb: 64_000_000, cpus: vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 1, memory_total_kb: 128_000_000, memory_free_kb: 80_000_000, cpus: vec![36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 2, memory_total_kb: 98_304_000, memory_free_kb: 50_000_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 } },
-        NumaNode { id: 3, memory_total_kb: 98_304_000, memory_free_kb: 60_000_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 1 } },
-        NumaNode { id: 4, memory_total_kb: 98_304_000, memory_free_kb: 70_000_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 2 } },
-        NumaNode { id: 5, memory_total_kb: 98_304_000, memory_free_kb: 90_000_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 3 } },
+        NumaNode { id: 0, memory_total_kb: 128_000_000, memory_free_kb: 64_000_000, cpus: vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 1, memory_total_kb: 128_000_000, memory_free_kb: 80_000_000, cpus: vec![36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 2, memory_total_kb: 98_304_000, memory_free_kb: 50_000_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 }, distances: vec![] },
+        NumaNode { id: 3, memory_total_kb: 98_304_000, memory_free_kb: 60_000_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 1 }, distances: vec![] },
+        NumaNode { id: 4, memory_total_kb: 98_304_000, memory_free_kb: 70_000_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 2 }, distances: vec![] },
+        NumaNode { id: 5, memory_total_kb: 98_304_000, memory_free_kb: 90_000_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 3 }, distances: vec![] },
     ];
 
     let procs = vec![
@@ -528,6 +1398,8 @@ fn test_render_unified_view_gb200() {
             gpu_memory_kb: Some(63_000_000),
             gpu_indices: vec![0, 1, 2, 3],
             location: ProcessLocation::CpuAndGpu,
+            rss_kb: None,
+            count: 1,
         },
         UnifiedProcessInfo {
             pid: 1001,
@@ -539,6 +1411,8 @@ fn test_render_unified_view_gb200() {
             gpu_memory_kb: None,
             gpu_indices: vec![],
             location: ProcessLocation::CpuOnly,
+            rss_kb: None,
+            count: 1,
         },
     ];
 
@@ -550,7 +1424,19 @@ fn test_render_unified_view_gb200() {
                 &theme,
                 &procs,
                 &SizeUnits::MB,
+                UnitBase::Binary,
                 &numa_nodes,
+                false,
+                None,
+                SortColumn::Swap,
+                false,
+                false,
+                &[],
+                false,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -570,8 +1456,8 @@ fn test_render_unified_view_hbm_red_local_green_style() {
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 } },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1, 2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 }, distances: vec![] },
     ];
 
     // CPU process with pages on node 0 (local) and HBM node 2
@@ -585,6 +1471,8 @@ fn test_render_unified_view_hbm_red_local_green_style() {
         gpu_memory_kb: None,
         gpu_indices: vec![],
         location: ProcessLocation::CpuAndGpu,
+        rss_kb: None,
+        count: 1,
     }];
 
     terminal
@@ -595,7 +1483,19 @@ fn test_render_unified_view_hbm_red_local_green_style() {
                 &theme,
                 &procs,
                 &SizeUnits::KB,
+                UnitBase::Binary,
                 &numa_nodes,
+                false,
+                None,
+                SortColumn::Swap,
+                false,
+                false,
+                &[],
+                false,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -648,12 +1548,17 @@ fn test_unit_controls_affect_all_views() {
                 &infos,
                 true,
                 &SizeUnits::MB,
+                UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                true,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
     let buf = terminal.backend().buffer().clone();
     let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
-    assert!(content.contains("MB"), "NUMA view should display MB units");
+    assert!(content.contains("MiB"), "NUMA view should display MiB units");
 
     // GPU view with GB
     terminal
@@ -664,14 +1569,23 @@ fn test_unit_controls_affect_all_views() {
                 &theme,
                 &mock.gpu_devices,
                 &mock.gpu_processes,
-                true,
+                &GpuStatus::Available,
                 &SizeUnits::GB,
+                UnitBase::Binary,
+                false,
+                &ui::gpu_view::IdleGpuHighlight::default(),
+                &HashMap::new(),
+                false,
+                false,
+                false,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
     let buf = terminal.backend().buffer().clone();
     let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
-    assert!(content.contains("GB"), "GPU view should display GB units");
+    assert!(content.contains("GiB"), "GPU view should display GiB units");
 }
 
 #[test]
@@ -683,8 +1597,8 @@ fn test_render_unified_view_local_green() {
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
     ];
 
     let procs = vec![UnifiedProcessInfo {
@@ -697,12 +1611,25 @@ fn test_render_unified_view_local_green() {
         gpu_memory_kb: None,
         gpu_indices: vec![],
         location: ProcessLocation::CpuOnly,
+        rss_kb: None,
+        count: 1,
     }];
 
     terminal
         .draw(|frame| {
             ui::unified_view::render_unified_view(
-                frame, frame.area(), &theme, &procs, &SizeUnits::KB, &numa_nodes,
+                frame, frame.area(), &theme, &procs, &SizeUnits::KB, UnitBase::Binary, &numa_nodes,
+                false,
+                None,
+                SortColumn::Swap,
+                false,
+                false,
+                &[],
+                false,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -722,8 +1649,8 @@ fn test_render_unified_view_remote_orange() {
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
     ];
 
     let procs = vec![UnifiedProcessInfo {
@@ -736,12 +1663,25 @@ fn test_render_unified_view_remote_orange() {
         gpu_memory_kb: None,
         gpu_indices: vec![],
         location: ProcessLocation::CpuOnly,
+        rss_kb: None,
+        count: 1,
     }];
 
     terminal
         .draw(|frame| {
             ui::unified_view::render_unified_view(
-                frame, frame.area(), &theme, &procs, &SizeUnits::KB, &numa_nodes,
+                frame, frame.area(), &theme, &procs, &SizeUnits::KB, UnitBase::Binary, &numa_nodes,
+                false,
+                None,
+                SortColumn::Swap,
+                false,
+                false,
+                &[],
+                false,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -761,8 +1701,8 @@ fn test_render_unified_view_hbm_red() {
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 } },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 }, distances: vec![] },
     ];
 
     let procs = vec![UnifiedProcessInfo {
@@ -775,12 +1715,25 @@ fn test_render_unified_view_hbm_red() {
         gpu_memory_kb: Some(10_000),
         gpu_indices: vec![0],
         location: ProcessLocation::CpuAndGpu,
+        rss_kb: None,
+        count: 1,
     }];
 
     terminal
         .draw(|frame| {
             ui::unified_view::render_unified_view(
-                frame, frame.area(), &theme, &procs, &SizeUnits::KB, &numa_nodes,
+                frame, frame.area(), &theme, &procs, &SizeUnits::KB, UnitBase::Binary, &numa_nodes,
+                false,
+                None,
+                SortColumn::Swap,
+                false,
+                false,
+                &[],
+                false,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -800,8 +1753,8 @@ fn test_render_numa_view_local_green() {
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
     ];
 
     let infos = vec![ProcessNumaInfo {
@@ -815,7 +1768,11 @@ fn test_render_numa_view_local_green() {
     terminal
         .draw(|frame| {
             ui::numa_view::render_numa_view(
-                frame, frame.area(), &theme, &numa_nodes, &infos, true, &SizeUnits::KB,
+                frame, frame.area(), &theme, &numa_nodes, &infos, true, &SizeUnits::KB, UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                true,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -826,6 +1783,42 @@ fn test_render_numa_view_local_green() {
     assert!(has_green, "NUMA view local node memory should be green (80,200,120)");
 }
 
+#[test]
+fn test_render_numa_view_other_column_for_unknown_nodes() {
+    // Sparse/hotplugged node 7 is not in the topology: its memory still shows up
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+
+    let numa_nodes = vec![
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+    ];
+    let infos = vec![ProcessNumaInfo {
+        pid: 42,
+        name: "hotplug_proc".into(),
+        kb_per_node: HashMap::from([(0, 1000), (7, 4321)]),
+        total_kb: 5321,
+        cpu_node: Some(0),
+    }];
+
+    terminal
+        .draw(|frame| {
+            ui::numa_view::render_numa_view(
+                frame, frame.area(), &theme, &numa_nodes, &infos, true, &SizeUnits::KB, UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                true,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    let buf = terminal.backend().buffer().clone();
+    let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
+    assert!(content.contains("OTHER"));
+    assert!(content.contains("4321"));
+    assert!(content.contains("5321"));
+}
+
 #[test]
 fn test_render_numa_view_remote_orange() {
     // NUMA view: process on cpu_node=0, memory on node 1 (remote CPU) → orange
@@ -837,8 +1830,8 @@ fn test_render_numa_view_remote_orange() {
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 1, memory_total_kb: 16_000_000, memory_free_kb: 10_000_000, cpus: vec![2, 3], node_type: NumaNodeType::Cpu, distances: vec![] },
     ];
 
     let infos = vec![ProcessNumaInfo {
@@ -852,7 +1845,11 @@ fn test_render_numa_view_remote_orange() {
     terminal
         .draw(|frame| {
             ui::numa_view::render_numa_view(
-                frame, frame.area(), &theme, &numa_nodes, &infos, true, &SizeUnits::KB,
+                frame, frame.area(), &theme, &numa_nodes, &infos, true, &SizeUnits::KB, UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                true,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -872,8 +1869,8 @@ fn test_render_numa_view_hbm_red() {
     let theme = Theme::from(ThemeType::Dracula);
 
     let numa_nodes = vec![
-        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu },
-        NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 } },
+        NumaNode { id: 0, memory_total_kb: 16_000_000, memory_free_kb: 8_000_000, cpus: vec![0, 1], node_type: NumaNodeType::Cpu, distances: vec![] },
+        NumaNode { id: 2, memory_total_kb: 81_920_000, memory_free_kb: 40_960_000, cpus: vec![], node_type: NumaNodeType::GpuHbm { gpu_index: 0 }, distances: vec![] },
     ];
 
     let infos = vec![ProcessNumaInfo {
@@ -887,7 +1884,11 @@ fn test_render_numa_view_hbm_red() {
     terminal
         .draw(|frame| {
             ui::numa_view::render_numa_view(
-                frame, frame.area(), &theme, &numa_nodes, &infos, true, &SizeUnits::KB,
+                frame, frame.area(), &theme, &numa_nodes, &infos, true, &SizeUnits::KB, UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                true,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -922,6 +1923,8 @@ fn test_app_view_cycling() {
 fn test_app_sort_cycling() {
     let col = SortColumn::Swap;
     let col = col.next();
+    assert_eq!(col, SortColumn::Committed);
+    let col = col.next();
     assert_eq!(col, SortColumn::GpuMem);
     let col = col.next();
     assert_eq!(col, SortColumn::NumaNode);
@@ -931,34 +1934,93 @@ fn test_app_sort_cycling() {
     assert_eq!(col, SortColumn::Swap);
 }
 
+#[test]
+fn test_render_unified_view_baseline_deltas() {
+    let mut terminal = make_test_terminal();
+    let theme = Theme::from(ThemeType::Dracula);
+
+    let procs = vec![UnifiedProcessInfo {
+        pid: 100,
+        name: "train_model".into(),
+        swap_kb: 3072,
+        cpu_nodes: vec![],
+        gpu_nodes: vec![],
+        kb_per_node: HashMap::new(),
+        gpu_memory_kb: Some(1000),
+        gpu_indices: vec![0],
+        location: ProcessLocation::CpuAndGpu,
+        rss_kb: None,
+        count: 1,
+    }];
+    let baseline = crate::data::Baseline {
+        swap_kb: HashMap::from([(100, 1024)]),
+        swap_kb_by_name: HashMap::new(),
+        gpu_kb: HashMap::from([(100, 1500)]),
+        gpu_kb_by_name: HashMap::new(),
+    };
+
+    terminal
+        .draw(|frame| {
+            ui::unified_view::render_unified_view(
+                frame,
+                frame.area(),
+                &theme,
+                &procs,
+                &SizeUnits::KB,
+                UnitBase::Binary,
+                &[],
+                false,
+                Some(&baseline),
+                SortColumn::Swap,
+                false,
+                false,
+                &[],
+                false,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
+            );
+        })
+        .unwrap();
+
+    let buf = terminal.backend().buffer().clone();
+    let content: String = buf.content().iter().map(|c| c.symbol().to_string()).collect();
+    assert!(content.contains("Δ SWAP"));
+    assert!(content.contains("+2048 KiB"));
+    assert!(content.contains("-500 KiB"));
+}
+
 #[test]
 fn test_format_mem_boundary_values() {
     use crate::ui::unified_view::format_mem;
 
     // 0 KB
-    let s = format_mem(0, &SizeUnits::KB);
-    assert_eq!(s, "0 KB");
+    let s = format_mem(0, &SizeUnits::KB, UnitBase::Binary);
+    assert_eq!(s, "0 KiB");
 
     // 1023 KB
-    let s = format_mem(1023, &SizeUnits::KB);
-    assert_eq!(s, "1023 KB");
+    let s = format_mem(1023, &SizeUnits::KB, UnitBase::Binary);
+    assert_eq!(s, "1023 KiB");
 
-    // 1024 KB = 1.00 MB
-    let s = format_mem(1024, &SizeUnits::MB);
-    assert_eq!(s, "1.00 MB");
+    // 1024 KB = 1.00 MiB
+    let s = format_mem(1024, &SizeUnits::MB, UnitBase::Binary);
+    assert_eq!(s, "1.00 MiB");
 
-    // 1048576 KB = 1.00 GB
-    let s = format_mem(1048576, &SizeUnits::GB);
-    assert_eq!(s, "1.00 GB");
+    // 1048576 KB = 1.00 GiB
+    let s = format_mem(1048576, &SizeUnits::GB, UnitBase::Binary);
+    assert_eq!(s, "1.00 GiB");
 
-    // Large value — should not panic
-    let s = format_mem(u64::MAX, &SizeUnits::KB);
-    assert!(!s.is_empty());
+    // Large value — exact, not rounded through f64
+    let s = format_mem(u64::MAX, &SizeUnits::KB, UnitBase::Binary);
+    assert_eq!(s, "18446744073709551615 KiB");
+    let s = format_mem((1 << 53) + 1, &SizeUnits::KB, UnitBase::Binary);
+    assert_eq!(s, "9007199254740993 KiB");
 
-    let s = format_mem(u64::MAX, &SizeUnits::MB);
+    let s = format_mem(u64::MAX, &SizeUnits::MB, UnitBase::Binary);
     assert!(!s.is_empty());
 
-    let s = format_mem(u64::MAX, &SizeUnits::GB);
+    let s = format_mem(u64::MAX, &SizeUnits::GB, UnitBase::Binary);
     assert!(!s.is_empty());
 }
 
@@ -1052,7 +2114,7 @@ fn test_smoke_numa_maps_real_process() {
 
     let content = content.unwrap();
     let page_size_kb = procfs::page_size() / 1024;
-    let info = crate::data::numa::parse_numa_maps(&content, pid, "self", page_size_kb);
+    let info = crate::data::numa::parse_numa_maps(&content, pid, "self", page_size_kb, None);
 
     assert!(
         info.total_kb > 0,
@@ -1107,6 +2169,33 @@ fn test_smoke_gpu_processes() {
     // May be empty if no GPU processes running — that's OK
 }
 
+#[cfg(feature = "nvml")]
+#[test]
+fn test_smoke_nvml_backend() {
+    use crate::data::nvml::NvmlBackend;
+
+    // Without libnvidia-ml or a loaded driver, init fails and the provider
+    // falls back to nvidia-smi (or reports no GPU) instead of erroring
+    let Some(backend) = NvmlBackend::new() else {
+        eprintln!("SKIP: NVML not available");
+        let provider = ProcDataProvider;
+        let _ = provider.gpu_status();
+        let devices = provider.get_gpu_devices();
+        assert!(devices.is_ok() || gpu_smoke_available(), "get_gpu_devices failed: {:?}", devices.err());
+        return;
+    };
+
+    let devices = backend.devices();
+    assert!(devices.is_ok(), "NVML devices failed: {:?}", devices.err());
+    for dev in devices.unwrap() {
+        assert!(!dev.name.is_empty(), "GPU {} has empty name", dev.index);
+        assert!(dev.memory_total_kb > 0, "GPU {} has 0 memory_total_kb", dev.index);
+    }
+    let processes = backend.processes(true);
+    assert!(processes.is_ok(), "NVML processes failed: {:?}", processes.err());
+    assert_eq!(ProcDataProvider.gpu_status(), GpuStatus::Available);
+}
+
 #[test]
 fn test_smoke_full_render_cycle() {
     let provider = ProcDataProvider;
@@ -1155,6 +2244,11 @@ fn test_smoke_full_render_cycle() {
                 &numa_infos,
                 numa_available,
                 &SizeUnits::KB,
+                UnitBase::Binary,
+                ui::numa_view::NumaNodeSort::Id,
+                true,
+                NUMA_MAPS_TOP_N,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -1168,8 +2262,17 @@ fn test_smoke_full_render_cycle() {
                 &theme,
                 &gpu_devices,
                 &gpu_processes,
-                provider.is_gpu_available(),
+                &provider.gpu_status(),
                 &SizeUnits::KB,
+                UnitBase::Binary,
+                false,
+                &ui::gpu_view::IdleGpuHighlight::default(),
+                &HashMap::new(),
+                false,
+                false,
+                false,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
@@ -1183,7 +2286,19 @@ fn test_smoke_full_render_cycle() {
                 &theme,
                 &unified,
                 &SizeUnits::KB,
+                UnitBase::Binary,
                 &numa_nodes,
+                false,
+                None,
+                SortColumn::Swap,
+                false,
+                false,
+                &[],
+                false,
+                LocationFilter::All,
+                0,
+                None,
+                &mut ListScroll::default(),
             );
         })
         .unwrap();
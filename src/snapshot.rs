@@ -0,0 +1,301 @@
+//! Point-in-time data snapshot for non-interactive consumers (the `--serve`
+//! HTTP endpoint and `--once`). Serialises to JSON via serde, to a
+//! tab-separated process table, and to the Prometheus text exposition format.
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::data::{
+    DataProvider, GpuDevice, GpuProcessInfo, PidWatch, ProcessLocation, ProcessSwapInfo, SizeUnits, SwapUsedMode,
+    UnifiedProcessInfo, merge_process_data,
+};
+
+/// `--format` of the `--once` snapshot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum SnapshotFormat {
+    /// Everything in the snapshot, as one JSON document
+    #[default]
+    Json,
+    /// The unified process list as tab-separated columns with a header row
+    Tsv,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub total_swap_kb: u64,
+    /// SwapTotal - SwapFree, less `swap_cached_kb` under `--exclude-swap-cache`
+    pub used_swap_kb: u64,
+    pub swap_cached_kb: Option<u64>,
+    /// Per-process swap as read from /proc; `swap_size` is in KB
+    pub swap_processes: Vec<ProcessSwapInfo>,
+    pub gpu_devices: Vec<GpuDevice>,
+    pub gpu_processes: Vec<GpuProcessInfo>,
+    /// Swap and GPU processes merged by PID
+    pub processes: Vec<UnifiedProcessInfo>,
+}
+
+/// Gather one round of swap and GPU data and merge it by PID.
+/// Per-process numa_maps are not read: they are too expensive to collect
+/// on every scrape.
+#[cfg(any(feature = "serve", test))]
+pub fn collect_snapshot(provider: &dyn DataProvider) -> Snapshot {
+    collect_snapshot_with(provider, &[], SwapUsedMode::IncludeCached)
+}
+
+/// `collect_snapshot` as `--once` sees it with the TUI's flags: only the
+/// processes in `pids` (all when empty, as `--pid`) and used swap under
+/// `used_mode` (`--exclude-swap-cache`).
+pub fn collect_snapshot_with(provider: &dyn DataProvider, pids: &[u32], used_mode: SwapUsedMode) -> Snapshot {
+    let swap = provider.get_swap_info(&SizeUnits::KB).unwrap_or_default();
+    let mut swap_procs = provider.get_processes_swap(&SizeUnits::KB).unwrap_or_default();
+    let (gpu_devices, mut gpu_procs) = if provider.is_gpu_available() {
+        (
+            provider.get_gpu_devices().unwrap_or_default(),
+            provider.get_gpu_processes().unwrap_or_default(),
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    if !pids.is_empty() {
+        let mut watch = PidWatch::new(pids.to_vec());
+        watch.filter_swap(&mut swap_procs);
+        watch.filter_gpu(&mut gpu_procs);
+    }
+
+    let processes = merge_process_data(&swap_procs, &gpu_procs, &[], &[], &gpu_devices);
+    Snapshot {
+        total_swap_kb: swap.total_swap,
+        used_swap_kb: swap.used_kb(used_mode),
+        swap_cached_kb: swap.swap_cached,
+        swap_processes: swap_procs,
+        gpu_devices,
+        gpu_processes: gpu_procs,
+        processes,
+    }
+}
+
+pub fn to_json(snapshot: &Snapshot) -> String {
+    serde_json::to_string_pretty(snapshot).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// The unified process list as a table for `cut`/`awk`: one header row, then
+/// one row per process, largest swap user first. Sizes are KB; empty fields
+/// mean "not applicable" (no RSS for GPU-only rows, no GPU memory for
+/// CPU-only ones). Tabs and newlines in names are replaced by spaces.
+pub fn to_tsv(snapshot: &Snapshot) -> String {
+    let mut out = String::from("pid\tname\tlocation\tswap_kb\trss_kb\tgpu_memory_kb\tgpu_indices\n");
+    let mut processes: Vec<&UnifiedProcessInfo> = snapshot.processes.iter().collect();
+    processes.sort_by(|a, b| b.swap_kb.cmp(&a.swap_kb).then(a.pid.cmp(&b.pid)));
+    for proc in processes {
+        let location = match proc.location {
+            ProcessLocation::CpuOnly => "cpu",
+            ProcessLocation::GpuOnly => "gpu",
+            ProcessLocation::CpuAndGpu => "cpu+gpu",
+        };
+        let gpu_indices: Vec<String> = proc.gpu_indices.iter().map(|i| i.to_string()).collect();
+        let _ = writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            proc.pid,
+            proc.name.replace(['\t', '\n'], " "),
+            location,
+            proc.swap_kb,
+            proc.rss_kb.map(|kb| kb.to_string()).unwrap_or_default(),
+            proc.gpu_memory_kb.map(|kb| kb.to_string()).unwrap_or_default(),
+            gpu_indices.join(","),
+        );
+    }
+    out
+}
+
+/// `--once`: the snapshot in `format`.
+pub fn render(snapshot: &Snapshot, format: SnapshotFormat) -> String {
+    match format {
+        SnapshotFormat::Json => to_json(snapshot) + "\n",
+        SnapshotFormat::Tsv => to_tsv(snapshot),
+    }
+}
+
+/// Render the snapshot in the Prometheus text exposition format.
+/// All memory values are exported in bytes, per Prometheus convention.
+#[cfg(any(feature = "serve", test))]
+pub fn to_prometheus(snapshot: &Snapshot) -> String {
+    let mut out = String::new();
+
+    write_metric_header(&mut out, "nv_swaptop_swap_total_bytes", "Total swap space");
+    let _ = writeln!(out, "nv_swaptop_swap_total_bytes {}", snapshot.total_swap_kb * 1024);
+    write_metric_header(&mut out, "nv_swaptop_swap_used_bytes", "Used swap space");
+    let _ = writeln!(out, "nv_swaptop_swap_used_bytes {}", snapshot.used_swap_kb * 1024);
+    if let Some(cached) = snapshot.swap_cached_kb {
+        write_metric_header(&mut out, "nv_swaptop_swap_cached_bytes", "Swap also cached in RAM (SwapCached)");
+        let _ = writeln!(out, "nv_swaptop_swap_cached_bytes {}", cached * 1024);
+    }
+
+    write_metric_header(&mut out, "nv_swaptop_gpu_memory_total_bytes", "GPU memory total");
+    for dev in &snapshot.gpu_devices {
+        let _ = writeln!(
+            out,
+            "nv_swaptop_gpu_memory_total_bytes{{gpu=\"{}\",name=\"{}\"}} {}",
+            dev.index,
+            escape_label(&dev.name),
+            dev.memory_total_kb * 1024
+        );
+    }
+    write_metric_header(&mut out, "nv_swaptop_gpu_memory_used_bytes", "GPU memory used");
+    for dev in &snapshot.gpu_devices {
+        let _ = writeln!(
+            out,
+            "nv_swaptop_gpu_memory_used_bytes{{gpu=\"{}\",name=\"{}\"}} {}",
+            dev.index,
+            escape_label(&dev.name),
+            dev.memory_used_kb * 1024
+        );
+    }
+
+    write_metric_header(&mut out, "nv_swaptop_process_swap_bytes", "Per-process swap usage");
+    for proc in &snapshot.processes {
+        let _ = writeln!(
+            out,
+            "nv_swaptop_process_swap_bytes{{pid=\"{}\",name=\"{}\"}} {}",
+            proc.pid,
+            escape_label(&proc.name),
+            proc.swap_kb * 1024
+        );
+    }
+    write_metric_header(&mut out, "nv_swaptop_process_gpu_memory_bytes", "Per-process GPU memory usage");
+    for proc in &snapshot.processes {
+        if let Some(kb) = proc.gpu_memory_kb {
+            let _ = writeln!(
+                out,
+                "nv_swaptop_process_gpu_memory_bytes{{pid=\"{}\",name=\"{}\"}} {}",
+                proc.pid,
+                escape_label(&proc.name),
+                kb * 1024
+            );
+        }
+    }
+
+    out
+}
+
+#[cfg(any(feature = "serve", test))]
+fn write_metric_header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+}
+
+/// Escape a Prometheus label value (backslash, double quote, newline).
+#[cfg(any(feature = "serve", test))]
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{GpuProcessInfo, MockDataProvider};
+
+    fn make_gpu_mock() -> MockDataProvider {
+        let mut mock = MockDataProvider::new();
+        mock.gpu_available = true;
+        mock.gpu_devices = vec![GpuDevice {
+            index: 0, name: "NVIDIA H100".into(), memory_total_kb: 81_920_000,
+            memory_used_kb: 4096, memory_free_kb: 81_915_904,
+            numa_node_id: None, temperature: Some(40), pci_bus_id: "00:01.0".into(), memory_reserved_kb: None, mig_instances: Vec::new(), utilization_percent: None, memory_utilization_percent: None,
+        }];
+        mock.gpu_processes = vec![GpuProcessInfo {
+            pid: 1, name: "test_proc".into(), gpu_index: 0, gpu_memory_used_kb: 4096, sm_util: None, mem_util: None,
+        }];
+        mock
+    }
+
+    #[test]
+    fn test_collect_snapshot_merges_swap_and_gpu() {
+        let snapshot = collect_snapshot(&make_gpu_mock());
+        assert_eq!(snapshot.total_swap_kb, 8_000_000);
+        assert_eq!(snapshot.used_swap_kb, 2_000_000);
+        assert_eq!(snapshot.gpu_devices.len(), 1);
+        assert_eq!(snapshot.processes.len(), 2);
+        let proc = snapshot.processes.iter().find(|p| p.pid == 1).unwrap();
+        assert_eq!(proc.gpu_memory_kb, Some(4096));
+    }
+
+    #[test]
+    fn test_collect_snapshot_with_pids_and_used_mode() {
+        let mut mock = make_gpu_mock();
+        mock.swap_update.swap_cached = Some(500_000);
+        let snapshot = collect_snapshot_with(&mock, &[2], SwapUsedMode::ExcludeCached);
+        assert_eq!(snapshot.used_swap_kb, 1_500_000);
+        assert_eq!(snapshot.swap_processes.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![2]);
+        assert!(snapshot.gpu_processes.is_empty());
+        assert_eq!(snapshot.processes.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![2]);
+        // Devices are not per-process and stay whole
+        assert_eq!(snapshot.gpu_devices.len(), 1);
+    }
+
+    #[test]
+    fn test_to_json_is_valid() {
+        let snapshot = collect_snapshot(&make_gpu_mock());
+        let value: serde_json::Value = serde_json::from_str(&to_json(&snapshot)).unwrap();
+        assert_eq!(value["total_swap_kb"], 8_000_000);
+        assert_eq!(value["processes"].as_array().unwrap().len(), 2);
+        assert_eq!(value["gpu_devices"][0]["name"], "NVIDIA H100");
+    }
+
+    #[test]
+    fn test_render_json_contains_process_lists() {
+        let snapshot = collect_snapshot(&make_gpu_mock());
+        let value: serde_json::Value = serde_json::from_str(&render(&snapshot, SnapshotFormat::Json)).unwrap();
+        let swap_pids: Vec<u64> = value["swap_processes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["pid"].as_u64().unwrap())
+            .collect();
+        assert_eq!(swap_pids, vec![1, 2]);
+        assert_eq!(value["swap_processes"][0]["name"], "test_proc");
+        assert_eq!(value["gpu_processes"][0]["gpu_memory_used_kb"], 4096);
+        let merged = value["processes"].as_array().unwrap().iter().find(|p| p["pid"] == 1).unwrap().clone();
+        assert_eq!(merged["location"], "CpuAndGpu");
+        // Both lists carry the row's process count
+        assert_eq!(value["swap_processes"][0]["count"], 1);
+        assert_eq!(merged["count"], 1);
+    }
+
+    #[test]
+    fn test_to_tsv_rows() {
+        let mut mock = make_gpu_mock();
+        mock.processes[1].name = "tab\tname".into();
+        mock.gpu_processes.push(GpuProcessInfo {
+            pid: 9, name: "gpu_only".into(), gpu_index: 0, gpu_memory_used_kb: 100, sm_util: None, mem_util: None,
+        });
+        let tsv = to_tsv(&collect_snapshot(&mock));
+        let lines: Vec<&str> = tsv.lines().collect();
+        assert_eq!(lines[0], "pid\tname\tlocation\tswap_kb\trss_kb\tgpu_memory_kb\tgpu_indices");
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().skip(1).all(|line| line.split('\t').count() == 7));
+        assert_eq!(lines[2], "2\ttab name\tcpu\t512\t\t\t");
+        assert_eq!(lines[3], "9\tgpu_only\tgpu\t0\t\t100\t0");
+    }
+
+    #[test]
+    fn test_to_prometheus_metrics() {
+        let snapshot = collect_snapshot(&make_gpu_mock());
+        let text = to_prometheus(&snapshot);
+        assert!(text.contains("# TYPE nv_swaptop_swap_used_bytes gauge"));
+        assert!(text.contains(&format!("nv_swaptop_swap_used_bytes {}", 2_000_000u64 * 1024)));
+        assert!(text.contains("nv_swaptop_gpu_memory_used_bytes{gpu=\"0\",name=\"NVIDIA H100\"} 4194304"));
+        assert!(text.contains("nv_swaptop_process_swap_bytes{pid=\"2\",name=\"another\"} 524288"));
+        assert!(text.contains("nv_swaptop_process_gpu_memory_bytes{pid=\"1\",name=\"test_proc\"} 4194304"));
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(escape_label("line\nbreak"), "line\\nbreak");
+    }
+}
@@ -1,6 +1,6 @@
 use ratatui::style::Color;
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
 pub enum ThemeType {
     #[default]
     Default,
@@ -8,6 +8,8 @@ pub enum ThemeType {
     Monokai,
     Dracula,
     Nord,
+    /// Blue/orange status colours, distinguishable with red-green colour blindness
+    Colorblind,
 }
 #[derive(Debug, Clone, Copy)]
 pub struct Theme {
@@ -17,8 +19,19 @@ pub struct Theme {
     pub border: Color,
     pub background: Color,
     pub scrollbar: Color,
+    /// Status colours: NUMA-local memory / low thrash
+    pub good: Color,
+    /// NUMA-remote memory, CPU/memory misalignment / medium thrash
+    pub warn: Color,
+    /// GPU HBM memory / high thrash, misconfigured swap priority
+    pub critical: Color,
 }
 
+// Status colours shared by the original themes
+const GOOD_GREEN: Color = Color::Rgb(80, 200, 120);
+const WARN_ORANGE: Color = Color::Rgb(255, 183, 77);
+const CRITICAL_RED: Color = Color::Rgb(255, 85, 85);
+
 impl Theme {
     pub fn from(theme_type: ThemeType) -> Self {
         match theme_type {
@@ -27,6 +40,7 @@ impl Theme {
             ThemeType::Monokai => Self::monokai_theme(),
             ThemeType::Dracula => Self::dracula_theme(),
             ThemeType::Nord => Self::nord_theme(),
+            ThemeType::Colorblind => Self::colorblind_theme(),
         }
     }
 
@@ -38,6 +52,9 @@ impl Theme {
             border: Color::Rgb(80, 80, 120),
             background: Color::Rgb(20, 20, 30),
             scrollbar: Color::Rgb(100, 100, 140),
+            good: GOOD_GREEN,
+            warn: WARN_ORANGE,
+            critical: CRITICAL_RED,
         }
     }
 
@@ -49,6 +66,9 @@ impl Theme {
             border: Color::Rgb(88, 110, 117),     // Base01
             background: Color::Rgb(0, 43, 54),    // Base03
             scrollbar: Color::Rgb(101, 123, 131), // Base00
+            good: GOOD_GREEN,
+            warn: WARN_ORANGE,
+            critical: CRITICAL_RED,
         }
     }
 
@@ -60,6 +80,9 @@ impl Theme {
             border: Color::Rgb(117, 113, 94),     // Gray
             background: Color::Rgb(39, 40, 34),   // Dark gray
             scrollbar: Color::Rgb(105, 105, 105),
+            good: GOOD_GREEN,
+            warn: WARN_ORANGE,
+            critical: CRITICAL_RED,
         }
     }
 
@@ -71,6 +94,9 @@ impl Theme {
             border: Color::Rgb(98, 114, 164),     // Blue-gray
             background: Color::Rgb(40, 42, 54),   // Dark purple
             scrollbar: Color::Rgb(68, 71, 90),
+            good: GOOD_GREEN,
+            warn: WARN_ORANGE,
+            critical: CRITICAL_RED,
         }
     }
 
@@ -82,6 +108,34 @@ impl Theme {
             border: Color::Rgb(76, 86, 106),      // PolarNight2
             background: Color::Rgb(46, 52, 64),   // PolarNight0
             scrollbar: Color::Rgb(67, 76, 94),
+            good: GOOD_GREEN,
+            warn: WARN_ORANGE,
+            critical: CRITICAL_RED,
+        }
+    }
+
+    /// Default base colours with status colours from the Okabe-Ito palette,
+    /// which avoids relying on red vs green.
+    fn colorblind_theme() -> Self {
+        Self {
+            good: Color::Rgb(86, 180, 233),     // Sky blue
+            warn: Color::Rgb(230, 159, 0),      // Orange
+            critical: Color::Rgb(204, 121, 167), // Reddish purple
+            ..Self::default_theme()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colorblind_theme_avoids_green() {
+        let theme = Theme::from(ThemeType::Colorblind);
+        assert_ne!(theme.good, GOOD_GREEN);
+        assert_ne!(theme.critical, CRITICAL_RED);
+        let Color::Rgb(r, g, b) = theme.good else { panic!("expected RGB") };
+        assert!(b > g && b > r, "local/low colour should be blue");
+    }
+}
@@ -1,16 +1,196 @@
-use crate::data::types::{SizeUnits, convert_swap};
+use crate::data::types::{SizeUnits, UnitBase, total_used_label};
+use crate::ui::unified_view::{format_mem, format_mem_delta};
 use crate::theme::Theme;
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     style::{Style, Stylize},
     symbols::Marker,
     text::Line,
-    widgets::{Axis, Block, BorderType, Chart, Dataset, GraphType},
+    widgets::{Axis, Block, BorderType, Chart, Dataset, GraphType, LegendPosition},
 };
 
 const LINUX: bool = cfg!(target_os = "linux");
 
+/// Granularity of the x-axis labels (`x` cycles).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TimeAxisUnit {
+    #[default]
+    Ticks,
+    Seconds,
+    Minutes,
+}
+
+impl TimeAxisUnit {
+    pub fn next(self) -> Self {
+        match self {
+            TimeAxisUnit::Ticks => TimeAxisUnit::Seconds,
+            TimeAxisUnit::Seconds => TimeAxisUnit::Minutes,
+            TimeAxisUnit::Minutes => TimeAxisUnit::Ticks,
+        }
+    }
+}
+
+/// A line on the swap chart that can be shown or hidden.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartSeries {
+    Used,
+    Mean,
+    /// ±1σ around the mean, drawn as two lines
+    Band,
+    /// Swap-in/out pages per second, in a panel under the usage chart
+    Rate,
+}
+
+impl ChartSeries {
+    /// In menu order: `1` toggles the first.
+    pub const ALL: [ChartSeries; 4] = [ChartSeries::Used, ChartSeries::Mean, ChartSeries::Band, ChartSeries::Rate];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChartSeries::Used => "used",
+            ChartSeries::Mean => "mean",
+            ChartSeries::Band => "±1σ",
+            ChartSeries::Rate => "I/O",
+        }
+    }
+
+    /// Series toggled by menu key `key` ('1' for the first).
+    pub fn from_menu_key(key: char) -> Option<Self> {
+        let index = key.to_digit(10)?.checked_sub(1)?;
+        Self::ALL.get(index as usize).copied()
+    }
+}
+
+/// Which chart series are drawn. Only used swap by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisibleSeries([bool; 4]);
+
+impl Default for VisibleSeries {
+    fn default() -> Self {
+        VisibleSeries([true, false, false, false])
+    }
+}
+
+impl VisibleSeries {
+    pub fn contains(self, series: ChartSeries) -> bool {
+        self.0[series as usize]
+    }
+
+    pub fn toggle(&mut self, series: ChartSeries) {
+        self.0[series as usize] = !self.0[series as usize];
+    }
+
+    /// `b`: hide mean and band if either is shown, otherwise show both.
+    pub fn toggle_stats(&mut self) {
+        let show = !self.contains(ChartSeries::Mean) && !self.contains(ChartSeries::Band);
+        self.0[ChartSeries::Mean as usize] = show;
+        self.0[ChartSeries::Band as usize] = show;
+    }
+
+    /// The usage chart's own lines, without the separate I/O rate panel.
+    fn usage_lines(mut self) -> Self {
+        self.0[ChartSeries::Rate as usize] = false;
+        self
+    }
+
+    /// Names of the drawn series, in menu order.
+    pub fn labels(self) -> Vec<&'static str> {
+        ChartSeries::ALL.into_iter().filter(|&s| self.contains(s)).map(ChartSeries::label).collect()
+    }
+}
+
+/// Legend entries padded to one width: the legend box does not clear the
+/// plot underneath, so a shorter name would leave line fragments beside it.
+fn legend_name(series: ChartSeries) -> String {
+    let width = ChartSeries::ALL.iter().map(|s| s.label().chars().count()).max().unwrap_or(0);
+    format!("{:<width$}", series.label())
+}
+
+/// Bottom title while the series menu is open: "1 [x] used  2 [ ] mean ...".
+fn series_menu_title(series: VisibleSeries) -> String {
+    let entries: Vec<String> = ChartSeries::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, &s)| format!("{} [{}] {}", i + 1, if series.contains(s) { 'x' } else { ' ' }, s.label()))
+        .collect();
+    format!(" series: {}  (y/Esc to close) ", entries.join("  "))
+}
+
+/// Label for a point `ticks_ago` refreshes back. Wall-clock units assume the
+/// current refresh interval held for the whole window.
+fn time_label(ticks_ago: f64, unit: TimeAxisUnit, timeout_ms: u64) -> String {
+    if ticks_ago <= 0.0 {
+        return "now".to_string();
+    }
+    let secs = ticks_ago * timeout_ms as f64 / 1000.0;
+    match unit {
+        TimeAxisUnit::Ticks => format!("-{:.0}t", ticks_ago),
+        TimeAxisUnit::Seconds if secs < 10.0 => format!("-{:.1}s", secs),
+        TimeAxisUnit::Seconds => format!("-{:.0}s", secs),
+        TimeAxisUnit::Minutes => format!("-{:.1}m", secs / 60.0),
+    }
+}
+
+/// Left, middle and right x-axis labels for `time_window`.
+fn time_axis_labels(time_window: [f64; 2], unit: TimeAxisUnit, timeout_ms: u64) -> Vec<String> {
+    let span = time_window[1] - time_window[0];
+    [span, span / 2.0, 0.0]
+        .iter()
+        .map(|&ago| time_label(ago, unit, timeout_ms))
+        .collect()
+}
+
+/// Mean of the plotted samples and, with at least two, their (sample)
+/// standard deviation. None without data.
+pub(crate) fn mean_stddev(chart_data: &[(f64, f64)]) -> Option<(f64, Option<f64>)> {
+    if chart_data.is_empty() {
+        return None;
+    }
+    let n = chart_data.len() as f64;
+    let mean = chart_data.iter().map(|&(_, y)| y).sum::<f64>() / n;
+    let stddev = (chart_data.len() >= 2).then(|| {
+        let variance = chart_data.iter().map(|&(_, y)| (y - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        variance.sqrt()
+    });
+    Some((mean, stddev))
+}
+
+/// Horizontal line at `y` across the x range of the plotted samples.
+fn level_line(chart_data: &[(f64, f64)], y: f64) -> [(f64, f64); 2] {
+    let first = chart_data.first().map_or(0.0, |&(x, _)| x);
+    let last = chart_data.last().map_or(0.0, |&(x, _)| x);
+    [(first, y), (last, y)]
+}
+
+/// Sample selected by the chart cursor, `cursor` samples back from the
+/// newest; clamped to the oldest retained sample. None without data.
+pub(crate) fn cursor_sample(chart_data: &[(f64, f64)], cursor: usize) -> Option<(f64, f64)> {
+    let newest = chart_data.len().checked_sub(1)?;
+    chart_data.get(newest.saturating_sub(cursor)).copied()
+}
+
+/// "cursor -5t: 1.91 GiB (Δ now +120.00 MiB)" for the selected sample.
+#[allow(clippy::too_many_arguments)]
+fn cursor_title(
+    sample: (f64, f64),
+    chart_data: &[(f64, f64)],
+    time_window: [f64; 2],
+    unit: &SizeUnits,
+    base: UnitBase,
+    time_unit: TimeAxisUnit,
+    timeout_ms: u64,
+) -> String {
+    let (x, used_kb) = sample;
+    let now_kb = chart_data.last().map(|&(_, y)| y).unwrap_or(used_kb);
+    format!(
+        " cursor {}: {} (Δ now {}) ",
+        time_label(time_window[1] - x, time_unit, timeout_ms),
+        format_mem(used_kb as u64, unit, base),
+        format_mem_delta(now_kb as i64 - used_kb as i64, unit, base)
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn render_animated_chart(
     frame: &mut Frame,
@@ -21,16 +201,24 @@ pub fn render_animated_chart(
     total_swap: u64,
     used_swap: u64,
     swap_size_unit: &SizeUnits,
+    unit_base: UnitBase,
     display_devices: bool,
+    time_unit: TimeAxisUnit,
+    timeout_ms: u64,
+    cursor: Option<usize>,
+    series: VisibleSeries,
+    series_menu: bool,
+    swap_rates: [&[(f64, f64)]; 2],
+    log_y: bool,
 ) {
-    let total = convert_swap(total_swap, swap_size_unit.clone());
-    let used = convert_swap(used_swap, swap_size_unit.clone());
-
-    let total_used_title: String = match swap_size_unit {
-        SizeUnits::KB => format!("total: {} | used: {}", total, used),
-        SizeUnits::MB => format!("total: {} | used: {:.2}", total.round(), used),
-        SizeUnits::GB => format!("total: {:.2} | used: {:.2}", total, used),
+    let area = if series.contains(ChartSeries::Rate) {
+        let [usage, rates] = Layout::vertical([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)]).areas(area);
+        render_rate_chart(frame, rates, theme, swap_rates, time_window, time_unit, timeout_ms);
+        usage
+    } else {
+        area
     };
+    let total_used_title = total_used_label(total_swap, used_swap, swap_size_unit, unit_base);
 
     let total_n_used_line = if display_devices {
         Line::from("").fg(theme.text).left_aligned()
@@ -38,19 +226,100 @@ pub fn render_animated_chart(
         Line::from(total_used_title).fg(theme.text).left_aligned()
     };
 
-    let swap_usage_percent = used_swap as f64 / total_swap as f64 * 100.0;
-    let datasets = vec![
-        Dataset::default()
-            .marker(Marker::Braille)
-            .style(Style::default().fg(theme.primary))
-            .graph_type(GraphType::Line)
-            .data(chart_data),
-    ];
+    // Mean line plus a ±1σ band drawn as two lines; the band needs two samples
+    let stats = mean_stddev(chart_data);
+    let mean_line = stats
+        .filter(|_| series.contains(ChartSeries::Mean))
+        .map(|(mean, _)| level_line(chart_data, mean));
+    let band_lines = stats.filter(|_| series.contains(ChartSeries::Band)).and_then(|(mean, stddev)| {
+        let stddev = stddev?;
+        Some([level_line(chart_data, mean + stddev), level_line(chart_data, (mean - stddev).max(0.0))])
+    });
+    let used_line: &[(f64, f64)] = if series.contains(ChartSeries::Used) { chart_data } else { &[] };
+
+    let visible_lines = [used_line, mean_line.as_ref().map_or(&[], |l| &l[..]), band_lines.as_ref().map_or(&[], |b| &b[0][..])];
+    let ceiling = y_axis_ceiling(total_swap, &visible_lines);
+    let selected = cursor.and_then(|c| cursor_sample(chart_data, c));
+
+    // Statistics, the ceiling and the cursor title use KB; only the plotted
+    // points are rescaled
+    let scale_y = |y: f64| if log_y { log_scale(y) } else { y };
+    let plot = |line: &[(f64, f64)]| line.iter().map(|&(x, y)| (x, scale_y(y))).collect::<Vec<_>>();
+    let used_points = plot(used_line);
+    let mean_points = mean_line.as_ref().map(|line| plot(line));
+    let band_points = band_lines.as_ref().map(|[upper, lower]| [plot(upper), plot(lower)]);
+    let cursor_line = selected.map(|(x, _)| [(x, 0.0), (x, scale_y(ceiling))]);
+    let cursor_point = selected.map(|(x, y)| [(x, scale_y(y))]);
 
-    let bottom_title = if LINUX && !display_devices {
-        "(h to show swap devices)"
+    let mut datasets = Vec::new();
+    if let Some([upper, lower]) = &band_points {
+        datasets.push(
+            Dataset::default()
+                .name(legend_name(ChartSeries::Band))
+                .marker(Marker::Dot)
+                .style(Style::default().fg(theme.border))
+                .graph_type(GraphType::Line)
+                .data(upper),
+        );
+        datasets.push(
+            Dataset::default()
+                .marker(Marker::Dot)
+                .style(Style::default().fg(theme.border))
+                .graph_type(GraphType::Line)
+                .data(lower),
+        );
+    }
+    if let Some(line) = &mean_points {
+        datasets.push(
+            Dataset::default()
+                .name(legend_name(ChartSeries::Mean))
+                .marker(Marker::Braille)
+                .style(Style::default().fg(theme.secondary))
+                .graph_type(GraphType::Line)
+                .data(line),
+        );
+    }
+    if series.contains(ChartSeries::Used) {
+        datasets.push(
+            Dataset::default()
+                .name(legend_name(ChartSeries::Used))
+                .marker(Marker::Braille)
+                .style(Style::default().fg(theme.primary))
+                .graph_type(GraphType::Line)
+                .data(&used_points),
+        );
+    }
+    if let (Some(line), Some(point)) = (&cursor_line, &cursor_point) {
+        datasets.push(
+            Dataset::default()
+                .marker(Marker::Braille)
+                .style(Style::default().fg(theme.secondary))
+                .graph_type(GraphType::Line)
+                .data(line),
+        );
+        datasets.push(
+            Dataset::default()
+                .marker(Marker::Block)
+                .style(Style::default().fg(theme.warn))
+                .graph_type(GraphType::Scatter)
+                .data(point),
+        );
+    }
+
+    let bottom_title = if series_menu {
+        Line::from(series_menu_title(series)).fg(theme.secondary)
+    } else if LINUX && !display_devices {
+        Line::from("(h to show swap devices) (x for time units) ([/] cursor) (b mean/σ) (y series) (L log)")
     } else {
-        ""
+        Line::from("(x for time units) ([/] cursor) (b mean/σ) (y series) (L log)")
+    };
+    // The default single line needs no legend
+    let legend = (series.usage_lines() != VisibleSeries::default() || series_menu).then_some(LegendPosition::TopLeft);
+    let cursor_line_title = match selected {
+        Some(sample) => Line::from(cursor_title(sample, chart_data, time_window, swap_size_unit, unit_base, time_unit, timeout_ms))
+            .fg(theme.secondary)
+            .centered(),
+        None => Line::from(""),
     };
     let chart = Chart::new(datasets)
         .block(
@@ -58,26 +327,291 @@ pub fn render_animated_chart(
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(theme.border))
                 .title(
-                    Line::from(format!("swap usage {}%", swap_usage_percent.round() as u64))
+                    Line::from(swap_usage_title(total_swap, used_swap))
                         .fg(theme.primary)
                         .bold()
                         .right_aligned(),
                 )
                 .title(total_n_used_line)
-                .title_bottom(Line::from(bottom_title).left_aligned())
+                .title(cursor_line_title)
+                .title_bottom(bottom_title.left_aligned())
                 .border_style(Style::default().fg(theme.border))
                 .style(Style::default().bg(theme.background)),
         )
         .x_axis(
             Axis::default()
                 .style(Style::default().fg(theme.text))
-                .bounds(time_window),
+                .bounds(time_window)
+                .labels(time_axis_labels(time_window, time_unit, timeout_ms)),
+        )
+        .y_axis(y_axis(ceiling, log_y, swap_size_unit, unit_base).style(Style::default().fg(theme.text)))
+        .legend_position(legend)
+        .hidden_legend_constraints((Constraint::Ratio(1, 3), Constraint::Ratio(1, 1)));
+
+    frame.render_widget(chart, area);
+}
+
+/// Swap-in and swap-out pages per second over the same time window as the
+/// usage chart. Constant usage can hide heavy paging; this shows it.
+fn render_rate_chart(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    [swap_in, swap_out]: [&[(f64, f64)]; 2],
+    time_window: [f64; 2],
+    time_unit: TimeAxisUnit,
+    timeout_ms: u64,
+) {
+    let peak = swap_in.iter().chain(swap_out).map(|&(_, y)| y).fold(0.0, f64::max);
+    let datasets = vec![
+        Dataset::default()
+            .name("in ")
+            .marker(Marker::Braille)
+            .style(Style::default().fg(theme.secondary))
+            .graph_type(GraphType::Line)
+            .data(swap_in),
+        Dataset::default()
+            .name("out")
+            .marker(Marker::Braille)
+            .style(Style::default().fg(theme.warn))
+            .graph_type(GraphType::Line)
+            .data(swap_out),
+    ];
+    let chart = Chart::new(datasets)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.border))
+                .title(Line::from(rate_title(swap_in, swap_out, peak)).fg(theme.primary).right_aligned())
+                .style(Style::default().bg(theme.background)),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.text))
+                .bounds(time_window)
+                .labels(time_axis_labels(time_window, time_unit, timeout_ms)),
         )
         .y_axis(
             Axis::default()
                 .style(Style::default().fg(theme.text))
-                .bounds([0.0, total_swap as f64]),
-        );
+                .bounds([0.0, peak.max(1.0)]),
+        )
+        .legend_position(Some(LegendPosition::TopLeft))
+        .hidden_legend_constraints((Constraint::Ratio(1, 3), Constraint::Ratio(1, 1)));
 
     frame.render_widget(chart, area);
 }
+
+/// "swap I/O pages/s in 120 out 4000 (peak 5100)"; the latest samples, or a
+/// note while fewer than two vmstat readings exist.
+fn rate_title(swap_in: &[(f64, f64)], swap_out: &[(f64, f64)], peak: f64) -> String {
+    match (swap_in.last(), swap_out.last()) {
+        (Some(&(_, rate_in)), Some(&(_, rate_out))) => {
+            format!(" swap I/O pages/s in {:.0} out {:.0} (peak {:.0}) ", rate_in, rate_out, peak)
+        }
+        _ => " swap I/O pages/s: waiting for two /proc/vmstat samples ".to_string(),
+    }
+}
+
+/// "swap usage N%"; 0% with no swap configured rather than NaN.
+fn swap_usage_title(total_swap: u64, used_swap: u64) -> String {
+    let percent = if total_swap > 0 {
+        used_swap as f64 / total_swap as f64 * 100.0
+    } else {
+        0.0
+    };
+    format!("swap usage {}%", percent.round() as u64)
+}
+
+/// `ln(1 + kb)`, the log y-axis transform (`L`). Monotonic, maps 0 to 0,
+/// and clamps negative inputs to 0.
+pub(crate) fn log_scale(kb: f64) -> f64 {
+    kb.max(0.0).ln_1p()
+}
+
+/// Inverse of `log_scale`, for axis labels.
+fn log_unscale(y: f64) -> f64 {
+    y.exp_m1()
+}
+
+/// Usage chart y-axis up to `ceiling` KB, labelled by `y_axis_labels`.
+fn y_axis(ceiling: f64, log_y: bool, unit: &SizeUnits, base: UnitBase) -> Axis<'static> {
+    let top = if log_y { log_scale(ceiling) } else { ceiling };
+    Axis::default().bounds([0.0, top]).labels(y_axis_labels(ceiling, log_y, unit, base))
+}
+
+/// Bottom, middle and top y-axis labels for a `ceiling` in KB, in `unit`
+/// of `base`. On the log axis the middle is the geometric midpoint, since
+/// equal steps no longer mean equal sizes.
+fn y_axis_labels(ceiling: f64, log_y: bool, unit: &SizeUnits, base: UnitBase) -> Vec<String> {
+    let middle = if log_y { log_unscale(log_scale(ceiling) / 2.0) } else { ceiling / 2.0 };
+    [0.0, middle, ceiling]
+        .iter()
+        .map(|&kb| format_mem(kb.round() as u64, unit, base))
+        .collect()
+}
+
+/// Upper y bound: the current swap total, raised to the highest point of the
+/// visible series so history recorded before a `swapoff` shrank the total
+/// stays on screen. Growing the total (`swapon`) simply rescales the whole
+/// history. Never 0, so the chart still draws with no swap configured.
+fn y_axis_ceiling(total_swap: u64, visible_lines: &[&[(f64, f64)]]) -> f64 {
+    visible_lines
+        .iter()
+        .flat_map(|line| line.iter())
+        .map(|&(_, y)| y)
+        .fold(total_swap as f64, f64::max)
+        .max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_scale_is_monotonic_from_zero() {
+        assert_eq!(log_scale(0.0), 0.0);
+        assert_eq!(log_scale(-5.0), 0.0);
+        let samples = [0.0, 0.5, 1.0, 10.0, 1024.0, 1_048_576.0, 1e12];
+        assert!(samples.windows(2).all(|w| log_scale(w[0]) < log_scale(w[1])));
+        assert!((log_unscale(log_scale(8_388_608.0)) - 8_388_608.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_y_axis_labels() {
+        assert_eq!(y_axis_labels(8_388_608.0, false, &SizeUnits::KB, UnitBase::Binary), vec!["0 KiB", "4194304 KiB", "8388608 KiB"]);
+        assert_eq!(y_axis_labels(8_388_608.0, false, &SizeUnits::GB, UnitBase::Binary), vec!["0.00 GiB", "4.00 GiB", "8.00 GiB"]);
+        assert_eq!(y_axis_labels(8_388_608.0, false, &SizeUnits::MB, UnitBase::Decimal), vec!["0.00 MB", "4294.97 MB", "8589.93 MB"]);
+        // Log axis: 0, sqrt(1 + ceiling) - 1 and the ceiling itself
+        assert_eq!(y_axis_labels(1_048_575.0, true, &SizeUnits::KB, UnitBase::Binary), vec!["0 KiB", "1023 KiB", "1048575 KiB"]);
+    }
+
+    #[test]
+    fn test_mean_stddev() {
+        assert_eq!(mean_stddev(&[]), None);
+        // One sample: mean only, no band
+        assert_eq!(mean_stddev(&[(0.0, 5.0)]), Some((5.0, None)));
+        let (mean, stddev) = mean_stddev(&[(0.0, 2.0), (1.0, 4.0), (2.0, 6.0)]).unwrap();
+        assert_eq!(mean, 4.0);
+        assert_eq!(stddev, Some(2.0));
+    }
+
+    #[test]
+    fn test_level_line_spans_samples() {
+        assert_eq!(level_line(&[(3.0, 1.0), (9.0, 2.0)], 7.5), [(3.0, 7.5), (9.0, 7.5)]);
+    }
+
+    #[test]
+    fn test_cursor_sample_clamps_to_oldest() {
+        let data = [(1.0, 100.0), (2.0, 200.0), (3.0, 300.0)];
+        assert_eq!(cursor_sample(&data, 0), Some((3.0, 300.0)));
+        assert_eq!(cursor_sample(&data, 2), Some((1.0, 100.0)));
+        assert_eq!(cursor_sample(&data, 10), Some((1.0, 100.0)));
+        assert_eq!(cursor_sample(&[], 0), None);
+    }
+
+    #[test]
+    fn test_cursor_title_shows_value_and_delta() {
+        let data = [(8.0, 1024.0), (9.0, 1536.0), (10.0, 2048.0)];
+        assert_eq!(
+            cursor_title(data[0], &data, [0.0, 11.0], &SizeUnits::KB, UnitBase::Binary, TimeAxisUnit::Ticks, 1000),
+            " cursor -3t: 1024 KiB (Δ now +1024 KiB) "
+        );
+    }
+
+    #[test]
+    fn test_y_axis_ceiling_tracks_total() {
+        assert_eq!(y_axis_ceiling(1000, &[&[(0.0, 200.0), (1.0, 300.0)]]), 1000.0);
+        // swapon grew the total: history rescales against the new total
+        assert_eq!(y_axis_ceiling(4000, &[&[(0.0, 900.0)]]), 4000.0);
+    }
+
+    #[test]
+    fn test_y_axis_ceiling_keeps_history_after_shrink() {
+        // swapoff left 500 KB total, but earlier samples used up to 900 KB
+        assert_eq!(y_axis_ceiling(500, &[&[(0.0, 900.0), (1.0, 400.0)]]), 900.0);
+    }
+
+    #[test]
+    fn test_y_axis_ceiling_uses_visible_series_only() {
+        let used = [(0.0, 900.0), (1.0, 100.0)];
+        let band_upper = [(0.0, 1200.0), (1.0, 1200.0)];
+        assert_eq!(y_axis_ceiling(500, &[&used, &band_upper]), 1200.0);
+        // Used hidden: its old peak no longer raises the axis
+        assert_eq!(y_axis_ceiling(500, &[&[], &[(0.0, 300.0)]]), 500.0);
+    }
+
+    #[test]
+    fn test_visible_series_toggles() {
+        let mut series = VisibleSeries::default();
+        assert_eq!(series.labels(), vec!["used"]);
+        series.toggle_stats();
+        assert_eq!(series.labels(), vec!["used", "mean", "±1σ"]);
+        series.toggle(ChartSeries::Used);
+        series.toggle(ChartSeries::Band);
+        assert_eq!(series.labels(), vec!["mean"]);
+        // Either statistic shown: b hides both
+        series.toggle_stats();
+        assert!(series.labels().is_empty());
+        assert_eq!(ChartSeries::from_menu_key('3'), Some(ChartSeries::Band));
+        assert_eq!(ChartSeries::from_menu_key('0'), None);
+        assert_eq!(ChartSeries::from_menu_key('4'), Some(ChartSeries::Rate));
+        assert_eq!(ChartSeries::from_menu_key('5'), None);
+    }
+
+    #[test]
+    fn test_series_menu_title() {
+        assert_eq!(
+            series_menu_title(VisibleSeries::default()),
+            " series: 1 [x] used  2 [ ] mean  3 [ ] ±1σ  4 [ ] I/O  (y/Esc to close) "
+        );
+    }
+
+    #[test]
+    fn test_rate_title() {
+        let swap_in = [(1.0, 50.0), (2.0, 120.0)];
+        let swap_out = [(1.0, 5100.0), (2.0, 4000.0)];
+        assert_eq!(rate_title(&swap_in, &swap_out, 5100.0), " swap I/O pages/s in 120 out 4000 (peak 5100) ");
+        assert_eq!(rate_title(&[], &[], 0.0), " swap I/O pages/s: waiting for two /proc/vmstat samples ");
+    }
+
+    #[test]
+    fn test_rate_panel_keeps_usage_legend_off() {
+        let mut series = VisibleSeries::default();
+        series.toggle(ChartSeries::Rate);
+        assert_eq!(series.usage_lines(), VisibleSeries::default());
+        assert_eq!(series.labels(), vec!["used", "I/O"]);
+    }
+
+    #[test]
+    fn test_time_axis_labels_per_unit() {
+        let window = [0.0, 60.0];
+        assert_eq!(time_axis_labels(window, TimeAxisUnit::Ticks, 500), vec!["-60t", "-30t", "now"]);
+        assert_eq!(time_axis_labels(window, TimeAxisUnit::Seconds, 500), vec!["-30s", "-15s", "now"]);
+        assert_eq!(time_axis_labels(window, TimeAxisUnit::Minutes, 2000), vec!["-2.0m", "-1.0m", "now"]);
+    }
+
+    #[test]
+    fn test_time_label_short_intervals_keep_precision() {
+        assert_eq!(time_label(30.0, TimeAxisUnit::Seconds, 100), "-3.0s");
+    }
+
+    #[test]
+    fn test_time_axis_unit_cycle() {
+        let unit = TimeAxisUnit::default();
+        assert_eq!(unit.next(), TimeAxisUnit::Seconds);
+        assert_eq!(unit.next().next().next(), TimeAxisUnit::Ticks);
+    }
+
+    #[test]
+    fn test_y_axis_ceiling_without_swap() {
+        assert_eq!(y_axis_ceiling(0, &[&[]]), 1.0);
+    }
+
+    #[test]
+    fn test_swap_usage_title_without_swap() {
+        let update = crate::data::SwapUpdate { total_swap: 0, used_swap: 0, ..Default::default() };
+        assert_eq!(swap_usage_title(update.total_swap, update.used_swap), "swap usage 0%");
+        assert_eq!(swap_usage_title(8_000_000, 2_000_000), "swap usage 25%");
+    }
+}
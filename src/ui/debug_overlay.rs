@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use crate::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph},
+};
+
+const OVERLAY_WIDTH: u16 = 26;
+const OVERLAY_HEIGHT: u16 = 5;
+
+/// Self-timing shown by the hidden `--debug` overlay.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameTimings {
+    /// Duration of the previous `terminal.draw`
+    pub render: Duration,
+    /// Duration of the last data refresh tick
+    pub refresh: Duration,
+    /// Total time spent in nvidia-smi during the last refresh that called it
+    pub nvidia_smi: Option<Duration>,
+}
+
+impl FrameTimings {
+    fn lines(&self) -> Vec<Line<'static>> {
+        let nvidia_smi = self
+            .nvidia_smi
+            .map(format_ms)
+            .unwrap_or_else(|| "-".to_string());
+        vec![
+            Line::from(format!("render     {:>10}", format_ms(self.render))),
+            Line::from(format!("refresh    {:>10}", format_ms(self.refresh))),
+            Line::from(format!("nvidia-smi {:>10}", nvidia_smi)),
+        ]
+    }
+}
+
+fn format_ms(d: Duration) -> String {
+    format!("{:.1}ms", d.as_secs_f64() * 1000.0)
+}
+
+/// Draw the timings box in the top-right corner of `area`, over whatever is there.
+pub fn render_debug_overlay(frame: &mut Frame, area: Rect, theme: &Theme, timings: &FrameTimings) {
+    let width = OVERLAY_WIDTH.min(area.width);
+    let height = OVERLAY_HEIGHT.min(area.height);
+    let overlay = Rect {
+        x: area.x + area.width - width,
+        y: area.y,
+        width,
+        height,
+    };
+
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.secondary))
+        .style(Style::default().bg(theme.background))
+        .title(Line::from("debug").fg(theme.text).left_aligned());
+
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(Paragraph::new(timings.lines()).fg(theme.text).block(block), overlay);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timings_lines() {
+        let timings = FrameTimings {
+            render: Duration::from_micros(1500),
+            refresh: Duration::from_millis(12),
+            nvidia_smi: None,
+        };
+        let lines: Vec<String> = timings.lines().iter().map(|l| l.to_string()).collect();
+        assert_eq!(lines[0], "render          1.5ms");
+        assert_eq!(lines[1], "refresh        12.0ms");
+        assert_eq!(lines[2], "nvidia-smi          -");
+    }
+}
@@ -0,0 +1,88 @@
+//! Totals footer on the main window's bottom border: the aggregate memory
+//! figures for the active view, all formatted with `format_mem` so every
+//! view reports used/free the same way.
+
+use crate::data::{GpuDevice, NumaNode, SizeUnits, UnitBase};
+use crate::theme::Theme;
+use crate::ui::unified_view::format_mem;
+use ratatui::{style::Stylize, text::Line};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Totals {
+    pub label: &'static str,
+    pub used_kb: u64,
+    pub free_kb: u64,
+}
+
+pub fn swap_totals(total_kb: u64, used_kb: u64) -> Totals {
+    Totals { label: "swap", used_kb, free_kb: total_kb.saturating_sub(used_kb) }
+}
+
+/// Summed over all GPUs; None when there are none.
+pub fn gpu_totals(devices: &[GpuDevice]) -> Option<Totals> {
+    if devices.is_empty() {
+        return None;
+    }
+    Some(Totals {
+        label: "VRAM",
+        used_kb: devices.iter().map(|d| d.memory_used_kb).sum(),
+        free_kb: devices.iter().map(|d| d.memory_free_kb).sum(),
+    })
+}
+
+/// Summed over all NUMA nodes; None when topology is unavailable.
+pub fn numa_totals(nodes: &[NumaNode]) -> Option<Totals> {
+    if nodes.is_empty() {
+        return None;
+    }
+    Some(Totals {
+        label: "node mem",
+        used_kb: nodes.iter().map(|n| n.memory_total_kb.saturating_sub(n.memory_free_kb)).sum(),
+        free_kb: nodes.iter().map(|n| n.memory_free_kb).sum(),
+    })
+}
+
+/// " swap used 1.91 GiB / free 5.72 GiB | VRAM used ... ", empty without totals.
+pub fn totals_line(totals: &[Totals], unit: &SizeUnits, base: UnitBase, theme: &Theme) -> Line<'static> {
+    if totals.is_empty() {
+        return Line::from("");
+    }
+    let parts: Vec<String> = totals
+        .iter()
+        .map(|t| format!("{} used {} / free {}", t.label, format_mem(t.used_kb, unit, base), format_mem(t.free_kb, unit, base)))
+        .collect();
+    Line::from(format!(" {} ", parts.join(" | "))).fg(theme.secondary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::NumaNodeType;
+    use crate::theme::ThemeType;
+
+    #[test]
+    fn test_totals_line_formats_each_section() {
+        let theme = Theme::from(ThemeType::Default);
+        let totals = [swap_totals(8192, 2048), Totals { label: "VRAM", used_kb: 1024, free_kb: 3072 }];
+        assert_eq!(
+            totals_line(&totals, &SizeUnits::MB, UnitBase::Binary, &theme).to_string(),
+            " swap used 2.00 MiB / free 6.00 MiB | VRAM used 1.00 MiB / free 3.00 MiB "
+        );
+        assert_eq!(totals_line(&[], &SizeUnits::MB, UnitBase::Binary, &theme).to_string(), "");
+    }
+
+    #[test]
+    fn test_swap_totals_clamps_free() {
+        assert_eq!(swap_totals(100, 150).free_kb, 0);
+    }
+
+    #[test]
+    fn test_numa_totals_sums_nodes() {
+        let node = |id, total, free| NumaNode {
+            id, memory_total_kb: total, memory_free_kb: free, cpus: vec![], node_type: NumaNodeType::Cpu, distances: vec![],
+        };
+        assert_eq!(numa_totals(&[]), None);
+        let totals = numa_totals(&[node(0, 1000, 400), node(1, 500, 100)]).unwrap();
+        assert_eq!((totals.used_kb, totals.free_kb), (1000, 500));
+    }
+}
@@ -1,51 +1,191 @@
-use crate::data::types::{GpuDevice, GpuProcessInfo, SizeUnits, convert_swap};
+use crate::data::types::{GpuDevice, GpuProcessInfo, GpuStatus, SizeUnits, UnitBase};
 use crate::theme::Theme;
+use crate::ui::process_list::ListScroll;
+use crate::ui::truncate_chars;
+use crate::ui::unified_view::format_mem;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Style, Stylize},
-    text::Line,
-    widgets::{Block, BorderType, Paragraph},
+    style::{Color, Style, Stylize},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, BorderType, Chart, Dataset, GraphType, Paragraph},
 };
+use std::collections::{BTreeMap, HashMap};
 
+/// Highlight for GPUs that have processes holding memory but little or no
+/// compute activity (`--idle-gpu-util`, `--idle-gpu-color`).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IdleGpuHighlight {
+    /// Utilisation at or below which a held GPU counts as idle
+    pub max_utilization_percent: u32,
+    /// Row colour; None uses the theme's warning colour
+    pub color: Option<Color>,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_gpu_view(
     frame: &mut Frame,
     area: Rect,
     theme: &Theme,
     gpu_devices: &[GpuDevice],
     gpu_processes: &[GpuProcessInfo],
-    gpu_available: bool,
+    gpu_status: &GpuStatus,
     unit: &SizeUnits,
+    base: UnitBase,
+    show_mig_instances: bool,
+    idle_highlight: &IdleGpuHighlight,
+    cmdlines: &HashMap<u32, String>,
+    collapse_gpus: bool,
+    group_by_device: bool,
+    processes_hidden: bool,
+    vram_history: Option<(u32, &[(f64, f64)])>,
+    scroll: &mut ListScroll,
 ) {
-    if !gpu_available || gpu_devices.is_empty() {
+    if *gpu_status != GpuStatus::Available || gpu_devices.is_empty() {
         let block = Block::bordered()
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(theme.border))
             .style(Style::default().bg(theme.background))
             .title(Line::from(" GPU Info ").fg(theme.primary).bold());
-        let msg = Paragraph::new("No NVIDIA GPU detected (nvidia-smi not available)")
+        let msg = Paragraph::new(gpu_unavailable_message(gpu_status))
             .block(block)
             .centered();
         frame.render_widget(msg, area);
         return;
     }
 
+    // The VRAM chart, once there is history, sits above the device table
+    let charted = vram_history.and_then(|(index, history)| {
+        gpu_devices.iter().find(|d| d.index == index).map(|device| (device, history))
+    });
+    let area = match charted {
+        Some((device, history)) => {
+            let [chart, rest] = Layout::vertical([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(area);
+            render_vram_chart(frame, chart, theme, device, history, unit, base, gpu_devices.len() > 1);
+            rest
+        }
+        None => area,
+    };
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
         .split(area);
 
-    render_device_summary(frame, chunks[0], theme, gpu_devices, unit);
-    render_gpu_process_list(frame, chunks[1], theme, gpu_processes, unit);
+    render_device_summary(
+        frame,
+        chunks[0],
+        theme,
+        gpu_devices,
+        gpu_processes,
+        unit,
+        base,
+        show_mig_instances,
+        idle_highlight,
+        collapse_gpus,
+    );
+    render_gpu_process_list(
+        frame,
+        chunks[1],
+        theme,
+        gpu_processes,
+        unit,
+        base,
+        cmdlines,
+        group_by_device,
+        processes_hidden,
+        scroll,
+    );
+}
+
+/// Used VRAM over time for one GPU, against its total. `cycle_hint` adds
+/// the `V` hint when there are other GPUs to switch to.
+#[allow(clippy::too_many_arguments)]
+fn render_vram_chart(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    device: &GpuDevice,
+    history: &[(f64, f64)],
+    unit: &SizeUnits,
+    base: UnitBase,
+    cycle_hint: bool,
+) {
+    let first = history.first().map_or(0.0, |&(x, _)| x);
+    let last = history.last().map_or(0.0, |&(x, _)| x);
+    let ceiling = history
+        .iter()
+        .map(|&(_, kb)| kb)
+        .fold(device.memory_total_kb as f64, f64::max)
+        .max(1.0);
+    let used = format!(
+        " GPU {} VRAM {} / {} ",
+        device.index,
+        format_mem(device.memory_used_kb, unit, base),
+        format_mem(device.memory_total_kb, unit, base)
+    );
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .style(Style::default().fg(theme.primary))
+        .graph_type(GraphType::Line)
+        .data(history);
+    let mut block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border))
+        .style(Style::default().bg(theme.background))
+        .title(Line::from(used).fg(theme.primary).bold());
+    if cycle_hint {
+        block = block.title_bottom(Line::from("(V next GPU)").left_aligned());
+    }
+    let chart = Chart::new(vec![dataset])
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.text))
+                .bounds([first, last.max(first + 1.0)])
+                .labels(history_time_labels(last - first)),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.text))
+                .bounds([0.0, ceiling])
+                .labels([0.0, ceiling / 2.0, ceiling].map(|kb| format_mem(kb.round() as u64, unit, base))),
+        );
+    frame.render_widget(chart, area);
+}
+
+/// Left, middle and right x labels for a history spanning `span_secs`.
+/// Device samples arrive on the device cache TTL, not every refresh, so
+/// the axis is in wall-clock seconds.
+fn history_time_labels(span_secs: f64) -> [String; 3] {
+    let label = |ago: f64| if ago <= 0.0 { "now".to_string() } else { format!("-{:.0}s", ago) };
+    [label(span_secs), label(span_secs / 2.0), label(0.0)]
+}
+
+fn gpu_unavailable_message(status: &GpuStatus) -> String {
+    match status {
+        GpuStatus::NotFound => "No NVIDIA GPU detected (nvidia-smi not found, and no AMD GPU via rocm-smi)".into(),
+        GpuStatus::Failed(err) => format!("nvidia-smi present but failed: {}", err),
+        GpuStatus::Available => "No NVIDIA GPU detected (nvidia-smi reported no devices)".into(),
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_device_summary(
     frame: &mut Frame,
     area: Rect,
     theme: &Theme,
     devices: &[GpuDevice],
+    processes: &[GpuProcessInfo],
     unit: &SizeUnits,
+    base: UnitBase,
+    show_mig_instances: bool,
+    idle_highlight: &IdleGpuHighlight,
+    collapse_gpus: bool,
 ) {
+    let idle_color = idle_highlight.color.unwrap_or(theme.warn);
+    let mut any_idle = false;
     let mut lines = Vec::new();
 
     lines.push(Line::from(vec![
@@ -57,17 +197,24 @@ fn render_device_summary(
         " | ".into(),
         format!("{:>10}", "MEM USED").bold(),
         " | ".into(),
+        format!("{:>10}", "RESERVED").bold(),
+        " | ".into(),
         format!("{:>10}", "MEM FREE").bold(),
         " | ".into(),
         format!("{:>5}", "TEMP").bold(),
         " | ".into(),
+        format!("{:>9}", "SM%/MEM%").bold(),
+        " | ".into(),
         format!("{:>6}", "NUMA").bold(),
     ]));
 
-    for dev in devices {
-        let total = format_mem(dev.memory_total_kb, unit);
-        let used = format_mem(dev.memory_used_kb, unit);
-        let free = format_mem(dev.memory_free_kb, unit);
+    let per_gpu: &[GpuDevice] = if collapse_gpus {
+        lines.push(aggregate_line(devices, processes, unit, base));
+        &[]
+    } else {
+        devices
+    };
+    for dev in per_gpu {
         let temp = dev
             .temperature
             .map(|t| format!("{}°C", t))
@@ -76,22 +223,46 @@ fn render_device_summary(
             .numa_node_id
             .map(|n| n.to_string())
             .unwrap_or_else(|| "-".into());
+        let util = utilization_cell(dev.utilization_percent, dev.memory_utilization_percent);
 
-        lines.push(Line::from(vec![
-            format!("{:>4}", dev.index).into(),
-            " | ".into(),
-            format!("{:<24}", truncate(&dev.name, 24)).into(),
-            " | ".into(),
-            format!("{:>10}", total).into(),
-            " | ".into(),
-            format!("{:>10}", used).into(),
-            " | ".into(),
-            format!("{:>10}", free).into(),
-            " | ".into(),
-            format!("{:>5}", temp).into(),
-            " | ".into(),
-            format!("{:>6}", numa).into(),
-        ]));
+        if show_mig_instances && !dev.mig_instances.is_empty() {
+            for mig in &dev.mig_instances {
+                lines.push(device_line(
+                    &format!("{}:{}", dev.index, mig.device),
+                    &format!("MIG GI {} CI {}", mig.gpu_instance, mig.compute_instance),
+                    mig.memory_total_kb,
+                    mig.memory_used_kb,
+                    mig.memory_total_kb.saturating_sub(mig.memory_used_kb),
+                    None,
+                    &temp,
+                    "-",
+                    &numa,
+                    unit,
+                    base,
+                ));
+            }
+        } else {
+            let (total, used, free) = dev.flattened_memory_kb();
+            let line = device_line(
+                &dev.index.to_string(),
+                &dev.name,
+                total,
+                used,
+                free,
+                dev.memory_reserved_kb,
+                &temp,
+                &util,
+                &numa,
+                unit,
+                base,
+            );
+            if dev.is_idle_but_held(processes, idle_highlight.max_utilization_percent) {
+                any_idle = true;
+                lines.push(line.fg(idle_color));
+            } else {
+                lines.push(line);
+            }
+        }
     }
 
     let block = Block::bordered()
@@ -99,21 +270,125 @@ fn render_device_summary(
         .border_style(Style::default().fg(theme.border))
         .style(Style::default().bg(theme.background))
         .title(Line::from(" GPU Devices ").fg(theme.primary).bold());
+    let mut hints = Vec::new();
+    if any_idle {
+        hints.push(format!(
+            "(highlighted: processes present, util <= {}%)",
+            idle_highlight.max_utilization_percent
+        ));
+    }
+    if collapse_gpus {
+        hints.push("(A for per-GPU rows)".to_string());
+    } else if devices.len() > 1 {
+        hints.push("(A to collapse GPUs)".to_string());
+    }
+    if !collapse_gpus && devices.iter().any(|d| !d.mig_instances.is_empty()) {
+        let hint = if show_mig_instances { "(i to flatten MIG)" } else { "(i to show MIG instances)" };
+        hints.push(hint.to_string());
+    }
+    let block = if hints.is_empty() {
+        block
+    } else {
+        block.title_bottom(Line::from(hints.join(" ")).left_aligned())
+    };
 
     let para = Paragraph::new(lines).block(block).centered();
     frame.render_widget(para, area);
 }
 
+/// All GPUs as one row: memory summed, hottest temperature, mean
+/// utilisation over the GPUs that report it, and the number of distinct
+/// GPU processes in place of a name.
+fn aggregate_line(devices: &[GpuDevice], processes: &[GpuProcessInfo], unit: &SizeUnits, base: UnitBase) -> Line<'static> {
+    let (mut total, mut used, mut free) = (0, 0, 0);
+    for dev in devices {
+        let (t, u, f) = dev.flattened_memory_kb();
+        total += t;
+        used += u;
+        free += f;
+    }
+    let reserved = devices.iter().filter_map(|d| d.memory_reserved_kb).reduce(|a, b| a + b);
+    let temp = devices
+        .iter()
+        .filter_map(|d| d.temperature)
+        .max()
+        .map(|t| format!("{}°C", t))
+        .unwrap_or_else(|| "-".into());
+    let mean = |values: Vec<u32>| (!values.is_empty()).then(|| values.iter().sum::<u32>() / values.len() as u32);
+    let util = utilization_cell(
+        mean(devices.iter().filter_map(|d| d.utilization_percent).collect()),
+        mean(devices.iter().filter_map(|d| d.memory_utilization_percent).collect()),
+    );
+    let pids: std::collections::HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+    let name = format!("{} GPUs, {} procs", devices.len(), pids.len());
+    device_line("ALL", &name, total, used, free, reserved, &temp, &util, "-", unit, base)
+}
+
+/// Compute and memory-bandwidth utilisation as "SM%/MEM%", e.g. "87%/40%";
+/// "-" stands in for whichever is not reported.
+fn utilization_cell(gpu: Option<u32>, memory: Option<u32>) -> String {
+    let percent = |p: Option<u32>| p.map(|p| format!("{}%", p)).unwrap_or_else(|| "-".into());
+    match (gpu, memory) {
+        (None, None) => "-".into(),
+        _ => format!("{}/{}", percent(gpu), percent(memory)),
+    }
+}
+
+/// One row of the device summary.
+#[allow(clippy::too_many_arguments)]
+fn device_line(
+    label: &str,
+    name: &str,
+    total_kb: u64,
+    used_kb: u64,
+    free_kb: u64,
+    reserved_kb: Option<u64>,
+    temp: &str,
+    util: &str,
+    numa: &str,
+    unit: &SizeUnits,
+    base: UnitBase,
+) -> Line<'static> {
+    Line::from(vec![
+        format!("{:>4}", label).into(),
+        " | ".into(),
+        format!("{:<24}", truncate_chars(name, 24)).into(),
+        " | ".into(),
+        format!("{:>10}", format_mem(total_kb, unit, base)).into(),
+        " | ".into(),
+        format!("{:>10}", format_mem(used_kb, unit, base)).into(),
+        " | ".into(),
+        format!("{:>10}", reserved_kb.map(|kb| format_mem(kb, unit, base)).unwrap_or_else(|| "-".into())).into(),
+        " | ".into(),
+        format!("{:>10}", format_mem(free_kb, unit, base)).into(),
+        " | ".into(),
+        format!("{:>5}", temp).into(),
+        " | ".into(),
+        format!("{:>9}", util).into(),
+        " | ".into(),
+        format!("{:>6}", numa).into(),
+    ])
+}
+
+#[allow(clippy::too_many_arguments)]
 fn render_gpu_process_list(
     frame: &mut Frame,
     area: Rect,
     theme: &Theme,
     processes: &[GpuProcessInfo],
     unit: &SizeUnits,
+    base: UnitBase,
+    cmdlines: &HashMap<u32, String>,
+    group_by_device: bool,
+    processes_hidden: bool,
+    scroll: &mut ListScroll,
 ) {
     let mut lines = Vec::new();
+    // Only with --gpu-pmon (or when a driver reports it); otherwise every
+    // cell would be "-"
+    let show_sm = processes.iter().any(|p| p.sm_util.is_some());
 
-    lines.push(Line::from(vec![
+    let mut header = vec![
         format!("{:>8}", "PID").bold(),
         " | ".into(),
         format!("{:<30}", "PROCESS").bold(),
@@ -121,22 +396,59 @@ fn render_gpu_process_list(
         format!("{:>4}", "GPU").bold(),
         " | ".into(),
         format!("{:>12}", "VRAM USED").bold(),
-    ]));
+    ];
+    if show_sm {
+        header.extend([" | ".into(), format!("{:>4}", "SM%").bold()]);
+    }
+    lines.push(Line::from(header));
 
-    if processes.is_empty() {
+    if processes.is_empty() && processes_hidden {
+        // Devices are in use, so "no processes" would be wrong
+        lines.push(Line::from("  No GPU processes visible, but the GPUs report memory or compute in use").fg(theme.warn));
+        lines.push(Line::from("  nvidia-smi may be withholding them: run as root, or in the host PID namespace"));
+    } else if processes.is_empty() {
         lines.push(Line::from("  No GPU processes running"));
     } else {
-        for proc in processes {
-            let mem = format_mem(proc.gpu_memory_used_kb, unit);
-            lines.push(Line::from(vec![
+        let process_line = |proc: &GpuProcessInfo| {
+            let mem = format_mem(proc.gpu_memory_used_kb, unit, base);
+            // nvidia-smi's name is often just the basename; prefer the local cmdline
+            let name = cmdlines.get(&proc.pid).unwrap_or(&proc.name);
+            let mut row = vec![
                 format!("{:>8}", proc.pid).into(),
                 " | ".into(),
-                format!("{:<30}", truncate(&proc.name, 30)).into(),
+                format!("{:<30}", truncate_chars(name, 30)).into(),
                 " | ".into(),
                 format!("{:>4}", proc.gpu_index).into(),
                 " | ".into(),
                 format!("{:>12}", mem).into(),
-            ]));
+            ];
+            if show_sm {
+                let sm = proc.sm_util.map_or_else(|| "-".to_string(), |v| v.to_string());
+                row.extend([" | ".into(), format!("{:>4}", sm).into()]);
+            }
+            Line::from(row)
+        };
+        if group_by_device {
+            for group in group_by_gpu(processes) {
+                let count = group.processes.len();
+                let label = format!("GPU {} total ({} process{})", group.gpu_index, count, if count == 1 { "" } else { "es" });
+                let mut row: Vec<Span> = vec![
+                    format!("{:>8}", "").into(),
+                    " | ".into(),
+                    format!("{:<30}", label).into(),
+                    " | ".into(),
+                    format!("{:>4}", group.gpu_index).into(),
+                    " | ".into(),
+                    format!("{:>12}", format_mem(group.total_kb, unit, base)).into(),
+                ];
+                if show_sm {
+                    row.extend([" | ".into(), format!("{:>4}", "").into()]);
+                }
+                lines.push(Line::from(row).fg(theme.secondary).bold());
+                lines.extend(group.processes.into_iter().map(process_line));
+            }
+        } else {
+            lines.extend(processes.iter().map(process_line));
         }
     }
 
@@ -144,29 +456,55 @@ fn render_gpu_process_list(
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(theme.border))
         .style(Style::default().bg(theme.background))
-        .title(Line::from(" GPU Processes ").fg(theme.primary).bold());
+        .title(Line::from(if group_by_device { " GPU Processes by GPU " } else { " GPU Processes " }).fg(theme.primary).bold())
+        .title(Line::from(" (a to group by GPU) ").fg(theme.text).right_aligned());
 
-    let para = Paragraph::new(lines).block(block).centered();
-    frame.render_widget(para, area);
+    scroll.fit(lines.len(), area);
+    scroll.render(frame, area, theme, Paragraph::new(lines).block(block).centered());
 }
 
-fn format_mem(kb: u64, unit: &SizeUnits) -> String {
-    let val = convert_swap(kb, unit.clone());
-    let suffix = match unit {
-        SizeUnits::KB => "KB",
-        SizeUnits::MB => "MB",
-        SizeUnits::GB => "GB",
-    };
-    match unit {
-        SizeUnits::KB => format!("{} {}", val as u64, suffix),
-        _ => format!("{:.2} {}", val, suffix),
+/// The processes on one GPU, under that GPU's VRAM subtotal.
+#[derive(Debug)]
+pub struct GpuProcessGroup<'a> {
+    pub gpu_index: u32,
+    /// VRAM used by `processes`, in KB
+    pub total_kb: u64,
+    pub processes: Vec<&'a GpuProcessInfo>,
+}
+
+/// Group processes by GPU, in index order. Processes keep their order
+/// within a group.
+pub fn group_by_gpu(processes: &[GpuProcessInfo]) -> Vec<GpuProcessGroup<'_>> {
+    let mut groups: BTreeMap<u32, GpuProcessGroup> = BTreeMap::new();
+    for proc in processes {
+        let group = groups.entry(proc.gpu_index).or_insert_with(|| GpuProcessGroup {
+            gpu_index: proc.gpu_index,
+            total_kb: 0,
+            processes: Vec::new(),
+        });
+        group.total_kb += proc.gpu_memory_used_kb;
+        group.processes.push(proc);
     }
+    groups.into_values().collect()
 }
 
-fn truncate(s: &str, max: usize) -> String {
-    if s.len() <= max {
-        s.to_string()
-    } else {
-        format!("{}…", &s[..max - 1])
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(pid: u32, gpu_index: u32, gpu_memory_used_kb: u64) -> GpuProcessInfo {
+        GpuProcessInfo { pid, name: format!("p{}", pid), gpu_index, gpu_memory_used_kb, sm_util: None, mem_util: None }
+    }
+
+    #[test]
+    fn test_group_by_gpu_subtotals() {
+        let procs = vec![proc(10, 1, 300), proc(11, 0, 1000), proc(12, 0, 24)];
+        let groups = group_by_gpu(&procs);
+        assert_eq!(groups.len(), 2);
+        assert_eq!((groups[0].gpu_index, groups[0].total_kb), (0, 1024));
+        assert_eq!(groups[0].processes.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![11, 12]);
+        assert_eq!((groups[1].gpu_index, groups[1].total_kb), (1, 300));
+        assert_eq!(groups[1].processes.len(), 1);
+        assert!(group_by_gpu(&[]).is_empty());
     }
 }
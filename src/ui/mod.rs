@@ -1,7 +1,39 @@
 pub mod chart;
+pub mod debug_overlay;
+pub mod footer;
 pub mod gpu_view;
 pub mod process_list;
 pub mod swap_devices;
 pub mod unified_view;
 #[cfg(target_os = "linux")]
 pub mod numa_view;
+
+/// `s` cut to at most `max` chars, the last being "…" when it was cut.
+/// Counts chars rather than bytes, so multibyte names are cut on a char
+/// boundary instead of panicking mid-character.
+pub(crate) fn truncate_chars(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        let end = s.char_indices().nth(max.saturating_sub(1)).map_or(s.len(), |(i, _)| i);
+        format!("{}…", &s[..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_chars_multibyte() {
+        assert_eq!(truncate_chars("nvidia-smi", 16), "nvidia-smi");
+        assert_eq!(truncate_chars("python3-training-job", 8), "python3…");
+        // 3-byte chars: the old byte slice panicked mid-character
+        let name = "绘图程序渲染器";
+        let cut = truncate_chars(name, 5);
+        assert_eq!(cut, "绘图程序…");
+        assert_eq!(cut.chars().count(), 5);
+        assert_eq!(truncate_chars(name, 7), name);
+        assert_eq!(truncate_chars("🚀🚀🚀", 2), "🚀…");
+    }
+}
@@ -1,19 +1,53 @@
-use crate::data::types::{NumaNode, NumaNodeType, ProcessNumaInfo, SizeUnits};
+use crate::data::types::{NumaNode, NumaNodeType, ProcessNumaInfo, SizeUnits, UnitBase, convert_swap};
 use crate::theme::Theme;
+use crate::ui::process_list::ListScroll;
+use crate::ui::truncate_chars;
+use crate::ui::swap_devices::percent_bar;
 use crate::ui::unified_view::format_mem;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Block, BorderType, Paragraph},
 };
 
-// NUMA memory locality colour constants
-const COLOR_LOCAL_GREEN: Color = Color::Rgb(80, 200, 120);
-const COLOR_REMOTE_ORANGE: Color = Color::Rgb(255, 183, 77);
-const COLOR_HBM_RED: Color = Color::Rgb(255, 85, 85);
+/// Row order of the topology table (`N` toggles). Only the table is
+/// reordered; the per-process node columns stay in id order.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum NumaNodeSort {
+    #[default]
+    Id,
+    /// Memory used, descending
+    Used,
+}
+
+impl NumaNodeSort {
+    pub fn next(self) -> Self {
+        match self {
+            NumaNodeSort::Id => NumaNodeSort::Used,
+            NumaNodeSort::Used => NumaNodeSort::Id,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NumaNodeSort::Id => "id",
+            NumaNodeSort::Used => "used",
+        }
+    }
+}
 
+/// Nodes in display order; ties on used memory keep id order.
+fn sorted_nodes(numa_nodes: &[NumaNode], sort: NumaNodeSort) -> Vec<&NumaNode> {
+    let mut nodes: Vec<&NumaNode> = numa_nodes.iter().collect();
+    if sort == NumaNodeSort::Used {
+        nodes.sort_by_key(|n| std::cmp::Reverse(n.memory_total_kb.saturating_sub(n.memory_free_kb)));
+    }
+    nodes
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_numa_view(
     frame: &mut Frame,
     area: Rect,
@@ -22,6 +56,11 @@ pub fn render_numa_view(
     process_numa_infos: &[ProcessNumaInfo],
     numa_available: bool,
     unit: &SizeUnits,
+    base: UnitBase,
+    node_sort: NumaNodeSort,
+    show_processes: bool,
+    top_n: usize,
+    scroll: &mut ListScroll,
 ) {
     if !numa_available || numa_nodes.is_empty() {
         let block = Block::bordered()
@@ -36,13 +75,84 @@ pub fn render_numa_view(
         return;
     }
 
+    if !show_processes {
+        render_topology_panels(frame, area, theme, numa_nodes, node_sort, show_processes, base);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
         .split(area);
 
-    render_topology_table(frame, chunks[0], theme, numa_nodes);
-    render_process_numa_distribution(frame, chunks[1], theme, process_numa_infos, numa_nodes, unit);
+    render_topology_panels(frame, chunks[0], theme, numa_nodes, node_sort, show_processes, base);
+    render_process_numa_distribution(frame, chunks[1], theme, process_numa_infos, numa_nodes, unit, base, top_n, scroll);
+}
+
+/// The topology table, with the distance matrix beside it when the kernel
+/// reports node distances.
+fn render_topology_panels(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    numa_nodes: &[NumaNode],
+    node_sort: NumaNodeSort,
+    show_processes: bool,
+    base: UnitBase,
+) {
+    if numa_nodes.iter().all(|n| n.distances.is_empty()) {
+        render_topology_table(frame, area, theme, numa_nodes, node_sort, show_processes, base);
+        return;
+    }
+    let [table, matrix] =
+        Layout::horizontal([Constraint::Min(0), Constraint::Length(distance_matrix_width(numa_nodes.len()))]).areas(area);
+    render_topology_table(frame, table, theme, numa_nodes, node_sort, show_processes, base);
+    render_distance_matrix(frame, matrix, theme, numa_nodes);
+}
+
+/// Row label plus one 5-wide column per node, inside the borders.
+fn distance_matrix_width(node_count: usize) -> u16 {
+    (4 + 5 * node_count + 3) as u16
+}
+
+/// N×N relative access cost between nodes (sysfs `nodeN/distance`, 10 =
+/// local), in node id order. Local entries are green and GPU HBM node
+/// labels red, as in the memory columns; "-" where a node has no data.
+fn render_distance_matrix(frame: &mut Frame, area: Rect, theme: &Theme, numa_nodes: &[NumaNode]) {
+    let mut nodes: Vec<&NumaNode> = numa_nodes.iter().collect();
+    nodes.sort_by_key(|n| n.id);
+    let label = |node: &NumaNode, width: usize| {
+        let text = format!("N{}", node.id);
+        match node.node_type {
+            NumaNodeType::GpuHbm { .. } => Span::styled(format!("{:>width$}", text), Style::default().fg(theme.critical)),
+            _ => format!("{:>width$}", text).into(),
+        }
+    };
+
+    let mut header = vec![Span::raw("    ")];
+    header.extend(nodes.iter().map(|node| label(node, 5)));
+    let mut lines = vec![Line::from(header).bold()];
+    for (row, node) in nodes.iter().enumerate() {
+        let mut spans = vec![Span::from(format!("{:<4}", format!("N{}", node.id)))];
+        if matches!(node.node_type, NumaNodeType::GpuHbm { .. }) {
+            spans[0] = spans[0].clone().fg(theme.critical);
+        }
+        for col in 0..nodes.len() {
+            let cell = match node.distances.get(col) {
+                Some(distance) => format!("{:>5}", distance),
+                None => format!("{:>5}", "-"),
+            };
+            spans.push(if col == row { Span::styled(cell, Style::default().fg(theme.good)) } else { cell.into() });
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border))
+        .style(Style::default().bg(theme.background))
+        .title(Line::from(" Distances ").fg(theme.primary).bold());
+    frame.render_widget(Paragraph::new(lines).block(block), area);
 }
 
 fn render_topology_table(
@@ -50,6 +160,9 @@ fn render_topology_table(
     area: Rect,
     theme: &Theme,
     numa_nodes: &[NumaNode],
+    node_sort: NumaNodeSort,
+    show_processes: bool,
+    base: UnitBase,
 ) {
     let mut lines = Vec::new();
 
@@ -62,10 +175,12 @@ fn render_topology_table(
         " | ".into(),
         format!("{:>12}", "MEM USED").bold(),
         " | ".into(),
+        format!("{:<19}", "USAGE").bold(),
+        " | ".into(),
         format!("{:<20}", "CPUs").bold(),
     ]));
 
-    for node in numa_nodes {
+    for node in sorted_nodes(numa_nodes, node_sort) {
         let type_str = match &node.node_type {
             NumaNodeType::Cpu => "CPU".to_string(),
             NumaNodeType::GpuHbm { gpu_index } => format!("GPU HBM {}", gpu_index),
@@ -73,8 +188,9 @@ fn render_topology_table(
         };
 
         let mem_used_kb = node.memory_total_kb.saturating_sub(node.memory_free_kb);
-        let total_mb = node.memory_total_kb as f64 / 1024.0;
-        let used_mb = mem_used_kb as f64 / 1024.0;
+        let total_mb = convert_swap(node.memory_total_kb, SizeUnits::MB, base);
+        let used_mb = convert_swap(mem_used_kb, SizeUnits::MB, base);
+        let mb = base.suffix(&SizeUnits::MB);
 
         let cpu_str = if node.cpus.is_empty() {
             "-".to_string()
@@ -93,24 +209,65 @@ fn render_topology_table(
             " | ".into(),
             format!("{:<10}", type_str).into(),
             " | ".into(),
-            format!("{:>10.0} MB", total_mb).into(),
+            format!("{:>10.0} {}", total_mb, mb).into(),
             " | ".into(),
-            format!("{:>10.0} MB", used_mb).into(),
+            format!("{:>10.0} {}", used_mb, mb).into(),
+            " | ".into(),
+            usage_span(theme, node, mem_used_kb),
             " | ".into(),
             format!("{:<20}", cpu_str).into(),
         ]));
     }
 
+    let processes_hint = if show_processes { "" } else { ", P for processes" };
     let block = Block::bordered()
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(theme.border))
         .style(Style::default().bg(theme.background))
-        .title(Line::from(" NUMA Topology ").fg(theme.primary).bold());
+        .title(
+            Line::from(format!(" NUMA Topology (by {}, N to change{}) ", node_sort.label(), processes_hint))
+                .fg(theme.primary)
+                .bold(),
+        );
 
     let para = Paragraph::new(lines).block(block).centered();
     frame.render_widget(para, area);
 }
 
+/// Cells of the topology table's usage bar
+const USAGE_BAR_WIDTH: usize = 10;
+
+/// Used/total as a bar (10 cells) and percent, green below 70%, amber
+/// below 90%, red above. GPU HBM nodes fill with ▓ instead of █ so they
+/// stand apart from CPU memory.
+fn usage_span(theme: &Theme, node: &NumaNode, used_kb: u64) -> Span<'static> {
+    let percent = if node.memory_total_kb > 0 {
+        used_kb as f64 * 100.0 / node.memory_total_kb as f64
+    } else {
+        0.0
+    };
+    let hbm = matches!(node.node_type, NumaNodeType::GpuHbm { .. });
+    let color = if percent >= 90.0 {
+        theme.critical
+    } else if percent >= 70.0 {
+        theme.warn
+    } else {
+        theme.good
+    };
+    Span::styled(
+        format!("[{}] {:>5.1}%", usage_bar(percent, USAGE_BAR_WIDTH, hbm), percent),
+        Style::default().fg(color),
+    )
+}
+
+fn usage_bar(percent: f64, width: usize, hbm: bool) -> String {
+    let bar = percent_bar(percent / 100.0, width);
+    if hbm { bar.replace('█', "▓") } else { bar }
+}
+
+/// Rows for the `top_n` largest swap consumers; the same `top_n` bounds
+/// how many numa_maps are read, so every row has been fetched.
+#[allow(clippy::too_many_arguments)]
 fn render_process_numa_distribution(
     frame: &mut Frame,
     area: Rect,
@@ -118,6 +275,9 @@ fn render_process_numa_distribution(
     process_infos: &[ProcessNumaInfo],
     numa_nodes: &[NumaNode],
     unit: &SizeUnits,
+    base: UnitBase,
+    top_n: usize,
+    scroll: &mut ListScroll,
 ) {
     let mut lines = Vec::new();
 
@@ -135,28 +295,24 @@ fn render_process_numa_distribution(
         header_spans.push(" | ".into());
         header_spans.push(format!("{:>10}", format!("N{}", node.id)).bold());
     }
+    // Memory on nodes not (yet) in the topology, e.g. sparse hotplug node ids
+    let show_other = process_infos.iter().any(|info| info.kb_outside(numa_nodes) > 0);
+    if show_other {
+        header_spans.push(" | ".into());
+        header_spans.push(format!("{:>10}", "OTHER").bold());
+    }
     lines.push(Line::from(header_spans));
 
-    for info in process_infos.iter().take(20) {
-        // Determine dominant memory node (node with most KB)
-        let dominant_node = info
-            .kb_per_node
-            .iter()
-            .max_by_key(|(_, v)| **v)
-            .map(|(k, _)| *k);
-
-        // Check misalignment: cpu_node differs from dominant memory node
-        let misaligned = match (info.cpu_node, dominant_node) {
-            (Some(cpu), Some(mem)) => cpu != mem,
-            _ => false,
-        };
+    for info in process_infos.iter().take(top_n) {
+        // Misaligned: cpu_node differs from the dominant memory node
+        let misaligned = info.is_misaligned();
 
         let cpu_str = match info.cpu_node {
             Some(n) => format!("{:>3}", n),
             None => format!("{:>3}", "-"),
         };
         let cpu_span: Span = if misaligned {
-            Span::styled(cpu_str, Style::default().fg(Color::Rgb(255, 183, 77)))
+            Span::styled(cpu_str, Style::default().fg(theme.warn))
         } else {
             cpu_str.into()
         };
@@ -164,25 +320,25 @@ fn render_process_numa_distribution(
         let mut spans = vec![
             format!("{:>8}", info.pid).into(),
             " | ".into(),
-            format!("{:<20}", truncate_name(&info.name, 20)).into(),
+            format!("{:<20}", truncate_chars(&info.name, 20)).into(),
             " | ".into(),
             cpu_span,
             " | ".into(),
-            format!("{:>10}", format_mem(info.total_kb, unit)).into(),
+            format!("{:>10}", format_mem(info.total_kb, unit, base)).into(),
         ];
         for node in numa_nodes {
             let kb = info.kb_per_node.get(&node.id).copied().unwrap_or(0);
             let cell = if kb > 0 {
-                format_mem(kb, unit)
+                format_mem(kb, unit, base)
             } else {
                 "-".to_string()
             };
             spans.push(" | ".into());
             if kb > 0 {
                 let color = match &node.node_type {
-                    NumaNodeType::GpuHbm { .. } => COLOR_HBM_RED,
-                    NumaNodeType::Cpu if info.cpu_node == Some(node.id) => COLOR_LOCAL_GREEN,
-                    _ => COLOR_REMOTE_ORANGE,
+                    NumaNodeType::GpuHbm { .. } => theme.critical,
+                    NumaNodeType::Cpu if info.cpu_node == Some(node.id) => theme.good,
+                    _ => theme.warn,
                 };
                 spans.push(Span::styled(
                     format!("{:>10}", cell),
@@ -192,6 +348,12 @@ fn render_process_numa_distribution(
                 spans.push(format!("{:>10}", cell).into());
             }
         }
+        if show_other {
+            let kb = info.kb_outside(numa_nodes);
+            let cell = if kb > 0 { format_mem(kb, unit, base) } else { "-".to_string() };
+            spans.push(" | ".into());
+            spans.push(format!("{:>10}", cell).into());
+        }
         lines.push(Line::from(spans));
     }
 
@@ -204,31 +366,36 @@ fn render_process_numa_distribution(
         .border_style(Style::default().fg(theme.border))
         .style(Style::default().bg(theme.background))
         .title(
-            Line::from(" Per-Process NUMA Distribution (top 20 swap consumers) ")
+            Line::from(format!(" Per-Process NUMA Distribution (top {} swap consumers) ", top_n))
                 .fg(theme.primary)
                 .bold(),
         )
         .title(
             Line::from(vec![
                 Span::raw(" "),
-                Span::styled("local", Style::default().fg(COLOR_LOCAL_GREEN)),
+                Span::styled("local", Style::default().fg(theme.good)),
                 Span::raw("  "),
-                Span::styled("remote", Style::default().fg(COLOR_REMOTE_ORANGE)),
+                Span::styled("remote", Style::default().fg(theme.warn)),
                 Span::raw("  "),
-                Span::styled("GPU HBM", Style::default().fg(COLOR_HBM_RED)),
+                Span::styled("GPU HBM", Style::default().fg(theme.critical)),
                 Span::raw(" "),
             ])
                 .right_aligned(),
         );
 
-    let para = Paragraph::new(lines).block(block).centered();
-    frame.render_widget(para, area);
+    scroll.fit(lines.len(), area);
+    scroll.render(frame, area, theme, Paragraph::new(lines).block(block).centered());
 }
 
-fn truncate_name(name: &str, max_len: usize) -> String {
-    if name.len() <= max_len {
-        name.to_string()
-    } else {
-        format!("{}…", &name[..max_len - 1])
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_bar_widths() {
+        assert_eq!(usage_bar(0.0, 10, false), "░░░░░░░░░░");
+        assert_eq!(usage_bar(50.0, 10, false), "█████░░░░░");
+        assert_eq!(usage_bar(100.0, 10, false), "██████████");
+        assert_eq!(usage_bar(50.0, 10, true), "▓▓▓▓▓░░░░░");
     }
 }
@@ -1,56 +1,392 @@
-use crate::data::{DataProvider, SizeUnits, aggregate_processes};
+use crate::data::{Baseline, DataProvider, PidWatch, ProcessNames, ProcessSwapInfo, SizeUnits, SwapDeltas, SwapTrend, UnitBase, aggregate_processes, convert_swap, format_unit_value, mnt_ns_label, rebase};
 use crate::theme::Theme;
+use std::collections::HashMap;
+use std::hash::Hash;
 use ratatui::{
     Frame,
-    layout::{Alignment, Rect},
-    style::{Style, Stylize},
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
     text::Line,
     widgets::{Block, BorderType, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
 };
 
-pub fn create_process_lines(
+/// How the swap process list shows mount namespaces (`n` cycles).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum NamespaceMode {
+    #[default]
+    Off,
+    /// Extra MNT NS column: "host" or the container's namespace inode
+    Annotate,
+    /// One row per mount namespace, summed like aggregate mode
+    Group,
+}
+
+impl NamespaceMode {
+    pub fn next(self) -> Self {
+        match self {
+            NamespaceMode::Off => NamespaceMode::Annotate,
+            NamespaceMode::Annotate => NamespaceMode::Group,
+            NamespaceMode::Group => NamespaceMode::Off,
+        }
+    }
+}
+
+/// Fetch swapping processes in display order: sorted by swap descending,
+/// aggregated by name when requested, or by mount namespace when
+/// `group_by_namespace` (which takes precedence). With a `watch`, only
+/// watched PIDs are kept, plus placeholder rows for watched PIDs that
+/// recently exited.
+pub fn collect_process_rows(
     provider: &dyn DataProvider,
     swap_size_unit: &SizeUnits,
     aggregated: bool,
+    group_by_namespace: bool,
+    watch: Option<&mut PidWatch>,
+) -> Vec<ProcessSwapInfo> {
+    let Ok(mut processes) = provider.get_processes_swap(swap_size_unit) else {
+        return Vec::new();
+    };
+    if let Some(watch) = watch {
+        watch.filter_swap(&mut processes);
+        processes.extend(watch.exited_rows());
+    }
+    processes.sort_by(|a, b| {
+        b.swap_size
+            .partial_cmp(&a.swap_size)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    if group_by_namespace {
+        let host = provider.host_mnt_ns();
+        for process in &mut processes {
+            process.name = mnt_ns_label(process.mnt_ns, host);
+        }
+        processes = aggregate_processes(processes);
+    } else if aggregated {
+        processes = aggregate_processes(processes);
+    }
+    processes
+}
+
+/// Number of processes with swap in use, counting every member of an
+/// aggregated row; exited `--pid` rows (zero swap) are not counted.
+pub fn swapping_process_count(rows: &[ProcessSwapInfo]) -> usize {
+    rows.iter()
+        .filter(|p| p.swap_size > 0.0)
+        .map(|p| p.count as usize)
+        .sum()
+}
+
+/// Whether `name` contains `filter`, ignoring case. An empty filter matches
+/// every name.
+pub fn name_matches(name: &str, filter: &str) -> bool {
+    filter.is_empty() || name.to_lowercase().contains(&filter.to_lowercase())
+}
+
+/// The rows `create_process_lines` lists, in order: those whose name
+/// matches `filter`, at most `max_rows` of them.
+pub fn shown_rows<'a>(
+    processes: &'a [ProcessSwapInfo],
+    filter: &'a str,
+    max_rows: usize,
+) -> impl Iterator<Item = &'a ProcessSwapInfo> {
+    processes.iter().filter(move |p| name_matches(&p.name, filter)).take(max_rows)
+}
+
+/// Row index of the process with the largest swap, if any.
+pub fn heaviest_row(processes: &[ProcessSwapInfo]) -> Option<usize> {
+    processes
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| {
+            a.swap_size
+                .partial_cmp(&b.swap_size)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|(i, _)| i)
+}
+
+/// Put `rows` back in the order they had in `previous` (matched by `key`),
+/// so values update in place while re-sorting is held back. Rows that were
+/// not in `previous` go last, keeping their relative order.
+pub fn keep_previous_order<T, K: Eq + Hash>(rows: &mut [T], previous: &[T], key: impl Fn(&T) -> K) {
+    let positions: HashMap<K, usize> = previous.iter().enumerate().map(|(i, row)| (key(row), i)).collect();
+    rows.sort_by_key(|row| positions.get(&key(row)).copied().unwrap_or(usize::MAX));
+}
+
+/// Identity of a swap row across refreshes. Aggregated rows all have PID 0,
+/// so they are matched by name alone.
+pub fn swap_row_key(row: &ProcessSwapInfo) -> (u32, String) {
+    (row.pid, row.name.clone())
+}
+
+/// Build the swap process table from rows returned by `collect_process_rows`.
+/// With a `baseline`, the USED column shows the change since the baseline;
+/// processes absent from it count from zero. Processes that exec'd since
+/// they were first seen are shown as "new (was old)". The SWP I/O column
+/// shows each row's swap growth since the previous tick in KB/s, next to
+/// its resident set size (RSS; summed for aggregated rows). In
+/// `NamespaceMode::Annotate` a MNT NS column labels each row against
+/// `host_mnt_ns`. Rows whose swap exceeds the `swap_highlight` threshold
+/// (in KB) are drawn in its colour. Sizes are shown in `swap_size_unit`
+/// of `base`. Only rows whose name matches `filter` (see `name_matches`)
+/// are listed. At most `max_rows` rows are built; the rest are summarised
+/// in a trailing line.
+#[allow(clippy::too_many_arguments)]
+pub fn create_process_lines(
+    processes: &[ProcessSwapInfo],
+    swap_size_unit: &SizeUnits,
+    base: UnitBase,
+    aggregated: bool,
+    baseline: Option<&Baseline>,
+    names: &ProcessNames,
+    deltas: &SwapDeltas,
+    namespace_mode: NamespaceMode,
+    host_mnt_ns: Option<u64>,
+    max_rows: usize,
+    swap_highlight: Option<(u64, Color)>,
+    filter: &str,
 ) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
+    let annotate = namespace_mode == NamespaceMode::Annotate;
+    // Threshold in the rows' unit, to compare against `swap_size`
+    let highlight = swap_highlight.map(|(kb, color)| (convert_swap(kb, swap_size_unit.clone(), UnitBase::Binary), color));
 
-    lines.push(Line::from(vec![
+    let mut header = vec![
         format!("{:>12}", if aggregated { "COUNT" } else { "PID" }).bold(),
         " | ".into(),
-        format!("{:30}", "PROCESS").bold(),
+        format!("{:30}", if namespace_mode == NamespaceMode::Group { "MNT NS" } else { "PROCESS" }).bold(),
+        " | ".into(),
+        format!("{:10}", if baseline.is_some() { "Δ USED" } else { "USED" }).bold(),
+        " | ".into(),
+        format!("{:>10}", "TREND").bold(),
+        " | ".into(),
+        format!("{:>10}", "RSS").bold(),
         " | ".into(),
-        format!("{:10}", "USED").bold(),
-    ]));
+        format!("{:>10}", "SWP I/O").bold(),
+    ];
+    if annotate {
+        header.push(" | ".into());
+        header.push(format!("{:<16}", "MNT NS").bold());
+    }
+    lines.push(Line::from(header));
 
-    if let Ok(mut processes) = provider.get_processes_swap(swap_size_unit) {
-        processes.sort_by(|a, b| {
-            b.swap_size
-                .partial_cmp(&a.swap_size)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+    let processes: Vec<&ProcessSwapInfo> = processes.iter().filter(|p| name_matches(&p.name, filter)).collect();
+    for process in processes.iter().take(max_rows) {
+        let process_size = match baseline {
+            Some(baseline) => {
+                let base_kb = if aggregated {
+                    baseline.swap_kb_by_name.get(&process.name)
+                } else {
+                    baseline.swap_kb.get(&process.pid)
+                };
+                let start = convert_swap(base_kb.copied().unwrap_or(0), swap_size_unit.clone(), UnitBase::Binary);
+                let delta = rebase(process.swap_size - start, swap_size_unit, base);
+                match swap_size_unit {
+                    SizeUnits::KB => format!("{:+.0}", delta),
+                    _ => format!("{:+.2}", delta),
+                }
+            }
+            None => format_unit_value(rebase(process.swap_size, swap_size_unit, base), swap_size_unit),
+        };
+
+        let name = match names.previous_name(process.pid) {
+            Some(old) if !aggregated => format!("{} (was {})", process.name, old),
+            _ => process.name.clone(),
+        };
 
-        if aggregated {
-            processes = aggregate_processes(processes);
+        let mut row = vec![
+            format!("{:12}", if aggregated { process.count } else { process.pid }).into(),
+            " | ".into(),
+            format!("{:30}", name).into(),
+            " | ".into(),
+            format!("{:10}", process_size).into(),
+            " | ".into(),
+            format!("{:>10}", format_trend(deltas.trend(process, aggregated), swap_size_unit, base)).into(),
+            " | ".into(),
+            format!("{:>10}", format_rss(process.rss_kb, swap_size_unit, base)).into(),
+            " | ".into(),
+            format!("{:>10}", format_swap_rate(deltas.rate(process, aggregated))).into(),
+        ];
+        if annotate {
+            row.push(" | ".into());
+            row.push(format!("{:<16}", mnt_ns_label(process.mnt_ns, host_mnt_ns)).into());
         }
+        let mut line = Line::from(row);
+        if let Some((threshold, color)) = highlight
+            && process.swap_size > threshold
+        {
+            line = line.fg(color).bold();
+        }
+        lines.push(line);
+    }
+
+    if processes.len() > max_rows {
+        lines.push(
+            Line::from(format!(
+                "… {} more not shown (--max-processes {})",
+                processes.len() - max_rows,
+                max_rows
+            ))
+            .italic(),
+        );
+    }
 
-        for process in processes {
-            let mut process_size: String = format!("{:.2}", process.swap_size);
-            if let SizeUnits::KB = swap_size_unit {
-                process_size = format!("{}", process.swap_size)
+    lines
+}
+
+/// "KiB─▶MiB◀─GiB─TiB": every unit in `base`, the selected one marked.
+fn unit_buttons(selected: &SizeUnits, base: UnitBase) -> String {
+    [SizeUnits::KB, SizeUnits::MB, SizeUnits::GB, SizeUnits::TB]
+        .iter()
+        .map(|unit| {
+            if unit == selected {
+                format!("▶{}◀", base.suffix(unit))
+            } else {
+                base.suffix(unit).to_string()
             }
+        })
+        .collect::<Vec<_>>()
+        .join("─")
+}
 
-            lines.push(Line::from(vec![
-                format!("{:12}", process.pid).into(),
-                " | ".into(),
-                format!("{:30}", process.name).into(),
-                " | ".into(),
-                format!("{:10}", process_size).into(),
-            ]));
-        }
+/// Resident set size in the list's unit, or "-" when unknown (kernel
+/// threads, exited `--pid` rows).
+fn format_rss(rss_kb: Option<u64>, swap_size_unit: &SizeUnits, base: UnitBase) -> String {
+    match rss_kb {
+        Some(kb) => format_unit_value(convert_swap(kb, swap_size_unit.clone(), base), swap_size_unit),
+        None => "-".to_string(),
     }
+}
 
-    lines
+/// "↑ 120" / "↓ 40" (change since the previous refresh, in the list's
+/// unit), "→" when unchanged, "new" for rows not listed last time, or "-"
+/// before there is a previous refresh.
+fn format_trend(trend: Option<SwapTrend>, swap_size_unit: &SizeUnits, base: UnitBase) -> String {
+    let kb = match trend {
+        None => return "-".to_string(),
+        Some(SwapTrend::New) => return "new".to_string(),
+        Some(SwapTrend::Changed(kb)) => kb,
+    };
+    // Sizes are whole KB; anything smaller is conversion noise
+    if kb.abs() < 0.5 {
+        return "→".to_string();
+    }
+    let arrow = if kb > 0.0 { '↑' } else { '↓' };
+    let amount = convert_swap(kb.abs().round() as u64, swap_size_unit.clone(), base);
+    format!("{} {}", arrow, format_unit_value(amount, swap_size_unit))
+}
+
+/// "+120" (KB/s swapped out), "-40" (swapped in), "0", or "-" when unknown.
+fn format_swap_rate(rate: Option<f64>) -> String {
+    match rate {
+        None => "-".to_string(),
+        Some(rate) if rate.round() == 0.0 => "0".to_string(),
+        Some(rate) => format!("{:+.0}", rate),
+    }
+}
+
+/// Scroll offset that keeps `line` visible in a window of `visible_rows`,
+/// moving the current `scroll` as little as possible.
+pub fn scroll_to_reveal(line: usize, scroll: usize, visible_rows: usize) -> usize {
+    if line < scroll {
+        line
+    } else if visible_rows > 0 && line >= scroll + visible_rows {
+        line + 1 - visible_rows
+    } else {
+        scroll
+    }
+}
+
+/// Largest scroll offset that still fills a panel of `visible_height` rows
+/// (borders included) with `line_count` lines; 0 when they all fit.
+pub fn max_scroll(line_count: usize, visible_height: usize) -> usize {
+    (line_count + 2).saturating_sub(visible_height)
+}
+
+/// Clamp `offset` to the lines a panel of `visible_height` rows can scroll
+/// through and point the scrollbar `state` at it.
+pub fn fit_scroll(offset: &mut usize, state: &mut ScrollbarState, line_count: usize, visible_height: usize) {
+    let max = max_scroll(line_count, visible_height);
+    *offset = (*offset).min(max);
+    // One scrollbar position per scroll offset; none at all when the list
+    // fits, so an empty or short list draws no thumb
+    let positions = if max == 0 { 0 } else { max + 1 };
+    *state = state
+        .content_length(positions)
+        .viewport_content_length(visible_height.saturating_sub(2))
+        .position(*offset);
+}
+
+/// Scrollbar along the right border of a scrollable panel.
+pub fn render_scrollbar(frame: &mut Frame, area: Rect, theme: &Theme, state: &mut ScrollbarState) {
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓"))
+            .style(Style::default().fg(theme.scrollbar))
+            .thumb_style(Style::default().fg(theme.primary)),
+        area,
+        state,
+    );
+}
+
+/// Scroll position of a table outside the swap view. Each view keeps its
+/// own, so switching views does not lose the position.
+#[derive(Debug, Default, Clone)]
+pub struct ListScroll {
+    pub offset: usize,
+    pub state: ScrollbarState,
+    /// Panel height, borders included, as last rendered
+    pub visible_height: usize,
+    /// Lines in the table as last rendered
+    pub line_count: usize,
+}
+
+impl ListScroll {
+    /// Record the panel the table is drawn in and clamp the offset to it.
+    pub fn fit(&mut self, line_count: usize, area: Rect) {
+        self.line_count = line_count;
+        self.visible_height = area.height as usize;
+        fit_scroll(&mut self.offset, &mut self.state, line_count, self.visible_height);
+    }
+
+    pub fn scroll_to(&mut self, offset: usize) {
+        self.offset = offset.min(max_scroll(self.line_count, self.visible_height));
+        self.state = self.state.position(self.offset);
+    }
+
+    /// Lines PgUp/PgDn move, keeping a little overlap
+    pub fn page_size(&self) -> usize {
+        self.visible_height.saturating_sub(4)
+    }
+
+    /// Render `paragraph` scrolled to the offset, with a scrollbar.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, paragraph: Paragraph<'_>) {
+        frame.render_widget(paragraph.scroll((self.offset as u16, 0)), area);
+        render_scrollbar(frame, area, theme, &mut self.state);
+    }
+
+    /// Like `render`, but the first of `lines` (the column header) stays on
+    /// the block's top row and only the lines below it scroll.
+    pub fn render_with_header(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        block: Block<'_>,
+        mut lines: Vec<Line<'_>>,
+    ) {
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if !lines.is_empty() {
+            let header = lines.remove(0);
+            let [header_area, body_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+            frame.render_widget(Paragraph::new(header), header_area);
+            frame.render_widget(Paragraph::new(lines).scroll((self.offset as u16, 0)), body_area);
+        }
+        render_scrollbar(frame, area, theme, &mut self.state);
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -59,54 +395,295 @@ pub fn render_processes_list(
     area: Rect,
     theme: &Theme,
     swap_size_unit: &SizeUnits,
+    base: UnitBase,
     swap_processes_lines: &[Line<'static>],
     vertical_scroll: &mut usize,
     vertical_scroll_state: &mut ScrollbarState,
     visible_height: &mut usize,
+    selected_row: Option<usize>,
 ) {
-    let unit_buttons = match swap_size_unit {
-        SizeUnits::KB => "▶KB◀─MB─GB",
-        SizeUnits::MB => "KB─▶MB◀─GB",
-        SizeUnits::GB => "KB─MB─▶GB◀",
-    };
+    let unit_buttons = unit_buttons(swap_size_unit, base);
 
     *visible_height = area.height as usize;
-    let content_height = swap_processes_lines.len() + 2;
-    *vertical_scroll = (*vertical_scroll).min(content_height.saturating_sub(*visible_height));
-    *vertical_scroll_state = vertical_scroll_state
-        .content_length(content_height)
-        .position(*vertical_scroll);
+    fit_scroll(vertical_scroll, vertical_scroll_state, swap_processes_lines.len(), *visible_height);
 
     let bottom_block = Block::bordered()
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(theme.border))
         .style(Style::default().bg(theme.background))
         .title(
-            Line::from("(a to aggregate) (u/d|▲/▼|home/end|pgup/pgdown to scroll)")
+            Line::from("(a to aggregate) (▲/▼ select, K kill) (u/d|home/end|pgup/pgdown to scroll)")
                 .fg(theme.text)
                 .right_aligned(),
         )
         .title(
-            Line::from(format!("unit (k/m/g to change): {}", unit_buttons))
+            Line::from(format!("unit (k/m/g/T to change, B base): {}", unit_buttons))
                 .fg(theme.secondary)
                 .bold()
                 .left_aligned(),
         );
 
-    let process_paragraph = Paragraph::new(swap_processes_lines.to_vec())
+    let mut lines = swap_processes_lines.to_vec();
+    // Row 0 of the data is line 1, below the header
+    if let Some(line) = selected_row.and_then(|row| lines.get_mut(row + 1)) {
+        *line = line.clone().patch_style(Style::default().reversed());
+    }
+
+    let process_paragraph = Paragraph::new(lines)
         .alignment(Alignment::Center)
         .block(bottom_block)
         .scroll((*vertical_scroll as u16, 0));
 
     frame.render_widget(process_paragraph, area);
+    render_scrollbar(frame, area, theme, vertical_scroll_state);
+}
 
-    frame.render_stateful_widget(
-        Scrollbar::new(ScrollbarOrientation::VerticalRight)
-            .begin_symbol(Some("↑"))
-            .end_symbol(Some("↓"))
-            .style(Style::default().fg(theme.scrollbar))
-            .thumb_style(Style::default().fg(theme.primary)),
-        area,
-        vertical_scroll_state,
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(pid: u32, swap_size: f64) -> ProcessSwapInfo {
+        ProcessSwapInfo { pid, name: format!("p{}", pid), swap_size, last_cpu: None, rss_kb: None, mnt_ns: None, count: 1 }
+    }
+
+    #[test]
+    fn test_heaviest_row() {
+        assert_eq!(heaviest_row(&[]), None);
+        let rows = vec![proc(1, 10.0), proc(2, 300.0), proc(3, 20.0)];
+        assert_eq!(heaviest_row(&rows), Some(1));
+    }
+
+    #[test]
+    fn test_keep_previous_order() {
+        let previous = vec![proc(2, 10.0), proc(1, 5.0)];
+        // Fresh rows, re-sorted by swap, plus a newcomer
+        let mut rows = vec![proc(1, 50.0), proc(3, 40.0), proc(2, 10.0)];
+        keep_previous_order(&mut rows, &previous, swap_row_key);
+        assert_eq!(rows.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![2, 1, 3]);
+
+        // Aggregated rows match by name, whatever their count
+        let group = |pid, swap_size, count| ProcessSwapInfo { pid: 0, count, ..proc(pid, swap_size) };
+        let previous = vec![group(2, 10.0, 1), group(1, 5.0, 1)];
+        let mut rows = vec![group(1, 50.0, 7), group(2, 10.0, 1)];
+        keep_previous_order(&mut rows, &previous, swap_row_key);
+        assert_eq!(rows.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), vec!["p2", "p1"]);
+    }
+
+    #[test]
+    fn test_swapping_process_count() {
+        let rows = vec![proc(1, 10.0), proc(2, 300.0), proc(3, 0.0)];
+        assert_eq!(swapping_process_count(&rows), 2);
+        // Aggregated rows count every member
+        let group = |swap_size, count| ProcessSwapInfo { pid: 0, count, ..proc(1, swap_size) };
+        let groups = vec![group(10.0, 4), group(300.0, 2), group(0.0, 1)];
+        assert_eq!(swapping_process_count(&groups), 6);
+    }
+
+    #[test]
+    fn test_create_process_lines_rss_column() {
+        let rows = vec![ProcessSwapInfo { rss_kb: Some(2048), ..proc(1, 100.0) }, proc(2, 50.0)];
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &ProcessNames::default(), &SwapDeltas::default(), NamespaceMode::Off, None, usize::MAX, None, "");
+        let header = lines[0].to_string();
+        assert!(header.contains("USED       |      TREND |        RSS | "), "{}", header);
+        assert!(lines[1].to_string().contains("100        |          - |       2048 | "), "{}", lines[1]);
+        assert!(lines[2].to_string().contains(" |          - | "), "{}", lines[2]);
+
+        let lines = create_process_lines(&rows, &SizeUnits::MB, UnitBase::Binary, false, None, &ProcessNames::default(), &SwapDeltas::default(), NamespaceMode::Off, None, usize::MAX, None, "");
+        assert!(lines[1].to_string().contains("|       2.00 | "), "{}", lines[1]);
+    }
+
+    #[test]
+    fn test_create_process_lines_marks_exec() {
+        let mut names = ProcessNames::default();
+        names.observe(&[proc(1, 5.0)]);
+        let renamed = ProcessSwapInfo { name: "python3".into(), ..proc(1, 5.0) };
+        names.observe(std::slice::from_ref(&renamed));
+
+        let lines = create_process_lines(std::slice::from_ref(&renamed), &SizeUnits::KB, UnitBase::Binary, false, None, &names, &SwapDeltas::default(), NamespaceMode::Off, None, usize::MAX, None, "");
+        assert!(lines[1].to_string().contains("python3 (was p1)"));
+
+        // Aggregated rows stand for several PIDs: never marked
+        let lines = create_process_lines(&[renamed], &SizeUnits::KB, UnitBase::Binary, true, None, &names, &SwapDeltas::default(), NamespaceMode::Off, None, usize::MAX, None, "");
+        assert!(!lines[1].to_string().contains("(was"));
+    }
+
+    #[test]
+    fn test_create_process_lines_shows_group_count() {
+        let group = ProcessSwapInfo { pid: 0, count: 5, ..proc(1, 5.0) };
+        let lines = create_process_lines(&[group], &SizeUnits::KB, UnitBase::Binary, true, None, &ProcessNames::default(), &SwapDeltas::default(), NamespaceMode::Off, None, usize::MAX, None, "");
+        assert!(lines[0].to_string().contains("COUNT"));
+        assert!(lines[1].to_string().trim_start().starts_with("5 |"));
+    }
+
+    #[test]
+    fn test_create_process_lines_highlights_over_threshold() {
+        let rows = vec![proc(1, 2048.0), proc(2, 1024.0)];
+        let red = Color::Red;
+        // 1 MB threshold, compared in the display unit
+        for (unit, rows) in [(SizeUnits::KB, rows.clone()), (SizeUnits::MB, rows.iter().map(|p| ProcessSwapInfo { swap_size: p.swap_size / 1024.0, ..p.clone() }).collect())] {
+            let lines = create_process_lines(&rows, &unit, UnitBase::Binary, false, None, &ProcessNames::default(), &SwapDeltas::default(), NamespaceMode::Off, None, usize::MAX, Some((1024, red)), "");
+            assert_eq!(lines[1].style.fg, Some(red));
+            // Exactly at the threshold is not over it
+            assert_eq!(lines[2].style.fg, None);
+        }
+
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &ProcessNames::default(), &SwapDeltas::default(), NamespaceMode::Off, None, usize::MAX, None, "");
+        assert_eq!(lines[1].style.fg, None);
+    }
+
+    #[test]
+    fn test_create_process_lines_caps_rows() {
+        let rows: Vec<_> = (1..=5).map(|pid| proc(pid, pid as f64)).collect();
+        let names = ProcessNames::default();
+
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &names, &SwapDeltas::default(), NamespaceMode::Off, None, 3, None, "");
+        // header + 3 rows + truncation notice
+        assert_eq!(lines.len(), 5);
+        assert!(lines[4].to_string().contains("2 more not shown"));
+
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &names, &SwapDeltas::default(), NamespaceMode::Off, None, 5, None, "");
+        assert_eq!(lines.len(), 6);
+        assert!(!lines[5].to_string().contains("not shown"));
+    }
+
+    #[test]
+    fn test_create_process_lines_filter() {
+        let rows = vec![
+            ProcessSwapInfo { name: "Firefox".into(), ..proc(1, 300.0) },
+            ProcessSwapInfo { name: "postgres".into(), ..proc(2, 200.0) },
+            ProcessSwapInfo { name: "java".into(), ..proc(3, 100.0) },
+        ];
+        let names = ProcessNames::default();
+
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &names, &SwapDeltas::default(), NamespaceMode::Off, None, usize::MAX, None, "fire");
+        // header + the one match
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].to_string().contains("PROCESS"));
+        assert!(lines[1].to_string().contains("Firefox"));
+
+        // The cap counts matching rows only
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &names, &SwapDeltas::default(), NamespaceMode::Off, None, 1, None, "E");
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].to_string().contains("Firefox"));
+        assert!(lines[2].to_string().contains("1 more not shown"));
+    }
+
+    #[test]
+    fn test_shown_rows() {
+        let rows = vec![
+            ProcessSwapInfo { name: "firefox".into(), ..proc(10, 300.0) },
+            ProcessSwapInfo { name: "postgres".into(), ..proc(20, 200.0) },
+            ProcessSwapInfo { name: "firefox-bin".into(), ..proc(30, 100.0) },
+        ];
+        let pids = |filter, max_rows| shown_rows(&rows, filter, max_rows).map(|p| p.pid).collect::<Vec<_>>();
+        assert_eq!(pids("", usize::MAX), vec![10, 20, 30]);
+        assert_eq!(pids("fire", usize::MAX), vec![10, 30]);
+        assert_eq!(pids("", 2), vec![10, 20]);
+    }
+
+    #[test]
+    fn test_name_matches() {
+        assert!(name_matches("firefox", ""));
+        assert!(name_matches("firefox", "FOX"));
+        assert!(name_matches("pt_data_worker", "data_w"));
+        assert!(!name_matches("postgres", "firefox"));
+    }
+
+    #[test]
+    fn test_create_process_lines_swap_rate_column() {
+        let names = ProcessNames::default();
+        let mut deltas = SwapDeltas::default();
+        deltas.update(&[proc(1, 100.0), proc(2, 500.0), proc(3, 7.0)], &SizeUnits::KB, false, 1.0);
+        let rows = vec![proc(1, 300.0), proc(2, 400.0), proc(3, 7.0), proc(4, 9.0)];
+        deltas.update(&rows, &SizeUnits::KB, false, 2.0);
+
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &names, &deltas, NamespaceMode::Off, None, usize::MAX, None, "");
+        assert!(lines[0].to_string().contains("SWP I/O"));
+        assert!(lines[1].to_string().ends_with("+100"));
+        assert!(lines[2].to_string().ends_with("-50"));
+        assert!(lines[3].to_string().ends_with(" 0"));
+        assert!(lines[4].to_string().ends_with(" -"));
+    }
+
+    #[test]
+    fn test_create_process_lines_trend_column() {
+        let names = ProcessNames::default();
+        let mut deltas = SwapDeltas::default();
+        let trend_cell = |line: &Line| line.to_string().split(" | ").nth(3).unwrap().trim().to_string();
+        let rows = vec![proc(1, 100.0), proc(2, 500.0)];
+        deltas.update(&rows, &SizeUnits::KB, false, 1.0);
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &names, &deltas, NamespaceMode::Off, None, usize::MAX, None, "");
+        assert!(lines[0].to_string().contains("TREND"));
+        assert_eq!(trend_cell(&lines[1]), "-");
+
+        let rows = vec![proc(1, 3172.0), proc(2, 500.0), proc(3, 7.0)];
+        deltas.update(&rows, &SizeUnits::KB, false, 1.0);
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &names, &deltas, NamespaceMode::Off, None, usize::MAX, None, "");
+        assert_eq!(trend_cell(&lines[1]), "↑ 3072");
+        assert_eq!(trend_cell(&lines[2]), "→");
+        assert_eq!(trend_cell(&lines[3]), "new");
+
+        // Shrinking, shown in the list's unit
+        deltas.update(&[proc(1, 1.0)], &SizeUnits::MB, false, 1.0);
+        let lines = create_process_lines(&[proc(1, 1.0)], &SizeUnits::MB, UnitBase::Binary, false, None, &names, &deltas, NamespaceMode::Off, None, usize::MAX, None, "");
+        assert_eq!(trend_cell(&lines[1]), "↓ 2.10");
+    }
+
+    #[test]
+    fn test_create_process_lines_namespace_column() {
+        let names = ProcessNames::default();
+        let rows = vec![
+            ProcessSwapInfo { mnt_ns: Some(100), ..proc(1, 10.0) },
+            ProcessSwapInfo { mnt_ns: Some(200), ..proc(2, 5.0) },
+            proc(3, 1.0),
+        ];
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &names, &SwapDeltas::default(), NamespaceMode::Annotate, Some(100), usize::MAX, None, "");
+        assert!(lines[0].to_string().contains("MNT NS"));
+        assert!(lines[1].to_string().trim_end().ends_with("| host"));
+        assert!(lines[2].to_string().trim_end().ends_with("| mnt:200"));
+        assert!(lines[3].to_string().trim_end().ends_with("| -"));
+
+        let lines = create_process_lines(&rows, &SizeUnits::KB, UnitBase::Binary, false, None, &names, &SwapDeltas::default(), NamespaceMode::Off, Some(100), usize::MAX, None, "");
+        assert!(!lines[0].to_string().contains("MNT NS"));
+    }
+
+    #[test]
+    fn test_collect_process_rows_groups_by_namespace() {
+        let mut mock = crate::data::MockDataProvider::new();
+        mock.host_mnt_ns = Some(100);
+        mock.processes = vec![
+            ProcessSwapInfo { mnt_ns: Some(100), ..proc(1, 10.0) },
+            ProcessSwapInfo { mnt_ns: Some(200), ..proc(2, 30.0) },
+            ProcessSwapInfo { mnt_ns: Some(200), ..proc(3, 5.0) },
+        ];
+        let rows = collect_process_rows(&mock, &SizeUnits::KB, false, true, None);
+        let groups: Vec<(&str, u32, f64)> = rows.iter().map(|r| (r.name.as_str(), r.count, r.swap_size)).collect();
+        assert_eq!(groups, vec![("mnt:200", 2, 35.0), ("host", 1, 10.0)]);
+    }
+
+    #[test]
+    fn test_namespace_mode_cycles() {
+        assert_eq!(NamespaceMode::Off.next(), NamespaceMode::Annotate);
+        assert_eq!(NamespaceMode::Annotate.next(), NamespaceMode::Group);
+        assert_eq!(NamespaceMode::Group.next(), NamespaceMode::Off);
+    }
+
+    #[test]
+    fn test_max_scroll() {
+        // Header only (nothing swapping) or a short list: no scrolling
+        assert_eq!(max_scroll(1, 20), 0);
+        assert_eq!(max_scroll(18, 20), 0);
+        assert_eq!(max_scroll(19, 20), 1);
+        assert_eq!(max_scroll(5, 0), 7);
+    }
+
+    #[test]
+    fn test_scroll_to_reveal() {
+        // Already visible: unchanged
+        assert_eq!(scroll_to_reveal(5, 0, 10), 0);
+        // Above the window: scroll up to it
+        assert_eq!(scroll_to_reveal(2, 8, 10), 2);
+        // Below the window: scroll so it is the last visible line
+        assert_eq!(scroll_to_reveal(25, 0, 10), 16);
+    }
 }
@@ -1,15 +1,50 @@
-use crate::data::types::{InfoSwap, SizeUnits, convert_swap};
+use crate::data::types::{InfoSwap, MediaClass, SizeUnits, UnitBase, format_unit_value, media_summary, priority_warnings, rebase, total_used_label, unit_to_kb};
+use crate::ui::unified_view::format_mem_dual;
 use crate::theme::Theme;
+use std::collections::HashMap;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Style, Stylize},
+    style::{Color, Style, Stylize},
     text::Line,
     widgets::{Block, BorderType, Paragraph},
 };
 
-#[cfg(target_os = "linux")]
-use crate::data::swap::find_mount_device;
+/// Column set of the swap devices table (`l` cycles). Auto picks the wide
+/// layout when the panel is at least 80 columns.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DeviceLayout {
+    #[default]
+    Auto,
+    Wide,
+    Narrow,
+}
+
+impl DeviceLayout {
+    pub fn next(self) -> Self {
+        match self {
+            DeviceLayout::Auto => DeviceLayout::Wide,
+            DeviceLayout::Wide => DeviceLayout::Narrow,
+            DeviceLayout::Narrow => DeviceLayout::Auto,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DeviceLayout::Auto => "auto",
+            DeviceLayout::Wide => "wide",
+            DeviceLayout::Narrow => "narrow",
+        }
+    }
+
+    fn is_wide(self, width: u16) -> bool {
+        match self {
+            DeviceLayout::Auto => width >= 80,
+            DeviceLayout::Wide => true,
+            DeviceLayout::Narrow => false,
+        }
+    }
+}
 
 #[cfg(target_os = "linux")]
 #[allow(clippy::too_many_arguments)]
@@ -18,19 +53,23 @@ pub fn render_swap_devices(
     area: Rect,
     theme: &Theme,
     swap_devices: &[InfoSwap],
+    device_sources: &HashMap<String, String>,
     swap_size_unit: &SizeUnits,
+    unit_base: UnitBase,
     total_swap: u64,
     used_swap: u64,
+    swapping_processes: usize,
     display_devices: bool,
+    show_bars: bool,
+    layout: DeviceLayout,
+    show_queue: bool,
+    dual_units: bool,
 ) {
-    let total = convert_swap(total_swap, swap_size_unit.clone());
-    let used = convert_swap(used_swap, swap_size_unit.clone());
-
-    let total_used_title: String = match swap_size_unit {
-        SizeUnits::KB => format!("total: {} | used: {}", total, used),
-        SizeUnits::MB => format!("total: {} | used: {:.2}", total.round(), used),
-        SizeUnits::GB => format!("total: {:.2} | used: {:.2}", total, used),
-    };
+    let total_used_title = format!(
+        "{} | procs swapping: {}",
+        total_used_label(total_swap, used_swap, swap_size_unit, unit_base),
+        swapping_processes
+    );
 
     let total_n_used_line = if !display_devices {
         Line::from("").fg(theme.text).left_aligned()
@@ -45,68 +84,226 @@ pub fn render_swap_devices(
         .unwrap_or(10)
         .max(10);
 
+    // Resolved once per refresh by the caller; mountinfo is too slow to
+    // parse on every frame
+    let source = |device: &InfoSwap| device_sources.get(&device.name).map_or("RAM", String::as_str);
     let source_width = swap_devices
         .iter()
-        .map(|d| {
-            let src = find_mount_device(std::path::Path::new(&d.name))
-                .unwrap_or_else(|| "RAM".into());
-            src.len()
-        })
+        .map(|d| source(d).len())
         .max()
         .unwrap_or(4)
         .max(4);
 
-    let wide = area.width >= 80;
+    let wide = layout.is_wide(area.width);
+    let warnings = priority_warnings(swap_devices);
     let mut lines = Vec::new();
+    // Dual values ("12.29 GiB (12884901 KiB)") need wider size columns
+    let size_width = if dual_units { 24 } else { 10 };
 
-    if wide {
-        lines.push(Line::from(format!(
-            "{:<source_width$} | {:<name_width$} | {:<10} | {:>8} | {:>10} | {:>10}",
-            "disk", "path", "type", "priority", "total", "used"
-        )));
+    if show_bars {
+        lines = device_bar_lines(swap_devices, name_width, area.width as usize);
     } else {
-        lines.push(Line::from(format!(
-            "{:<source_width$} | {:<name_width$} | {:<10} | {:>10}",
-            "disk", "path", "total", "used"
-        )));
-    }
-
-    for device in swap_devices {
-        let used = match swap_size_unit {
-            SizeUnits::KB => device.used_kb.to_string(),
-            _ => format!("{:.2}", device.used_kb),
-        };
-
-        let source = find_mount_device(std::path::Path::new(&device.name))
-            .unwrap_or_else(|| "RAM".into());
-
-        let total = match swap_size_unit {
-            SizeUnits::KB => device.size_kb.to_string(),
-            _ => format!("{:.2}", device.size_kb),
-        };
-
-        let row = if wide {
+        let mut header = if wide {
             format!(
-                "{:<source_width$} | {:<name_width$} | {:<10} | {:>8} | {:>10} | {:>10}",
-                source, device.name, device.kind, device.priority, total, used
+                "{:<source_width$} | {:<name_width$} | {:<10} | {:<5} | {:>8} | {:>size_width$} | {:>size_width$}",
+                "disk", "path", "type", "media", "priority", "total", "used"
             )
         } else {
             format!(
-                "{:<source_width$} | {:<name_width$} | {:<10} | {:>10}",
-                source, device.name, total, used
+                "{:<source_width$} | {:<name_width$} | {:<size_width$} | {:>size_width$}",
+                "disk", "path", "total", "used"
             )
         };
-        lines.push(Line::from(row));
+        if show_queue {
+            header.push_str(&queue_columns("sched", "ra_kb"));
+        }
+        lines.push(Line::from(header));
+
+        for device in swap_devices {
+            let size_cell = |value| {
+                if dual_units {
+                    format_mem_dual(unit_to_kb(value, swap_size_unit), unit_base)
+                } else {
+                    format_unit_value(rebase(value, swap_size_unit, unit_base), swap_size_unit)
+                }
+            };
+            let used = size_cell(device.used_kb);
+            let source = source(device);
+            let total = size_cell(device.size_kb);
+
+            let mut row = if wide {
+                format!(
+                    "{:<source_width$} | {:<name_width$} | {:<10} | {:<5} | {:>8} | {:>size_width$} | {:>size_width$}",
+                    source, device.name, device.kind, device.media.label(), device.priority, total, used
+                )
+            } else {
+                format!(
+                    "{:<source_width$} | {:<name_width$} | {:<size_width$} | {:>size_width$}",
+                    source, device.name, total, used
+                )
+            };
+            if show_queue {
+                let (scheduler, read_ahead) = queue_cells(device);
+                row.push_str(&queue_columns(&scheduler, &read_ahead));
+            }
+            lines.push(Line::from(row));
+        }
     }
 
+    // Header row offsets the table lines by one; bar lines map 1:1
+    let mut offset = if show_bars { 0 } else { 1 };
+    if let Some(summary) = media_summary_line(swap_devices) {
+        lines.insert(0, Line::from(summary).fg(theme.secondary));
+        offset += 1;
+    }
+    for (i, _) in warnings.iter().enumerate().filter(|(_, w)| **w) {
+        if let Some(line) = lines.get_mut(i + offset) {
+            let flagged = std::mem::take(line);
+            *line = warning_line(flagged, theme.critical);
+        }
+    }
+    let hint = if warnings.contains(&true) {
+        "(! HDD swap has higher priority than SSD/NVMe) (h to hide swap devices) (p for bars/table) (l layout) (o sched/read-ahead)"
+    } else {
+        "(h to hide swap devices) (p for bars/table) (l layout) (o sched/read-ahead)"
+    };
+
     let block = Block::bordered()
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(theme.border))
         .style(Style::default().bg(theme.background))
         .title(total_n_used_line.right_aligned())
         .title(Line::from("swap devices").fg(theme.text).left_aligned())
-        .title_bottom(Line::from("(h to hide swap devices)").left_aligned());
+        .title_bottom(Line::from(hint).left_aligned());
 
     let para = Paragraph::new(lines).block(block).centered();
     frame.render_widget(para, area);
 }
+
+/// One "name [bar] percent" line per device; size-0 devices get an empty bar.
+fn device_bar_lines(swap_devices: &[InfoSwap], name_width: usize, area_width: usize) -> Vec<Line<'static>> {
+    let bar_width = area_width.saturating_sub(name_width + 16).clamp(10, 40);
+    swap_devices
+        .iter()
+        .map(|device| {
+            let (bar, percent) = if device.size_kb > 0.0 {
+                let ratio = device.used_kb / device.size_kb;
+                (percent_bar(ratio, bar_width), format!("{:>5.1}%", ratio * 100.0))
+            } else {
+                (percent_bar(0.0, bar_width), format!("{:>6}", "-"))
+            };
+            Line::from(format!("{:<name_width$} [{}] {}", device.name, bar, percent))
+        })
+        .collect()
+}
+
+fn queue_columns(scheduler: &str, read_ahead: &str) -> String {
+    format!(" | {:<11} | {:>6}", scheduler, read_ahead)
+}
+
+/// Scheduler and read-ahead of the device's queue, "-" when unavailable.
+fn queue_cells(device: &InfoSwap) -> (String, String) {
+    (
+        device.scheduler.clone().unwrap_or_else(|| "-".into()),
+        device.read_ahead_kb.map(|kb| kb.to_string()).unwrap_or_else(|| "-".into()),
+    )
+}
+
+/// "by media: hdd 80% (38% used) | zram 20% (100% used)". None unless the
+/// media of at least one device is known.
+fn media_summary_line(swap_devices: &[InfoSwap]) -> Option<String> {
+    let summary = media_summary(swap_devices);
+    if summary.iter().all(|share| share.media == MediaClass::Unknown) {
+        return None;
+    }
+    let parts: Vec<String> = summary
+        .iter()
+        .map(|share| {
+            let label = match share.media {
+                MediaClass::Unknown => "other",
+                media => media.label(),
+            };
+            format!("{} {:.0}% ({:.0}% used)", label, share.capacity_percent, share.used_percent)
+        })
+        .collect();
+    Some(format!("by media: {}", parts.join(" | ")))
+}
+
+/// Highlight a device row whose priority ordering puts a slow disk first.
+fn warning_line(line: Line<'static>, color: Color) -> Line<'static> {
+    let mut line = line.fg(color);
+    line.spans.push(" !".into());
+    line
+}
+
+/// Render a fill ratio (clamped to 0..=1) as a fixed-width block-character bar.
+pub(crate) fn percent_bar(ratio: f64, width: usize) -> String {
+    let ratio = if ratio.is_finite() { ratio.clamp(0.0, 1.0) } else { 0.0 };
+    let filled = (ratio * width as f64).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_bar_widths() {
+        assert_eq!(percent_bar(0.0, 10), "░░░░░░░░░░");
+        assert_eq!(percent_bar(0.5, 10), "█████░░░░░");
+        assert_eq!(percent_bar(1.0, 10), "██████████");
+    }
+
+    #[test]
+    fn test_media_summary_line() {
+        let device = |name: &str, media, size_kb, used_kb| InfoSwap {
+            name: name.into(), path: name.into(), kind: "partition".into(), size_kb, used_kb, priority: 0, media,
+            scheduler: None, read_ahead_kb: None,
+        };
+        let devices = vec![
+            device("/dev/sda2", MediaClass::Hdd, 800.0, 300.0),
+            device("/swapfile", MediaClass::Unknown, 200.0, 0.0),
+        ];
+        assert_eq!(
+            media_summary_line(&devices).as_deref(),
+            Some("by media: hdd 80% (38% used) | other 20% (0% used)")
+        );
+        assert_eq!(media_summary_line(&devices[1..]), None);
+    }
+
+    #[test]
+    fn test_queue_cells() {
+        let mut device = InfoSwap {
+            name: "/dev/sda2".into(), path: "/dev/sda2".into(), kind: "partition".into(), size_kb: 1.0, used_kb: 0.0, priority: 0,
+            media: MediaClass::Hdd, scheduler: None, read_ahead_kb: None,
+        };
+        assert_eq!(queue_cells(&device), ("-".to_string(), "-".to_string()));
+        device.scheduler = Some("mq-deadline".into());
+        device.read_ahead_kb = Some(128);
+        assert_eq!(queue_cells(&device), ("mq-deadline".to_string(), "128".to_string()));
+        assert_eq!(queue_columns("bfq", "128"), " | bfq         |    128");
+    }
+
+    #[test]
+    fn test_warning_line_marks_row() {
+        let line = warning_line(Line::from("/dev/sda2"), Color::Rgb(255, 85, 85));
+        assert_eq!(line.to_string(), "/dev/sda2 !");
+        assert_eq!(line.style.fg, Some(Color::Rgb(255, 85, 85)));
+    }
+
+    #[test]
+    fn test_device_layout_override() {
+        assert!(DeviceLayout::Auto.is_wide(80));
+        assert!(!DeviceLayout::Auto.is_wide(79));
+        assert!(DeviceLayout::Wide.is_wide(40));
+        assert!(!DeviceLayout::Narrow.is_wide(200));
+        assert_eq!(DeviceLayout::Auto.next().next().next(), DeviceLayout::Auto);
+    }
+
+    #[test]
+    fn test_percent_bar_clamps_out_of_range() {
+        assert_eq!(percent_bar(1.7, 4), "████");
+        assert_eq!(percent_bar(-0.2, 4), "░░░░");
+        assert_eq!(percent_bar(f64::NAN, 4), "░░░░");
+    }
+}
@@ -1,26 +1,121 @@
-use crate::data::types::{NumaNode, NumaNodeType, SizeUnits, UnifiedProcessInfo, convert_swap};
+use crate::app::SortColumn;
+use crate::data::types::{Baseline, GpuDevice, NumaNode, NumaNodeType, ProcessLocation, SizeUnits, UnifiedProcessInfo, UnitBase, convert_swap};
 use crate::theme::Theme;
+use crate::ui::process_list::ListScroll;
+use crate::ui::truncate_chars;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, BorderType, Paragraph},
+    widgets::{Block, BorderType},
 };
 
-// NUMA memory locality colour constants
-const COLOR_LOCAL_GREEN: Color = Color::Rgb(80, 200, 120);
-const COLOR_REMOTE_ORANGE: Color = Color::Rgb(255, 183, 77);
-const COLOR_HBM_RED: Color = Color::Rgb(255, 85, 85);
+// Column widths; keep in sync with `header_column_at`
+const PID_WIDTH: u16 = 8;
+const NAME_WIDTH: u16 = 16;
+/// CPU→N / GPU→N, with room for the sort arrow
+const NODE_REF_WIDTH: u16 = 6;
+const NODE_MEM_WIDTH: u16 = 9;
+const MEM_WIDTH: u16 = 10;
 
+/// Map an x offset within the table (0 = first column) to the sortable
+/// column whose header covers it. Columns are separated by one space.
+pub fn header_column_at(x: u16, node_count: usize, show_committed: bool) -> Option<SortColumn> {
+    let name_start = PID_WIDTH + 1;
+    let cpu_node_start = name_start + NAME_WIDTH + 1;
+    let gpu_node_start = cpu_node_start + NODE_REF_WIDTH + 1;
+    let swap_start = gpu_node_start + NODE_REF_WIDTH + (NODE_MEM_WIDTH + 1) * node_count as u16 + 1;
+    let rss_start = swap_start + MEM_WIDTH + 1;
+    let gpu_mem_start = rss_start + MEM_WIDTH + 1;
+    let committed_start = gpu_mem_start + MEM_WIDTH + 1;
+
+    if (name_start..name_start + NAME_WIDTH).contains(&x) {
+        Some(SortColumn::Name)
+    } else if (cpu_node_start..cpu_node_start + NODE_REF_WIDTH).contains(&x) {
+        Some(SortColumn::NumaNode)
+    } else if (swap_start..swap_start + MEM_WIDTH).contains(&x) {
+        Some(SortColumn::Swap)
+    } else if (gpu_mem_start..gpu_mem_start + MEM_WIDTH).contains(&x) {
+        Some(SortColumn::GpuMem)
+    } else if show_committed && (committed_start..committed_start + MEM_WIDTH).contains(&x) {
+        Some(SortColumn::Committed)
+    } else {
+        None
+    }
+}
+
+/// Which process locations the unified view lists (`F` cycles), to isolate
+/// e.g. the CPU processes whose pages migrated to GPU HBM.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LocationFilter {
+    #[default]
+    All,
+    CpuOnly,
+    GpuOnly,
+    CpuAndGpu,
+}
+
+impl LocationFilter {
+    pub fn next(self) -> Self {
+        match self {
+            LocationFilter::All => LocationFilter::CpuOnly,
+            LocationFilter::CpuOnly => LocationFilter::GpuOnly,
+            LocationFilter::GpuOnly => LocationFilter::CpuAndGpu,
+            LocationFilter::CpuAndGpu => LocationFilter::All,
+        }
+    }
+
+    pub fn matches(self, location: &ProcessLocation) -> bool {
+        match self {
+            LocationFilter::All => true,
+            LocationFilter::CpuOnly => *location == ProcessLocation::CpuOnly,
+            LocationFilter::GpuOnly => *location == ProcessLocation::GpuOnly,
+            LocationFilter::CpuAndGpu => *location == ProcessLocation::CpuAndGpu,
+        }
+    }
+
+    /// As the title shows it; None when nothing is filtered out
+    fn label(self) -> Option<&'static str> {
+        match self {
+            LocationFilter::All => None,
+            LocationFilter::CpuOnly => Some("CPU-only"),
+            LocationFilter::GpuOnly => Some("GPU-only"),
+            LocationFilter::CpuAndGpu => Some("CPU+GPU"),
+        }
+    }
+}
+
+/// Header label with a direction arrow when `column` is the active sort.
+fn sort_label(label: &str, column: SortColumn, sort_column: SortColumn, sort_reversed: bool) -> String {
+    if column != sort_column {
+        return label.to_string();
+    }
+    format!("{}{}", label, column.arrow(sort_reversed))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_unified_view(
     frame: &mut Frame,
     area: Rect,
     theme: &Theme,
     unified_procs: &[UnifiedProcessInfo],
     unit: &SizeUnits,
+    base: UnitBase,
     numa_nodes: &[NumaNode],
+    aggregated: bool,
+    baseline: Option<&Baseline>,
+    sort_column: SortColumn,
+    sort_reversed: bool,
+    show_committed: bool,
+    gpu_devices: &[GpuDevice],
+    show_gpu_percent: bool,
+    location_filter: LocationFilter,
+    total_swap_kb: u64,
+    swap_highlight_kb: Option<u64>,
+    scroll: &mut ListScroll,
 ) {
+    let label = |text: &str, column| sort_label(text, column, sort_column, sort_reversed);
     let mut lines = Vec::new();
 
     // Build sorted list of NUMA node IDs for dynamic columns
@@ -44,13 +139,13 @@ pub fn render_unified_view(
 
     // Header
     let mut header_spans: Vec<Span> = vec![
-        format!("{:>8}", "PID").bold(),
+        format!("{:>8}", if aggregated { "COUNT" } else { "PID" }).bold(),
         Span::from(" "),
-        format!("{:<16}", "NAME").bold(),
+        format!("{:<16}", label("NAME", SortColumn::Name)).bold(),
         Span::from(" "),
-        format!("{:>5}", "CPU→N").bold(),
+        format!("{:>w$}", label("CPU→N", SortColumn::NumaNode), w = NODE_REF_WIDTH as usize).bold(),
         Span::from(" "),
-        format!("{:>5}", "GPU→N").bold(),
+        format!("{:>w$}", "GPU→N", w = NODE_REF_WIDTH as usize).bold(),
     ];
 
     // Dynamic per-node columns
@@ -60,21 +155,57 @@ pub fn render_unified_view(
     }
 
     header_spans.push(Span::from(" "));
-    header_spans.push(format!("{:>10}", "SWAP").bold());
+    let (swap_label, gpu_label) = if baseline.is_some() {
+        ("Δ SWAP", "Δ GPU MEM")
+    } else {
+        ("SWAP", "GPU MEM")
+    };
+    header_spans.push(format!("{:>10}", label(swap_label, SortColumn::Swap)).bold());
     header_spans.push(Span::from(" "));
-    header_spans.push(format!("{:>10}", "GPU MEM").bold());
+    header_spans.push(format!("{:>10}", "RSS").bold());
+    header_spans.push(Span::from(" "));
+    header_spans.push(format!("{:>10}", label(gpu_label, SortColumn::GpuMem)).bold());
+    if show_committed {
+        header_spans.push(Span::from(" "));
+        header_spans.push(format!("{:>10}", label("SWAP+RSS", SortColumn::Committed)).bold());
+    }
+    if show_gpu_percent {
+        header_spans.push(Span::from(" "));
+        header_spans.push(format!("{:>6}", "GPU%").bold());
+    }
 
     lines.push(Line::from(header_spans));
 
+    let rows: Vec<&UnifiedProcessInfo> = unified_procs.iter().filter(|p| location_filter.matches(&p.location)).collect();
     if unified_procs.is_empty() {
         lines.push(Line::from("  No process data available"));
+    } else if rows.is_empty() {
+        lines.push(Line::from("  No processes in this location (F to change)"));
     } else {
-        for proc in unified_procs {
-            let swap_str = format_mem(proc.swap_kb, unit);
-            let gpu_str = proc
-                .gpu_memory_kb
-                .map(|kb| format_mem(kb, unit))
-                .unwrap_or_else(|| "-".into());
+        for proc in rows {
+            let (swap_str, gpu_str) = match baseline {
+                Some(baseline) => {
+                    let (base_swap, base_gpu) = if aggregated {
+                        (baseline.swap_kb_by_name.get(&proc.name), baseline.gpu_kb_by_name.get(&proc.name))
+                    } else {
+                        (baseline.swap_kb.get(&proc.pid), baseline.gpu_kb.get(&proc.pid))
+                    };
+                    let (base_swap, base_gpu) = (base_swap.copied().unwrap_or(0), base_gpu.copied().unwrap_or(0));
+                    let gpu_kb = proc.gpu_memory_kb.unwrap_or(0);
+                    let gpu_str = if proc.gpu_memory_kb.is_some() || base_gpu > 0 {
+                        format_mem_delta(gpu_kb as i64 - base_gpu as i64, unit, base)
+                    } else {
+                        "-".into()
+                    };
+                    (format_mem_delta(proc.swap_kb as i64 - base_swap as i64, unit, base), gpu_str)
+                }
+                None => (
+                    format_mem(proc.swap_kb, unit, base),
+                    proc.gpu_memory_kb
+                        .map(|kb| format_mem(kb, unit, base))
+                        .unwrap_or_else(|| "-".into()),
+                ),
+            };
 
             let cpu_n_str = if proc.cpu_nodes.is_empty() {
                 "-".to_string()
@@ -89,13 +220,13 @@ pub fn render_unified_view(
             };
 
             let mut spans: Vec<Span> = vec![
-                format!("{:>8}", proc.pid).into(),
+                format!("{:>8}", if aggregated { proc.count } else { proc.pid }).into(),
                 " ".into(),
-                format!("{:<16}", truncate(&proc.name, 16)).into(),
+                format!("{:<16}", truncate_chars(&proc.name, 16)).into(),
                 " ".into(),
-                format!("{:>5}", cpu_n_str).into(),
+                format!("{:>w$}", cpu_n_str, w = NODE_REF_WIDTH as usize).into(),
                 " ".into(),
-                format!("{:>5}", gpu_n_str).into(),
+                format!("{:>w$}", gpu_n_str, w = NODE_REF_WIDTH as usize).into(),
             ];
 
             // Per-node memory columns with locality colouring:
@@ -103,7 +234,7 @@ pub fn render_unified_view(
             for &node_id in &node_ids {
                 let kb = proc.kb_per_node.get(&node_id).copied().unwrap_or(0);
                 let cell = if kb > 0 {
-                    format_mem(kb, unit)
+                    format_mem(kb, unit, base)
                 } else {
                     "-".to_string()
                 };
@@ -111,9 +242,9 @@ pub fn render_unified_view(
                 if kb > 0 {
                     let node_type = numa_nodes.iter().find(|n| n.id == node_id).map(|n| &n.node_type);
                     let color = match node_type {
-                        Some(NumaNodeType::GpuHbm { .. }) => COLOR_HBM_RED,
-                        Some(NumaNodeType::Cpu) if proc.cpu_nodes.contains(&node_id) => COLOR_LOCAL_GREEN,
-                        _ => COLOR_REMOTE_ORANGE,
+                        Some(NumaNodeType::GpuHbm { .. }) => theme.critical,
+                        Some(NumaNodeType::Cpu) if proc.cpu_nodes.contains(&node_id) => theme.good,
+                        _ => theme.warn,
                     };
                     spans.push(Span::styled(
                         format!("{:>9}", cell),
@@ -127,55 +258,234 @@ pub fn render_unified_view(
             spans.push(" ".into());
             spans.push(format!("{:>10}", swap_str).into());
             spans.push(" ".into());
+            let rss_str = proc.rss_kb.map(|kb| format_mem(kb, unit, base)).unwrap_or_else(|| "-".into());
+            spans.push(format!("{:>10}", rss_str).into());
+            spans.push(" ".into());
             spans.push(format!("{:>10}", gpu_str).into());
+            if show_committed {
+                spans.push(" ".into());
+                spans.push(format!("{:>10}", format_mem(proc.committed_kb(), unit, base)).into());
+            }
+            if show_gpu_percent {
+                let percent = gpu_memory_percent(proc, gpu_devices)
+                    .map(|p| format!("{:.1}%", p))
+                    .unwrap_or_else(|| "-".into());
+                spans.push(" ".into());
+                spans.push(format!("{:>6}", percent).into());
+            }
 
-            lines.push(Line::from(spans));
+            let mut line = Line::from(spans);
+            if swap_highlight_kb.is_some_and(|kb| proc.swap_kb > kb) {
+                line = line.fg(theme.critical).bold();
+            }
+            lines.push(line);
         }
     }
 
-    let block = Block::bordered()
+    let mut block = Block::bordered()
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(theme.border))
         .style(Style::default().bg(theme.background))
         .title(
-            Line::from(" Unified CPU+GPU+NUMA View ")
+            Line::from(match location_filter.label() {
+                Some(label) => format!(" Unified CPU+GPU+NUMA View [{} only] ", label),
+                None => " Unified CPU+GPU+NUMA View ".to_string(),
+            })
                 .fg(theme.primary)
                 .bold(),
         )
         .title(
             Line::from(vec![
                 Span::raw(" "),
-                Span::styled("local", Style::default().fg(COLOR_LOCAL_GREEN)),
+                Span::styled("local", Style::default().fg(theme.good)),
                 Span::raw("  "),
-                Span::styled("remote", Style::default().fg(COLOR_REMOTE_ORANGE)),
+                Span::styled("remote", Style::default().fg(theme.warn)),
                 Span::raw("  "),
-                Span::styled("GPU HBM", Style::default().fg(COLOR_HBM_RED)),
+                Span::styled("GPU HBM", Style::default().fg(theme.critical)),
                 Span::raw(" "),
             ])
                 .right_aligned(),
         );
+    if let Some((cpu_only, cpu_and_gpu)) = location_swap_shares(unified_procs, total_swap_kb) {
+        block = block.title_bottom(
+            Line::from(format!(" swap held: CPU-only {:.1}%  CPU+GPU {:.1}% ", cpu_only, cpu_and_gpu)).left_aligned(),
+        );
+    }
 
-    let para = Paragraph::new(lines).block(block);
-    frame.render_widget(para, area);
+    scroll.fit(lines.len(), area);
+    scroll.render_with_header(frame, area, theme, block, lines);
 }
 
-pub(crate) fn format_mem(kb: u64, unit: &SizeUnits) -> String {
-    let val = convert_swap(kb, unit.clone());
-    let suffix = match unit {
-        SizeUnits::KB => "KB",
-        SizeUnits::MB => "MB",
-        SizeUnits::GB => "GB",
+/// Percentages of the system's swap (`total_swap_kb`) held by CPU-only and
+/// by CPU+GPU processes. None without any swap configured.
+fn location_swap_shares(procs: &[UnifiedProcessInfo], total_swap_kb: u64) -> Option<(f64, f64)> {
+    if total_swap_kb == 0 {
+        return None;
+    }
+    let held = |location: ProcessLocation| -> u64 {
+        procs.iter().filter(|p| p.location == location).map(|p| p.swap_kb).sum()
     };
+    let percent = |kb: u64| kb as f64 / total_swap_kb as f64 * 100.0;
+    Some((percent(held(ProcessLocation::CpuOnly)), percent(held(ProcessLocation::CpuAndGpu))))
+}
+
+/// GPU memory of `proc` as a percentage of the memory of the GPU it runs on.
+/// A process spread over several GPUs has its memory summed, so it is
+/// measured against their combined total. GPUs missing from `devices` (or
+/// reporting no total) are skipped; None when none remain.
+fn gpu_memory_percent(proc: &UnifiedProcessInfo, devices: &[GpuDevice]) -> Option<f64> {
+    let used_kb = proc.gpu_memory_kb?;
+    let total_kb: u64 = devices
+        .iter()
+        .filter(|d| proc.gpu_indices.contains(&d.index))
+        .map(|d| d.memory_total_kb)
+        .sum();
+    (total_kb > 0).then(|| used_kb as f64 / total_kb as f64 * 100.0)
+}
+
+pub(crate) fn format_mem(kb: u64, unit: &SizeUnits, base: UnitBase) -> String {
+    let suffix = base.suffix(unit);
+    match (unit, base) {
+        (SizeUnits::KB, UnitBase::Binary) => format!("{} {}", kb, suffix),
+        (SizeUnits::KB, UnitBase::Decimal) => format!("{:.0} {}", convert_swap(kb, SizeUnits::KB, base), suffix),
+        _ => format!("{:.2} {}", convert_swap(kb, unit.clone(), base), suffix),
+    }
+}
+
+/// Readable and exact at once: "12.29 GiB (12884901 KiB)", in the largest
+/// unit that keeps the value at least 1. Values under 1 MiB are KiB only.
+/// The exact figure is always the KiB count the kernel reports.
+pub(crate) fn format_mem_dual(kb: u64, base: UnitBase) -> String {
+    let unit = [SizeUnits::TB, SizeUnits::GB, SizeUnits::MB]
+        .into_iter()
+        .find(|unit| convert_swap(kb, unit.clone(), base) >= 1.0);
     match unit {
-        SizeUnits::KB => format!("{} {}", val as u64, suffix),
-        _ => format!("{:.2} {}", val, suffix),
+        Some(unit) => format!("{} ({})", format_mem(kb, &unit, base), format_mem(kb, &SizeUnits::KB, UnitBase::Binary)),
+        None => format_mem(kb, &SizeUnits::KB, UnitBase::Binary),
     }
 }
 
-fn truncate(s: &str, max: usize) -> String {
-    if s.len() <= max {
-        s.to_string()
-    } else {
-        format!("{}…", &s[..max - 1])
+/// Format a signed KB delta, e.g. "+120.00 MiB" or "-512 KiB".
+pub(crate) fn format_mem_delta(delta_kb: i64, unit: &SizeUnits, base: UnitBase) -> String {
+    let sign = if delta_kb < 0 { '-' } else { '+' };
+    format!("{}{}", sign, format_mem(delta_kb.unsigned_abs(), unit, base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpu(index: u32, total_kb: u64) -> GpuDevice {
+        GpuDevice {
+            index, name: "GPU".into(), memory_total_kb: total_kb, memory_used_kb: 0, memory_free_kb: total_kb,
+            numa_node_id: None, temperature: None, pci_bus_id: String::new(), memory_reserved_kb: None,
+            mig_instances: Vec::new(), utilization_percent: None, memory_utilization_percent: None,
+        }
+    }
+
+    fn gpu_proc(gpu_memory_kb: Option<u64>, gpu_indices: Vec<u32>) -> UnifiedProcessInfo {
+        UnifiedProcessInfo {
+            pid: 1, name: "p".into(), swap_kb: 0, cpu_nodes: vec![], gpu_nodes: vec![],
+            kb_per_node: Default::default(), gpu_memory_kb, gpu_indices,
+            location: ProcessLocation::GpuOnly, rss_kb: None, count: 1,
+        }
+    }
+
+    #[test]
+    fn test_format_mem_dual() {
+        assert_eq!(format_mem_dual(512, UnitBase::Binary), "512 KiB");
+        assert_eq!(format_mem_dual(2048, UnitBase::Binary), "2.00 MiB (2048 KiB)");
+        assert_eq!(format_mem_dual(12_884_901, UnitBase::Binary), "12.29 GiB (12884901 KiB)");
+        assert_eq!(format_mem_dual(3 << 30, UnitBase::Binary), "3.00 TiB (3221225472 KiB)");
+        assert_eq!(format_mem(1 << 29, &SizeUnits::TB, UnitBase::Binary), "0.50 TiB");
+        assert_eq!(format_mem_dual(12_884_901, UnitBase::Decimal), "13.19 GB (12884901 KiB)");
+    }
+
+    #[test]
+    fn test_format_mem_binary_and_decimal() {
+        assert_eq!(format_mem(1_000_000, &SizeUnits::MB, UnitBase::Binary), "976.56 MiB");
+        assert_eq!(format_mem(1_000_000, &SizeUnits::MB, UnitBase::Decimal), "1024.00 MB");
+        assert_eq!(format_mem(1_000_000, &SizeUnits::KB, UnitBase::Binary), "1000000 KiB");
+        assert_eq!(format_mem(1_000_000, &SizeUnits::KB, UnitBase::Decimal), "1024000 kB");
+        assert_eq!(format_mem_delta(-2048, &SizeUnits::MB, UnitBase::Binary), "-2.00 MiB");
+    }
+
+    #[test]
+    fn test_gpu_memory_percent() {
+        let devices = vec![gpu(0, 1000), gpu(1, 3000)];
+        assert_eq!(gpu_memory_percent(&gpu_proc(Some(250), vec![0]), &devices), Some(25.0));
+        // Multi-GPU: summed memory against the combined total
+        assert_eq!(gpu_memory_percent(&gpu_proc(Some(1000), vec![0, 1]), &devices), Some(25.0));
+        // Unknown GPU index or no GPU memory
+        assert_eq!(gpu_memory_percent(&gpu_proc(Some(100), vec![7]), &devices), None);
+        assert_eq!(gpu_memory_percent(&gpu_proc(None, vec![]), &devices), None);
+    }
+
+    #[test]
+    fn test_location_swap_shares() {
+        let swapping = |swap_kb, location| UnifiedProcessInfo { swap_kb, location, ..gpu_proc(None, vec![]) };
+        let procs = vec![
+            swapping(300, ProcessLocation::CpuOnly),
+            swapping(100, ProcessLocation::CpuOnly),
+            swapping(100, ProcessLocation::CpuAndGpu),
+            swapping(0, ProcessLocation::GpuOnly),
+        ];
+        assert_eq!(location_swap_shares(&procs, 1000), Some((40.0, 10.0)));
+        assert_eq!(location_swap_shares(&[], 1000), Some((0.0, 0.0)));
+        // No swap configured
+        assert_eq!(location_swap_shares(&procs, 0), None);
+    }
+
+    #[test]
+    fn test_header_column_at_without_numa_nodes() {
+        assert_eq!(header_column_at(0, 0, false), None); // PID
+        assert_eq!(header_column_at(9, 0, false), Some(SortColumn::Name));
+        assert_eq!(header_column_at(26, 0, false), Some(SortColumn::NumaNode));
+        assert_eq!(header_column_at(31, 0, false), Some(SortColumn::NumaNode)); // arrow
+        assert_eq!(header_column_at(33, 0, false), None); // GPU→N
+        assert_eq!(header_column_at(40, 0, false), Some(SortColumn::Swap));
+        assert_eq!(header_column_at(49, 0, false), Some(SortColumn::Swap));
+        assert_eq!(header_column_at(50, 0, false), None); // separator
+        assert_eq!(header_column_at(51, 0, false), None); // RSS is not a sort key
+        assert_eq!(header_column_at(62, 0, false), Some(SortColumn::GpuMem));
+        assert_eq!(header_column_at(72, 0, false), None);
+    }
+
+    #[test]
+    fn test_header_column_at_committed_only_when_shown() {
+        assert_eq!(header_column_at(73, 0, false), None);
+        assert_eq!(header_column_at(73, 0, true), Some(SortColumn::Committed));
+        assert_eq!(header_column_at(82, 0, true), Some(SortColumn::Committed));
+    }
+
+    #[test]
+    fn test_header_column_at_shifts_with_numa_nodes() {
+        // Two node columns of 9 + separator push SWAP right by 20
+        assert_eq!(header_column_at(40, 2, false), None);
+        assert_eq!(header_column_at(60, 2, false), Some(SortColumn::Swap));
+        assert_eq!(header_column_at(82, 2, false), Some(SortColumn::GpuMem));
+    }
+
+    #[test]
+    fn test_sort_label_arrows() {
+        assert_eq!(sort_label("SWAP", SortColumn::Swap, SortColumn::Swap, false), "SWAP▼");
+        assert_eq!(sort_label("SWAP", SortColumn::Swap, SortColumn::Swap, true), "SWAP▲");
+        assert_eq!(sort_label("NAME", SortColumn::Name, SortColumn::Name, false), "NAME▲");
+        assert_eq!(sort_label("NAME", SortColumn::Name, SortColumn::Swap, false), "NAME");
+    }
+
+    #[test]
+    fn test_location_filter_cycles_back_to_all() {
+        let mut filter = LocationFilter::All;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            seen.push(filter);
+            filter = filter.next();
+        }
+        assert_eq!(filter, LocationFilter::All);
+        assert_eq!(seen[3], LocationFilter::CpuAndGpu);
+        assert!(LocationFilter::CpuAndGpu.matches(&ProcessLocation::CpuAndGpu));
+        assert!(!LocationFilter::CpuAndGpu.matches(&ProcessLocation::CpuOnly));
+        assert!(LocationFilter::All.matches(&ProcessLocation::GpuOnly));
     }
 }